@@ -24,6 +24,7 @@ pub enum ProposalStatus {
     Executed,
     Expired,
     Rejected,
+    Cancelled,
 }
 
 impl ProposalStatus {
@@ -34,6 +35,7 @@ impl ProposalStatus {
             Self::Executed => "executed",
             Self::Expired => "expired",
             Self::Rejected => "rejected",
+            Self::Cancelled => "cancelled",
         }
     }
 }
@@ -83,6 +85,11 @@ pub struct SignProposalRequest {
     pub comment: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CancelProposalRequest {
+    pub canceller: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ListProposalsQuery {
     pub status: Option<String>,
@@ -154,6 +161,12 @@ pub struct ExecuteProposalResponse {
     pub executed_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct CancelProposalResponse {
+    pub status: String,
+    pub cancelled_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ProposalInfoResponse {
     pub proposal: DeployProposal,
@@ -713,6 +726,112 @@ pub async fn execute_proposal(
     }))
 }
 
+pub async fn cancel_proposal(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(payload): Json<CancelProposalRequest>,
+) -> ApiResult<Json<CancelProposalResponse>> {
+    let proposal_id = Uuid::parse_str(&id).map_err(|_| {
+        ApiError::bad_request("InvalidProposalId", "proposal id must be a valid UUID")
+    })?;
+
+    let canceller = payload.canceller.trim().to_string();
+    if canceller.is_empty() {
+        return Err(ApiError::bad_request(
+            "InvalidCanceller",
+            "canceller cannot be empty",
+        ));
+    }
+
+    let mut tx = state.db.begin().await.map_err(|e| {
+        tracing::error!(error = ?e, "failed to start cancel transaction");
+        ApiError::db_error("Failed to cancel proposal")
+    })?;
+
+    let proposal = sqlx::query_as::<_, (ProposalStatus, String, String)>(
+        "SELECT p.status, p.proposer, mp.created_by
+         FROM deploy_proposals p
+         JOIN multisig_policies mp ON mp.id = p.policy_id
+         WHERE p.id = $1
+         FOR UPDATE OF p",
+    )
+    .bind(proposal_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = ?e, "failed to fetch proposal for cancellation");
+        ApiError::db_error("Failed to load proposal")
+    })?
+    .ok_or_else(|| ApiError::not_found("ProposalNotFound", "deployment proposal not found"))?;
+
+    let (status, proposer, policy_admin) = proposal;
+
+    // Only the proposer or the admin who created the governing policy may
+    // withdraw a proposal.
+    if canceller != proposer && canceller != policy_admin {
+        return Err(ApiError::forbidden(
+            "only the proposer or a policy admin can cancel this proposal",
+        ));
+    }
+
+    if !matches!(status, ProposalStatus::Pending | ProposalStatus::Approved) {
+        return Err(ApiError::conflict(
+            "InvalidProposalState",
+            format!(
+                "proposal cannot be cancelled while in '{}' state",
+                status.as_str()
+            ),
+        ));
+    }
+
+    let cancelled_at = sqlx::query_scalar::<_, DateTime<Utc>>(
+        "UPDATE deploy_proposals
+         SET status = 'cancelled',
+             cancelled_at = NOW(),
+             cancelled_by = $2,
+             updated_at = NOW()
+         WHERE id = $1
+         RETURNING cancelled_at",
+    )
+    .bind(proposal_id)
+    .bind(&canceller)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = ?e, "failed to mark proposal cancelled");
+        ApiError::db_error("Failed to cancel proposal")
+    })?;
+
+    sqlx::query(
+        "INSERT INTO multisig_approval_audit_events (
+            proposal_id, actor_address, action, metadata
+         )
+         VALUES ($1, $2, 'proposal_cancelled', $3)",
+    )
+    .bind(proposal_id)
+    .bind(&canceller)
+    .bind(json!({
+        "previous_status": status.as_str(),
+        "cancelled_at": cancelled_at,
+    }))
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = ?e, "failed to insert cancellation audit event");
+        ApiError::db_error("Failed to record audit trail")
+    })?;
+
+    tx.commit().await.map_err(|e| {
+        tracing::error!(error = ?e, "failed to commit cancel transaction");
+        ApiError::db_error("Failed to finalize cancellation")
+    })?;
+
+    Ok(Json(CancelProposalResponse {
+        status: ProposalStatus::Cancelled.as_str().to_string(),
+        cancelled_at,
+    }))
+}
+
 pub async fn proposal_info(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -802,13 +921,11 @@ pub async fn list_proposals(
 
     let (items, total) = if let Some(status) = query.status.as_deref() {
         match status {
-            "pending" | "approved" | "executed" | "expired" | "rejected" => {}
-            _ => {
-                return Err(ApiError::bad_request(
-                    "InvalidStatus",
-                    "status must be one of: pending, approved, executed, expired, rejected",
-                ))
-            }
+            "pending" | "approved" | "executed" | "expired" | "rejected" | "cancelled" => {}
+            _ => return Err(ApiError::bad_request(
+                "InvalidStatus",
+                "status must be one of: pending, approved, executed, expired, rejected, cancelled",
+            )),
         }
 
         let total: i64 = sqlx::query_scalar(
@@ -27,6 +27,10 @@ pub fn routes() -> Router<AppState> {
             "/api/contracts/:id/execute",
             post(multisig_handlers::execute_proposal),
         )
+        .route(
+            "/api/contracts/:id/cancel",
+            post(multisig_handlers::cancel_proposal),
+        )
         .route(
             "/api/contracts/:id/proposal",
             get(multisig_handlers::proposal_info),
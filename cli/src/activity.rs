@@ -0,0 +1,352 @@
+//! On-chain activity for `search --min-invocations` and `--active-since`.
+//!
+//! A registry that tracks usage reports it on each record (an invocation
+//! count and a last-activity time), and that is used as is. For records
+//! without it, activity is read from Soroban RPC: the contract's events over
+//! the window, as a proxy for invocations. RPC only keeps about a week of
+//! events, and at most [`MAX_EVENT_PAGES`] pages are read per contract, so
+//! those counts are lower bounds.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// `getEvents` pages read per contract.
+pub const MAX_EVENT_PAGES: usize = 5;
+const EVENT_PAGE_SIZE: usize = 200;
+/// Contracts queried over RPC at once.
+const RPC_CONCURRENCY: usize = 4;
+/// Ledgers close about every five seconds.
+const LEDGER_SECS: i64 = 5;
+/// RPC window when only `--min-invocations` is given.
+const DEFAULT_WINDOW_DAYS: i64 = 1;
+/// Default event retention of a Soroban RPC node.
+const RPC_RETENTION_DAYS: i64 = 7;
+
+/// Registry record keys for usage stats, top level or under `stats`/`usage`.
+const INVOCATION_KEYS: &[&str] = &["invocation_count", "total_invocations", "invocations"];
+const LAST_ACTIVE_KEYS: &[&str] = &["last_activity", "last_invoked_at", "last_active_at"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    Registry,
+    Rpc,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Activity {
+    /// Invocations tracked by the registry, or contract events seen over
+    /// RPC within the window.
+    pub invocations: Option<u64>,
+    /// The RPC count stopped at [`MAX_EVENT_PAGES`]; the real one is higher.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub capped: bool,
+    pub last_active: Option<DateTime<Utc>>,
+    pub source: Source,
+}
+
+impl Activity {
+    /// Short table cell, e.g. `1200 calls, 3h ago`.
+    pub fn summary(&self, now: DateTime<Utc>) -> String {
+        let mut parts = Vec::new();
+        if let Some(n) = self.invocations {
+            let noun = match self.source {
+                Source::Registry => "calls",
+                Source::Rpc => "events",
+            };
+            parts.push(format!(
+                "{}{} {}",
+                n,
+                if self.capped { "+" } else { "" },
+                noun
+            ));
+        }
+        if let Some(at) = self.last_active {
+            let age = now - at;
+            parts.push(if age.num_days() > 0 {
+                format!("{}d ago", age.num_days())
+            } else if age.num_hours() > 0 {
+                format!("{}h ago", age.num_hours())
+            } else {
+                format!("{}m ago", age.num_minutes().max(0))
+            });
+        }
+        if parts.is_empty() {
+            "—".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityFilter {
+    pub min_invocations: Option<u64>,
+    pub active_since: Option<Duration>,
+}
+
+impl ActivityFilter {
+    /// `None` when neither flag was given.
+    pub fn new(min_invocations: Option<u64>, active_since: Option<Duration>) -> Option<Self> {
+        (min_invocations.is_some() || active_since.is_some()).then_some(ActivityFilter {
+            min_invocations,
+            active_since,
+        })
+    }
+
+    /// Contracts with unknown activity never match.
+    pub fn matches(&self, activity: Option<&Activity>, now: DateTime<Utc>) -> bool {
+        let Some(activity) = activity else {
+            return false;
+        };
+        let enough = self
+            .min_invocations
+            .is_none_or(|min| activity.invocations.is_some_and(|n| n >= min));
+        let recent = self
+            .active_since
+            .is_none_or(|since| activity.last_active.is_some_and(|t| t >= now - since));
+        enough && recent
+    }
+
+    /// Query parameters for registries that filter server-side.
+    pub fn params(&self, now: DateTime<Utc>) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if let Some(min) = self.min_invocations {
+            params.push(("min_invocations", min.to_string()));
+        }
+        if let Some(since) = self.active_since {
+            params.push(("active_since", (now - since).to_rfc3339()));
+        }
+        params
+    }
+
+    pub fn label(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(min) = self.min_invocations {
+            parts.push(format!("≥{} invocations", min));
+        }
+        if let Some(since) = self.active_since {
+            parts.push(format!("active in the last {}", format_window(since)));
+        }
+        parts.join(", ")
+    }
+
+    /// How far back to read events over RPC.
+    fn window(&self) -> Duration {
+        self.active_since
+            .unwrap_or_else(|| Duration::days(DEFAULT_WINDOW_DAYS))
+            .min(Duration::days(RPC_RETENTION_DAYS))
+    }
+}
+
+/// `--active-since` values: `30m`, `12h`, `7d` or `2w`.
+pub fn parse_window(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let invalid = || format!("invalid duration '{}' (expected e.g. 30m, 12h, 7d, 2w)", s);
+    let unit = s.chars().last().ok_or_else(invalid)?;
+    let number: i64 = s[..s.len() - unit.len_utf8()]
+        .parse()
+        .map_err(|_| invalid())?;
+    if number <= 0 {
+        return Err(invalid());
+    }
+    match unit {
+        'm' => Ok(Duration::minutes(number)),
+        'h' => Ok(Duration::hours(number)),
+        'd' => Ok(Duration::days(number)),
+        'w' => Ok(Duration::weeks(number)),
+        _ => Err(invalid()),
+    }
+}
+
+pub fn format_window(window: Duration) -> String {
+    if window.num_days() > 0 && window.num_hours() % 24 == 0 {
+        format!("{}d", window.num_days())
+    } else if window.num_hours() > 0 && window.num_minutes() % 60 == 0 {
+        format!("{}h", window.num_hours())
+    } else {
+        format!("{}m", window.num_minutes())
+    }
+}
+
+/// Usage stats the registry put on `record`, if any.
+pub fn from_record(record: &Value) -> Option<Activity> {
+    let lookup = |keys: &[&str]| {
+        [record, &record["stats"], &record["usage"]]
+            .into_iter()
+            .flat_map(|object| keys.iter().map(move |k| &object[*k]))
+            .find(|v| !v.is_null())
+    };
+    let invocations = lookup(INVOCATION_KEYS).and_then(Value::as_u64);
+    let last_active = lookup(LAST_ACTIVE_KEYS)
+        .and_then(Value::as_str)
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|t| t.with_timezone(&Utc));
+    if invocations.is_none() && last_active.is_none() {
+        return None;
+    }
+    Some(Activity {
+        invocations,
+        capped: false,
+        last_active,
+        source: Source::Registry,
+    })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EventsPage {
+    #[serde(default)]
+    events: Vec<Event>,
+    cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Event {
+    ledger_closed_at: Option<String>,
+}
+
+/// Events `contract_id` emitted over the last `window`, read from RPC.
+pub async fn from_rpc(
+    client: &reqwest::Client,
+    endpoint: &str,
+    contract_id: &str,
+    window: Duration,
+) -> Result<Activity> {
+    let latest = crate::rpc::latest_ledger(client, endpoint).await?;
+    let ledgers = (window.num_seconds() / LEDGER_SECS).max(1) as u64;
+    let start = latest.saturating_sub(ledgers).max(1);
+    let filters = json!([{ "type": "contract", "contractIds": [contract_id] }]);
+
+    let mut count = 0u64;
+    let mut last_active: Option<DateTime<Utc>> = None;
+    let mut cursor: Option<String> = None;
+    let mut capped = false;
+    for page in 0..MAX_EVENT_PAGES {
+        let params = match &cursor {
+            None => json!({
+                "startLedger": start,
+                "filters": filters,
+                "pagination": { "limit": EVENT_PAGE_SIZE },
+            }),
+            Some(cursor) => json!({
+                "filters": filters,
+                "pagination": { "cursor": cursor, "limit": EVENT_PAGE_SIZE },
+            }),
+        };
+        let result: EventsPage = crate::rpc::call(client, endpoint, "getEvents", params)
+            .await
+            .with_context(|| format!("Failed to read events for {}", contract_id))?;
+
+        count += result.events.len() as u64;
+        let newest = result
+            .events
+            .iter()
+            .filter_map(|e| DateTime::parse_from_rfc3339(e.ledger_closed_at.as_deref()?).ok())
+            .map(|t| t.with_timezone(&Utc))
+            .max();
+        last_active = last_active.max(newest);
+
+        if result.events.len() < EVENT_PAGE_SIZE || result.cursor.is_none() {
+            break;
+        }
+        capped = page + 1 == MAX_EVENT_PAGES;
+        cursor = result.cursor;
+    }
+
+    Ok(Activity {
+        invocations: Some(count),
+        capped,
+        last_active,
+        source: Source::Rpc,
+    })
+}
+
+/// Activity for each of `items`: from the record when the registry tracks
+/// it, otherwise over RPC. `None` where neither is available; how many is
+/// reported on stderr.
+pub async fn resolve(
+    client: &reqwest::Client,
+    filter: &ActivityFilter,
+    items: &[Value],
+) -> Result<Vec<Option<Activity>>> {
+    let window = filter.window();
+    let jobs: Vec<_> = items
+        .iter()
+        .map(|record| {
+            let client = client.clone();
+            let known = from_record(record);
+            let contract_id = record["contract_id"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            let endpoint = record["network"]
+                .as_str()
+                .and_then(crate::network::rpc_endpoint);
+            async move {
+                if known.is_some() {
+                    return known;
+                }
+                let endpoint = endpoint?;
+                from_rpc(&client, &endpoint, &contract_id, window)
+                    .await
+                    .map_err(|e| log::debug!("No activity for {}: {:#}", contract_id, e))
+                    .ok()
+            }
+        })
+        .collect();
+    let (activity, _) = crate::concurrency::run_bounded(RPC_CONCURRENCY, jobs, |_, _| {}).await?;
+
+    let unknown = activity.iter().filter(|a| a.is_none()).count();
+    if unknown > 0 {
+        eprintln!(
+            "{} Activity unknown for {} of {} result(s); they are left out",
+            "⚠".yellow(),
+            unknown,
+            activity.len()
+        );
+    }
+    Ok(activity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_stats_are_read_and_filtered() {
+        let now = DateTime::parse_from_rfc3339("2024-06-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let record = json!({
+            "contract_id": "CABC",
+            "stats": { "invocation_count": 1200, "last_activity": "2024-06-01T09:00:00Z" },
+        });
+        let activity = from_record(&record).unwrap();
+        assert_eq!(activity.invocations, Some(1200));
+        assert_eq!(activity.source, Source::Registry);
+        assert_eq!(activity.summary(now), "1200 calls, 3h ago");
+        assert!(from_record(&json!({ "contract_id": "CABC" })).is_none());
+
+        let filter = ActivityFilter::new(Some(1000), Some(parse_window("6h").unwrap())).unwrap();
+        assert!(filter.matches(Some(&activity), now));
+        assert!(!filter.matches(None, now));
+        let stale = Activity {
+            last_active: Some(now - Duration::days(2)),
+            ..activity.clone()
+        };
+        assert!(!filter.matches(Some(&stale), now));
+        assert_eq!(filter.label(), "≥1000 invocations, active in the last 6h");
+        assert_eq!(filter.window(), Duration::hours(6));
+
+        assert!(ActivityFilter::new(None, None).is_none());
+        assert_eq!(parse_window("2w"), Ok(Duration::days(14)));
+        assert!(parse_window("7").is_err());
+        assert!(parse_window("0d").is_err());
+        assert!(parse_window("").is_err());
+    }
+}
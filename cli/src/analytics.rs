@@ -0,0 +1,160 @@
+//! Registry-wide aggregates over contract metadata (`tag-graph`).
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::Value;
+
+/// How often each tag appears and how often each pair appears together.
+#[derive(Debug, Default, Serialize)]
+pub struct TagCooccurrence {
+    pub contracts: usize,
+    /// Tag → number of contracts carrying it.
+    pub tags: BTreeMap<String, usize>,
+    /// Pairs with `a < b`, most frequent first.
+    pub pairs: Vec<TagPair>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TagPair {
+    pub a: String,
+    pub b: String,
+    pub count: usize,
+}
+
+/// Count tag co-occurrence across `contracts`. Tags are compared
+/// case-insensitively and counted once per contract.
+pub fn tag_cooccurrence(contracts: &[Value]) -> TagCooccurrence {
+    let mut tags: BTreeMap<String, usize> = BTreeMap::new();
+    let mut pairs: BTreeMap<(String, String), usize> = BTreeMap::new();
+
+    for contract in contracts {
+        let mut own: Vec<String> = crate::tags::tag_names(contract)
+            .iter()
+            .map(|t| t.trim().to_lowercase())
+            .filter(|t| !t.is_empty())
+            .collect();
+        own.sort();
+        own.dedup();
+
+        for (i, a) in own.iter().enumerate() {
+            *tags.entry(a.clone()).or_default() += 1;
+            for b in &own[i + 1..] {
+                *pairs.entry((a.clone(), b.clone())).or_default() += 1;
+            }
+        }
+    }
+
+    let mut pairs: Vec<TagPair> = pairs
+        .into_iter()
+        .map(|((a, b), count)| TagPair { a, b, count })
+        .collect();
+    pairs.sort_by_key(|pair| std::cmp::Reverse(pair.count));
+
+    TagCooccurrence {
+        contracts: contracts.len(),
+        tags,
+        pairs,
+    }
+}
+
+fn dot_id(tag: &str) -> String {
+    format!("\"{}\"", tag.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Undirected DOT graph: one node per tag (labelled with its count), one
+/// edge per co-occurring pair weighted by how often they appear together.
+pub fn to_dot(graph: &TagCooccurrence) -> String {
+    let mut out = String::from("graph tags {\n");
+    for (tag, count) in &graph.tags {
+        out.push_str(&format!(
+            "  {} [label=\"{} ({})\"];\n",
+            dot_id(tag),
+            tag.replace('"', "\\\""),
+            count
+        ));
+    }
+    for pair in &graph.pairs {
+        out.push_str(&format!(
+            "  {} -- {} [weight={}, label=\"{}\"];\n",
+            dot_id(&pair.a),
+            dot_id(&pair.b),
+            pair.count,
+            pair.count
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Build the tag co-occurrence graph for the whole registry. Written as DOT
+/// when `output` ends in `.dot`/`.gv`, otherwise as JSON (to stdout when no
+/// output is given).
+pub async fn tag_graph(api_url: &str, output: Option<&str>) -> Result<()> {
+    let client = reqwest::Client::new();
+    let contracts = crate::tags::fetch_all_contracts(&client, api_url).await?;
+    let graph = tag_cooccurrence(&contracts);
+
+    let is_dot = output
+        .and_then(|o| std::path::Path::new(o).extension())
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| matches!(e, "dot" | "gv"));
+
+    match output {
+        Some(path) if is_dot => {
+            std::fs::write(path, to_dot(&graph))
+                .with_context(|| format!("Failed to write {}", path))?;
+        }
+        Some(path) => {
+            crate::output::Emitter::to_file(path).emit(&graph, crate::output::Format::Json)?;
+            return Ok(());
+        }
+        None => {
+            crate::output::Emitter::current().emit(&graph, crate::output::Format::Json)?;
+            return Ok(());
+        }
+    }
+
+    eprintln!(
+        "{} Wrote {} tag(s) and {} pair(s) from {} contract(s) to {}",
+        "✓".green(),
+        graph.tags.len(),
+        graph.pairs.len(),
+        graph.contracts,
+        output.unwrap_or_default()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn counts_pairs_once_per_contract() {
+        let contracts = vec![
+            json!({ "tags": ["DeFi", "token", "defi"] }),
+            json!({ "tags": [{ "name": "token" }, { "name": "defi" }, { "name": "amm" }] }),
+            json!({ "tags": [] }),
+        ];
+        let graph = tag_cooccurrence(&contracts);
+        assert_eq!(graph.contracts, 3);
+        assert_eq!(graph.tags["defi"], 2);
+        assert_eq!(
+            graph.pairs[0],
+            TagPair {
+                a: "defi".into(),
+                b: "token".into(),
+                count: 2
+            }
+        );
+        assert_eq!(graph.pairs.len(), 3);
+
+        let dot = to_dot(&graph);
+        assert!(dot.starts_with("graph tags {"));
+        assert!(dot.contains("\"defi\" -- \"token\" [weight=2, label=\"2\"];"));
+    }
+}
@@ -0,0 +1,95 @@
+//! Security audit report links attached to published contracts.
+
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One published audit report, as stored in a contract's `audits` field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditReport {
+    pub url: String,
+    pub auditor: String,
+    /// Report date, `YYYY-MM-DD`
+    pub date: String,
+}
+
+/// Parse a `--audit <url>,<auditor>,<YYYY-MM-DD>` value. The auditor and
+/// date are split from the right so the URL may itself contain commas.
+pub fn parse(spec: &str) -> Result<AuditReport> {
+    let mut parts = spec.rsplitn(3, ',').map(str::trim);
+    let (Some(date), Some(auditor), Some(url)) = (parts.next(), parts.next(), parts.next()) else {
+        bail!(
+            "Invalid --audit '{}': expected <url>,<auditor>,<YYYY-MM-DD>",
+            spec
+        );
+    };
+
+    let parsed = reqwest::Url::parse(url).with_context(|| format!("Invalid audit URL: {}", url))?;
+    if !matches!(parsed.scheme(), "https" | "http") {
+        bail!("Audit URL must be http(s): {}", url);
+    }
+    if auditor.is_empty() {
+        bail!("Audit '{}' is missing the auditor name", url);
+    }
+    let day = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .with_context(|| format!("Invalid audit date '{}' (expected YYYY-MM-DD)", date))?;
+    if day > chrono::Utc::now().date_naive() {
+        bail!("Audit date {} is in the future", date);
+    }
+
+    Ok(AuditReport {
+        url: url.to_string(),
+        auditor: auditor.to_string(),
+        date: day.format("%Y-%m-%d").to_string(),
+    })
+}
+
+/// Audit reports recorded on a contract, newest first. Malformed entries are
+/// skipped.
+pub fn reports(contract: &Value) -> Vec<AuditReport> {
+    let mut reports: Vec<AuditReport> = contract["audits"]
+        .as_array()
+        .map(|a| {
+            a.iter()
+                .filter_map(|r| serde_json::from_value(r.clone()).ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    reports.sort_by(|a, b| b.date.cmp(&a.date));
+    reports
+}
+
+pub fn is_audited(contract: &Value) -> bool {
+    !reports(contract).is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_and_validates_audit_specs() {
+        let report = parse("https://audits.example/r?a=1,b=2, OtterSec ,2024-05-01").unwrap();
+        assert_eq!(report.url, "https://audits.example/r?a=1,b=2");
+        assert_eq!(report.auditor, "OtterSec");
+        assert_eq!(report.date, "2024-05-01");
+
+        assert!(parse("https://audits.example/r,OtterSec").is_err());
+        assert!(parse("ftp://audits.example/r,OtterSec,2024-05-01").is_err());
+        assert!(parse("https://audits.example/r,,2024-05-01").is_err());
+        assert!(parse("https://audits.example/r,OtterSec,2024-13-01").is_err());
+        assert!(parse("https://audits.example/r,OtterSec,2999-01-01").is_err());
+
+        let contract = json!({ "audits": [
+            { "url": "https://a", "auditor": "A", "date": "2023-01-01" },
+            { "url": "https://b", "auditor": "B", "date": "2024-01-01" },
+            { "bogus": true }
+        ]});
+        let found = reports(&contract);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].auditor, "B");
+        assert!(!is_audited(&json!({})));
+    }
+}
@@ -2,35 +2,47 @@
 
 use anyhow::{Context, Result};
 use colored::Colorize;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::path::Path;
 
+use crate::concurrency::{Concurrency, Signal};
+use crate::progress;
+
 const MAX_BATCH_SIZE: usize = 50;
 const REGISTER_TIMEOUT_SECS: u64 = 60;
 
 // ── Manifest types ────────────────────────────────────────────────────────────
 
 /// A single contract entry in the manifest file.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ManifestEntry {
     pub contract_id: String,
     pub name: String,
     /// Per-entry network override. Falls back to the manifest-level default.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub network: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub category: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub wasm_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub source_url: Option<String>,
 }
 
 /// Top-level manifest file structure (YAML or JSON).
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct RegisterManifest {
     /// Default publisher address for all entries (overridden by --publisher flag).
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub publisher: Option<String>,
     /// Default network for entries that don't specify their own.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub network: Option<String>,
     pub contracts: Vec<ManifestEntry>,
 }
@@ -56,7 +68,7 @@ struct RegisterPayload {
 }
 
 /// Per-contract result collected during registration.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct RegistrationResult {
     pub contract_id: String,
     pub name: String,
@@ -67,13 +79,16 @@ pub struct RegistrationResult {
 }
 
 /// Final summary emitted after all registrations complete.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct RegistrationSummary {
     pub total: usize,
     pub registered: usize,
     pub failed: usize,
     pub skipped: usize,
     pub results: Vec<RegistrationResult>,
+    /// Timing statistics, with `--concurrency-report`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<crate::concurrency::ConcurrencyReport>,
 }
 
 // ── Public entry point ────────────────────────────────────────────────────────
@@ -84,12 +99,16 @@ pub struct RegistrationSummary {
 /// * `publisher`     – Stellar address; overrides `publisher` field in manifest
 /// * `dry_run`       – validate and print what would be registered, but skip API calls
 /// * `json`          – emit machine-readable JSON instead of human-readable output
+/// * `concurrency`   – registrations in flight at once, or `auto` to adapt to the registry
+/// * `report`        – include throughput statistics in the summary
 pub async fn run_batch_register(
     api_url: &str,
     manifest_path: &str,
     publisher: Option<&str>,
     dry_run: bool,
     json: bool,
+    concurrency: Concurrency,
+    report: bool,
 ) -> Result<()> {
     // 1. Load and parse manifest
     let manifest = load_manifest(manifest_path)?;
@@ -131,13 +150,19 @@ pub async fn run_batch_register(
         return emit_dry_run(entries, skipped_duplicates, json);
     }
 
-    // 7. Submit one by one, collecting results
-    let summary = register_all(api_url, entries, skipped_duplicates, json).await?;
+    // 7. Submit, `concurrency` at a time, collecting results
+    let mut summary = register_all(api_url, entries, skipped_duplicates, json, concurrency).await?;
+    if !report {
+        summary.concurrency = None;
+    }
 
     if json {
         println!("{}", serde_json::to_string_pretty(&summary)?);
     } else {
         print_summary(&summary);
+        if let Some(report) = &summary.concurrency {
+            report.print();
+        }
     }
 
     // Exit with non-zero if any failed
@@ -151,11 +176,16 @@ pub async fn run_batch_register(
 // ── Manifest loading ──────────────────────────────────────────────────────────
 
 fn load_manifest(path: &str) -> Result<RegisterManifest> {
-    let ext = Path::new(path)
-        .extension()
-        .and_then(|e| e.to_str())
-        .unwrap_or("")
-        .to_lowercase();
+    // stdin has no extension; YAML is a superset of JSON so it parses both.
+    let ext = if crate::io_utils::is_stdin(path) {
+        "yaml".to_string()
+    } else {
+        Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase()
+    };
 
     match ext.as_str() {
         "yaml" | "yml" | "json" => {}
@@ -164,7 +194,7 @@ fn load_manifest(path: &str) -> Result<RegisterManifest> {
         }
     }
 
-    let content = std::fs::read_to_string(path)
+    let content = crate::io_utils::read_input_to_string(path)
         .with_context(|| format!("Cannot read manifest file: {path}"))?;
 
     match ext.as_str() {
@@ -292,6 +322,7 @@ fn emit_dry_run(entries: Vec<ResolvedEntry>, skipped_duplicates: usize, json: bo
         failed: 0,
         skipped: skipped_duplicates,
         results,
+        concurrency: None,
     };
 
     if json {
@@ -336,6 +367,7 @@ async fn register_all(
     entries: Vec<ResolvedEntry>,
     skipped_duplicates: usize,
     json: bool,
+    concurrency: Concurrency,
 ) -> Result<RegistrationSummary> {
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(REGISTER_TIMEOUT_SECS))
@@ -343,50 +375,66 @@ async fn register_all(
 
     let url = format!("{}/api/contracts", api_url);
     let total = entries.len();
-    let mut results: Vec<RegistrationResult> = Vec::with_capacity(total);
-    let mut registered = 0usize;
-    let mut failed = 0usize;
-
-    for (i, entry) in entries.into_iter().enumerate() {
-        if !json {
-            print!(
-                "  [{}/{}] Registering {} ... ",
-                i + 1,
-                total,
-                entry.payload.contract_id.bold()
-            );
-        }
 
-        match register_one(&client, &url, entry).await {
-            Ok(result) => {
-                if !json {
-                    println!("{}", "registered".green());
-                }
-                registered += 1;
-                results.push(result);
-            }
-            Err(err) => {
-                let err_str = err.to_string();
-                if !json {
-                    println!("{} — {}", "failed".red(), err_str.red());
+    let jobs: Vec<_> = entries
+        .into_iter()
+        .map(|entry| {
+            let client = client.clone();
+            let url = url.clone();
+            async move {
+                let contract_id = entry.payload.contract_id.clone();
+                let name = entry.payload.name.clone();
+                match register_one(&client, &url, entry).await {
+                    Ok(result) => (result, Signal::Ok),
+                    Err(err) => (
+                        RegistrationResult {
+                            contract_id,
+                            name,
+                            status: "failed".to_string(),
+                            registry_id: None,
+                            error: Some(err.to_string()),
+                        },
+                        Signal::from_error(&err),
+                    ),
                 }
-                failed += 1;
-                results.push(RegistrationResult {
-                    contract_id: String::new(), // filled in by register_one on success path only
-                    name: String::new(),
-                    status: "failed".to_string(),
-                    registry_id: None,
-                    error: Some(err_str),
-                });
             }
-        }
-    }
+        })
+        .collect();
+
+    let mut done = 0usize;
+    let (results, timing) = crate::concurrency::run_with(
+        concurrency,
+        jobs,
+        |(_, signal)| *signal,
+        |_, (result, _): &(RegistrationResult, Signal)| {
+            done += 1;
+            let status = match result.status.as_str() {
+                "registered" => progress::Status::Ok,
+                "skipped" => progress::Status::Skipped,
+                _ => progress::Status::Failed,
+            };
+            progress::emit(
+                json,
+                &progress::Event::Progress {
+                    done,
+                    total,
+                    item: &result.contract_id,
+                    status,
+                    message: result.error.as_deref(),
+                },
+            );
+        },
+    )
+    .await?;
+    let results: Vec<RegistrationResult> = results.into_iter().map(|(r, _)| r).collect();
 
+    let count = |status: &str| results.iter().filter(|r| r.status == status).count();
     Ok(RegistrationSummary {
         total,
-        registered,
-        failed,
+        registered: count("registered"),
+        failed: count("failed"),
         skipped: skipped_duplicates,
+        concurrency: Some(timing.report()),
         results,
     })
 }
@@ -417,10 +465,12 @@ async fn register_one(
         });
     }
 
-    if !response.status().is_success() {
+    if let Err(err) = response.error_for_status_ref() {
+        // Keep the reqwest error in the chain so `--concurrency auto` can
+        // tell overload (429/5xx) from a rejected entry.
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
-        anyhow::bail!("HTTP {}: {}", status, body);
+        return Err(anyhow::Error::new(err).context(format!("HTTP {}: {}", status, body)));
     }
 
     let body: serde_json::Value = response
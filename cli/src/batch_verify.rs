@@ -2,6 +2,7 @@
 
 use anyhow::{Context, Result};
 use colored::Colorize;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
@@ -22,7 +23,7 @@ pub struct BatchContractEntry {
     pub version: Option<String>,
 }
 
-#[derive(Debug, Deserialize, serde::Serialize)]
+#[derive(Debug, Deserialize, serde::Serialize, JsonSchema)]
 pub struct BatchVerifyResponse {
     pub batch_id: String,
     pub total: usize,
@@ -35,7 +36,7 @@ pub struct BatchVerifyResponse {
     pub duration_ms: Option<u64>,
 }
 
-#[derive(Debug, Deserialize, serde::Serialize)]
+#[derive(Debug, Deserialize, serde::Serialize, JsonSchema)]
 pub struct ContractVerifyResult {
     pub contract_id: String,
     pub version: Option<String>,
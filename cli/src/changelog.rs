@@ -0,0 +1,171 @@
+//! `CHANGELOG.md` generation from a contract's registered version history.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_json::Value;
+use shared::SemVer;
+
+fn parse_version(raw: &str) -> Option<SemVer> {
+    SemVer::parse(raw.trim().trim_start_matches('v'))
+}
+
+/// Note text as markdown list items: existing bullets are kept, other
+/// non-empty lines become bullets.
+fn bullets(notes: &str) -> Vec<String> {
+    notes
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|l| {
+            let item = l
+                .strip_prefix("- ")
+                .or_else(|| l.strip_prefix("* "))
+                .unwrap_or(l);
+            format!("- {}", item)
+        })
+        .collect()
+}
+
+/// Render a Keep-a-Changelog document, newest version first. Versions at or
+/// below `since` are left out; versions that aren't valid semver sort last.
+pub fn render(name: &str, versions: &[Value], since: Option<&SemVer>) -> String {
+    let mut entries: Vec<(&Value, Option<SemVer>)> = versions
+        .iter()
+        .map(|v| (v, parse_version(v["version"].as_str().unwrap_or_default())))
+        .filter(|(_, semver)| match (since, semver) {
+            (Some(since), Some(v)) => v > since,
+            _ => true,
+        })
+        .collect();
+    entries.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let mut out = String::from("# Changelog\n\n");
+    out.push_str(&format!(
+        "All notable changes to `{}` are documented in this file.\n\n\
+         The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),\n\
+         and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).\n",
+        name
+    ));
+
+    for (version, _) in entries {
+        let date = version["created_at"]
+            .as_str()
+            .and_then(|d| d.get(..10))
+            .unwrap_or("unreleased");
+        out.push_str(&format!(
+            "\n## [{}] - {}\n",
+            version["version"].as_str().unwrap_or("unknown"),
+            date
+        ));
+
+        if version["is_revert"].as_bool().unwrap_or(false) {
+            out.push_str(&format!(
+                "\n### Reverted\n\n- Reverted to {}\n",
+                version["reverted_from"]
+                    .as_str()
+                    .unwrap_or("a previous version")
+            ));
+        }
+
+        let changes = version["change_notes"]
+            .as_str()
+            .map(bullets)
+            .unwrap_or_default();
+        if !changes.is_empty() {
+            out.push_str("\n### Changed\n\n");
+            out.push_str(&changes.join("\n"));
+            out.push('\n');
+        }
+
+        // Release notes are free-form; keep them as written.
+        if let Some(notes) = version["release_notes"]
+            .as_str()
+            .filter(|n| !n.trim().is_empty())
+        {
+            out.push('\n');
+            out.push_str(notes.trim());
+            out.push('\n');
+        }
+    }
+    out
+}
+
+pub async fn generate(
+    api_url: &str,
+    contract_id: &str,
+    output: &str,
+    since_version: Option<&str>,
+) -> Result<()> {
+    let since = since_version
+        .map(|v| parse_version(v).with_context(|| format!("Invalid --since-version: {}", v)))
+        .transpose()?;
+
+    let client = reqwest::Client::new();
+    let response = client
+        .get(format!("{}/api/contracts/{}", api_url, contract_id))
+        .send()
+        .await
+        .context("Failed to fetch contract from registry")?;
+    if !response.status().is_success() {
+        anyhow::bail!(
+            "Contract not found: {} ({})",
+            contract_id,
+            response.status()
+        );
+    }
+    let body: Value = response.json().await?;
+    let contract = body.get("contract").unwrap_or(&body);
+    let uuid = contract["id"]
+        .as_str()
+        .context("Contract response missing id")?;
+    let name = contract["name"].as_str().unwrap_or(contract_id);
+
+    let versions = crate::commands::fetch_version_history(&client, api_url, uuid).await?;
+    let markdown = render(name, &versions, since.as_ref());
+
+    if crate::io_utils::is_stdin(output) {
+        print!("{}", markdown);
+        return Ok(());
+    }
+    std::fs::write(output, &markdown).with_context(|| format!("Failed to write {}", output))?;
+    println!(
+        "{} Wrote {} version(s) of {} to {}",
+        "✓".green(),
+        markdown.matches("\n## [").count(),
+        name.bold(),
+        output
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn renders_newest_first_and_honours_since() {
+        let versions = vec![
+            json!({ "version": "1.0.0", "created_at": "2024-01-05T10:00:00Z",
+                    "release_notes": "Initial release." }),
+            json!({ "version": "1.2.0", "created_at": "2024-03-01T10:00:00Z",
+                    "change_notes": "Add pause()\n- Fix rounding in withdraw" }),
+            json!({ "version": "1.1.0", "created_at": "2024-02-01T10:00:00Z",
+                    "is_revert": true, "reverted_from": "1.0.0" }),
+        ];
+
+        let doc = render("vault", &versions, None);
+        let order: Vec<usize> = ["[1.2.0] - 2024-03-01", "[1.1.0]", "[1.0.0]"]
+            .iter()
+            .map(|h| doc.find(h).unwrap())
+            .collect();
+        assert!(order.windows(2).all(|w| w[0] < w[1]));
+        assert!(doc.contains("### Changed\n\n- Add pause()\n- Fix rounding in withdraw\n"));
+        assert!(doc.contains("- Reverted to 1.0.0"));
+
+        let since = SemVer::parse("1.0.0").unwrap();
+        let doc = render("vault", &versions, Some(&since));
+        assert!(!doc.contains("[1.0.0]"));
+        assert!(doc.contains("[1.1.0]"));
+    }
+}
@@ -26,7 +26,7 @@ pub async fn validate_env(contract_path: &str) -> Result<()> {
     }
 
     // 3. Check for API environment variables
-    if std::env::var("SOROBAN_REGISTRY_API_TOKEN").is_err() {
+    if std::env::var(crate::http::API_TOKEN_ENV).is_err() {
         println!(
             "  {} SOROBAN_REGISTRY_API_TOKEN not set (required for CI/CD)",
             "⚠".yellow()
@@ -91,20 +91,34 @@ pub async fn run_pipeline(
 
     crate::commands::publish(
         api_url,
-        &contract_id,
-        name,
-        Some("Automatically registered via CI/CD Integration Framework"),
-        network,
-        None,
-        vec!["cicd".to_string(), "automated".to_string()],
-        &publisher,
-        true,
-        contract_path,
-        None,
-        false,
-        0.0,
-        false,
-    ).await?;
+        crate::commands::PublishParams {
+            contract_id: &contract_id,
+            name,
+            description: Some("Automatically registered via CI/CD Integration Framework"),
+            network,
+            category: None,
+            tags: vec!["cicd".to_string(), "automated".to_string()],
+            publisher: &publisher,
+            is_cicd: true,
+            contract_path,
+            test_command: None,
+            require_coverage: false,
+            coverage_threshold: 0.0,
+            skip_tests: false,
+            strict: false,
+            audits: &[],
+            custom_fields: &Default::default(),
+            preflight: false,
+            force: false,
+            optimize: false,
+            relations: &[],
+            metadata_schema: None,
+            dry_run: false,
+            wasm: None,
+            expected_hash: None,
+        },
+    )
+    .await?;
 
     // Step 5: Verify
     if !json {
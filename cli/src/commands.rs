@@ -4,7 +4,6 @@ use anyhow::{Context, Result};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use serde_yaml;
 use std::fmt;
 use std::fs;
 use std::process::Command;
@@ -117,6 +116,81 @@ pub fn profile(
     Ok(())
 }
 
+/// Fields of a `search` result record, for `--fields` validation.
+pub const SEARCH_FIELDS: &[&str] = &[
+    "id",
+    "name",
+    "is_verified",
+    "network",
+    "category",
+    "audits",
+    "links",
+    "score",
+    "score_source",
+    "abi",
+    "activity",
+];
+
+/// Header of `search --format csv`.
+const SEARCH_CSV_HEADER: [&str; 5] = ["contract_id", "name", "category", "verified", "downloads"];
+
+/// Search results as CSV, one row per contract. The header is written even
+/// when there are no results.
+fn write_search_csv<W: std::io::Write>(writer: W, items: &[serde_json::Value]) -> Result<()> {
+    let mut csv = csv::Writer::from_writer(writer);
+    csv.write_record(SEARCH_CSV_HEADER)?;
+    for c in items {
+        csv.write_record([
+            crate::conversions::as_str(&c["contract_id"], "contract_id")?,
+            crate::conversions::as_str(&c["name"], "name")?,
+            c["category"].as_str().unwrap_or_default().to_string(),
+            crate::conversions::as_bool(&c["is_verified"], "is_verified")?.to_string(),
+            crate::ranking::downloads(c)
+                .map(|d| d.to_string())
+                .unwrap_or_default(),
+        ])?;
+    }
+    csv.flush().context("Failed to write CSV")?;
+    Ok(())
+}
+
+/// ABI requests in flight at once for `search --with-abi`.
+const SEARCH_ABI_CONCURRENCY: usize = 8;
+
+/// Each result's ABI, fetched with bounded concurrency. Results without one
+/// get `None`; how many is reported on stderr.
+async fn fetch_result_abis(
+    client: &reqwest::Client,
+    api_url: &str,
+    items: &[serde_json::Value],
+) -> Result<Vec<Option<serde_json::Value>>> {
+    let jobs: Vec<_> = items
+        .iter()
+        .map(|c| {
+            let client = client.clone();
+            let api_url = api_url.to_string();
+            let uuid = c["id"]
+                .as_str()
+                .or_else(|| c["contract_id"].as_str())
+                .unwrap_or_default()
+                .to_string();
+            async move { crate::recommend::fetch_abi(&client, &api_url, &uuid).await }
+        })
+        .collect();
+    let (abis, _) =
+        crate::concurrency::run_bounded(SEARCH_ABI_CONCURRENCY, jobs, |_, _| {}).await?;
+    let missing = abis.iter().filter(|a| a.is_none()).count();
+    if missing > 0 {
+        eprintln!(
+            "{} No ABI available for {} of {} result(s)",
+            "⚠".yellow(),
+            missing,
+            abis.len()
+        );
+    }
+    Ok(abis)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn search(
     api_url: &str,
@@ -128,8 +202,24 @@ pub async fn search(
     limit: usize,
     offset: usize,
     json: bool,
+    show_score: bool,
+    filter: Option<&str>,
+    audited_only: bool,
+    publisher: Option<&str>,
+    fields: Option<&crate::output::Projection>,
+    with_abi: bool,
+    activity: Option<&crate::activity::ActivityFilter>,
+    sort: Option<crate::ranking::SortKey>,
+    order: crate::ranking::SortOrder,
+    format: crate::output::OutputFormat,
 ) -> Result<()> {
+    if let Some(fields) = fields {
+        fields.validate(SEARCH_FIELDS)?;
+    }
     let t0 = std::time::Instant::now();
+    let json = json || format == crate::output::OutputFormat::Json;
+    let filter_expr_label = filter;
+    let filter = filter.map(crate::query::parse_filter).transpose()?;
     let client = reqwest::Client::new();
 
     let mut params: Vec<(&str, String)> = vec![
@@ -152,6 +242,19 @@ pub async fn search(
         params.push(("category", cat.to_string()));
     }
 
+    if let Some(publisher) = publisher {
+        params.push(("publisher", publisher.to_string()));
+    }
+
+    if let Some(activity) = activity {
+        params.extend(activity.params(chrono::Utc::now()));
+    }
+
+    if let Some(sort_by) = sort.and_then(crate::ranking::SortKey::api_param) {
+        params.push(("sort_by", sort_by.to_string()));
+        params.push(("sort_order", order.as_str().to_string()));
+    }
+
     let response = client
         .get(format!("{}/api/contracts", api_url))
         .query(&params)
@@ -160,27 +263,133 @@ pub async fn search(
         .context("Failed to search contracts")?;
 
     let data: serde_json::Value = response.json().await?;
-    let items = data["items"].as_array().context("Invalid response")?;
+    let mut items = crate::query::apply(
+        data["items"].as_array().context("Invalid response")?.clone(),
+        filter.as_ref(),
+    );
+    if audited_only {
+        items.retain(crate::audit::is_audited);
+    }
+    // Registries that ignore the `publisher` parameter still get filtered.
+    if let Some(publisher) = publisher {
+        items.retain(|c| crate::publisher::matches(c, publisher));
+    }
+    // Registries without usage stats get it looked up over RPC; the result
+    // rides on each record as `activity`.
+    if let Some(filter) = activity {
+        let found = crate::activity::resolve(&client, filter, &items).await?;
+        let now = chrono::Utc::now();
+        items = items
+            .into_iter()
+            .zip(found)
+            .filter(|(_, a)| filter.matches(a.as_ref(), now))
+            .map(|(mut c, a)| {
+                c["activity"] = serde_json::to_value(a).unwrap_or_default();
+                c
+            })
+            .collect();
+    }
 
-    if json {
+    // Relevance comes from the API when available, otherwise it's computed
+    // locally, so `--show-score` can explain the ranking. `--show-score`
+    // alone sorts by it.
+    let sort = sort.or(show_score.then_some(crate::ranking::SortKey::Relevance));
+    let mut scores: Option<Vec<f64>> = None;
+    let mut scores_from_api = false;
+    if show_score || sort == Some(crate::ranking::SortKey::Relevance) {
+        let (raw, from_api) = crate::ranking::relevance_scores(query, &items);
+        scores = Some(raw);
+        scores_from_api = from_api;
+    }
+    // Re-sorted locally too, for registries that ignore `sort_by`.
+    if let Some(key) = sort {
+        let indices = crate::ranking::sorted_indices(&items, scores.as_deref(), key, order);
+        items = indices.iter().map(|&i| items[i].clone()).collect();
+        scores = scores.map(|s| indices.iter().map(|&i| s[i]).collect());
+    }
+    if !show_score {
+        scores = None;
+    }
+
+    let emitter = crate::output::Emitter::current();
+    if emitter.emits(crate::output::Format::Protobuf, crate::output::Format::Json) {
+        if fields.is_some() || with_abi {
+            anyhow::bail!("--fields and --with-abi can't be combined with protobuf output");
+        }
+        let score_source = if scores_from_api {
+            "api"
+        } else {
+            "client_bm25"
+        };
+        let contracts = items
+            .iter()
+            .enumerate()
+            .map(|(i, c)| -> Result<_> {
+                let mut contract = crate::proto::Contract::from_record(c, api_url)?;
+                if let Some(ref scores) = scores {
+                    contract.score = Some(scores[i]);
+                    contract.score_source = score_source.to_string();
+                }
+                Ok(contract)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        emitter.emit_protobuf(&contracts)?;
+        if emitter.emits_only(crate::output::Format::Protobuf, crate::output::Format::Json) {
+            return Ok(());
+        }
+    }
+    if format == crate::output::OutputFormat::Csv {
+        if fields.is_some() || with_abi {
+            anyhow::bail!("--fields and --with-abi can't be combined with CSV output");
+        }
+        return write_search_csv(std::io::stdout().lock(), &items);
+    }
+    if json
+        || format.structured().is_some()
+        || fields.is_some()
+        || with_abi
+        || emitter.structured()
+    {
+        let abis = if with_abi {
+            Some(fetch_result_abis(&client, api_url, &items).await?)
+        } else {
+            None
+        };
         let contracts: Vec<serde_json::Value> = items
             .iter()
-            .map(|c| -> Result<_> {
+            .enumerate()
+            .map(|(i, c)| -> Result<_> {
                 let contract_id = crate::conversions::as_str(&c["contract_id"], "contract_id")?;
-                Ok(serde_json::json!({
+                let mut entry = serde_json::json!({
                     "id":          contract_id.clone(),
                     "name":        crate::conversions::as_str(&c["name"], "name")?,
                     "is_verified": crate::conversions::as_bool(&c["is_verified"], "is_verified")?,
                     "network":     crate::conversions::as_str(&c["network"], "network")?,
                     "category":    c["category"].as_str().unwrap_or(""),
+                    "audits":      crate::audit::reports(c),
                     "links": { "detail": format!("{}/contracts/{}", api_url, contract_id) },
-                }))
+                });
+                if let Some(ref scores) = scores {
+                    entry["score"] = serde_json::json!(scores[i]);
+                    entry["score_source"] =
+                        serde_json::json!(if scores_from_api { "api" } else { "client_bm25" });
+                }
+                if let Some(ref abis) = abis {
+                    entry["abi"] = abis[i].clone().unwrap_or(serde_json::Value::Null);
+                }
+                if activity.is_some() {
+                    entry["activity"] = c["activity"].clone();
+                }
+                Ok(match fields {
+                    Some(fields) => fields.apply(&entry),
+                    None => entry,
+                })
             })
             .collect::<Result<_, _>>()?;
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&serde_json::json!({ "contracts": contracts }))?
-        );
+        emitter.emit(
+            &serde_json::json!({ "contracts": contracts }),
+            format.structured().unwrap_or(crate::output::Format::Json),
+        )?;
         return Ok(());
     }
 
@@ -197,6 +406,18 @@ pub async fn search(
     if verified_only {
         active_filters.push("verified only".to_string());
     }
+    if audited_only {
+        active_filters.push("audited only".to_string());
+    }
+    if let Some(publisher) = publisher {
+        active_filters.push(format!("publisher: {}", publisher));
+    }
+    if let Some(expr) = filter_expr_label {
+        active_filters.push(format!("filter: {}", expr));
+    }
+    if let Some(activity) = activity {
+        active_filters.push(activity.label());
+    }
     if !active_filters.is_empty() {
         println!(
             "  {} {}\n",
@@ -204,6 +425,14 @@ pub async fn search(
             active_filters.join(" | ").bright_blue()
         );
     }
+    if let Some(key) = sort {
+        println!(
+            "  {} {} ({})\n",
+            "Sorted by:".bold(),
+            key.label(),
+            order.as_str()
+        );
+    }
 
     if items.is_empty() {
         println!("{}", "No contracts found matching your filters.".yellow());
@@ -218,6 +447,15 @@ pub async fn search(
         if verified_only {
             println!("  • Remove --verified-only to include unverified contracts");
         }
+        if audited_only {
+            println!("  • Remove --audited-only to include contracts without audit reports");
+        }
+        if filter.is_some() {
+            println!("  • Loosen or remove the --filter expression");
+        }
+        if activity.is_some() {
+            println!("  • Lower --min-invocations or widen --active-since");
+        }
         println!("  • Use 'list' command to browse all contracts\n");
         return Ok(());
     }
@@ -245,7 +483,9 @@ pub async fn search(
         .unwrap_or(0)
         .max("Category".len());
     // "○ Unverified" is the longest possible verified cell value (12 visible chars).
-    let ver_w = "○ Unverified".chars().count();
+    let verified_label = format!("{} Verified", crate::table_format::glyph("✓", "+"));
+    let unverified_label = format!("{} Unverified", crate::table_format::glyph("○", "-"));
+    let ver_w = unverified_label.chars().count();
     let link_prefix = format!("{}/contracts/", api_url);
     let link_w = items
         .iter()
@@ -256,8 +496,25 @@ pub async fn search(
         .max("Links".len())
         .min(60);
 
+    let score_w = "Score".len().max(8);
+    let now = chrono::Utc::now();
+    let activity_cells: Vec<String> = items
+        .iter()
+        .map(|c| {
+            serde_json::from_value::<crate::activity::Activity>(c["activity"].clone())
+                .map(|a| a.summary(now))
+                .unwrap_or_default()
+        })
+        .collect();
+    let activity_w = activity_cells
+        .iter()
+        .map(|s| s.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Activity".len());
+
     let mut rows: Vec<Vec<String>> = Vec::new();
-    for contract in items {
+    for (i, contract) in items.iter().enumerate() {
         let name = crate::conversions::as_str(&contract["name"], "name")?;
         let contract_id = crate::conversions::as_str(&contract["contract_id"], "contract_id")?;
         let is_verified = crate::conversions::as_bool(&contract["is_verified"], "is_verified")?;
@@ -268,34 +525,59 @@ pub async fn search(
         let name_cell = crate::table_format::highlight_match(&name, query);
         let net_cell = net.bright_blue().to_string();
         let cat_display = if cat.is_empty() {
-            "—".to_string()
+            crate::table_format::glyph("—", "-").to_string()
         } else {
             cat
         };
         let cat_cell = crate::table_format::highlight_match(&cat_display, query);
         let ver_cell = if is_verified {
-            "✓ Verified".green().to_string()
+            verified_label.green().to_string()
         } else {
-            "○ Unverified".yellow().to_string()
+            unverified_label.yellow().to_string()
         };
         let link_cell = link.bright_black().to_string();
 
-        rows.push(vec![name_cell, net_cell, cat_cell, ver_cell, link_cell]);
+        let mut row = vec![name_cell, net_cell, cat_cell, ver_cell, link_cell];
+        if let Some(ref scores) = scores {
+            row.insert(0, format!("{:.3}", scores[i]).bold().to_string());
+        }
+        if activity.is_some() {
+            row.insert(row.len() - 1, activity_cells[i].clone());
+        }
+        rows.push(row);
     }
 
-    let col_widths = [name_w, net_w, cat_w, ver_w, link_w];
-    let headers = ["Name", "Network", "Category", "Verified", "Links"];
+    let mut col_widths = vec![name_w, net_w, cat_w, ver_w, link_w];
+    let mut headers = vec!["Name", "Network", "Category", "Verified", "Links"];
+    if scores.is_some() {
+        col_widths.insert(0, score_w);
+        headers.insert(0, "Score");
+    }
+    if activity.is_some() {
+        col_widths.insert(col_widths.len() - 1, activity_w);
+        headers.insert(headers.len() - 1, "Activity");
+    }
     print!(
         "{}",
         crate::table_format::render_table(&headers, &col_widths, &rows)
     );
 
+    if scores.is_some() {
+        let source = if scores_from_api {
+            "registry relevance scores"
+        } else {
+            "client-side BM25 over name and description (API returned no scores)"
+        };
+        println!("\n  {} Ranked by {}", "ℹ".bright_blue(), source);
+    }
+
     let elapsed_ms = t0.elapsed().as_millis();
     println!(
-        "\n{} {} result(s) for \"{}\"  |  {}ms\n",
+        "\n{} {} result(s) for \"{}\"  |  {}  |  {}ms\n",
         "→".cyan(),
         items.len(),
         query.bold(),
+        crate::pagination::footer(offset, items.len(), data["total"].as_u64()),
         elapsed_ms
     );
 
@@ -468,36 +750,151 @@ fn resolve_smart_routing(current_network: Network) -> String {
     }
 }
 
-#[allow(clippy::too_many_arguments)]
-pub async fn publish(
-    api_url: &str,
-    contract_id: &str,
-    name: &str,
-    description: Option<&str>,
-    network: Network,
-    category: Option<&str>,
-    tags: Vec<String>,
-    publisher: &str,
-    is_cicd: bool,
-    contract_path: &str,
-    test_command: Option<&str>,
-    require_coverage: bool,
-    coverage_threshold: f64,
-    skip_tests: bool,
-) -> Result<()> {
-    if !skip_tests {
-        run_contract_tests(
-            contract_path,
-            test_command,
-            require_coverage,
-            coverage_threshold,
-            true,
+/// What to publish and how, as given to `publish` or built by
+/// `import --republish` and the CI/CD pipeline.
+pub struct PublishParams<'a> {
+    pub contract_id: &'a str,
+    pub name: &'a str,
+    pub description: Option<&'a str>,
+    pub network: Network,
+    pub category: Option<&'a str>,
+    pub tags: Vec<String>,
+    pub publisher: &'a str,
+    pub is_cicd: bool,
+    pub contract_path: &'a str,
+    pub test_command: Option<&'a str>,
+    pub require_coverage: bool,
+    pub coverage_threshold: f64,
+    pub skip_tests: bool,
+    pub strict: bool,
+    pub audits: &'a [crate::audit::AuditReport],
+    pub custom_fields: &'a crate::custom_fields::CustomFields,
+    pub preflight: bool,
+    pub force: bool,
+    pub optimize: bool,
+    pub relations: &'a [crate::relations::Target],
+    pub metadata_schema: Option<&'a Path>,
+    pub dry_run: bool,
+    pub wasm: Option<&'a Path>,
+    pub expected_hash: Option<&'a str>,
+}
+
+pub async fn publish(api_url: &str, params: PublishParams<'_>) -> Result<()> {
+    let PublishParams {
+        contract_id,
+        name,
+        description,
+        network,
+        category,
+        tags,
+        publisher,
+        is_cicd,
+        contract_path,
+        test_command,
+        require_coverage,
+        coverage_threshold,
+        skip_tests,
+        strict,
+        audits,
+        custom_fields,
+        preflight,
+        force,
+        optimize,
+        relations,
+        metadata_schema,
+        dry_run,
+        wasm,
+        expected_hash,
+    } = params;
+
+    // Checked first: there's no point in anything else for the wrong build.
+    let wasm_hash = match wasm {
+        Some(path) => {
+            let actual = crate::wasm::sha256_file(path)?;
+            if let Some(expected) = expected_hash {
+                let expected = expected.trim().trim_start_matches("0x").to_lowercase();
+                if expected != actual {
+                    anyhow::bail!(
+                        "WASM hash mismatch for {}:\n  expected: {}\n  actual:   {}",
+                        path.display(),
+                        expected,
+                        actual
+                    );
+                }
+            }
+            println!("{} WASM hash: {}", "✓".green(), actual.bright_black());
+            Some(actual)
+        }
+        None => None,
+    };
+
+    if optimize {
+        // Re-optimize from the original build, not a previous run's output.
+        let wasm = crate::preflight::locate_wasm(Path::new(contract_path))
+            .map(|p| {
+                let original = p
+                    .to_string_lossy()
+                    .strip_suffix(".optimized.wasm")
+                    .map(|stem| std::path::PathBuf::from(format!("{}.wasm", stem)));
+                original.unwrap_or(p)
+            })
+            .with_context(|| {
+                format!(
+                    "--optimize needs a built contract; no single wasm found for {}",
+                    contract_path
+                )
+            })?;
+        crate::wasm::optimize_file(&wasm.to_string_lossy(), None, false)?;
+    }
+
+    if preflight {
+        let required = crate::config::strict_required_fields()?;
+        let report = crate::preflight::run(
+            api_url,
+            &crate::preflight::PreflightInput {
+                contract_id,
+                network: &network.to_string(),
+                fields: crate::publish_checks::PublishFields {
+                    description,
+                    category,
+                    tags: &tags,
+                    contract_path: Path::new(contract_path),
+                },
+                required: &required,
+            },
         )
-        .await?;
+        .await;
+        report.print();
+        if !report.passed() {
+            if !force {
+                anyhow::bail!(
+                    "Preflight failed: {} check(s) did not pass (rerun with --force to publish anyway)",
+                    report.failures()
+                );
+            }
+            println!(
+                "{} Publishing despite {} failed preflight check(s) (--force)",
+                "⚠".yellow(),
+                report.failures()
+            );
+        }
+    }
+
+    if strict {
+        let required = crate::config::strict_required_fields()?;
+        crate::publish_checks::enforce(
+            &required,
+            &crate::publish_checks::PublishFields {
+                description,
+                category,
+                tags: &tags,
+                contract_path: Path::new(contract_path),
+            },
+        )?;
     }
 
     let client = reqwest::Client::new();
-    let url = format!("{}/api/contracts", api_url);
+    let relations = crate::relations::resolve(&client, api_url, relations).await?;
 
     let mut payload = json!({
         "contract_id": contract_id,
@@ -509,18 +906,62 @@ pub async fn publish(
         "publisher_address": publisher,
     });
 
+    if let Some(hash) = &wasm_hash {
+        payload["wasm_hash"] = json!(hash);
+    }
     if is_cicd {
         payload["is_cicd"] = json!(true);
     }
+    if !audits.is_empty() {
+        payload["audits"] = json!(audits);
+    }
+    if !custom_fields.is_empty() {
+        payload["custom_fields"] = json!(custom_fields);
+    }
+    if !relations.is_empty() {
+        payload["relations"] = crate::relations::payload(&relations);
+    }
+
+    // Checked before the tests run, so a policy violation fails fast.
+    let metadata_schema = match metadata_schema {
+        Some(path) => Some(path.to_path_buf()),
+        None => crate::config::metadata_schema()?,
+    };
+    if let Some(schema) = metadata_schema {
+        crate::metadata_schema::enforce(&schema, &payload)?;
+    }
+
+    if !skip_tests {
+        run_contract_tests(
+            contract_path,
+            test_command,
+            require_coverage,
+            coverage_threshold,
+            true,
+            test_framework::resolve_seed(None),
+        )
+        .await?;
+    }
+
+    let url = format!("{}/api/contracts", api_url);
+    let idempotency_key = crate::http::idempotency_key("publish", &payload);
+    log::debug!("publish idempotency key: {}", idempotency_key);
+
+    if dry_run {
+        log::debug!("dry run: skipping POST {}", url);
+        print_publish_dry_run(&payload)?;
+        return Ok(());
+    }
 
     println!("\n{}", "Publishing contract...".bold().cyan());
 
-    let response = client
-        .post(&url)
-        .json(&payload)
-        .send()
-        .await
-        .context("Failed to publish contract")?;
+    let response = crate::http::send_with_retry(
+        || client.post(&url).json(&payload),
+        Some(&idempotency_key),
+        crate::http::RetryPolicy::default(),
+    )
+    .await
+    .context("Failed to publish contract")?;
 
     if !response.status().is_success() {
         let error_text = response.text().await?;
@@ -550,6 +991,40 @@ pub async fn publish(
     Ok(())
 }
 
+/// What `publish --dry-run` would have submitted.
+fn print_publish_dry_run(payload: &serde_json::Value) -> Result<()> {
+    let tags = payload["tags"]
+        .as_array()
+        .map(|tags| {
+            tags.iter()
+                .filter_map(serde_json::Value::as_str)
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .filter(|tags| !tags.is_empty())
+        .unwrap_or_else(|| "none".to_string());
+
+    println!("\n{}", "Dry run — nothing published".bold().yellow());
+    println!("{}", "=".repeat(80).cyan());
+    println!(
+        "  {}: {}",
+        "Network".bold(),
+        payload["network"]
+            .as_str()
+            .unwrap_or_default()
+            .bright_blue()
+    );
+    println!("  {}: {}", "Tags".bold(), tags);
+    println!(
+        "  {}: {}",
+        "Publisher".bold(),
+        payload["publisher_address"].as_str().unwrap_or_default()
+    );
+    println!("\n  {}", "Payload".bold());
+    println!("{}\n", serde_json::to_string_pretty(payload)?);
+    Ok(())
+}
+
 pub async fn list(api_url: &str, limit: usize, network: Network, json: bool) -> Result<()> {
 fn detect_test_command(contract_dir: &Path) -> Option<String> {
     if contract_dir.join("Cargo.toml").exists() {
@@ -661,6 +1136,7 @@ pub async fn run_contract_tests(
     require_coverage: bool,
     coverage_threshold: f64,
     show_coverage: bool,
+    seed: u64,
 ) -> Result<()> {
     let contract_dir = Path::new(contract_path);
     if !contract_dir.exists() {
@@ -680,12 +1156,18 @@ pub async fn run_contract_tests(
     println!("\n{}", "Running Contract Tests...".bold().cyan());
     println!("{}", "=".repeat(80).cyan());
     println!("{} {}", "Command:".bold(), selected_command.bright_blue());
+    println!("{} {}", "Seed:".bold(), seed);
 
     let start = std::time::Instant::now();
-    let output = Command::new("sh")
+    let mut command = Command::new("sh");
+    command
         .arg("-c")
         .arg(&selected_command)
-        .current_dir(contract_dir)
+        .current_dir(contract_dir);
+    for var in test_framework::SEED_ENV_VARS {
+        command.env(var, seed.to_string());
+    }
+    let output = command
         .output()
         .with_context(|| format!("Failed to execute test command: {}", selected_command))?;
 
@@ -709,6 +1191,7 @@ pub async fn run_contract_tests(
         for suggestion in summarize_failure(&stdout, &stderr) {
             println!("  - {}", suggestion);
         }
+        println!("  - Replay this run with --seed {}", seed);
 
         anyhow::bail!("Contract tests failed. Submission blocked.");
     }
@@ -805,38 +1288,76 @@ mod contract_test_helpers_tests {
     }
 }
 
-pub async fn list(api_url: &str, limit: usize, network: Network, json: bool) -> Result<()> {
+/// Fields of a `list` result record, for `--fields` validation.
+pub const LIST_FIELDS: &[&str] = &["id", "name", "is_verified", "network"];
+
+pub async fn list(
+    api_url: &str,
+    limit: usize,
+    offset: usize,
+    network: Network,
+    json: bool,
+    filter: Option<&str>,
+    fields: Option<&crate::output::Projection>,
+) -> Result<()> {
+    if let Some(fields) = fields {
+        fields.validate(LIST_FIELDS)?;
+    }
+    let filter = filter.map(crate::query::parse_filter).transpose()?;
     let client = reqwest::Client::new();
-    let url = format!(
-        "{}/api/contracts?page_size={}&network={}",
-        api_url, limit, network
-    );
+    let params = [
+        ("limit", limit.to_string()),
+        ("offset", offset.to_string()),
+        ("network", network.to_string()),
+    ];
 
     let response = client
-        .get(&url)
+        .get(format!("{}/api/contracts", api_url))
+        .query(&params)
         .send()
         .await
         .context("Failed to list contracts")?;
 
     let data: serde_json::Value = response.json().await?;
-    let items = data["items"].as_array().context("Invalid response")?;
+    let items = crate::query::apply(
+        data["items"].as_array().context("Invalid response")?.clone(),
+        filter.as_ref(),
+    );
 
-    if json {
+    let emitter = crate::output::Emitter::current();
+    if emitter.emits(crate::output::Format::Protobuf, crate::output::Format::Json) {
+        if fields.is_some() {
+            anyhow::bail!("--fields can't be combined with protobuf output");
+        }
+        let contracts = items
+            .iter()
+            .map(|c| crate::proto::Contract::from_record(c, api_url))
+            .collect::<Result<Vec<_>>>()?;
+        emitter.emit_protobuf(&contracts)?;
+        if emitter.emits_only(crate::output::Format::Protobuf, crate::output::Format::Json) {
+            return Ok(());
+        }
+    }
+    if json || fields.is_some() || emitter.structured() {
         let contracts: Vec<serde_json::Value> = items
             .iter()
             .map(|c| -> Result<_> {
-                Ok(serde_json::json!({
+                let entry = serde_json::json!({
                     "id":          crate::conversions::as_str(&c["contract_id"], "contract_id")?,
                     "name":        crate::conversions::as_str(&c["name"], "name")?,
                     "is_verified": crate::conversions::as_bool(&c["is_verified"], "is_verified")?,
                     "network":     crate::conversions::as_str(&c["network"], "network")?,
-                }))
+                });
+                Ok(match fields {
+                    Some(fields) => fields.apply(&entry),
+                    None => entry,
+                })
             })
             .collect::<Result<_, _>>()?;
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&serde_json::json!({ "contracts": contracts }))?
-        );
+        emitter.emit(
+            &serde_json::json!({ "contracts": contracts }),
+            crate::output::Format::Json,
+        )?;
         return Ok(());
     }
 
@@ -857,7 +1378,7 @@ pub async fn list(api_url: &str, limit: usize, network: Network, json: bool) ->
         println!(
             "\n{}. {} {}",
             i + 1,
-            name.bold(),
+            crate::table_format::fit(&name).bold(),
             if is_verified {
                 "✓".green()
             } else {
@@ -872,7 +1393,11 @@ pub async fn list(api_url: &str, limit: usize, network: Network, json: bool) ->
     }
 
     println!("\n{}", "=".repeat(80).cyan());
-    println!();
+    println!(
+        "{} {}\n",
+        "→".cyan(),
+        crate::pagination::footer(offset, items.len(), data["total"].as_u64())
+    );
 
     Ok(())
 }
@@ -908,8 +1433,9 @@ pub async fn breaking_changes(api_url: &str, old_id: &str, new_id: &str, json: b
 
     let report: serde_json::Value = response.json().await?;
 
-    if json {
-        println!("{}", serde_json::to_string_pretty(&report)?);
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(&report, crate::output::Format::Json)?;
         return Ok(());
     }
 
@@ -949,15 +1475,139 @@ pub async fn breaking_changes(api_url: &str, old_id: &str, new_id: &str, json: b
     Ok(())
 }
 
+/// Compare the public interfaces of two registered contracts. With
+/// `semantic`, each change is classified and a semver bump recommended.
+pub async fn interface_diff(
+    api_url: &str,
+    old_id: &str,
+    new_id: &str,
+    semantic: bool,
+    json: bool,
+) -> Result<()> {
+    use crate::diff::{Bump, InterfaceChangeKind};
+
+    let client = reqwest::Client::new();
+    let (old, new) = tokio::try_join!(
+        crate::recommend::fetch_contract(&client, api_url, old_id),
+        crate::recommend::fetch_contract(&client, api_url, new_id),
+    )?;
+    let uuid =
+        |contract: &serde_json::Value, id: &str| contract["id"].as_str().unwrap_or(id).to_string();
+    let (old_uuid, new_uuid) = (uuid(&old, old_id), uuid(&new, new_id));
+    let (old_abi, new_abi, versions) = tokio::join!(
+        crate::recommend::fetch_abi(&client, api_url, &old_uuid),
+        crate::recommend::fetch_abi(&client, api_url, &new_uuid),
+        fetch_version_history(&client, api_url, &old_uuid),
+    );
+    let abi_items = |abi: Option<serde_json::Value>, id: &str| -> Result<Vec<serde_json::Value>> {
+        abi.as_ref()
+            .and_then(serde_json::Value::as_array)
+            .cloned()
+            .with_context(|| format!("No ABI registered for {}", id))
+    };
+    let changes =
+        crate::diff::interface_diff(&abi_items(old_abi, old_id)?, &abi_items(new_abi, new_id)?);
+
+    let bump = crate::diff::required_bump(&changes);
+    // The latest release of the old contract is the base for the suggestion.
+    let current_version = versions.ok().and_then(|v| {
+        v.last()
+            .and_then(|v| v["version"].as_str())
+            .map(str::to_string)
+    });
+    let suggested_version = current_version
+        .as_deref()
+        .and_then(|v| crate::diff::next_version(v, bump));
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        let mut report = json!({
+            "old": old_id,
+            "new": new_id,
+            "changes": changes,
+        });
+        if semantic {
+            report["semver"] = json!({
+                "bump": bump,
+                "current_version": current_version,
+                "suggested_version": suggested_version,
+            });
+        }
+        emitter.emit(&report, crate::output::Format::Json)?;
+        return Ok(());
+    }
+
+    println!("\n{}", "Interface Diff".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    println!("  {} → {}\n", old_id.bright_black(), new_id.bright_black());
+    if changes.is_empty() {
+        println!("{}", "✓ Public interface is unchanged".green());
+    }
+    for change in &changes {
+        let line = match change.kind {
+            InterfaceChangeKind::Added => {
+                format!(
+                    "{} {}",
+                    "+".green(),
+                    change.new.as_deref().unwrap_or_default()
+                )
+            }
+            InterfaceChangeKind::Removed => {
+                format!(
+                    "{} {}",
+                    "-".red(),
+                    change.old.as_deref().unwrap_or_default()
+                )
+            }
+            InterfaceChangeKind::SignatureChanged => format!(
+                "{} {}\n      → {}",
+                "~".yellow(),
+                change.old.as_deref().unwrap_or_default(),
+                change.new.as_deref().unwrap_or_default()
+            ),
+        };
+        if semantic {
+            let label = match change.kind.bump() {
+                Bump::Major => "BREAKING".red().bold(),
+                _ => "COMPATIBLE".green().bold(),
+            };
+            println!("  {:<10} {}", label, line);
+        } else {
+            println!("  {}", line);
+        }
+    }
+
+    if semantic {
+        let bump_label = match bump {
+            Bump::Major => bump.to_string().red().bold(),
+            Bump::Minor => bump.to_string().yellow().bold(),
+            Bump::Patch => bump.to_string().green().bold(),
+        };
+        println!("\n{} {} version bump", "→ Recommended:".bold(), bump_label);
+        match (&current_version, &suggested_version) {
+            (Some(current), Some(next)) => println!("  {} → {}", current, next.bold()),
+            (Some(current), None) => println!(
+                "  {} Current version {} isn't MAJOR.MINOR.PATCH; bump it by hand",
+                "⚠".yellow(),
+                current
+            ),
+            (None, _) => {}
+        }
+    }
+    println!();
+    Ok(())
+}
+
 pub async fn migrate(
     api_url: &str,
     contract_id: &str,
     wasm_path: &str,
     simulate_fail: bool,
     dry_run: bool,
+    canary: Option<u8>,
+    require_tests: Option<&str>,
 ) -> Result<()> {
     use sha2::{Digest, Sha256};
-    use tokio::process::Command;
 
     println!("\n{}", "Migration Tool".bold().cyan());
     println!("{}", "=".repeat(80).cyan());
@@ -974,6 +1624,23 @@ pub async fn migrate(
     println!("Contract ID: {}", contract_id.green());
     println!("WASM Hash: {}", wasm_hash.bright_black());
 
+    // Quality gate: the new wasm must pass its suite before anything is sent.
+    if let Some(test_file) = require_tests {
+        run_tests(
+            test_file,
+            Some(wasm_path),
+            None,
+            false,
+            false,
+            test_framework::resolve_seed(None),
+            None,
+            false,
+        )
+        .await
+        .context("Migration aborted: the new WASM did not pass --require-tests")?;
+        println!("{}", "✓ Test gate passed".green());
+    }
+
     if dry_run {
         println!(
             "\n{}",
@@ -986,33 +1653,63 @@ pub async fn migrate(
         return Ok(());
     }
 
-    // 3. Create Migration Record (Pending)
     let client = reqwest::Client::new();
-    let create_url = format!("{}/api/migrations", api_url);
-
     let payload = json!({
         "contract_id": contract_id,
         "wasm_hash": wasm_hash,
     });
+    let (migration, migration_id, status) =
+        record_migration(&client, api_url, &payload, simulate_fail).await?;
 
-    print!("\nInitializing migration... ");
-    let response = client
-        .post(&create_url)
-        .json(&payload)
-        .send()
-        .await
-        .context("Failed to contact registry API")?;
-
-    if !response.status().is_success() {
-        println!("{}", "Failed".red());
-        let err = response.text().await?;
-        anyhow::bail!("API Error: {}", err);
+    if let Some(percent) = canary {
+        if status == shared::models::MigrationStatus::Failed {
+            anyhow::bail!("Migration failed; canary rollout not started");
+        }
+        // Route to the new deployment when the registry reports one; the
+        // migration record stands in for it otherwise.
+        let deployment_id = migration["deployment_id"]
+            .as_str()
+            .unwrap_or(&migration_id);
+        start_canary(&client, api_url, contract_id, deployment_id, percent).await?;
     }
 
-    let migration: serde_json::Value = response.json().await?;
-    let migration_id = extract_migration_id(&migration)?;
-    println!("{}", "OK".green());
-    println!("Migration ID: {}", migration_id);
+    Ok(())
+}
+
+/// Create a migration record for `payload`, run the deployment and record
+/// its outcome. Returns the record, its ID and the final status.
+async fn record_migration(
+    client: &reqwest::Client,
+    api_url: &str,
+    payload: &serde_json::Value,
+    simulate_fail: bool,
+) -> Result<(serde_json::Value, String, shared::models::MigrationStatus)> {
+    use tokio::process::Command;
+
+    // 3. Create Migration Record (Pending)
+    let create_url = format!("{}/api/migrations", api_url);
+
+    print!("\nInitializing migration... ");
+    let idempotency_key = crate::http::idempotency_key("migrate", payload);
+    log::debug!("migrate idempotency key: {}", idempotency_key);
+    let response = crate::http::send_with_retry(
+        || client.post(&create_url).json(payload),
+        Some(&idempotency_key),
+        crate::http::RetryPolicy::default(),
+    )
+    .await
+    .context("Failed to contact registry API")?;
+
+    if !response.status().is_success() {
+        println!("{}", "Failed".red());
+        let err = response.text().await?;
+        anyhow::bail!("API Error: {}", err);
+    }
+
+    let migration: serde_json::Value = response.json().await?;
+    let migration_id = extract_migration_id(&migration)?;
+    println!("{}", "OK".green());
+    println!("Migration ID: {}", migration_id);
 
     // 4. Execute Migration (Mock or Real)
     println!("\n{}", "Executing migration logic...".bold());
@@ -1066,12 +1763,14 @@ pub async fn migrate(
         "log_output": log_output
     });
 
-    let update_res = client
-        .put(&update_url)
-        .json(&update_payload)
-        .send()
-        .await
-        .context("Failed to update migration status")?;
+    // Status updates are naturally idempotent (PUT), so plain retries are safe.
+    let update_res = crate::http::send_with_retry(
+        || client.put(&update_url).json(&update_payload),
+        None,
+        crate::http::RetryPolicy::default(),
+    )
+    .await
+    .context("Failed to update migration status")?;
 
     if !update_res.status().is_success() {
         println!("{}", "Failed to update status!".red());
@@ -1084,49 +1783,567 @@ pub async fn migrate(
         }
     }
 
+    Ok((migration, migration_id, status))
+}
+
+/// Open a canary release routing `percent`% of resolutions to the new
+/// deployment, leaving the rest on the current version.
+async fn start_canary(
+    client: &reqwest::Client,
+    api_url: &str,
+    contract_id: &str,
+    deployment_id: &str,
+    percent: u8,
+) -> Result<()> {
+    let contract: serde_json::Value = client
+        .get(format!("{}/api/contracts/{}", api_url, contract_id))
+        .send()
+        .await
+        .context("Failed to fetch contract")?
+        .error_for_status()
+        .context("Failed to fetch contract")?
+        .json()
+        .await?;
+    let contract_uuid = contract
+        .get("contract")
+        .unwrap_or(&contract)
+        .get("id")
+        .and_then(|v| v.as_str())
+        .context("Contract response missing id")?;
+
+    print!("\nStarting canary at {}%... ", percent);
+    let response = client
+        .post(format!("{}/api/contracts/{}/canary", api_url, contract_uuid))
+        .json(&json!({
+            "contract_id": contract_uuid,
+            "to_deployment_id": deployment_id,
+        }))
+        .send()
+        .await
+        .context("Failed to create canary release")?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        println!("{}", "Failed".red());
+        anyhow::bail!("This registry does not support canary routing");
+    }
+    if !response.status().is_success() {
+        println!("{}", "Failed".red());
+        let err = response.text().await?;
+        anyhow::bail!("API Error: {}", err);
+    }
+    let release: serde_json::Value = response.json().await?;
+    let canary_id = crate::conversions::as_str(&release["id"], "id")?;
+
+    let advance = client
+        .post(format!("{}/api/canary/{}/advance", api_url, canary_id))
+        .json(&json!({ "canary_id": canary_id, "target_percentage": percent }))
+        .send()
+        .await
+        .context("Failed to advance canary release")?;
+    if !advance.status().is_success() {
+        println!("{}", "Failed".red());
+        let err = advance.text().await?;
+        anyhow::bail!("API Error: {}", err);
+    }
+    let release: serde_json::Value = advance.json().await?;
+    println!("{}", "OK".green());
+    println!("Canary ID: {}", canary_id);
+    println!(
+        "Routing: {}% new / {}% current",
+        release["current_percentage"].as_i64().unwrap_or(percent as i64),
+        100 - release["current_percentage"].as_i64().unwrap_or(percent as i64)
+    );
+    println!(
+        "\n  {} Complete the rollout with: soroban-registry migrate promote {}\n",
+        "→".bright_black(),
+        canary_id
+    );
+    Ok(())
+}
+
+/// Finish a canary rollout, sending all resolutions to the new version.
+pub async fn migrate_promote(api_url: &str, canary_id: &str) -> Result<()> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{}/api/canary/{}/complete", api_url, canary_id))
+        .send()
+        .await
+        .context("Failed to promote canary release")?;
+
+    if !response.status().is_success() {
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            anyhow::bail!("No active canary release {}", canary_id);
+        }
+        let err = response.text().await?;
+        anyhow::bail!("API Error: {}", err);
+    }
+
+    let release: serde_json::Value = response.json().await?;
+    println!("{}", "✓ Canary promoted — all traffic on the new version".green().bold());
+    println!(
+        "  {}: {}",
+        "Status".bold(),
+        release["status"].as_str().unwrap_or("completed")
+    );
+    Ok(())
+}
+
+/// The version to revert to from `current_hash`: the newest registered
+/// version older than the deployed one with a different wasm.
+fn rollback_target<'a>(
+    versions: &'a [serde_json::Value],
+    current_hash: &str,
+) -> Option<&'a serde_json::Value> {
+    let mut versions: Vec<&serde_json::Value> = versions.iter().collect();
+    versions.sort_by_key(|v| {
+        std::cmp::Reverse(
+            v["created_at"]
+                .as_str()
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok()),
+        )
+    });
+    let current = versions
+        .iter()
+        .position(|v| v["wasm_hash"].as_str() == Some(current_hash))?;
+    versions[current..]
+        .iter()
+        .find(|v| v["wasm_hash"].as_str().is_some_and(|h| h != current_hash))
+        .copied()
+}
+
+/// Re-deploy the wasm a contract ran before its current version, recorded
+/// in migration history like any other migration.
+pub async fn migrate_rollback(
+    api_url: &str,
+    contract_id: &str,
+    simulate_fail: bool,
+    dry_run: bool,
+) -> Result<()> {
+    println!("\n{}", "Migration Rollback".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+
+    let client = reqwest::Client::new();
+    let contract: serde_json::Value = client
+        .get(format!("{}/api/contracts/{}", api_url, contract_id))
+        .send()
+        .await
+        .context("Failed to fetch contract")?
+        .error_for_status()
+        .context("Failed to fetch contract")?
+        .json()
+        .await?;
+    let contract = contract.get("contract").unwrap_or(&contract);
+    let contract_uuid = crate::conversions::as_str(&contract["id"], "id")?;
+    let current_hash = crate::conversions::as_str(&contract["wasm_hash"], "wasm_hash")?;
+
+    let versions = fetch_version_history(&client, api_url, &contract_uuid).await?;
+    let Some(target) = rollback_target(&versions, &current_hash) else {
+        anyhow::bail!(
+            "No previous version recorded for {}; nothing to roll back to",
+            contract_id
+        );
+    };
+    let target_hash = crate::conversions::as_str(&target["wasm_hash"], "wasm_hash")?;
+
+    println!("Contract ID: {}", contract_id.green());
+    println!("Current WASM Hash: {}", current_hash.bright_black());
+    println!(
+        "Reverting to: {} {}",
+        target_hash.bold(),
+        format!("(version {})", target["version"].as_str().unwrap_or("?")).bright_black()
+    );
+
+    if dry_run {
+        println!(
+            "\n{}",
+            "Dry run enabled: nothing re-deployed or recorded.".yellow()
+        );
+        return Ok(());
+    }
+
+    let payload = json!({
+        "contract_id": contract_id,
+        "wasm_hash": target_hash,
+        "rollback_from": current_hash,
+    });
+    let (_, migration_id, status) =
+        record_migration(&client, api_url, &payload, simulate_fail).await?;
+    if status == shared::models::MigrationStatus::Failed {
+        anyhow::bail!("Rollback {} failed", migration_id);
+    }
+    crate::migration::record_redeploy_rollback(
+        &migration_id,
+        contract_id,
+        &current_hash,
+        &target_hash,
+        target["version"].as_str(),
+    )?;
+    println!(
+        "{}",
+        format!("✓ {} rolled back to {}", contract_id, target_hash)
+            .green()
+            .bold()
+    );
     Ok(())
 }
 
-pub async fn export(_api_url: &str, id: &str, output: &str, contract_dir: &str) -> Result<()> {
+/// Where `export` builds its archive and where the result ends up. When
+/// encrypting, the plaintext archive is built in a temp dir and only the
+/// encrypted `<output>.enc` is written next to the output.
+struct ExportTarget<'a> {
+    archive: std::path::PathBuf,
+    output: std::path::PathBuf,
+    passphrase: Option<&'a str>,
+    _staging: Option<tempfile::TempDir>,
+}
+
+impl<'a> ExportTarget<'a> {
+    fn new(output: &str, passphrase: Option<&'a str>) -> Result<Self> {
+        let Some(passphrase) = passphrase else {
+            return Ok(ExportTarget {
+                archive: output.into(),
+                output: output.into(),
+                passphrase: None,
+                _staging: None,
+            });
+        };
+        let staging = tempfile::tempdir().context("failed to create temp dir")?;
+        Ok(ExportTarget {
+            archive: staging.path().join("export.tar.gz"),
+            output: crate::export::encrypted_path(Path::new(output)),
+            passphrase: Some(passphrase),
+            _staging: Some(staging),
+        })
+    }
+
+    fn finish(&self) -> Result<()> {
+        if let Some(passphrase) = self.passphrase {
+            crate::export::encrypt_file(&self.archive, &self.output, passphrase)?;
+        }
+        Ok(())
+    }
+
+    fn print(&self, reproducible: bool) -> Result<()> {
+        println!("  {}: {}", "Output".bold(), self.output.display());
+        if self.passphrase.is_some() {
+            println!("  {}: AES-256-GCM, Argon2id key", "Encryption".bold());
+        }
+        if reproducible {
+            // Of the archive itself: encryption salts every run differently.
+            let digest = crate::io_utils::compute_sha256_streaming(&self.archive)?;
+            println!("  {}: {}", "SHA-256".bold(), digest);
+        }
+        Ok(())
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn export(
+    api_url: &str,
+    ids: &[String],
+    output: &str,
+    contract_dir: &str,
+    format: &str,
+    reproducible: bool,
+    continue_on_error: bool,
+    passphrase: Option<&str>,
+) -> Result<()> {
+    let target = ExportTarget::new(output, passphrase)?;
+    let [id] = ids else {
+        return export_bundle(
+            api_url,
+            ids,
+            &target,
+            contract_dir,
+            format,
+            reproducible,
+            continue_on_error,
+        )
+        .await;
+    };
+    let id = id.as_str();
     let source = std::path::Path::new(contract_dir);
     anyhow::ensure!(
         source.is_dir(),
         "contract directory does not exist: {}",
         contract_dir
     );
-    crate::export::create_archive(
-        source,
-        std::path::Path::new(output),
-        id,
-        "contract",
-        "testnet",
-    )?;
+    match format {
+        "tar.gz" | "tgz" => crate::export::create_archive(
+            source,
+            &target.archive,
+            id,
+            "contract",
+            "testnet",
+            reproducible,
+        )?,
+        #[cfg(feature = "oci")]
+        "oci" => crate::export::create_oci_archive(
+            source,
+            &target.archive,
+            id,
+            "contract",
+            "testnet",
+            reproducible,
+        )?,
+        #[cfg(not(feature = "oci"))]
+        "oci" => anyhow::bail!("OCI export requires the CLI to be built with the `oci` feature"),
+        other => anyhow::bail!("unsupported export format: {} (expected tar.gz or oci)", other),
+    }
+    target.finish()?;
     println!("{}", "✓ Export complete!".green().bold());
-    println!("  {}: {}", "Output".bold(), output);
+    target.print(reproducible)?;
+    println!("  {}: {}", "Format".bold(), format);
     println!("  {}: {}", "Contract".bold(), id.bright_black());
-    println!("  {}: contract\n", "Name".bold());
+    println!("  {}: contract", "Name".bold());
+    if reproducible {
+        println!(
+            "  {}: {}",
+            "Timestamps".bold(),
+            crate::export::reproducible_timestamp().to_rfc3339()
+        );
+    }
+    println!();
+    if format == "oci" && passphrase.is_none() {
+        println!(
+            "  {} Push with: oras cp --from-oci-layout {}:{} <registry>/<repo>:<tag>\n",
+            "→".bright_black(),
+            output,
+            id
+        );
+    }
+    Ok(())
+}
+
+/// `export` with several `--id`s: one archive with a directory per contract.
+/// Sources are taken from `<contract_dir>/<id>` where that exists.
+async fn export_bundle(
+    api_url: &str,
+    ids: &[String],
+    target: &ExportTarget<'_>,
+    contract_dir: &str,
+    format: &str,
+    reproducible: bool,
+    continue_on_error: bool,
+) -> Result<()> {
+    if !matches!(format, "tar.gz" | "tgz") {
+        anyhow::bail!("exporting several contracts needs --format tar.gz");
+    }
+    let mut seen = std::collections::HashSet::new();
+    let ids: Vec<&String> = ids.iter().filter(|id| seen.insert(*id)).collect();
+
+    let client = reqwest::Client::new();
+    let mut members = Vec::new();
+    let mut skipped = Vec::new();
+    for id in ids {
+        let fetched = async {
+            client
+                .get(format!("{}/api/contracts/{}", api_url, id))
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<serde_json::Value>()
+                .await
+        }
+        .await;
+        match fetched {
+            Ok(record) => {
+                let source_dir = Path::new(contract_dir).join(id);
+                members.push(crate::export::BundleMember {
+                    id: id.clone(),
+                    record,
+                    source_dir: source_dir.is_dir().then_some(source_dir),
+                });
+            }
+            Err(e) if continue_on_error => {
+                eprintln!("{} Skipping {}: {}", "⚠".yellow(), id, e);
+                skipped.push(crate::export::SkippedContract {
+                    id: id.clone(),
+                    error: e.to_string(),
+                });
+            }
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!(
+                        "Failed to fetch {}; bundle aborted (--continue-on-error skips it)",
+                        id
+                    )
+                })
+            }
+        }
+    }
+    if members.is_empty() {
+        anyhow::bail!("None of the contracts could be fetched; nothing exported");
+    }
+
+    let index = crate::export::create_bundle(&members, skipped, &target.archive, reproducible)?;
+    target.finish()?;
+    println!("{}", "✓ Bundle export complete!".green().bold());
+    target.print(reproducible)?;
+    println!("  {}: {}", "Contracts".bold(), index.contracts.len());
+    for entry in &index.contracts {
+        let sources = if entry.sha256.is_some() {
+            "with sources".green()
+        } else {
+            "metadata only".bright_black()
+        };
+        println!(
+            "    {} {} ({}) {}",
+            "✓".green(),
+            entry.id.bright_black(),
+            entry.name,
+            sources
+        );
+    }
+    for skip in &index.skipped {
+        println!(
+            "    {} {} {}",
+            "✗".red(),
+            skip.id.bright_black(),
+            skip.error
+        );
+    }
+    println!();
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn import(
     api_url: &str,
     archive: &str,
     network: Network,
     output_dir: &str,
-) -> Result<()> {
-    println!("\n{}", "Importing contract...".bold().cyan());
-
-    let archive_path = std::path::Path::new(archive);
+    json: bool,
+    new_contract_id: Option<&str>,
+    republish_as: Option<&str>,
+    passphrase: Option<&str>,
+    skip_verify: bool,
+    conflict: crate::io_utils::Conflict,
+) -> Result<crate::import::ImportSummary> {
+    // Archives piped through stdin are spooled to a temp file, since the
+    // extractors need to seek and re-read.
+    let spooled = if crate::io_utils::is_stdin(archive) {
+        let mut file = tempfile::NamedTempFile::new().context("Failed to create temp file")?;
+        std::io::Write::write_all(&mut file, &crate::io_utils::read_input(archive)?)
+            .context("Failed to buffer archive from stdin")?;
+        Some(file)
+    } else {
+        None
+    };
+    let archive_path = match &spooled {
+        Some(file) => file.path(),
+        None => std::path::Path::new(archive),
+    };
     anyhow::ensure!(archive_path.is_file(), "archive not found: {}", archive);
 
+    // `export --encrypt` archives are decrypted to a temp file first.
+    let decrypted = if crate::import::is_encrypted_archive(archive_path)? {
+        let passphrase = match passphrase {
+            Some(passphrase) => passphrase.to_string(),
+            None => crate::export::prompt_passphrase(false)?,
+        };
+        let file = tempfile::NamedTempFile::new().context("Failed to create temp file")?;
+        crate::import::decrypt_archive(archive_path, file.path(), &passphrase)?;
+        Some(file)
+    } else {
+        None
+    };
+    let archive_path = match &decrypted {
+        Some(file) => file.path(),
+        None => archive_path,
+    };
+
     let dest = std::path::Path::new(output_dir);
+    crate::io_utils::check_output_dir(dest, conflict)?;
 
-    let manifest = crate::import::extract_and_verify(archive_path, dest)?;
+    if !json {
+        println!("\n{}", "Importing contract...".bold().cyan());
+    }
 
-    println!(
-        "{}",
-        "✓ Import complete — integrity verified!".green().bold()
-    );
+    #[cfg(feature = "oci")]
+    let mut summary = if crate::import::is_oci_archive(archive_path)? {
+        crate::import::extract_oci_archive(archive_path, dest, conflict)?
+    } else {
+        crate::import::extract_and_verify(archive_path, dest, skip_verify, conflict)?
+    };
+    #[cfg(not(feature = "oci"))]
+    let mut summary = crate::import::extract_and_verify(archive_path, dest, skip_verify, conflict)?;
+    if decrypted.is_some() {
+        summary.archive = archive.to_string();
+    }
+
+    let rewritten = match new_contract_id {
+        Some(id) => Some(crate::import::rehome(&mut summary, dest, id)?),
+        None => None,
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+    } else {
+        print_import_summary(&summary, network, output_dir, rewritten, republish_as);
+    }
+
+    if let Some(publisher) = republish_as {
+        let manifest = &summary.manifest;
+        publish(
+            api_url,
+            PublishParams {
+                contract_id: &manifest.contract_id,
+                name: &manifest.name,
+                description: None,
+                network,
+                category: None,
+                tags: vec![],
+                publisher,
+                is_cicd: false,
+                contract_path: output_dir,
+                test_command: None,
+                require_coverage: false,
+                coverage_threshold: 0.0,
+                skip_tests: true,
+                strict: false,
+                audits: &[],
+                custom_fields: &Default::default(),
+                preflight: false,
+                force: false,
+                optimize: false,
+                relations: &[],
+                metadata_schema: None,
+                dry_run: false,
+                wasm: None,
+                expected_hash: None,
+            },
+        )
+        .await
+        .context("Failed to re-publish imported contract")?;
+    }
+
+    Ok(summary)
+}
+
+/// Overwritten or skipped paths listed by `import` before it elides the rest.
+const IMPORT_FILES_SHOWN: usize = 10;
+
+fn print_import_summary(
+    summary: &crate::import::ImportSummary,
+    network: Network,
+    output_dir: &str,
+    rewritten: Option<usize>,
+    republishing: Option<&str>,
+) {
+    let manifest = &summary.manifest;
+    if summary.verification.skipped {
+        println!(
+            "{}",
+            "⚠ Import complete — integrity NOT verified (--skip-verify)"
+                .yellow()
+                .bold()
+        );
+    } else {
+        println!(
+            "{}",
+            "✓ Import complete — integrity verified!".green().bold()
+        );
+    }
     println!(
         "  {}: {}",
         "Contract".bold(),
@@ -1145,8 +2362,65 @@ pub async fn import(
         "Contents".bold(),
         manifest.contents.len()
     );
+    println!(
+        "  {}: {} file(s), {} bytes",
+        "Extracted".bold(),
+        summary.files_extracted,
+        summary.bytes_extracted
+    );
+    if summary.verification.files_verified > 0 {
+        println!(
+            "  {}: {} file(s) match {}",
+            "Verified".bold(),
+            summary.verification.files_verified,
+            crate::export::CHECKSUMS_FILE
+        );
+    }
     println!("  {}: {}", "Extracted To".bold(), output_dir);
+    println!(
+        "  {}: {} created, {} overwritten, {} skipped",
+        "Files".bold(),
+        summary.files_created,
+        summary.files_overwritten.len(),
+        summary.files_skipped.len()
+    );
+    for (label, paths) in [
+        ("overwritten", &summary.files_overwritten),
+        ("skipped (already present)", &summary.files_skipped),
+    ] {
+        for path in paths.iter().take(IMPORT_FILES_SHOWN) {
+            println!(
+                "    {} {} {}",
+                "→".bright_black(),
+                path,
+                label.bright_black()
+            );
+        }
+        if paths.len() > IMPORT_FILES_SHOWN {
+            println!(
+                "    {}",
+                format!(
+                    "... and {} more {}",
+                    paths.len() - IMPORT_FILES_SHOWN,
+                    label
+                )
+                .bright_black()
+            );
+        }
+    }
+    if let (Some(old_id), Some(files)) = (&summary.rehomed_from, rewritten) {
+        println!(
+            "  {}: {} ({} file(s) rewritten, signature dropped)",
+            "Re-homed From".bold(),
+            old_id.bright_black(),
+            files
+        );
+    }
 
+    if republishing.is_some() {
+        println!();
+        return;
+    }
     println!(
         "\n  {} To register on {}, run:",
         "→".bright_black(),
@@ -1156,8 +2430,6 @@ pub async fn import(
         "    soroban-registry publish --contract-id {} --name \"{}\" --network {} --publisher <address>\n",
         manifest.contract_id, manifest.name, network
     );
-
-    Ok(())
 }
 
 fn severity_colored(sev: &Severity) -> colored::ColoredString {
@@ -1284,14 +2556,108 @@ pub async fn trust_score(api_url: &str, contract_id: &str, network: Network) ->
     Ok(())
 }
 
-pub async fn patch_notify(api_url: &str, patch_id: &str) -> Result<()> {
-    println!("\n{}", "Identifying vulnerable contracts...".bold().cyan());
-
-    let (patch, contracts) = PatchManager::find_vulnerable(api_url, patch_id).await?;
-
-    println!(
-        "\n{} {} patch for version {}",
-        "⚠".bold(),
+pub async fn patch_list(
+    api_url: &str,
+    severity: Option<Severity>,
+    version: Option<&str>,
+    publisher: Option<&str>,
+    format: crate::output::OutputFormat,
+) -> Result<()> {
+    if format == crate::output::OutputFormat::Csv {
+        anyhow::bail!("patch list has no CSV output; use --format table, json or yaml");
+    }
+    let patches = PatchManager::list(api_url, severity, version).await?;
+    let publisher = match publisher {
+        Some(account) => Some(account.to_string()),
+        None => crate::publisher::current()?,
+    };
+    let owned = match &publisher {
+        Some(account) => Some(
+            crate::publisher::fetch_contracts(&reqwest::Client::new(), api_url, account).await?,
+        ),
+        None => None,
+    };
+    let listings: Vec<crate::patch::PatchListing> = patches
+        .into_iter()
+        .map(|patch| crate::patch::PatchListing {
+            affected_contracts: owned
+                .as_deref()
+                .map(|contracts| crate::patch::affected_by(&patch, contracts)),
+            patch,
+        })
+        .collect();
+
+    let emitter = crate::output::Emitter::current();
+    if let Some(structured) = format.structured() {
+        return emitter.emit(&listings, structured);
+    }
+    if emitter.structured() {
+        return emitter.emit(&listings, crate::output::Format::Json);
+    }
+
+    println!("\n{}", "Security Patches".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    if let Some(account) = &publisher {
+        println!("  {}: {}", "Publisher".bold(), account);
+    }
+    if listings.is_empty() {
+        println!("{}", "No patches found.".yellow());
+        println!();
+        return Ok(());
+    }
+    println!(
+        "{:<36}  {:<18}  {:<8}  {:>7}  {}",
+        "ID".bold(),
+        "Target Version".bold(),
+        "Severity".bold(),
+        "Rollout".bold(),
+        "Affected".bold()
+    );
+    for listing in &listings {
+        let patch = &listing.patch;
+        let target = if patch.target_version.chars().count() > 18 {
+            format!(
+                "{}…",
+                patch.target_version.chars().take(17).collect::<String>()
+            )
+        } else {
+            patch.target_version.clone()
+        };
+        let affected = match &listing.affected_contracts {
+            None => "—".bright_black(),
+            Some(ids) if ids.is_empty() => "no".green(),
+            Some(ids) => format!("yes ({})", ids.len()).red().bold(),
+        };
+        println!(
+            "{:<36}  {:<18}  {:<8}  {:>6}%  {}",
+            patch.id,
+            target,
+            severity_colored(&patch.severity),
+            patch.rollout_percentage,
+            affected
+        );
+    }
+    println!("\n{} patch(es)", listings.len());
+    if publisher.is_none() {
+        println!(
+            "{}",
+            "Set [defaults] publisher in the config file, or pass --publisher, to check which \
+             patches affect your contracts."
+                .bright_black()
+        );
+    }
+    println!();
+    Ok(())
+}
+
+pub async fn patch_notify(api_url: &str, patch_id: &str) -> Result<()> {
+    println!("\n{}", "Identifying vulnerable contracts...".bold().cyan());
+
+    let (patch, contracts) = PatchManager::find_vulnerable(api_url, patch_id).await?;
+
+    println!(
+        "\n{} {} patch for version {}",
+        "⚠".bold(),
         severity_colored(&patch.severity),
         patch.target_version.bold()
     );
@@ -1321,38 +2687,242 @@ pub async fn patch_notify(api_url: &str, patch_id: &str) -> Result<()> {
     Ok(())
 }
 
-pub async fn patch_apply(api_url: &str, contract_id: &str, patch_id: &str) -> Result<()> {
+pub async fn patch_apply(
+    api_url: &str,
+    contract_id: &str,
+    patch_id: &str,
+    force: bool,
+) -> Result<()> {
     println!("\n{}", "Applying security patch...".bold().cyan());
 
-    let audit = PatchManager::apply(api_url, contract_id, patch_id).await?;
+    let (audit, previous) = PatchManager::apply(api_url, contract_id, patch_id, force).await?;
 
     println!("{}", "✓ Patch applied successfully!".green().bold());
     println!("  {}: {}", "Contract".bold(), audit.contract_id);
     println!("  {}: {}", "Patch".bold(), audit.patch_id);
-    println!("  {}: {}\n", "Applied At".bold(), audit.applied_at);
+    println!("  {}: {}", "Applied At".bold(), audit.applied_at);
+    match previous {
+        Some(hash) => println!(
+            "  {}: {} {}\n",
+            "Pre-patch WASM".bold(),
+            hash.bright_black(),
+            "(recorded for patch rollback)".bright_black()
+        ),
+        None => println!(
+            "  {} {}\n",
+            "⚠".yellow(),
+            "Applied with --force without the pre-patch WASM hash; this patch can't be rolled back"
+                .yellow()
+        ),
+    }
 
     Ok(())
 }
 
-pub async fn deps_list(api_url: &str, contract_id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
-    let url = format!("{}/api/contracts/{}/dependencies", api_url, contract_id);
+pub async fn patch_promote(api_url: &str, patch_id: &str, rollout: u8) -> Result<()> {
+    println!("\n{}", "Promoting security patch...".bold().cyan());
 
-    let response = client
-        .get(&url)
-        .send()
-        .await
-        .context("Failed to fetch contract dependencies")?;
+    let (patch, previous) = PatchManager::promote(api_url, patch_id, rollout).await?;
+    let (_, contracts) = PatchManager::find_vulnerable(api_url, patch_id).await?;
+    let ids: Vec<&str> = contracts
+        .iter()
+        .filter_map(|c| c["id"].as_str().or_else(|| c["contract_id"].as_str()))
+        .collect();
+    let cohort = |pct: u8| {
+        ids.iter()
+            .filter(|id| PatchManager::in_cohort(id, pct))
+            .count()
+    };
 
-    if !response.status().is_success() {
-        if response.status() == reqwest::StatusCode::NOT_FOUND {
-            anyhow::bail!("Contract not found");
+    println!("{}", "✓ Rollout widened".green().bold());
+    println!("  {}: {}", "Patch".bold(), patch.id);
+    println!(
+        "  {}: {}% → {}%",
+        "Rollout".bold(),
+        previous,
+        patch.rollout_percentage
+    );
+    println!(
+        "  {}: {} → {} of {} affected contract(s)\n",
+        "Cohort".bold(),
+        cohort(previous),
+        cohort(patch.rollout_percentage),
+        ids.len()
+    );
+
+    Ok(())
+}
+
+pub async fn patch_rollback(
+    api_url: &str,
+    contract_id: &str,
+    patch_id: &str,
+    dry_run: bool,
+) -> Result<()> {
+    let log = crate::patch::read_patch_log()?;
+    let Some(applied) = crate::patch::rollback_target(&log, contract_id, patch_id)? else {
+        anyhow::bail!(
+            "no pre-patch state recorded for contract {} and patch {}; only patches applied with `patch apply` on this machine can be rolled back",
+            contract_id,
+            patch_id
+        );
+    };
+
+    println!("\n{}", "Rolling back security patch...".bold().cyan());
+    println!("  {}: {}", "Contract".bold(), contract_id);
+    println!("  {}: {}", "Patch".bold(), patch_id);
+    println!("  {}: {}", "Applied At".bold(), applied.at);
+    println!(
+        "  {}: {}",
+        "Patched WASM".bold(),
+        applied.wasm_hash.bright_black()
+    );
+    println!(
+        "  {}: {}",
+        "Restores WASM".bold(),
+        applied.previous_wasm_hash.bright_black()
+    );
+
+    if dry_run {
+        println!("\n{}\n", "Dry run — nothing rolled back".yellow());
+        return Ok(());
+    }
+
+    let rollback = PatchManager::rollback(api_url, applied).await?;
+    println!("\n{}", "✓ Patch rolled back".green().bold());
+    println!("  {}: {}", "Restored Hash".bold(), rollback.wasm_hash);
+    println!("  {}: {}\n", "Rolled Back At".bold(), rollback.at);
+
+    Ok(())
+}
+
+pub async fn patch_verify(api_url: &str, patch_id: &str, wasm_url: Option<&str>) -> Result<()> {
+    println!("\n{}", "Verifying patch build...".bold().cyan());
+
+    let result = PatchManager::verify(api_url, patch_id, wasm_url).await?;
+
+    println!("  {}: {}", "Patch".bold(), result.patch.id);
+    println!(
+        "  {}: {}",
+        "Severity".bold(),
+        severity_colored(&result.patch.severity)
+    );
+    println!(
+        "  {}: {}",
+        "Expected Hash".bold(),
+        result.patch.new_wasm_hash.bright_black()
+    );
+    println!(
+        "  {}: {}",
+        "Computed Hash".bold(),
+        result.computed_hash.bright_black()
+    );
+    println!("  {}: {} bytes", "WASM Size".bold(), result.wasm_size);
+
+    if result.hash_matches {
+        println!("  {} Hash matches", "✓".green());
+    } else {
+        println!("  {} Hash mismatch", "✗".red());
+    }
+    match &result.module_error {
+        None => println!("  {} Valid Soroban module", "✓".green()),
+        Some(err) => println!("  {} Invalid module: {}", "✗".red(), err),
+    }
+    println!();
+
+    if !result.passed() {
+        anyhow::bail!("patch {} failed verification", patch_id);
+    }
+
+    println!("{}\n", "✓ Patch build verified".green().bold());
+    Ok(())
+}
+
+pub async fn patch_simulate_rollout(
+    api_url: &str,
+    schedule: &crate::patch::RolloutSchedule,
+    patch_id: Option<&str>,
+    contracts: Option<usize>,
+    json: bool,
+) -> Result<()> {
+    let affected = match (contracts, patch_id) {
+        (Some(count), _) => count,
+        (None, Some(patch_id)) => {
+            let (_, vulnerable) = PatchManager::find_vulnerable(api_url, patch_id).await?;
+            vulnerable.len()
         }
-        anyhow::bail!("Failed to fetch dependencies: {}", response.status());
+        (None, None) => anyhow::bail!("pass --patch-id or --contracts"),
+    };
+    let now = chrono::Utc::now();
+    let projection = schedule.project(affected, now);
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(&projection, crate::output::Format::Json)?;
+        return Ok(());
+    }
+
+    println!("\n{}", "Rollout Simulation".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    println!("  {}: {}", "Affected Contracts".bold(), affected);
+    println!(
+        "  {}: {} stage(s)\n",
+        "Schedule".bold(),
+        schedule.stages.len()
+    );
+
+    println!(
+        "  {:<7} {:>7} {:>9} {:>12}  {}",
+        "Stage".bold(),
+        "Target".bold(),
+        "Patched".bold(),
+        "Cumulative".bold(),
+        "Starts".bold()
+    );
+    for (i, stage) in projection.stages.iter().enumerate() {
+        let offset = stage.starts_at - now;
+        let starts = if offset.is_zero() {
+            "now".to_string()
+        } else {
+            format!(
+                "+{} ({})",
+                crate::activity::format_window(offset),
+                stage.starts_at.format("%Y-%m-%d %H:%M UTC")
+            )
+        };
+        println!(
+            "  {:<7} {:>6}% {:>9} {:>12}  {}",
+            i + 1,
+            stage.percent,
+            stage.patched,
+            stage.cumulative,
+            starts
+        );
+    }
+
+    println!(
+        "\n  {} Estimated completion: {}",
+        "→".cyan(),
+        projection.completes_at.format("%Y-%m-%d %H:%M UTC")
+    );
+    if projection.unpatched > 0 {
+        println!(
+            "  {} Schedule stops at {}%; {} contract(s) stay unpatched",
+            "⚠".yellow(),
+            schedule.stages.last().map_or(0, |s| s.percent),
+            projection.unpatched
+        );
+    }
+    if affected == 0 {
+        println!("  {} No contracts are affected", "⚠".yellow());
     }
+    println!();
+    Ok(())
+}
 
-    let items: serde_json::Value = response.json().await?;
-    let tree = items.as_array().context("Invalid response format")?;
+pub async fn deps_list(api_url: &str, contract_id: &str, max_depth: usize) -> Result<()> {
+    let client = reqwest::Client::new();
+    let tree = crate::deps::fetch_tree(&client, api_url, contract_id, max_depth).await?;
 
     println!("\n{}", "Dependency Tree:".bold().cyan());
     println!("{}", "=".repeat(80).cyan());
@@ -1395,9 +2965,6 @@ pub async fn deps_list(api_url: &str, contract_id: &str) -> Result<()> {
 
             if let Some(children) = node["dependencies"].as_array() {
                 if !children.is_empty() {
-                    let new_prefix =
-                        format!("{}{}", prefix, if is_node_last { "    " } else { "│   " });
-                    let _ = print_tree(children, &new_prefix, true);
                     let new_prefix =
                         format!("{}{}", prefix, if is_node_last { "    " } else { "│   " });
                     print_tree(children, &new_prefix, true)?;
@@ -1407,33 +2974,130 @@ pub async fn deps_list(api_url: &str, contract_id: &str) -> Result<()> {
         Ok(())
     }
 
-    print_tree(tree, "", false)?;
+    print_tree(&tree, "", false)?;
 
     println!("\n{}", "=".repeat(80).cyan());
     println!();
     Ok(())
 }
 
+/// `test --dry-run`: resolve the scenario and print the cases a run would
+/// execute, in order, without executing any.
+pub fn plan_tests(test_file: &str, contract_path: Option<&str>, seed: u64) -> Result<()> {
+    let test_path = Path::new(test_file);
+    if !crate::io_utils::is_stdin(test_file) && !test_path.exists() {
+        anyhow::bail!("Test file not found: {}", test_file);
+    }
+
+    let runner = test_framework::TestRunner::new(contract_path.unwrap_or("."), seed)?;
+    let scenario = test_framework::load_test_scenario(test_path)?;
+    let plan = runner.plan(&scenario);
+    let problems = plan.problems().count();
+
+    let emitter = crate::output::Emitter::current();
+    if emitter.structured() {
+        emitter.emit(&plan, crate::output::Format::Json)?;
+    } else {
+        use test_framework::Phase;
+
+        println!("\n{}", "Test Plan (dry run)".bold().cyan());
+        println!("{}", "=".repeat(80).cyan());
+        println!("{} {}", "Scenario:".bold(), plan.scenario);
+        println!("{} {}", "Seed:".bold(), plan.seed);
+        println!(
+            "{} {}",
+            "Contracts:".bold(),
+            if plan.contracts.is_empty() {
+                "none found".to_string()
+            } else {
+                plan.contracts.join(", ")
+            }
+        );
+        println!();
+
+        for (i, case) in plan.cases.iter().enumerate() {
+            let phase = match case.phase {
+                Phase::Setup => "setup",
+                Phase::Step => "step",
+                Phase::Teardown => "teardown",
+            };
+            let glyph = if case.problem.is_some() {
+                "✗".red()
+            } else {
+                "→".cyan()
+            };
+            let mut detail = Vec::new();
+            if let Some(ref target) = case.target {
+                detail.push(target.clone());
+            }
+            if case.assertions > 0 {
+                detail.push(format!("{} assertion(s)", case.assertions));
+            }
+            if let Some(ref expected) = case.expected_error {
+                detail.push(format!("expects error '{}'", expected));
+            }
+            println!(
+                "  {} {:>3}. {:<9} {} {}",
+                glyph,
+                i + 1,
+                phase.bright_black(),
+                case.name.bold(),
+                detail.join(", ").bright_black()
+            );
+            if let Some(ref problem) = case.problem {
+                println!("          {}", problem.red());
+            }
+        }
+
+        println!("{}", "=".repeat(80).cyan());
+        println!(
+            "{} case(s): {} setup, {} step(s), {} teardown. Nothing was executed.",
+            plan.cases.len(),
+            plan.count(Phase::Setup),
+            plan.count(Phase::Step),
+            plan.count(Phase::Teardown)
+        );
+    }
+
+    if problems > 0 {
+        anyhow::bail!("{} case(s) would fail before reaching a contract", problems);
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn run_tests(
     test_file: &str,
     contract_path: Option<&str>,
     junit_output: Option<&str>,
     show_coverage: bool,
     verbose: bool,
+    seed: u64,
+    coverage_baseline: Option<&str>,
+    use_cache: bool,
 ) -> Result<()> {
     let test_path = Path::new(test_file);
-    if !test_path.exists() {
+    if !crate::io_utils::is_stdin(test_file) && !test_path.exists() {
         anyhow::bail!("Test file not found: {}", test_file);
     }
 
     let contract_dir = contract_path.unwrap_or(".");
-    let mut runner = test_framework::TestRunner::new(contract_dir)?;
+    let mut runner = test_framework::TestRunner::new(contract_dir, seed)?;
 
     println!("\n{}", "Running Integration Tests...".bold().cyan());
     println!("{}", "=".repeat(80).cyan());
+    println!("{} {}", "Seed:".bold(), seed);
 
     let scenario = test_framework::load_test_scenario(test_path)?;
 
+    let cache_path = if use_cache {
+        let path = test_framework::ResultCache::path(&scenario.name, contract_dir)?;
+        runner = runner.with_cache(test_framework::ResultCache::load(&path));
+        Some(path)
+    } else {
+        None
+    };
+
     if verbose {
         println!("\n{}: {}", "Scenario".bold(), scenario.name);
         if let Some(desc) = &scenario.description {
@@ -1446,6 +3110,12 @@ pub async fn run_tests(
     let result = runner.run_scenario(scenario).await?;
     let total_time = start_time.elapsed();
 
+    if let (Some(path), Some(cache)) = (&cache_path, runner.cache()) {
+        if let Err(e) = cache.save(path) {
+            println!("{} Test cache not updated: {:#}", "⚠".yellow(), e);
+        }
+    }
+
     println!("\n{}", "Test Results:".bold().green());
     println!("{}", "=".repeat(80).cyan());
 
@@ -1463,19 +3133,23 @@ pub async fn run_tests(
         if let Some(ref err) = result.error {
             println!("{} {}", "Error:".bold().red(), err);
         }
+        println!(
+            "{} re-run with --seed {}",
+            "Replay:".bold().yellow(),
+            result.seed
+        );
     }
 
     println!("\n{}", "Step Results:".bold());
     for (i, step) in result.steps.iter().enumerate() {
         let step_icon = if step.passed { "✓" } else { "✗" };
+        let timing = if step.cached {
+            "cached".bright_black().to_string()
+        } else {
+            format!("{:.2}ms", step.duration.as_secs_f64() * 1000.0)
+        };
 
-        println!(
-            "  {}. {} {} ({:.2}ms)",
-            i + 1,
-            step_icon,
-            step.step_name.bold(),
-            step.duration.as_secs_f64() * 1000.0
-        );
+        println!("  {}. {} {} ({})", i + 1, step_icon, step.step_name.bold(), timing);
 
         if verbose {
             println!(
@@ -1489,6 +3163,14 @@ pub async fn run_tests(
             println!("     {}", err.red());
         }
     }
+    let cached = result.steps.iter().filter(|s| s.cached).count();
+    if cached > 0 {
+        println!(
+            "  {} step(s) reused from the last passing run, {} run (--no-cache to run all)",
+            cached,
+            result.steps.len() - cached
+        );
+    }
 
     if show_coverage {
         println!("\n{}", "Coverage Report:".bold().magenta());
@@ -1504,6 +3186,27 @@ pub async fn run_tests(
         }
     }
 
+    let mut coverage_regressed = false;
+    if let Some(baseline_path) = coverage_baseline.map(Path::new) {
+        let current = test_framework::CoverageBaseline::from_result(&result);
+        match test_framework::CoverageBaseline::load(baseline_path)? {
+            Some(baseline) => {
+                let delta = baseline.compare(&current);
+                coverage_regressed = delta.regressions().next().is_some();
+                print_coverage_delta(&delta);
+            }
+            None if result.passed => {
+                current.save(baseline_path)?;
+                println!(
+                    "\n{} Coverage baseline written to: {}",
+                    "✓".green(),
+                    baseline_path.display()
+                );
+            }
+            None => {}
+        }
+    }
+
     let passed = result.passed;
     if let Some(junit_path) = junit_output {
         test_framework::generate_junit_xml(&[result], Path::new(junit_path))?;
@@ -1528,15 +3231,91 @@ pub async fn run_tests(
     if !passed {
         anyhow::bail!("Tests failed");
     }
+    if coverage_regressed {
+        anyhow::bail!("Coverage dropped on changed methods");
+    }
 
     Ok(())
 }
 
+fn print_coverage_delta(delta: &test_framework::CoverageDelta) {
+    println!("\n{}", "Coverage vs Baseline:".bold().magenta());
+    let change = format!("{:+.2}%", delta.delta());
+    println!(
+        "  Coverage: {:.2}% → {:.2}% ({})",
+        delta.baseline_percent,
+        delta.current_percent,
+        if delta.delta() < 0.0 {
+            change.red()
+        } else {
+            change.green()
+        }
+    );
+
+    if delta.changed.is_empty() {
+        println!("  No changed methods since the baseline");
+        return;
+    }
+    println!("  Changed Methods: {}", delta.changed.len());
+    for method in &delta.changed {
+        let (icon, note) = match (method.was_covered, method.covered) {
+            (_, true) => ("✓".green(), "covered"),
+            (None, false) => ("✗".red(), "new, not covered"),
+            (Some(true), false) => ("✗".red(), "no longer covered"),
+            (Some(false), false) => ("⚠".yellow(), "not covered (wasn't before)"),
+        };
+        println!("    {} {} — {}", icon, method.method, note);
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::extract_migration_id;
+    use super::{extract_migration_id, rollback_target, write_search_csv};
     use serde_json::json;
 
+    #[test]
+    fn search_csv_quotes_fields_and_keeps_the_header() {
+        let items = vec![
+            json!({ "contract_id": "CA1", "name": "Swap, v2", "category": "dex", "is_verified": true, "downloads": 12 }),
+            json!({ "contract_id": "CB2", "name": "Vault \"Pro\"", "is_verified": false }),
+        ];
+        let mut out = Vec::new();
+        write_search_csv(&mut out, &items).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "contract_id,name,category,verified,downloads\n\
+             CA1,\"Swap, v2\",dex,true,12\n\
+             CB2,\"Vault \"\"Pro\"\"\",,false,\n"
+        );
+
+        let mut empty = Vec::new();
+        write_search_csv(&mut empty, &[]).unwrap();
+        assert_eq!(
+            String::from_utf8(empty).unwrap(),
+            "contract_id,name,category,verified,downloads\n"
+        );
+    }
+
+    #[test]
+    fn rollback_targets_the_version_before_the_deployed_one() {
+        let versions = vec![
+            json!({"version": "1.0.0", "wasm_hash": "aa", "created_at": "2024-01-01T00:00:00Z"}),
+            json!({"version": "1.2.0", "wasm_hash": "cc", "created_at": "2024-03-01T00:00:00Z"}),
+            json!({"version": "1.1.0", "wasm_hash": "bb", "created_at": "2024-02-01T00:00:00Z"}),
+            json!({"version": "1.1.1", "wasm_hash": "bb", "created_at": "2024-02-15T00:00:00Z"}),
+        ];
+        assert_eq!(
+            rollback_target(&versions, "cc").unwrap()["version"],
+            "1.1.1"
+        );
+        assert_eq!(
+            rollback_target(&versions, "bb").unwrap()["version"],
+            "1.0.0"
+        );
+        assert!(rollback_target(&versions, "aa").is_none());
+        assert!(rollback_target(&versions, "ff").is_none());
+    }
+
     #[test]
     fn extract_migration_id_returns_id_for_valid_payload() {
         let payload = json!({"id": "migration-123"});
@@ -2379,18 +4158,75 @@ pub async fn list_functions(api_url: &str, contract_id: &str) -> Result<()> {
     Ok(())
 }
 
+/// How much detail `info` prints in text mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InfoDisplay {
+    /// One line of key fields
+    Compact,
+    #[default]
+    Standard,
+    /// Everything, including versions, network config and raw metadata
+    Wide,
+}
+
+/// Fetch a contract's registered versions (with their release and change
+/// notes) by registry UUID.
+pub async fn fetch_version_history(
+    client: &reqwest::Client,
+    api_url: &str,
+    contract_uuid: &str,
+) -> Result<Vec<serde_json::Value>> {
+    let url = format!("{}/api/contracts/{}/versions", api_url, contract_uuid);
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to fetch version history")?;
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to fetch version history: {}", response.status());
+    }
+    response.json().await.context("Invalid version history response")
+}
+
+/// Top-level sections of the `info` result, for `--fields` validation.
+/// Nested fields (`metadata.name`) are addressed with dotted paths.
+pub const INFO_FIELDS: &[&str] = &[
+    "metadata",
+    "current_network_config",
+    "abi",
+    "deployments",
+    "dependencies",
+    "dependents",
+    "relations",
+    "versions",
+    "chain_integrity",
+];
+
 /// Fetch contract info from the registry. `id` is the contract's registry identifier.
+#[allow(clippy::too_many_arguments)]
 pub async fn info(
     api_url: &str,
     id: &str,
-    format: &str,
+    format: crate::output::OutputFormat,
     highlight_method: Option<&str>,
     network: crate::config::Network,
+    display: InfoDisplay,
+    fields: Option<&crate::output::Projection>,
+    check_chain: bool,
 ) -> Result<()> {
+    if format == crate::output::OutputFormat::Csv {
+        anyhow::bail!("info has no CSV output; use --format table, json or yaml");
+    }
+    if let Some(fields) = fields {
+        fields.validate(INFO_FIELDS)?;
+    }
     let client = reqwest::Client::new();
     let base_url = api_url.trim_end_matches('/');
 
-    if format == "text" {
+    if format == crate::output::OutputFormat::Table
+        && display != InfoDisplay::Compact
+        && fields.is_none()
+    {
         println!("\n{}", "Fetching contract information...".bold().cyan());
     }
 
@@ -2400,15 +4236,23 @@ pub async fn info(
         .get(&metadata_url)
         .query(&[("network", network.to_string())])
         .send()
-        .await?;
+        .await
+        .context("Failed to reach registry API")?;
 
+    // In JSON mode main prints these as an `{"error": ...}` object.
+    if metadata_res.status() == reqwest::StatusCode::NOT_FOUND {
+        anyhow::bail!("Contract not found: {}", id);
+    }
     if !metadata_res.status().is_success() {
         anyhow::bail!(
             "Failed to fetch contract metadata: {}",
             metadata_res.status()
         );
     }
-    let metadata: serde_json::Value = metadata_res.json().await?;
+    let metadata: serde_json::Value = metadata_res
+        .json()
+        .await
+        .context("Invalid contract metadata response")?;
 
     // Extract genuine UUID if 'id' was a name or address
     let contract_uuid = metadata["contract"]["id"]
@@ -2482,20 +4326,34 @@ pub async fn info(
     };
 
     // 6. Fetch Versions (for verification status)
-    let versions_url = format!("{}/api/contracts/{}/versions", base_url, contract_uuid);
-    let versions_res = client.get(&versions_url).send().await;
-    let versions: Vec<serde_json::Value> = if let Ok(res) = versions_res {
-        if res.status().is_success() {
-            res.json().await.unwrap_or_default()
-        } else {
-            Vec::new()
-        }
-    } else {
-        Vec::new()
+    let versions = fetch_version_history(&client, base_url, contract_uuid)
+        .await
+        .unwrap_or_default();
+
+    // 7. Fetch typed relations (supersedes / forks / related)
+    let relations = crate::relations::fetch_graph(&client, base_url, contract_uuid).await;
+
+    // 8. Compare the deployed wasm with the registered hash (opt-in: costs an RPC round trip)
+    let integrity = if check_chain {
+        let chain_network = metadata["contract"]["network"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| network.to_string());
+        Some(
+            crate::contract_verify::check_chain_integrity(
+                &client,
+                contract_address,
+                &chain_network,
+                metadata["contract"]["wasm_hash"].as_str(),
+            )
+            .await,
+        )
+    } else {
+        None
     };
 
     // Aggregate data
-    let full_info = json!({
+    let mut full_info = json!({
         "metadata": metadata["contract"],
         "current_network_config": metadata["network_config"],
         "abi": abi,
@@ -2504,22 +4362,43 @@ pub async fn info(
         "dependents": dependents,
         "versions": versions,
     });
+    if let Some(integrity) = &integrity {
+        full_info["chain_integrity"] = serde_json::to_value(integrity)?;
+    }
+    if !relations.is_empty() {
+        full_info["relations"] = serde_json::to_value(&relations)?;
+    }
 
     // Render output
-    match format {
-        "json" => {
-            println!("{}", serde_json::to_string_pretty(&full_info)?);
+    let emitter = crate::output::Emitter::current();
+    if let Some(fields) = fields {
+        let format = format.structured().unwrap_or(crate::output::Format::Json);
+        emitter.emit(&fields.apply(&full_info), format)?;
+        return Ok(());
+    }
+    match format.structured() {
+        Some(structured) => {
+            emitter.emit(&full_info, structured)?;
+        }
+        None if emitter.structured() => {
+            emitter.emit(&full_info, crate::output::Format::Json)?;
         }
-        "yaml" => {
-            let yaml = serde_yaml::to_string(&full_info)?;
-            println!("{}", yaml);
+        None if display == InfoDisplay::Compact => {
+            render_info_compact(
+                &full_info,
+                contract_address,
+                &network.to_string(),
+                integrity.as_ref(),
+            );
         }
-        _ => {
+        None => {
             render_info_text(
                 &full_info,
                 highlight_method,
                 contract_address,
                 &network.to_string(),
+                display == InfoDisplay::Wide,
+                integrity.as_ref(),
             )?;
         }
     }
@@ -2527,11 +4406,54 @@ pub async fn info(
     Ok(())
 }
 
+fn render_info_compact(
+    info: &serde_json::Value,
+    contract_address: &str,
+    network_str: &str,
+    integrity: Option<&crate::contract_verify::ChainIntegrity>,
+) {
+    let metadata = &info["metadata"];
+    let name = metadata["name"].as_str().unwrap_or("Unknown");
+    let verified = if metadata["is_verified"].as_bool().unwrap_or(false) {
+        "verified".green()
+    } else {
+        "unverified".red()
+    };
+    let methods = info["abi"]
+        .as_array()
+        .map(|abi| abi.iter().filter(|i| i["type"] == "function").count())
+        .unwrap_or(0);
+    let latest_version = info["versions"]
+        .as_array()
+        .and_then(|v| v.last())
+        .and_then(|v| v["version"].as_str())
+        .unwrap_or("-");
+
+    let chain = integrity
+        .map(|i| format!(" | {}", i.indicator()))
+        .unwrap_or_default();
+
+    println!(
+        "{} {} | {} | {} | health {}/100 | v{} | {} methods | {} audit(s){}",
+        name.bold().green(),
+        contract_address.yellow(),
+        network_str,
+        verified,
+        metadata["health_score"].as_i64().unwrap_or(0),
+        latest_version,
+        methods,
+        crate::audit::reports(metadata).len(),
+        chain
+    );
+}
+
 fn render_info_text(
     info: &serde_json::Value,
     highlight_method: Option<&str>,
     contract_address: &str,
     network_str: &str,
+    wide: bool,
+    integrity: Option<&crate::contract_verify::ChainIntegrity>,
 ) -> Result<()> {
     let metadata = &info["metadata"];
     let name = metadata["name"].as_str().unwrap_or("Unknown");
@@ -2553,10 +4475,46 @@ fn render_info_text(
             "Unverified".red()
         }
     );
+    if let Some(integrity) = integrity {
+        println!("{} {}", "CHAIN:   ".bold(), integrity.indicator());
+    }
     println!("{} {}/100", "HEALTH:  ".bold(), health_score);
-    println!("{} {}", "DESC:    ".bold(), desc);
+    println!("{} {}", "DESC:    ".bold(), crate::table_format::fit(desc));
     println!("{}", "=".repeat(80).cyan());
 
+    // Audit Reports
+    let audits = crate::audit::reports(metadata);
+    println!("\n{}", "AUDITS:".bold().underline());
+    if audits.is_empty() {
+        println!("  {}", "No published audit reports".yellow());
+    }
+    for audit in &audits {
+        println!(
+            "  • {} ({}): {}",
+            audit.auditor.bold(),
+            audit.date,
+            audit.url.blue().underline()
+        );
+    }
+
+    let custom_fields = crate::custom_fields::from_contract(metadata);
+    if !custom_fields.is_empty() {
+        println!("\n{}", "CUSTOM FIELDS:".bold().underline());
+        for (key, value) in &custom_fields {
+            println!("  • {}: {}", key.bold(), crate::table_format::fit(value));
+        }
+    }
+
+    // Relationship graph
+    if let Ok(relations) =
+        serde_json::from_value::<crate::relations::RelationGraph>(info["relations"].clone())
+    {
+        println!("\n{}", "RELATIONSHIPS:".bold().underline());
+        for (label, other) in relations.lines() {
+            println!("  • {} {}", label.bold(), other);
+        }
+    }
+
     // Explorer Links
     println!("\n{}", "BLOCK EXPLORERS:".bold().underline());
     let explorer_url = match network_str {
@@ -2630,36 +4588,86 @@ fn render_info_text(
         }
     }
 
+    if wide {
+        if let Some(versions) = info["versions"].as_array() {
+            if !versions.is_empty() {
+                println!("\n{}", "VERSIONS:".bold().underline());
+                for v in versions {
+                    println!(
+                        "  • {:<12} | {:<10} | {}",
+                        v["version"].as_str().unwrap_or("?"),
+                        if v["is_verified"].as_bool().unwrap_or(false) {
+                            "verified"
+                        } else {
+                            "unverified"
+                        },
+                        v["created_at"].as_str().unwrap_or("")
+                    );
+                }
+            }
+        }
+
+        if !info["current_network_config"].is_null() {
+            println!("\n{}", "NETWORK CONFIG:".bold().underline());
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&info["current_network_config"])?
+            );
+        }
+
+        println!("\n{}", "RAW METADATA:".bold().underline());
+        println!("{}", serde_json::to_string_pretty(metadata)?);
+    }
+
     println!("\n{}", "=".repeat(80).cyan());
     Ok(())
 }
 
-pub fn doc(contract_path: &str, output: &str) -> Result<()> {
+pub fn doc(
+    contract_path: Option<&str>,
+    spec: Option<&Path>,
+    output: &str,
+    format: crate::contract_docs::DocFormat,
+) -> Result<()> {
     println!("\n{}", "Generating contract documentation...".bold().cyan());
 
-    let content = format!(
-        r#"# Contract Documentation
-
-## Contract Path
-{}
-
-## Generated
-{}
-
-*This is a placeholder. Full documentation generation coming soon.*
-"#,
-        contract_path,
-        chrono::Utc::now().to_rfc3339()
+    // A spec is read as is, so deployed contracts can be documented without
+    // their source tree or the soroban CLI.
+    let (abi_json, source) = match (spec, contract_path) {
+        (Some(spec), _) => (
+            fs::read_to_string(spec)
+                .with_context(|| format!("Failed to read contract spec {}", spec.display()))?,
+            spec,
+        ),
+        (None, Some(path)) => (load_abi_json(path)?, Path::new(path)),
+        (None, None) => anyhow::bail!("pass a contract path or --spec <path>"),
+    };
+    let contract_name = source
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("contract");
+    let abi = contract_abi::parse_json_spec(&abi_json, contract_name).map_err(|e| {
+        anyhow::anyhow!("Failed to parse contract spec {}: {}", source.display(), e)
+    })?;
+    anyhow::ensure!(
+        !abi.functions.is_empty(),
+        "{} has no functions; is it a Soroban contract spec?",
+        source.display()
     );
 
-    fs::write(output, content)?;
-    println!("{} Documentation saved to: {}", "✓".green(), output);
+    let path = crate::contract_docs::write(&abi, Path::new(output), format)?;
+    println!(
+        "{} Documented {} function(s)",
+        "✓".green(),
+        abi.public_functions().count()
+    );
+    println!("{} Documentation saved to: {}", "✓".green(), path.display());
 
     Ok(())
 }
 
 /// Load ABI JSON string from WASM (soroban bindings) or from a JSON file
-fn load_abi_json(contract_path: &str) -> Result<String> {
+pub fn load_abi_json(contract_path: &str) -> Result<String> {
     if contract_path.to_lowercase().ends_with(".wasm") {
         let output = std::process::Command::new("soroban")
             .args(["contract", "bindings", "json", "--wasm", contract_path])
@@ -2677,49 +4685,6 @@ fn load_abi_json(contract_path: &str) -> Result<String> {
     }
 }
 
-/// Generate markdown from ContractABI
-fn abi_to_markdown(abi: &contract_abi::ContractABI) -> String {
-    let mut md = format!("# {}\n\n", abi.name);
-    if let Some(v) = &abi.version {
-        md.push_str(&format!("Version: {}\n\n", v));
-    }
-    md.push_str("## Functions\n\n");
-    for func in abi.public_functions() {
-        md.push_str(&format!("### `{}`\n\n", func.name));
-        if let Some(doc) = &func.doc {
-            md.push_str(&format!("{}\n\n", doc));
-        }
-        md.push_str("**Parameters:**\n");
-        if func.params.is_empty() {
-            md.push_str("- None\n");
-        } else {
-            for p in &func.params {
-                md.push_str(&format!(
-                    "- `{}`: `{}`\n",
-                    p.name,
-                    p.param_type.display_name()
-                ));
-            }
-        }
-        md.push_str(&format!(
-            "\n**Returns:** `{}`\n\n",
-            func.return_type.display_name()
-        ));
-    }
-    if !abi.errors.is_empty() {
-        md.push_str("## Errors\n\n");
-        for e in &abi.errors {
-            md.push_str(&format!(
-                "- **{}** (code {}): {}\n",
-                e.name,
-                e.code,
-                e.doc.as_deref().unwrap_or("")
-            ));
-        }
-    }
-    md
-}
-
 /// Generate self-contained HTML with Swagger UI and inline OpenAPI spec (JSON)
 fn openapi_to_html(spec_json: &str, title: &str) -> String {
     let spec_escaped = spec_json.replace("</script>", "<\\/script>");
@@ -2771,7 +4736,7 @@ pub fn openapi(contract_path: &str, output: &str, format: &str) -> Result<()> {
             let doc = contract_abi::generate_openapi(&abi, Some("/invoke"));
             contract_abi::to_json(&doc).map_err(|e| anyhow::anyhow!("{}", e))?
         }
-        "markdown" | "md" => abi_to_markdown(&abi),
+        "markdown" | "md" => crate::contract_docs::markdown(&abi),
         "html" => {
             let doc = contract_abi::generate_openapi(&abi, Some("/invoke"));
             let json = contract_abi::to_json(&doc).map_err(|e| anyhow::anyhow!("{}", e))?;
@@ -2816,3 +4781,652 @@ pub fn sla_status(id: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// Pull upstream template changes into a cloned project.
+///
+/// Each file is merged three ways against the template version recorded in
+/// the project: upstream-only changes are applied, files changed on both
+/// sides are line-merged, and overlapping edits are written with conflict
+/// markers for manual resolution. The project's recorded base moves to the
+/// new version once the update has been applied.
+pub async fn template_update(
+    api_url: &str,
+    project_dir: &str,
+    to_version: Option<&str>,
+) -> Result<()> {
+    use crate::template;
+    use std::collections::BTreeSet;
+
+    let dir = Path::new(project_dir);
+    let marker = template::read_marker(dir)?;
+    let client = reqwest::Client::new();
+
+    let base =
+        template::fetch(&client, api_url, &marker.template, Some(&marker.version)).await?;
+    let upstream = template::fetch(&client, api_url, &marker.template, to_version).await?;
+
+    println!("\n{}", "Template Update".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    println!(
+        "  {}: {} {} → {}\n",
+        "Template".bold(),
+        marker.template,
+        base.version.bright_black(),
+        upstream.version.green()
+    );
+
+    if upstream.version == base.version {
+        println!(
+            "{}\n",
+            "✓ Project is already on this template version".green().bold()
+        );
+        return Ok(());
+    }
+
+    let current = template::read_project_files(dir)?;
+    let theirs_label = format!("template {}", upstream.version);
+    let paths: BTreeSet<&String> = base
+        .files
+        .keys()
+        .chain(upstream.files.keys())
+        .chain(current.keys())
+        .collect();
+
+    let mut applied = 0;
+    let mut conflicts: Vec<String> = Vec::new();
+
+    for path in paths {
+        let b = base.files.get(path);
+        let u = upstream.files.get(path);
+        let c = current.get(path);
+        if u == b || c == u {
+            continue;
+        }
+
+        let target = dir.join(path);
+        if c == b {
+            match u {
+                Some(content) => {
+                    if let Some(parent) = target.parent() {
+                        fs::create_dir_all(parent)
+                            .with_context(|| format!("Failed to create {}", parent.display()))?;
+                    }
+                    fs::write(&target, content)
+                        .with_context(|| format!("Failed to write {}", target.display()))?;
+                    let verb = if c.is_none() { "added" } else { "updated" };
+                    println!("  {} {} {}", "✓".green(), verb, path);
+                }
+                None => {
+                    fs::remove_file(&target)
+                        .with_context(|| format!("Failed to remove {}", target.display()))?;
+                    println!("  {} removed {}", "✓".green(), path);
+                }
+            }
+            applied += 1;
+            continue;
+        }
+
+        match (c, u) {
+            (Some(ours), Some(theirs)) => {
+                let merged = crate::diff::merge3(
+                    b.map(String::as_str).unwrap_or(""),
+                    ours,
+                    theirs,
+                    "project",
+                    &theirs_label,
+                );
+                fs::write(&target, &merged.text)
+                    .with_context(|| format!("Failed to write {}", target.display()))?;
+                if merged.conflicts > 0 {
+                    println!(
+                        "  {} conflict {} ({} region(s))",
+                        "✗".red(),
+                        path,
+                        merged.conflicts
+                    );
+                    conflicts.push(path.clone());
+                } else {
+                    println!("  {} merged {}", "✓".green(), path);
+                    applied += 1;
+                }
+            }
+            (None, Some(_)) => {
+                println!(
+                    "  {} conflict {} (deleted locally, changed in template)",
+                    "✗".red(),
+                    path
+                );
+                conflicts.push(path.clone());
+            }
+            (Some(_), None) => {
+                println!(
+                    "  {} conflict {} (modified locally, removed from template)",
+                    "✗".red(),
+                    path
+                );
+                conflicts.push(path.clone());
+            }
+            (None, None) => {}
+        }
+    }
+
+    template::write_marker(
+        dir,
+        &template::TemplateMarker {
+            template: marker.template.clone(),
+            version: upstream.version.clone(),
+            cloned_at: marker.cloned_at.clone(),
+        },
+    )?;
+
+    println!("\n{}", "=".repeat(80).cyan());
+    println!("  {} file(s) updated", applied);
+    if !conflicts.is_empty() {
+        anyhow::bail!(
+            "{} file(s) need manual resolution: {}",
+            conflicts.len(),
+            conflicts.join(", ")
+        );
+    }
+    println!(
+        "{}\n",
+        format!("✓ Project updated to {}@{}", marker.template, upstream.version)
+            .green()
+            .bold()
+    );
+    Ok(())
+}
+
+/// Submit a contract's source for review so the registry can mark it verified.
+#[allow(clippy::too_many_arguments)]
+pub async fn request_verification(
+    api_url: &str,
+    contract_id: &str,
+    source_repo: &str,
+    build_command: &str,
+    commit: Option<&str>,
+    compiler_version: &str,
+    json: bool,
+) -> Result<()> {
+    let url = reqwest::Url::parse(source_repo)
+        .with_context(|| format!("Invalid --source-repo URL: {}", source_repo))?;
+    if !matches!(url.scheme(), "https" | "http" | "git" | "ssh") {
+        anyhow::bail!("Unsupported --source-repo scheme: {}", url.scheme());
+    }
+
+    let payload = json!({
+        "contract_id": contract_id,
+        "source_code": source_repo,
+        "build_params": {
+            "source_repo": source_repo,
+            "build_command": build_command,
+            "commit": commit,
+        },
+        "compiler_version": compiler_version,
+    });
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{}/api/contracts/verify", api_url))
+        .json(&payload)
+        .send()
+        .await
+        .context("Failed to submit verification request")?;
+
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    if !status.is_success() {
+        let message = body["message"].as_str().or(body["error"].as_str());
+        anyhow::bail!(
+            "Verification request rejected ({}): {}",
+            status,
+            message.unwrap_or("no details")
+        );
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&body)?);
+        return Ok(());
+    }
+
+    println!("\n{}", "Verification Request".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    println!("  {}: {}", "Contract".bold(), contract_id.bright_black());
+    println!("  {}: {}", "Source".bold(), source_repo);
+    if let Some(commit) = commit {
+        println!("  {}: {}", "Commit".bold(), commit.bright_black());
+    }
+    println!("  {}: {}", "Build".bold(), build_command);
+    if let Some(id) = body["verification_id"].as_str() {
+        println!("  {}: {}", "Request ID".bold(), id.bright_black());
+    }
+    let state = body["status"].as_str().unwrap_or("pending");
+    println!("  {}: {}", "Status".bold(), verification_status_colored(state));
+    println!(
+        "\n  {} Track progress with: soroban-registry verification-status {}\n",
+        "→".bright_black(),
+        contract_id
+    );
+    Ok(())
+}
+
+fn verification_status_colored(status: &str) -> colored::ColoredString {
+    match status {
+        "verified" => status.green().bold(),
+        "failed" => status.red().bold(),
+        _ => status.yellow().bold(),
+    }
+}
+
+/// Show where a contract's verification request stands.
+pub async fn verification_status(api_url: &str, contract_id: &str, json: bool) -> Result<()> {
+    let client = reqwest::Client::new();
+    let response = client
+        .get(format!(
+            "{}/api/contracts/{}/verification-status",
+            api_url, contract_id
+        ))
+        .send()
+        .await
+        .context("Failed to fetch verification status")?;
+
+    if !response.status().is_success() {
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            anyhow::bail!("No verification request found for {}", contract_id);
+        }
+        anyhow::bail!("Failed to fetch verification status: {}", response.status());
+    }
+    let body: serde_json::Value = response.json().await?;
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(&body, crate::output::Format::Json)?;
+        return Ok(());
+    }
+
+    let state = body["status"].as_str().unwrap_or("unverified");
+    println!("\n{}", "Verification Status".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    println!("  {}: {}", "Contract".bold(), contract_id.bright_black());
+    println!("  {}: {}", "Status".bold(), verification_status_colored(state));
+    for (label, key) in [
+        ("Submitted", "created_at"),
+        ("Verified At", "verified_at"),
+        ("Compiler", "compiler_version"),
+    ] {
+        if let Some(value) = body[key].as_str() {
+            println!("  {}: {}", label.bold(), value);
+        }
+    }
+    if let Some(err) = body["error_message"].as_str() {
+        println!("  {}: {}", "Reason".bold().red(), err);
+    }
+    println!();
+    Ok(())
+}
+
+/// Parse `deprecate --retire-at`: an RFC 3339 timestamp or a bare date
+/// (midnight UTC).
+fn parse_retirement_date(value: &str) -> Result<chrono::DateTime<chrono::Utc>> {
+    let value = value.trim();
+    if let Ok(at) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(at.with_timezone(&chrono::Utc));
+    }
+    let date = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").with_context(|| {
+        format!(
+            "Invalid --retire-at '{}': expected YYYY-MM-DD or an RFC 3339 timestamp",
+            value
+        )
+    })?;
+    Ok(date.and_time(chrono::NaiveTime::MIN).and_utc())
+}
+
+/// A registered contract that declares the deprecated one as a dependency.
+#[derive(Debug, Serialize)]
+pub struct AffectedDependent {
+    pub id: String,
+    pub name: Option<String>,
+    pub publisher: Option<String>,
+    pub version_constraint: Option<String>,
+}
+
+/// Dependents lookups in flight at once.
+const DEPENDENT_LOOKUP_CONCURRENCY: usize = 4;
+
+/// Contracts depending on `uuid`, one entry per dependent contract.
+async fn fetch_dependents(
+    client: &reqwest::Client,
+    api_url: &str,
+    uuid: &str,
+) -> Result<Vec<AffectedDependent>> {
+    let response = client
+        .get(format!("{}/api/contracts/{}/dependents", api_url, uuid))
+        .send()
+        .await
+        .context("Failed to fetch dependents")?;
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to fetch dependents: {}", response.status());
+    }
+    let body: serde_json::Value = response.json().await?;
+    let mut rows: Vec<(String, Option<String>)> = Vec::new();
+    for row in body["dependents"].as_array().into_iter().flatten() {
+        let Some(id) = row["contract_id"].as_str() else {
+            continue;
+        };
+        if !rows.iter().any(|(seen, _)| seen == id) {
+            let constraint = row["version_constraint"].as_str().map(str::to_string);
+            rows.push((id.to_string(), constraint));
+        }
+    }
+
+    let jobs: Vec<_> = rows
+        .into_iter()
+        .map(|(id, version_constraint)| {
+            let client = client.clone();
+            let api_url = api_url.to_string();
+            async move {
+                let contract = crate::recommend::fetch_contract(&client, &api_url, &id)
+                    .await
+                    .unwrap_or_default();
+                let field = |key: &str| contract[key].as_str().map(str::to_string);
+                AffectedDependent {
+                    name: field("name"),
+                    publisher: field("publisher_address").or_else(|| field("publisher_id")),
+                    id,
+                    version_constraint,
+                }
+            }
+        })
+        .collect();
+    let (dependents, _) =
+        crate::concurrency::run_bounded(DEPENDENT_LOOKUP_CONCURRENCY, jobs, |_, _| {}).await?;
+    Ok(dependents)
+}
+
+/// Deprecate a contract, then list the registered contracts that depend on
+/// it. With `notify`, the registry alerts their publishers through their
+/// subscriptions and `contract.deprecated` webhooks.
+#[allow(clippy::too_many_arguments)]
+pub async fn deprecate(
+    api_url: &str,
+    contract_id: &str,
+    retire_at: &str,
+    replacement: Option<&str>,
+    migration_guide: Option<&str>,
+    notes: Option<&str>,
+    notify: bool,
+    json: bool,
+) -> Result<()> {
+    let retirement_at = parse_retirement_date(retire_at)?;
+    if retirement_at <= chrono::Utc::now() {
+        anyhow::bail!("--retire-at must be in the future");
+    }
+
+    let client = reqwest::Client::new();
+    let contract = crate::recommend::fetch_contract(&client, api_url, contract_id).await?;
+    let uuid = contract["id"]
+        .as_str()
+        .context("Registry returned no contract ID")?;
+
+    let payload = json!({
+        "retirement_at": retirement_at,
+        "replacement_contract_id": replacement,
+        "migration_guide_url": migration_guide,
+        "notes": notes,
+        "notify_dependents": notify,
+    });
+    let response = client
+        .post(format!("{}/api/contracts/{}/deprecate", api_url, uuid))
+        .json(&payload)
+        .send()
+        .await
+        .context("Failed to submit deprecation")?;
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    if !status.is_success() {
+        let message = body["message"].as_str().or(body["error"].as_str());
+        anyhow::bail!(
+            "Deprecation rejected ({}): {}",
+            status,
+            message.unwrap_or("no details")
+        );
+    }
+
+    // The contract is deprecated at this point; a failed lookup shouldn't
+    // read as a failed deprecation.
+    let dependents = match fetch_dependents(&client, api_url, uuid).await {
+        Ok(dependents) => Some(dependents),
+        Err(e) => {
+            eprintln!("{} Couldn't list dependents: {}", "⚠".yellow(), e);
+            None
+        }
+    };
+    let notified = body["dependents_notified"].as_i64().unwrap_or(0);
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        let affected = dependents.as_ref().map(Vec::len);
+        emitter.emit(
+            &json!({
+                "deprecation": body,
+                "affected_dependents": affected,
+                "dependents": dependents,
+                "dependents_notified": notified,
+            }),
+            crate::output::Format::Json,
+        )?;
+        return Ok(());
+    }
+
+    let name = contract["name"].as_str().unwrap_or(contract_id);
+    println!("\n{}", "Contract Deprecated".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    println!(
+        "  {}: {} {}",
+        "Contract".bold(),
+        name,
+        contract_id.bright_black()
+    );
+    let days = body["days_remaining"]
+        .as_i64()
+        .map(|d| format!(" ({} days)", d))
+        .unwrap_or_default();
+    println!(
+        "  {}: {}{}",
+        "Retires".bold(),
+        retirement_at.format("%Y-%m-%d %H:%M UTC"),
+        days
+    );
+    if let Some(replacement) = replacement {
+        println!("  {}: {}", "Replacement".bold(), replacement);
+    }
+    if let Some(guide) = migration_guide {
+        println!("  {}: {}", "Migration".bold(), guide);
+    }
+
+    let Some(dependents) = dependents else {
+        println!();
+        return Ok(());
+    };
+    if dependents.is_empty() {
+        println!(
+            "\n  {} No registered contracts depend on {}\n",
+            "✓".green(),
+            name
+        );
+        return Ok(());
+    }
+    println!(
+        "\n  {} {} dependent contract(s) affected:",
+        "⚠".yellow(),
+        dependents.len().to_string().bold()
+    );
+    for dependent in &dependents {
+        let mut detail = vec![dependent.id.clone()];
+        if let Some(ref publisher) = dependent.publisher {
+            detail.push(format!("publisher {}", publisher));
+        }
+        if let Some(ref constraint) = dependent.version_constraint {
+            detail.push(format!("requires {}", constraint));
+        }
+        println!(
+            "    {} {} {}",
+            "⚠".yellow(),
+            dependent.name.as_deref().unwrap_or("Unknown").bold(),
+            detail.join(", ").bright_black()
+        );
+    }
+    if notify {
+        println!(
+            "\n  {} {} publisher notification(s) sent\n",
+            "→".cyan(),
+            notified
+        );
+    } else {
+        println!(
+            "\n  {} Re-run with --notify to alert their publishers\n",
+            "→".bright_black()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod deprecate_tests {
+    use super::parse_retirement_date;
+
+    #[test]
+    fn retirement_date_accepts_dates_and_timestamps() {
+        let date = parse_retirement_date("2031-01-15").unwrap();
+        assert_eq!(date.to_rfc3339(), "2031-01-15T00:00:00+00:00");
+        let at = parse_retirement_date("2031-01-15T12:00:00+02:00").unwrap();
+        assert_eq!(at.to_rfc3339(), "2031-01-15T10:00:00+00:00");
+        assert!(parse_retirement_date("next month").is_err());
+    }
+}
+
+/// One row of `verify-archives` output.
+#[derive(Debug, serde::Serialize)]
+pub struct ArchiveCheck {
+    pub archive: String,
+    pub contract_id: Option<String>,
+    pub signer: Option<String>,
+    pub passed: bool,
+    pub error: Option<String>,
+}
+
+/// Verify the integrity and embedded signature of every registry export
+/// (`*.tar.gz` / `*.tgz`) in `dir` against a trusted key file.
+pub fn verify_archives(dir: &str, trusted_keys: &str, json: bool) -> Result<()> {
+    let trusted = crate::import::load_trusted_keys(std::path::Path::new(trusted_keys))?;
+
+    let mut archives: Vec<std::path::PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory {}", dir))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.is_file()
+                && p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.ends_with(".tar.gz") || n.ends_with(".tgz"))
+        })
+        .collect();
+    archives.sort();
+    if archives.is_empty() {
+        anyhow::bail!("No .tar.gz or .tgz archives found in {}", dir);
+    }
+
+    let checks: Vec<ArchiveCheck> = archives
+        .iter()
+        .map(|path| {
+            let archive = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            match crate::import::verify_archive(path, &trusted) {
+                Ok((manifest, signer)) => ArchiveCheck {
+                    archive,
+                    contract_id: Some(manifest.contract_id),
+                    signer: Some(signer),
+                    passed: true,
+                    error: None,
+                },
+                Err(e) => ArchiveCheck {
+                    archive,
+                    contract_id: None,
+                    signer: None,
+                    passed: false,
+                    error: Some(format!("{:#}", e)),
+                },
+            }
+        })
+        .collect();
+    let failed = checks.iter().filter(|c| !c.passed).count();
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(
+            &serde_json::json!({
+                "directory": dir,
+                "trusted_keys": trusted.len(),
+                "archives": checks,
+                "failed": failed,
+            }),
+            crate::output::Format::Json,
+        )?;
+    } else {
+        println!("\n{}", "Archive Signature Verification".bold().cyan());
+        println!("{}", "=".repeat(80).cyan());
+
+        let headers = ["Archive", "Result", "Contract / Reason"];
+        let archive_w = checks
+            .iter()
+            .map(|c| c.archive.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(headers[0].len());
+        let rows: Vec<Vec<String>> = checks
+            .iter()
+            .map(|c| {
+                let (result, detail) = if c.passed {
+                    (
+                        format!("{} pass", crate::table_format::glyph("✓", "+"))
+                            .green()
+                            .to_string(),
+                        format!(
+                            "{} (signed by {})",
+                            c.contract_id.as_deref().unwrap_or_default(),
+                            c.signer.as_deref().unwrap_or_default()
+                        ),
+                    )
+                } else {
+                    (
+                        format!("{} fail", crate::table_format::glyph("✗", "x"))
+                            .red()
+                            .to_string(),
+                        c.error.clone().unwrap_or_default().red().to_string(),
+                    )
+                };
+                vec![c.archive.clone(), result, detail]
+            })
+            .collect();
+        println!(
+            "{}",
+            crate::table_format::render_table(&headers, &[archive_w, 6, 40], &rows)
+        );
+
+        println!("{}", "=".repeat(80).cyan());
+        if failed == 0 {
+            println!(
+                "{}\n",
+                format!("✓ All {} archive(s) verified", checks.len())
+                    .green()
+                    .bold()
+            );
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{} of {} archive(s) failed verification", failed, checks.len());
+    }
+    Ok(())
+}
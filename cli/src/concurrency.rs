@@ -0,0 +1,430 @@
+//! Bounded-concurrency execution for batch commands, with the per-item
+//! timing behind `--concurrency-report`.
+//!
+//! `--concurrency auto` runs through an adaptive semaphore: it starts at
+//! [`AUTO_START`] slots, adds one after a full window of fast successes and
+//! halves on `429`, `5xx` or connection failures.
+
+use std::fmt;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Most items a batch command keeps in flight, fixed or `auto`.
+pub const MAX_CONCURRENCY: usize = 16;
+
+/// Where `auto` starts.
+pub const AUTO_START: usize = 2;
+
+/// A success this many times slower than the fastest seen so far means the
+/// registry is queueing; `auto` holds instead of ramping.
+const SLOW_FACTOR: u32 = 3;
+
+/// A `--concurrency` value: a fixed limit or `auto`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Concurrency {
+    Fixed(usize),
+    Auto,
+}
+
+impl FromStr for Concurrency {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(Concurrency::Auto);
+        }
+        match s.parse::<usize>() {
+            Ok(n) if (1..=MAX_CONCURRENCY).contains(&n) => Ok(Concurrency::Fixed(n)),
+            _ => Err(format!(
+                "expected `auto` or a number from 1 to {}",
+                MAX_CONCURRENCY
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Concurrency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Concurrency::Fixed(n) => write!(f, "{}", n),
+            Concurrency::Auto => f.write_str("auto"),
+        }
+    }
+}
+
+/// How a finished job should steer `auto` concurrency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    Ok,
+    /// The registry is overloaded or unreachable: shrink.
+    Backoff,
+}
+
+impl Signal {
+    pub fn from_status(status: reqwest::StatusCode) -> Self {
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+            Signal::Backoff
+        } else {
+            Signal::Ok
+        }
+    }
+
+    /// Back off when a failed job's error chain holds a `429`/`5xx`
+    /// response, a timeout or a connection failure. Other errors (bad
+    /// input, `4xx`) say nothing about load.
+    pub fn from_error(err: &anyhow::Error) -> Self {
+        let Some(err) = err.chain().find_map(|e| e.downcast_ref::<reqwest::Error>()) else {
+            return Signal::Ok;
+        };
+        if err.is_timeout() || err.is_connect() {
+            return Signal::Backoff;
+        }
+        err.status().map_or(Signal::Ok, Signal::from_status)
+    }
+}
+
+/// Limit of the adaptive semaphore behind `auto`: additive increase,
+/// multiplicative decrease. Permits handed out = `limit + owed`; a shrink
+/// is paid off by retiring slots as in-flight jobs finish.
+#[derive(Debug)]
+struct AdaptiveLimit {
+    limit: usize,
+    max: usize,
+    /// Slots to retire before the semaphore matches `limit`.
+    owed: usize,
+    /// Fast successes since the last change.
+    streak: usize,
+    fastest: Option<Duration>,
+    last_cut: Option<Instant>,
+}
+
+impl AdaptiveLimit {
+    fn new(start: usize, max: usize) -> Self {
+        AdaptiveLimit {
+            limit: start.clamp(1, max),
+            max,
+            owed: 0,
+            streak: 0,
+            fastest: None,
+            last_cut: None,
+        }
+    }
+
+    /// Feed one finished job. Returns true when the limit grew and the
+    /// semaphore needs another permit.
+    fn observe(&mut self, signal: Signal, started: Instant, latency: Duration) -> bool {
+        if signal == Signal::Backoff {
+            // Jobs already in flight at the last cut saw the same overload;
+            // halving again for each of them would collapse to 1.
+            if self.last_cut.is_some_and(|cut| started < cut) {
+                return false;
+            }
+            let shrunk = (self.limit / 2).max(1);
+            self.owed += self.limit - shrunk;
+            self.limit = shrunk;
+            self.streak = 0;
+            self.last_cut = Some(started + latency);
+            return false;
+        }
+
+        let fastest = self.fastest.map_or(latency, |f| f.min(latency));
+        self.fastest = Some(fastest);
+        if latency > fastest * SLOW_FACTOR {
+            self.streak = 0;
+            return false;
+        }
+        self.streak += 1;
+        if self.streak < self.limit || self.limit >= self.max {
+            return false;
+        }
+        self.streak = 0;
+        self.limit += 1;
+        if self.owed > 0 {
+            self.owed -= 1;
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Whether the slot a finished job frees should be retired.
+    fn retire_slot(&mut self) -> bool {
+        if self.owed == 0 {
+            return false;
+        }
+        self.owed -= 1;
+        true
+    }
+}
+
+/// Wall time of a batch and how long each item took once it started.
+#[derive(Debug, Clone)]
+pub struct Timing {
+    /// The fixed limit, or the highest one `auto` reached.
+    pub limit: usize,
+    pub wall: Duration,
+    /// Per-item latency, excluding time spent waiting for a slot.
+    pub latencies: Vec<Duration>,
+}
+
+/// Run `jobs` with at most `limit` in flight. `on_done` sees each result as
+/// it completes (for progress output); the results are returned in input
+/// order along with the batch's timing.
+pub async fn run_bounded<T, Fut>(
+    limit: usize,
+    jobs: Vec<Fut>,
+    on_done: impl FnMut(usize, &T),
+) -> Result<(Vec<T>, Timing)>
+where
+    T: Send + 'static,
+    Fut: Future<Output = T> + Send + 'static,
+{
+    run_with(Concurrency::Fixed(limit), jobs, |_| Signal::Ok, on_done).await
+}
+
+/// [`run_bounded`] with a `--concurrency` value. Under `auto`, `signal`
+/// classifies each result to steer the limit.
+pub async fn run_with<T, Fut>(
+    concurrency: Concurrency,
+    jobs: Vec<Fut>,
+    signal: impl Fn(&T) -> Signal,
+    mut on_done: impl FnMut(usize, &T),
+) -> Result<(Vec<T>, Timing)>
+where
+    T: Send + 'static,
+    Fut: Future<Output = T> + Send + 'static,
+{
+    let limit = match concurrency {
+        Concurrency::Fixed(n) => n.max(1),
+        Concurrency::Auto => AUTO_START,
+    };
+    let mut adaptive =
+        (concurrency == Concurrency::Auto).then(|| AdaptiveLimit::new(limit, MAX_CONCURRENCY));
+    let mut peak = limit;
+    log::debug!("concurrency: {} (starting at {})", concurrency, limit);
+
+    let count = jobs.len();
+    let started = Instant::now();
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(limit));
+    let mut tasks = tokio::task::JoinSet::new();
+    for (index, job) in jobs.into_iter().enumerate() {
+        let semaphore = semaphore.clone();
+        tasks.spawn(async move {
+            // Held until the result is observed, so `auto` can retire it.
+            let permit = semaphore.acquire_owned().await.ok();
+            let item_started = Instant::now();
+            let value = job.await;
+            (index, item_started, item_started.elapsed(), value, permit)
+        });
+    }
+
+    let mut done: Vec<(usize, Duration, T)> = Vec::with_capacity(count);
+    while let Some(joined) = tasks.join_next().await {
+        let (index, item_started, elapsed, value, permit) =
+            joined.context("batch task panicked")?;
+        if let Some(adaptive) = adaptive.as_mut() {
+            let before = adaptive.limit;
+            if adaptive.observe(signal(&value), item_started, elapsed) {
+                semaphore.add_permits(1);
+            }
+            if let Some(permit) = permit {
+                if adaptive.retire_slot() {
+                    permit.forget();
+                }
+            }
+            if adaptive.limit != before {
+                log::debug!(
+                    "concurrency: auto {} -> {} (latency {:.0} ms)",
+                    before,
+                    adaptive.limit,
+                    ms(elapsed)
+                );
+            }
+            peak = peak.max(adaptive.limit);
+        }
+        on_done(index, &value);
+        done.push((index, elapsed, value));
+    }
+    let wall = started.elapsed();
+
+    done.sort_by_key(|(index, _, _)| *index);
+    let (latencies, values): (Vec<Duration>, Vec<T>) =
+        done.into_iter().map(|(_, d, v)| (d, v)).unzip();
+    Ok((
+        values,
+        Timing {
+            limit: peak,
+            wall,
+            latencies,
+        },
+    ))
+}
+
+/// Throughput statistics for `--concurrency-report`.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct ConcurrencyReport {
+    pub items: usize,
+    pub concurrency_limit: usize,
+    pub wall_ms: f64,
+    pub latency_min_ms: f64,
+    pub latency_p50_ms: f64,
+    pub latency_p90_ms: f64,
+    pub latency_p99_ms: f64,
+    pub latency_max_ms: f64,
+    /// Average number of items actually in flight: total item time over
+    /// wall time. Well below the limit means the batch is latency-bound
+    /// somewhere other than client-side parallelism.
+    pub effective_concurrency: f64,
+    pub throughput_per_sec: f64,
+}
+
+fn ms(d: Duration) -> f64 {
+    d.as_micros() as f64 / 1000.0
+}
+
+/// Nearest-rank percentile of sorted latencies.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+impl Timing {
+    pub fn report(&self) -> ConcurrencyReport {
+        let mut sorted = self.latencies.clone();
+        sorted.sort();
+        let wall = self.wall.as_secs_f64();
+        let busy: f64 = sorted.iter().map(Duration::as_secs_f64).sum();
+        let per_wall = |x: f64| if wall > 0.0 { x / wall } else { 0.0 };
+        ConcurrencyReport {
+            items: sorted.len(),
+            concurrency_limit: self.limit,
+            wall_ms: ms(self.wall),
+            latency_min_ms: sorted.first().copied().map(ms).unwrap_or_default(),
+            latency_p50_ms: ms(percentile(&sorted, 50.0)),
+            latency_p90_ms: ms(percentile(&sorted, 90.0)),
+            latency_p99_ms: ms(percentile(&sorted, 99.0)),
+            latency_max_ms: sorted.last().copied().map(ms).unwrap_or_default(),
+            effective_concurrency: per_wall(busy),
+            throughput_per_sec: per_wall(sorted.len() as f64),
+        }
+    }
+}
+
+impl ConcurrencyReport {
+    pub fn print(&self) {
+        println!("\n{}", "Concurrency Report".bold().cyan());
+        println!("{}", "=".repeat(60).cyan());
+        println!("  {}: {}", "Items".bold(), self.items);
+        println!("  {}: {:.0} ms", "Wall time".bold(), self.wall_ms);
+        println!(
+            "  {}: min {:.0} / p50 {:.0} / p90 {:.0} / p99 {:.0} / max {:.0} ms",
+            "Latency".bold(),
+            self.latency_min_ms,
+            self.latency_p50_ms,
+            self.latency_p90_ms,
+            self.latency_p99_ms,
+            self.latency_max_ms
+        );
+        println!(
+            "  {}: {:.2} of {} allowed",
+            "Effective concurrency".bold(),
+            self.effective_concurrency,
+            self.concurrency_limit
+        );
+        println!(
+            "  {}: {:.2} items/s",
+            "Throughput".bold(),
+            self.throughput_per_sec
+        );
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_derives_percentiles_and_effective_concurrency() {
+        let timing = Timing {
+            limit: 4,
+            wall: Duration::from_millis(500),
+            latencies: [100, 400, 200, 300]
+                .into_iter()
+                .map(Duration::from_millis)
+                .collect(),
+        };
+        let report = timing.report();
+        assert_eq!(report.items, 4);
+        assert_eq!(report.latency_min_ms, 100.0);
+        assert_eq!(report.latency_p50_ms, 200.0);
+        assert_eq!(report.latency_p90_ms, 400.0);
+        assert_eq!(report.latency_max_ms, 400.0);
+        assert!((report.effective_concurrency - 2.0).abs() < 1e-9);
+        assert!((report.throughput_per_sec - 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn auto_ramps_on_fast_successes_and_halves_on_backoff() {
+        assert_eq!("auto".parse(), Ok(Concurrency::Auto));
+        assert_eq!("4".parse(), Ok(Concurrency::Fixed(4)));
+        assert!("0".parse::<Concurrency>().is_err());
+        assert!("17".parse::<Concurrency>().is_err());
+
+        let fast = Duration::from_millis(100);
+        let t0 = Instant::now();
+        let mut auto = AdaptiveLimit::new(2, 4);
+        assert!(!auto.observe(Signal::Ok, t0, fast));
+        assert!(auto.observe(Signal::Ok, t0, fast));
+        assert_eq!(auto.limit, 3);
+        // Slow successes hold the limit where it is.
+        for _ in 0..5 {
+            assert!(!auto.observe(Signal::Ok, t0, fast * 4));
+        }
+        assert_eq!(auto.limit, 3);
+
+        let t1 = t0 + Duration::from_secs(1);
+        auto.observe(Signal::Backoff, t1, fast);
+        assert_eq!((auto.limit, auto.owed), (1, 2));
+        // A job that started before the cut doesn't cut again.
+        auto.observe(Signal::Backoff, t1, fast);
+        assert_eq!(auto.limit, 1);
+        assert!(auto.retire_slot());
+        assert!(auto.retire_slot());
+        assert!(!auto.retire_slot());
+
+        let err = anyhow::anyhow!("HTTP 400").context("register");
+        assert_eq!(Signal::from_error(&err), Signal::Ok);
+        assert_eq!(
+            Signal::from_status(reqwest::StatusCode::SERVICE_UNAVAILABLE),
+            Signal::Backoff
+        );
+    }
+
+    #[tokio::test]
+    async fn run_bounded_returns_results_in_input_order() {
+        let jobs: Vec<_> = [30u64, 10, 20]
+            .into_iter()
+            .map(|ms| async move {
+                tokio::time::sleep(Duration::from_millis(ms)).await;
+                ms
+            })
+            .collect();
+        let mut seen = Vec::new();
+        let (values, timing) = run_bounded(2, jobs, |i, _| seen.push(i)).await.unwrap();
+        assert_eq!(values, [30, 10, 20]);
+        assert_eq!(seen.len(), 3);
+        assert_eq!(timing.latencies.len(), 3);
+        assert_eq!(timing.limit, 2);
+    }
+}
@@ -2,6 +2,7 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -51,9 +52,103 @@ impl FromStr for Network {
     }
 }
 
+pub const MAINNET_PASSPHRASE: &str = "Public Global Stellar Network ; September 2015";
+pub const TESTNET_PASSPHRASE: &str = "Test SDF Network ; September 2015";
+pub const FUTURENET_PASSPHRASE: &str = "Test SDF Future Network ; October 2022";
+
+/// Canonical network passphrase. `Auto` routes to mainnet, so it uses the
+/// mainnet passphrase.
+pub fn network_passphrase(network: Network) -> &'static str {
+    match network {
+        Network::Mainnet | Network::Auto => MAINNET_PASSPHRASE,
+        Network::Testnet => TESTNET_PASSPHRASE,
+        Network::Futurenet => FUTURENET_PASSPHRASE,
+    }
+}
+
+/// Network a passphrase belongs to, if it is one of the known networks.
+pub fn network_for_passphrase(passphrase: &str) -> Option<Network> {
+    [Network::Mainnet, Network::Testnet, Network::Futurenet]
+        .into_iter()
+        .find(|n| network_passphrase(*n) == passphrase)
+}
+
+/// Fail unless `passphrase` is the canonical passphrase of `network`, so an
+/// RPC endpoint or override pointing at another network is caught before it
+/// is used.
+pub fn validate_passphrase(network: Network, passphrase: &str, source: &str) -> Result<()> {
+    let expected = network_passphrase(network);
+    if passphrase == expected {
+        return Ok(());
+    }
+    let actual = network_for_passphrase(passphrase)
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| "an unknown network".to_string());
+    anyhow::bail!(
+        "Network passphrase mismatch: resolved network is {} (\"{}\") but {} uses {} (\"{}\")",
+        network,
+        expected,
+        source,
+        actual,
+        passphrase
+    )
+}
+
+/// Passphrase to use for `network`. A `SOROBAN_NETWORK_PASSPHRASE` override
+/// is honoured only when it matches the network.
+pub fn resolve_passphrase(network: Network) -> Result<&'static str> {
+    if let Ok(value) = std::env::var("SOROBAN_NETWORK_PASSPHRASE") {
+        validate_passphrase(network, &value, "SOROBAN_NETWORK_PASSPHRASE")?;
+    }
+    Ok(network_passphrase(network))
+}
+
+/// Fields `publish --strict` requires when the config file does not override them.
+pub const DEFAULT_STRICT_REQUIRED_FIELDS: &[&str] =
+    &["description", "category", "tags", "license", "readme"];
+
 #[derive(Debug, Clone, Deserialize, Default)]
 struct ConfigFile {
     defaults: Option<DefaultsSection>,
+    publish: Option<PublishSection>,
+    compare_onchain: Option<CompareOnchainSection>,
+    health: Option<HealthSection>,
+    tags: Option<TagsSection>,
+    licenses: Option<LicensesSection>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+struct LicensesSection {
+    /// SPDX identifiers `deps licenses` accepts, e.g. `["MIT", "Apache-2.0"]`
+    allowed: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+struct TagsSection {
+    /// Spelling variants mapped to their canonical tag, e.g. `"de-fi" = "defi"`
+    aliases: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct HealthSection {
+    /// Read-only method invoked by `health` to confirm the contract responds
+    pub method: Option<String>,
+    /// TTL (in ledgers) below which `health` warns about upcoming archival
+    pub min_ttl_ledgers: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+struct CompareOnchainSection {
+    /// Storage keys compared by `compare-onchain`
+    keys: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+struct PublishSection {
+    /// Fields required by `publish --strict`
+    strict_required: Option<Vec<String>>,
+    /// JSON Schema every `publish` payload must satisfy
+    metadata_schema: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -61,6 +156,8 @@ struct DefaultsSection {
     network: Option<String>,
     api_base: Option<String>,
     timeout: Option<u64>,
+    /// Publisher (UUID or Stellar address) commands act as when not told
+    publisher: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -78,6 +175,19 @@ pub fn resolve_network(cli_network: Option<String>) -> Result<Network> {
     }
 }
 
+/// `[defaults] network` from the config file, as written.
+pub fn configured_network() -> Result<Option<String>> {
+    Ok(load_defaults_section()?.network)
+}
+
+/// `[defaults] publisher` from the config file.
+pub fn configured_publisher() -> Result<Option<String>> {
+    Ok(load_defaults_section()?
+        .publisher
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty()))
+}
+
 pub fn resolve_runtime_config(
     cli_network: Option<String>,
     cli_api_base: Option<String>,
@@ -125,6 +235,13 @@ pub fn show_config() -> Result<()> {
         "defaults.timeout = {}",
         defaults.timeout.unwrap_or(DEFAULT_TIMEOUT_SECS)
     );
+    println!(
+        "publish.strict_required = [{}]",
+        strict_required_fields()?.join(", ")
+    );
+    if let Some(schema) = metadata_schema()? {
+        println!("publish.metadata_schema = {}", schema.display());
+    }
 
     Ok(())
 }
@@ -147,6 +264,93 @@ pub fn edit_config() -> Result<()> {
     Ok(())
 }
 
+/// Required-field set for `publish --strict`, from `[publish] strict_required`
+/// in the config file, falling back to [`DEFAULT_STRICT_REQUIRED_FIELDS`].
+pub fn strict_required_fields() -> Result<Vec<String>> {
+    migrate_legacy_config()?;
+    let configured = match config_file_path() {
+        Some(path) if path.exists() => load_config_file(&path)?
+            .publish
+            .and_then(|p| p.strict_required),
+        _ => None,
+    };
+    Ok(configured.unwrap_or_else(|| {
+        DEFAULT_STRICT_REQUIRED_FIELDS
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    }))
+}
+
+/// Schema `publish` validates metadata against, from `[publish]
+/// metadata_schema` in the config file. A relative path is resolved against
+/// the config file's directory. `None` when not configured.
+pub fn metadata_schema() -> Result<Option<PathBuf>> {
+    migrate_legacy_config()?;
+    let Some(path) = config_file_path().filter(|p| p.exists()) else {
+        return Ok(None);
+    };
+    let schema = load_config_file(&path)?
+        .publish
+        .and_then(|p| p.metadata_schema);
+    Ok(schema.map(|schema| match path.parent() {
+        Some(dir) => dir.join(schema),
+        None => schema,
+    }))
+}
+
+/// Storage keys checked by `compare-onchain`, from `[compare_onchain] keys`
+/// in the config file. Empty when not configured.
+pub fn compare_onchain_keys() -> Result<Vec<String>> {
+    migrate_legacy_config()?;
+    Ok(match config_file_path() {
+        Some(path) if path.exists() => load_config_file(&path)?
+            .compare_onchain
+            .and_then(|c| c.keys)
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    })
+}
+
+/// `[health]` settings from the config file. Empty when not configured.
+pub fn health_settings() -> Result<HealthSection> {
+    migrate_legacy_config()?;
+    Ok(match config_file_path() {
+        Some(path) if path.exists() => load_config_file(&path)?.health.unwrap_or_default(),
+        _ => HealthSection::default(),
+    })
+}
+
+/// Tag aliases from `[tags] aliases` in the config file, keyed by the
+/// lower-cased variant. Empty when not configured.
+pub fn tag_aliases() -> Result<HashMap<String, String>> {
+    migrate_legacy_config()?;
+    let aliases = match config_file_path() {
+        Some(path) if path.exists() => load_config_file(&path)?
+            .tags
+            .and_then(|t| t.aliases)
+            .unwrap_or_default(),
+        _ => HashMap::new(),
+    };
+    Ok(aliases
+        .into_iter()
+        .map(|(from, to)| (from.trim().to_lowercase(), to.trim().to_lowercase()))
+        .collect())
+}
+
+/// License allowlist from `[licenses] allowed` in the config file. Empty
+/// when not configured, meaning every license is accepted.
+pub fn allowed_licenses() -> Result<Vec<String>> {
+    migrate_legacy_config()?;
+    Ok(match config_file_path() {
+        Some(path) if path.exists() => load_config_file(&path)?
+            .licenses
+            .and_then(|l| l.allowed)
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    })
+}
+
 fn load_defaults_section() -> Result<DefaultsSection> {
     migrate_legacy_config()?;
     let path = match config_file_path() {
@@ -182,6 +386,10 @@ fn ensure_config_file_exists(path: &Path) -> Result<()> {
 network = "testnet"
 api_base = "http://localhost:3001"
 timeout = 30
+
+[publish]
+# Fields required by `publish --strict`
+strict_required = ["description", "category", "tags", "license", "readme"]
 "#;
     fs::write(path, default_content)
         .with_context(|| format!("Failed to write default config to {:?}", path))?;
@@ -242,6 +450,17 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    #[test]
+    fn passphrase_validation_names_the_other_network() {
+        assert!(validate_passphrase(Network::Testnet, TESTNET_PASSPHRASE, "rpc").is_ok());
+        let err = validate_passphrase(Network::Mainnet, TESTNET_PASSPHRASE, "the RPC endpoint")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("resolved network is mainnet"));
+        assert!(err.contains("the RPC endpoint uses testnet"));
+        assert_eq!(network_passphrase(Network::Auto), MAINNET_PASSPHRASE);
+    }
+
     #[test]
     fn test_network_parsing() {
         assert_eq!("mainnet".parse::<Network>().unwrap(), Network::Mainnet);
@@ -262,6 +481,7 @@ mod tests {
 network = "mainnet"
 api_base = "http://localhost:9000"
 timeout = 55
+publisher = "GABC"
 "#,
         )
         .unwrap();
@@ -272,6 +492,27 @@ timeout = 55
         assert_eq!(defaults.network.as_deref(), Some("mainnet"));
         assert_eq!(defaults.api_base.as_deref(), Some("http://localhost:9000"));
         assert_eq!(defaults.timeout, Some(55));
+        assert_eq!(defaults.publisher.as_deref(), Some("GABC"));
+    }
+
+    #[test]
+    fn test_load_config_file_with_publish_section() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            r#"[publish]
+strict_required = ["description", "readme"]
+"#,
+        )
+        .unwrap();
+
+        let parsed = load_config_file(&config_path).unwrap();
+        assert!(parsed.defaults.is_none());
+        assert_eq!(
+            parsed.publish.unwrap().strict_required,
+            Some(vec!["description".to_string(), "readme".to_string()])
+        );
     }
 
     #[test]
@@ -0,0 +1,295 @@
+//! What a command will actually target: `context` (alias `whoami`) prints
+//! the resolved API URL, network and passphrase, the identity behind the
+//! API token and the modes in effect. The global `--resolve-aliases` flag
+//! echoes each contract reference as it's resolved to a registry ID or
+//! on-chain address.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Result;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Set by the global `--resolve-aliases` flag.
+static RESOLVE_ALIASES: AtomicBool = AtomicBool::new(false);
+
+pub fn set_resolve_aliases(on: bool) {
+    RESOLVE_ALIASES.store(on, Ordering::Relaxed);
+}
+
+/// With `--resolve-aliases`, print on stderr what `reference` resolved to.
+pub fn echo_resolved(reference: &str, resolved: &str, detail: &str) {
+    if !RESOLVE_ALIASES.load(Ordering::Relaxed) {
+        return;
+    }
+    eprintln!(
+        "{} {} resolves to {} ({})",
+        "→".cyan(),
+        reference.bold(),
+        resolved.bold(),
+        detail
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    Flag,
+    Env,
+    Config,
+    Default,
+}
+
+impl Source {
+    fn label(self) -> &'static str {
+        match self {
+            Source::Flag => "flag",
+            Source::Env => "environment",
+            Source::Config => "config file",
+            Source::Default => "default",
+        }
+    }
+}
+
+/// Who the API token says the caller is. Claims are read from the token
+/// as is, without checking its signature.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Identity {
+    pub token_set: bool,
+    /// `false` for tokens that aren't JWTs, whose holder only the registry knows.
+    pub decoded: bool,
+    pub subject: Option<String>,
+    pub issuer: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl Identity {
+    pub fn from_token(token: Option<&str>) -> Self {
+        let Some(token) = token.map(str::trim).filter(|t| !t.is_empty()) else {
+            return Identity::default();
+        };
+        let claims = token
+            .split('.')
+            .nth(1)
+            .and_then(|payload| URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok())
+            .and_then(|bytes| serde_json::from_slice::<Value>(&bytes).ok())
+            .filter(Value::is_object);
+        let Some(claims) = claims else {
+            return Identity {
+                token_set: true,
+                ..Default::default()
+            };
+        };
+        let claim = |keys: &[&str]| {
+            keys.iter()
+                .find_map(|k| claims[*k].as_str())
+                .map(str::to_string)
+        };
+        Identity {
+            token_set: true,
+            decoded: true,
+            subject: claim(&["preferred_username", "email", "name", "sub"]),
+            issuer: claim(&["iss"]),
+            expires_at: claims["exp"]
+                .as_i64()
+                .and_then(|exp| DateTime::from_timestamp(exp, 0)),
+        }
+    }
+
+    fn describe(&self, now: DateTime<Utc>) -> String {
+        if !self.token_set {
+            return format!("anonymous ({} not set)", crate::http::API_TOKEN_ENV);
+        }
+        if !self.decoded {
+            return "token set (opaque, identity known to the registry only)".to_string();
+        }
+        let mut text = self.subject.clone().unwrap_or_else(|| "unnamed".into());
+        if let Some(issuer) = &self.issuer {
+            text.push_str(&format!(" via {}", issuer));
+        }
+        match self.expires_at {
+            Some(at) if at <= now => text.push_str(&format!(", expired {}", at.to_rfc3339())),
+            Some(at) => text.push_str(&format!(", expires {}", at.to_rfc3339())),
+            None => {}
+        }
+        text
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuntimeContext {
+    pub config_file: Option<String>,
+    pub config_file_exists: bool,
+    pub api_url: String,
+    pub api_url_source: Source,
+    /// As configured; `auto` routes to mainnet.
+    pub network: String,
+    pub network_source: Source,
+    pub resolved_network: String,
+    pub passphrase: String,
+    pub identity: Identity,
+    pub wait_on_429: bool,
+    pub test_cache_dir: Option<String>,
+    pub test_cache_entries: usize,
+    pub resolve_aliases: bool,
+}
+
+impl RuntimeContext {
+    pub fn resolve(
+        api_url: &str,
+        cli_network: Option<&str>,
+        network: crate::config::Network,
+    ) -> Result<Self> {
+        let config_file = crate::config::config_file_path();
+        let api_url_source = match std::env::var(crate::API_URL_ENV) {
+            Ok(env) if env == api_url => Source::Env,
+            _ if api_url == crate::DEFAULT_API_URL => Source::Default,
+            _ => Source::Flag,
+        };
+        let network_source = if cli_network.is_some() {
+            Source::Flag
+        } else if crate::config::configured_network()?.is_some() {
+            Source::Config
+        } else {
+            Source::Default
+        };
+        let resolved = match network {
+            crate::config::Network::Auto => crate::config::Network::Mainnet,
+            other => other,
+        };
+        let test_cache_dir = crate::test_framework::ResultCache::dir().ok();
+        let test_cache_entries = test_cache_dir
+            .as_ref()
+            .and_then(|dir| std::fs::read_dir(dir).ok())
+            .map_or(0, |entries| entries.count());
+
+        Ok(RuntimeContext {
+            config_file_exists: config_file.as_ref().is_some_and(|p| p.exists()),
+            config_file: config_file.map(|p| p.display().to_string()),
+            api_url: api_url.to_string(),
+            api_url_source,
+            network: network.to_string(),
+            network_source,
+            resolved_network: resolved.to_string(),
+            passphrase: crate::config::resolve_passphrase(network)?.to_string(),
+            identity: Identity::from_token(
+                std::env::var(crate::http::API_TOKEN_ENV).ok().as_deref(),
+            ),
+            wait_on_429: crate::http::waits_on_429(),
+            test_cache_dir: test_cache_dir.map(|p| p.display().to_string()),
+            test_cache_entries,
+            resolve_aliases: RESOLVE_ALIASES.load(Ordering::Relaxed),
+        })
+    }
+}
+
+pub fn show(
+    api_url: &str,
+    cli_network: Option<&str>,
+    network: crate::config::Network,
+    json: bool,
+) -> Result<()> {
+    let context = RuntimeContext::resolve(api_url, cli_network, network)?;
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        return emitter.emit(&context, crate::output::Format::Json);
+    }
+
+    let on_off = |on: bool| {
+        if on {
+            "on".green()
+        } else {
+            "off".bright_black()
+        }
+    };
+
+    println!("\n{}", "Runtime Context".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    match &context.config_file {
+        Some(path) if context.config_file_exists => {
+            println!("  {}: {}", "Config file".bold(), path)
+        }
+        Some(path) => println!(
+            "  {}: {} {}",
+            "Config file".bold(),
+            path,
+            "(not created)".bright_black()
+        ),
+        None => println!("  {}: {}", "Config file".bold(), "—".bright_black()),
+    }
+    println!(
+        "  {}: {} {}",
+        "API URL".bold(),
+        context.api_url,
+        format!("({})", context.api_url_source.label()).bright_black()
+    );
+    let network = if context.network == context.resolved_network {
+        context.network.clone()
+    } else {
+        format!("{} → {}", context.network, context.resolved_network)
+    };
+    println!(
+        "  {}: {} {}",
+        "Network".bold(),
+        network,
+        format!("({})", context.network_source.label()).bright_black()
+    );
+    println!("  {}: {}", "Passphrase".bold(), context.passphrase);
+    println!(
+        "  {}: {}",
+        "Identity".bold(),
+        context.identity.describe(Utc::now())
+    );
+
+    println!("\n  {}", "Modes".bold());
+    println!(
+        "  {}: {}",
+        "Wait on 429".bold(),
+        on_off(context.wait_on_429)
+    );
+    println!(
+        "  {}: {}",
+        "Resolve aliases".bold(),
+        on_off(context.resolve_aliases)
+    );
+    if let Some(dir) = &context.test_cache_dir {
+        println!(
+            "  {}: {} {}",
+            "Test cache".bold(),
+            dir,
+            format!("({} file(s))", context.test_cache_entries).bright_black()
+        );
+    }
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_identity_from_jwt_claims() {
+        let claims = URL_SAFE_NO_PAD.encode(
+            r#"{"sub":"u-17","preferred_username":"alice","iss":"registry","exp":1700000000}"#,
+        );
+        let identity = Identity::from_token(Some(&format!("eyJhbGciOiJIUzI1NiJ9.{}.sig", claims)));
+        assert!(identity.decoded);
+        assert_eq!(identity.subject.as_deref(), Some("alice"));
+        assert_eq!(identity.issuer.as_deref(), Some("registry"));
+        let now = DateTime::from_timestamp(1_800_000_000, 0).unwrap();
+        assert_eq!(
+            identity.describe(now),
+            "alice via registry, expired 2023-11-14T22:13:20+00:00"
+        );
+
+        let opaque = Identity::from_token(Some("sk_live_abc123"));
+        assert!(opaque.token_set && !opaque.decoded);
+        assert_eq!(Identity::from_token(Some("  ")), Identity::default());
+        assert!(!Identity::from_token(None).token_set);
+    }
+}
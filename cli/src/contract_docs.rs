@@ -0,0 +1,267 @@
+//! Reference docs for `doc` and `openapi --format markdown`, written from
+//! the contract spec: one section per public function with its parameters,
+//! return type and doc comments.
+//!
+//! HTML is rendered from the same Markdown by [`markdown_to_html`], which
+//! only understands what [`markdown`] writes: headings, `- ` lists,
+//! paragraphs, `code` and **bold**.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use contract_abi::ContractABI;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum DocFormat {
+    #[default]
+    Markdown,
+    /// A single `index.html` with a table of contents
+    Html,
+}
+
+impl DocFormat {
+    /// File written into the output directory.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            DocFormat::Markdown => "index.md",
+            DocFormat::Html => "index.html",
+        }
+    }
+}
+
+pub fn markdown(abi: &ContractABI) -> String {
+    let mut md = format!("# {}\n\n", abi.name);
+    if let Some(v) = &abi.version {
+        md.push_str(&format!("Version: {}\n\n", v));
+    }
+    md.push_str("## Functions\n\n");
+    for func in abi.public_functions() {
+        md.push_str(&format!("### `{}`\n\n", func.name));
+        if let Some(doc) = &func.doc {
+            md.push_str(&format!("{}\n\n", doc.trim()));
+        }
+        md.push_str("**Parameters:**\n");
+        if func.params.is_empty() {
+            md.push_str("- None\n");
+        } else {
+            for p in &func.params {
+                md.push_str(&format!(
+                    "- `{}`: `{}`",
+                    p.name,
+                    p.param_type.display_name()
+                ));
+                match p.doc.as_deref().map(str::trim) {
+                    Some(doc) if !doc.is_empty() => md.push_str(&format!(" — {}\n", doc)),
+                    _ => md.push('\n'),
+                }
+            }
+        }
+        md.push_str(&format!(
+            "\n**Returns:** `{}`\n\n",
+            func.return_type.display_name()
+        ));
+    }
+    if !abi.errors.is_empty() {
+        md.push_str("## Errors\n\n");
+        for e in &abi.errors {
+            md.push_str(&format!(
+                "- **{}** (code {}): {}\n",
+                e.name,
+                e.code,
+                e.doc.as_deref().unwrap_or("")
+            ));
+        }
+    }
+    md
+}
+
+/// A standalone page for `md`, with a table of contents linking to each
+/// `###` (function) section.
+pub fn markdown_to_html(md: &str, title: &str) -> String {
+    let mut body = String::new();
+    let mut toc: Vec<(String, String)> = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut in_list = false;
+
+    for line in md.lines().map(str::trim_end) {
+        let item = line.strip_prefix("- ");
+        let level = heading_level(line);
+        if line.is_empty() || item.is_some() || level.is_some() {
+            flush_paragraph(&mut body, &mut paragraph);
+        }
+        if item.is_none() && in_list {
+            body.push_str("</ul>\n");
+            in_list = false;
+        }
+
+        if let Some(item) = item {
+            if !in_list {
+                body.push_str("<ul>\n");
+                in_list = true;
+            }
+            body.push_str(&format!("<li>{}</li>\n", inline(item)));
+        } else if let Some(level) = level {
+            let text = line[level..].trim();
+            if level == 3 {
+                let id = anchor(text, &toc);
+                body.push_str(&format!("<h3 id=\"{}\">{}</h3>\n", id, inline(text)));
+                toc.push((id, inline(text)));
+            } else {
+                body.push_str(&format!("<h{0}>{1}</h{0}>\n", level, inline(text)));
+            }
+        } else if !line.is_empty() {
+            paragraph.push(line);
+        }
+    }
+    flush_paragraph(&mut body, &mut paragraph);
+    if in_list {
+        body.push_str("</ul>\n");
+    }
+
+    let toc: String = toc
+        .iter()
+        .map(|(id, label)| format!("<li><a href=\"#{}\">{}</a></li>\n", id, label))
+        .collect();
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+  <meta charset="UTF-8">
+  <title>{}</title>
+  <style>
+    body {{ font-family: sans-serif; max-width: 50rem; margin: 2rem auto; line-height: 1.5; }}
+    code {{ background: #f3f3f3; padding: 0 0.2em; }}
+    nav ul {{ list-style: none; padding-left: 1rem; }}
+  </style>
+</head>
+<body>
+<nav>
+<h2>Contents</h2>
+<ul>
+{}</ul>
+</nav>
+{}</body>
+</html>
+"#,
+        escape(title),
+        toc,
+        body
+    )
+}
+
+/// Write the docs for `abi` into `dir`, returning the file written.
+pub fn write(abi: &ContractABI, dir: &Path, format: DocFormat) -> Result<PathBuf> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let md = markdown(abi);
+    let content = match format {
+        DocFormat::Markdown => md,
+        DocFormat::Html => markdown_to_html(&md, &abi.name),
+    };
+    let path = dir.join(format.file_name());
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// `Some(n)` for an `n`-hash ATX heading.
+fn heading_level(line: &str) -> Option<usize> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    ((1..=6).contains(&level) && line[level..].starts_with(' ')).then_some(level)
+}
+
+fn flush_paragraph(body: &mut String, paragraph: &mut Vec<&str>) {
+    if paragraph.is_empty() {
+        return;
+    }
+    body.push_str(&format!("<p>{}</p>\n", inline(&paragraph.join(" "))));
+    paragraph.clear();
+}
+
+/// `fn-<name>`, made unique among the anchors already in `toc`.
+fn anchor(heading: &str, toc: &[(String, String)]) -> String {
+    let slug: String = heading
+        .trim_matches('`')
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let base = format!("fn-{}", slug);
+    let mut id = base.clone();
+    let mut n = 1;
+    while toc.iter().any(|(taken, _)| *taken == id) {
+        n += 1;
+        id = format!("{}-{}", base, n);
+    }
+    id
+}
+
+fn inline(text: &str) -> String {
+    let mut html = String::new();
+    for (i, part) in text.split('`').enumerate() {
+        if i % 2 == 1 {
+            html.push_str(&format!("<code>{}</code>", escape(part)));
+            continue;
+        }
+        for (j, run) in part.split("**").enumerate() {
+            if j % 2 == 1 {
+                html.push_str(&format!("<strong>{}</strong>", escape(run)));
+            } else {
+                html.push_str(&escape(run));
+            }
+        }
+    }
+    html
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn functions_get_sections_and_anchored_contents() {
+        let spec = r#"[
+            {"type": "function", "name": "transfer", "doc": "Move <tokens> between accounts.",
+             "inputs": [
+                {"name": "from", "value": {"type": "address"}, "doc": "Sender"},
+                {"name": "amount", "value": {"type": "i128"}}
+             ],
+             "outputs": []},
+            {"type": "function", "name": "balance",
+             "inputs": [{"name": "id", "value": {"type": "address"}}],
+             "outputs": [{"type": "i128"}]},
+            {"type": "error_enum", "name": "TokenError",
+             "cases": [{"name": "InsufficientBalance", "value": 1, "doc": "Not enough funds"}]}
+        ]"#;
+        let abi = contract_abi::parse_json_spec(spec, "token").unwrap();
+
+        let md = markdown(&abi);
+        assert!(md.contains("### `transfer`\n\nMove <tokens> between accounts."));
+        assert!(md.contains("- `from`: `Address` — Sender\n- `amount`: `i128`\n"));
+        assert!(md.contains("**Returns:** `i128`"));
+        assert!(md.contains("- **TokenError::InsufficientBalance** (code 1): Not enough funds"));
+
+        let html = markdown_to_html(&md, &abi.name);
+        assert!(html.contains("<li><a href=\"#fn-transfer\"><code>transfer</code></a></li>"));
+        assert!(html.contains("<h3 id=\"fn-balance\"><code>balance</code></h3>"));
+        assert!(html.contains("<p>Move &lt;tokens&gt; between accounts.</p>"));
+        assert!(html.contains("<li><code>from</code>: <code>Address</code> — Sender</li>"));
+        assert!(html.contains("<p><strong>Returns:</strong> <code>i128</code></p>"));
+        assert_eq!(html.matches("<ul>").count(), html.matches("</ul>").count());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = write(&abi, &dir.path().join("docs"), DocFormat::Html).unwrap();
+        assert_eq!(path.file_name().unwrap(), "index.html");
+    }
+}
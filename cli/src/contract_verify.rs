@@ -133,6 +133,117 @@ pub async fn run(api_url: &str, address: &str, network: &str, json: bool) -> Res
     Ok(())
 }
 
+// ── On-chain integrity ───────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainIntegrityStatus {
+    /// The deployed wasm is the one the registry records.
+    Match,
+    /// The deployed wasm differs from the registered hash.
+    Mismatch,
+    /// No contract instance exists at the address.
+    NotDeployed,
+    /// Nothing to compare, or the RPC lookup failed.
+    Unknown,
+}
+
+/// Registered vs. deployed wasm hash for one contract.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainIntegrity {
+    pub status: ChainIntegrityStatus,
+    pub registered_hash: Option<String>,
+    pub onchain_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl ChainIntegrity {
+    /// Compare hashes; `onchain` is `None` when no instance was found.
+    pub fn classify(registered: Option<&str>, onchain: Option<Option<String>>) -> Self {
+        let registered_hash = registered
+            .map(str::trim)
+            .filter(|h| !h.is_empty())
+            .map(str::to_string);
+        let (status, onchain_hash, detail) = match onchain {
+            None => (ChainIntegrityStatus::NotDeployed, None, None),
+            Some(None) => (
+                ChainIntegrityStatus::Unknown,
+                None,
+                Some("contract is not backed by wasm (built-in executable)".to_string()),
+            ),
+            Some(Some(hash)) => {
+                let status = match &registered_hash {
+                    None => ChainIntegrityStatus::Unknown,
+                    Some(r) if r.eq_ignore_ascii_case(&hash) => ChainIntegrityStatus::Match,
+                    Some(_) => ChainIntegrityStatus::Mismatch,
+                };
+                let detail = registered_hash
+                    .is_none()
+                    .then(|| "registry has no wasm hash for this contract".to_string());
+                (status, Some(hash), detail)
+            }
+        };
+        ChainIntegrity {
+            status,
+            registered_hash,
+            onchain_hash,
+            detail,
+        }
+    }
+
+    fn unknown(registered: Option<&str>, detail: String) -> Self {
+        ChainIntegrity {
+            detail: Some(detail),
+            ..Self::classify(registered, Some(None))
+        }
+    }
+
+    /// One-line ✓/✗ indicator for inline display.
+    pub fn indicator(&self) -> String {
+        match self.status {
+            ChainIntegrityStatus::Match => {
+                format!("{} on-chain wasm matches registry", "✓".green())
+            }
+            ChainIntegrityStatus::Mismatch => format!(
+                "{} on-chain wasm {} differs from registered {}",
+                "✗".red().bold(),
+                self.onchain_hash.as_deref().unwrap_or("?"),
+                self.registered_hash.as_deref().unwrap_or("?")
+            ),
+            ChainIntegrityStatus::NotDeployed => {
+                format!("{} no contract instance on-chain", "✗".red().bold())
+            }
+            ChainIntegrityStatus::Unknown => format!(
+                "{} unchecked: {}",
+                "⚠".yellow(),
+                self.detail.as_deref().unwrap_or("unknown")
+            ),
+        }
+    }
+}
+
+/// Look up the wasm hash deployed at `address` and compare it with the
+/// registered one. RPC failures are reported as `Unknown`, never as errors,
+/// so callers can show the result inline.
+pub async fn check_chain_integrity(
+    client: &reqwest::Client,
+    address: &str,
+    network: &str,
+    registered_hash: Option<&str>,
+) -> ChainIntegrity {
+    let lookup = async {
+        let endpoint = crate::network::rpc_endpoint(network)
+            .with_context(|| format!("No RPC endpoint known for network {}", network))?;
+        crate::rpc::ensure_network(client, &endpoint, network.parse()?).await?;
+        crate::rpc::fetch_contract_entries(client, &endpoint, address).await
+    };
+    match lookup.await {
+        Ok(entries) => ChainIntegrity::classify(registered_hash, entries.map(|e| e.wasm_hash)),
+        Err(e) => ChainIntegrity::unknown(registered_hash, format!("RPC lookup failed: {}", e)),
+    }
+}
+
 // ── Helpers ───────────────────────────────────────────────────────────────────
 
 /// Pull the first contract item from either a paginated list or a direct object.
@@ -391,3 +502,28 @@ fn print_human(result: &VerificationResult, detail: &Option<Value>) {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_registered_against_onchain_hash() {
+        let deployed = |h: &str| Some(Some(h.to_string()));
+        assert_eq!(
+            ChainIntegrity::classify(Some("ABCD"), deployed("abcd")).status,
+            ChainIntegrityStatus::Match
+        );
+        assert_eq!(
+            ChainIntegrity::classify(Some("abcd"), deployed("ef01")).status,
+            ChainIntegrityStatus::Mismatch
+        );
+        assert_eq!(
+            ChainIntegrity::classify(Some("abcd"), None).status,
+            ChainIntegrityStatus::NotDeployed
+        );
+        let unregistered = ChainIntegrity::classify(Some(" "), deployed("abcd"));
+        assert_eq!(unregistered.status, ChainIntegrityStatus::Unknown);
+        assert_eq!(unregistered.onchain_hash.as_deref(), Some("abcd"));
+    }
+}
@@ -0,0 +1,128 @@
+//! Publisher-defined metadata attached to a contract with `publish --meta`.
+//!
+//! Stored on the record as a flat string map under `custom_fields`, so
+//! `--filter "custom_fields.owner=alice"` works like any other field.
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+/// Custom field name → value, ordered by name.
+pub type CustomFields = BTreeMap<String, String>;
+
+pub const MAX_FIELDS: usize = 32;
+pub const MAX_KEY_LEN: usize = 64;
+pub const MAX_VALUE_BYTES: usize = 256;
+
+/// Keys are lowercase so filters don't depend on how the publisher typed
+/// them, and exclude characters that mean something in a filter expression.
+fn validate_key(key: &str) -> Result<()> {
+    if key.is_empty() || key.len() > MAX_KEY_LEN {
+        bail!(
+            "Custom field name '{}' must be 1-{} characters",
+            key,
+            MAX_KEY_LEN
+        );
+    }
+    if !key.starts_with(|c: char| c.is_ascii_lowercase()) {
+        bail!(
+            "Custom field name '{}' must start with a lowercase letter",
+            key
+        );
+    }
+    if let Some(c) = key
+        .chars()
+        .find(|c| !matches!(c, 'a'..='z' | '0'..='9' | '_' | '-'))
+    {
+        bail!(
+            "Custom field name '{}' contains '{}' (allowed: a-z, 0-9, '_', '-')",
+            key,
+            c
+        );
+    }
+    Ok(())
+}
+
+/// Parse repeated `--meta key=value` arguments. The value is everything
+/// after the first `=` and may be empty; repeating a key is an error.
+pub fn parse(specs: &[String]) -> Result<CustomFields> {
+    if specs.len() > MAX_FIELDS {
+        bail!(
+            "Too many custom fields: {} (at most {})",
+            specs.len(),
+            MAX_FIELDS
+        );
+    }
+    let mut fields = CustomFields::new();
+    for spec in specs {
+        let Some((key, value)) = spec.split_once('=') else {
+            bail!("Invalid --meta '{}': expected <key>=<value>", spec);
+        };
+        let key = key.trim();
+        validate_key(key)?;
+        if value.len() > MAX_VALUE_BYTES {
+            bail!(
+                "Custom field '{}' is {} bytes (at most {})",
+                key,
+                value.len(),
+                MAX_VALUE_BYTES
+            );
+        }
+        if fields.insert(key.to_string(), value.to_string()).is_some() {
+            bail!("Custom field '{}' given more than once", key);
+        }
+    }
+    Ok(fields)
+}
+
+/// Custom fields recorded on a contract. Non-string values are skipped.
+pub fn from_contract(contract: &Value) -> CustomFields {
+    contract["custom_fields"]
+        .as_object()
+        .map(|fields| {
+            fields
+                .iter()
+                .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn specs(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_and_validates_meta_specs() {
+        let fields = parse(&specs(&["owner=alice", "ticket=OPS-12=b", "note="])).unwrap();
+        assert_eq!(fields["owner"], "alice");
+        assert_eq!(fields["ticket"], "OPS-12=b");
+        assert_eq!(fields["note"], "");
+
+        assert!(parse(&specs(&["owner"])).is_err());
+        assert!(parse(&specs(&["Owner=alice"])).is_err());
+        assert!(parse(&specs(&["team.name=core"])).is_err());
+        assert!(parse(&specs(&["owner=a", "owner=b"])).is_err());
+        assert!(parse(&specs(&[&format!(
+            "owner={}",
+            "x".repeat(MAX_VALUE_BYTES + 1)
+        )]))
+        .is_err());
+        assert!(parse(&vec!["k=v".to_string(); MAX_FIELDS + 1]).is_err());
+    }
+
+    #[test]
+    fn reads_fields_back_from_a_contract() {
+        let contract = json!({ "custom_fields": { "owner": "alice", "tier": 2 } });
+        let fields = from_contract(&contract);
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields["owner"], "alice");
+        assert!(from_contract(&json!({})).is_empty());
+    }
+}
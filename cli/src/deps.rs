@@ -0,0 +1,462 @@
+//! Dependency graph traversal for the `deps` commands.
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+use shared::{SemVer, VersionConstraint};
+
+/// Default for `--max-depth`: deeper graphs are treated as malformed.
+pub const DEFAULT_MAX_DEPTH: usize = 32;
+
+fn node_name(node: &Value) -> &str {
+    node["name"].as_str().unwrap_or("unknown")
+}
+
+/// Check a dependency tree before anything walks it: every branch must stay
+/// within `max_depth` levels and no dependency may appear among its own
+/// ancestors. Cycles are reported with their full path (`a → b → c → a`).
+pub fn validate_tree(root: &str, tree: &[Value], max_depth: usize) -> Result<()> {
+    fn walk(nodes: &[Value], path: &mut Vec<String>, max_depth: usize) -> Result<()> {
+        for node in nodes {
+            let name = node_name(node);
+            if let Some(start) = path.iter().position(|p| p == name) {
+                let mut cycle = path[start..].to_vec();
+                cycle.push(name.to_string());
+                bail!("Dependency cycle detected: {}", cycle.join(" → "));
+            }
+            let Some(children) = node["dependencies"].as_array().filter(|c| !c.is_empty()) else {
+                continue;
+            };
+            if path.len() >= max_depth {
+                bail!(
+                    "Dependency graph deeper than --max-depth {} at {} → {}",
+                    max_depth,
+                    path.join(" → "),
+                    name
+                );
+            }
+            path.push(name.to_string());
+            walk(children, path, max_depth)?;
+            path.pop();
+        }
+        Ok(())
+    }
+
+    walk(tree, &mut vec![root.to_string()], max_depth)
+}
+
+/// Fetch a contract's resolved dependency tree from the registry, rejecting
+/// cyclic or over-deep graphs (see [`validate_tree`]).
+pub async fn fetch_tree(
+    client: &reqwest::Client,
+    api_url: &str,
+    contract_id: &str,
+    max_depth: usize,
+) -> Result<Vec<Value>> {
+    let url = format!("{}/api/contracts/{}/dependencies", api_url, contract_id);
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to fetch contract dependencies")?;
+
+    if !response.status().is_success() {
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            anyhow::bail!("Contract not found");
+        }
+        anyhow::bail!("Failed to fetch dependencies: {}", response.status());
+    }
+
+    let items: Value = response.json().await?;
+    let tree = items
+        .as_array()
+        .cloned()
+        .context("Invalid response format")?;
+    validate_tree(contract_id, &tree, max_depth)?;
+    Ok(tree)
+}
+
+/// One version requirement on a dependency, and the chain that imposed it.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct Requirement {
+    pub constraint: String,
+    /// Dependency path from the root, ending with the dependent that
+    /// declared this requirement.
+    pub required_by: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Conflict {
+    pub dependency: String,
+    pub requirements: Vec<Requirement>,
+}
+
+/// Collect every version requirement in the tree, keyed by dependency name.
+pub fn collect_requirements(root: &str, tree: &[Value]) -> BTreeMap<String, Vec<Requirement>> {
+    fn walk(nodes: &[Value], path: &mut Vec<String>, out: &mut BTreeMap<String, Vec<Requirement>>) {
+        for node in nodes {
+            let name = node_name(node).to_string();
+            let constraint = node["constraint_to_parent"]
+                .as_str()
+                .unwrap_or("*")
+                .to_string();
+            out.entry(name.clone()).or_default().push(Requirement {
+                constraint,
+                required_by: path.clone(),
+            });
+
+            if let Some(children) = node["dependencies"].as_array() {
+                path.push(name);
+                walk(children, path, out);
+                path.pop();
+            }
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    walk(tree, &mut vec![root.to_string()], &mut out);
+    out
+}
+
+/// Lowest version a constraint admits.
+fn lower_bound(constraint: &VersionConstraint) -> &SemVer {
+    match constraint {
+        VersionConstraint::Exact(v) | VersionConstraint::Caret(v) | VersionConstraint::Tilde(v) => {
+            v
+        }
+    }
+}
+
+/// Whether some version satisfies every constraint. Each constraint is a
+/// range with an inclusive lower bound, so the ranges intersect exactly when
+/// the highest lower bound satisfies all of them. `*` and unparsable
+/// constraints are treated as unconstrained.
+pub fn satisfiable(constraints: &[&str]) -> bool {
+    let parsed: Vec<VersionConstraint> = constraints
+        .iter()
+        .filter(|c| c.trim() != "*")
+        .filter_map(|c| VersionConstraint::parse(c))
+        .collect();
+    let Some(candidate) = parsed.iter().map(lower_bound).max() else {
+        return true;
+    };
+    parsed.iter().all(|c| c.matches(candidate))
+}
+
+/// Dependencies whose combined requirements no single version can satisfy.
+pub fn find_conflicts(requirements: &BTreeMap<String, Vec<Requirement>>) -> Vec<Conflict> {
+    requirements
+        .iter()
+        .filter(|(_, reqs)| {
+            let constraints: Vec<&str> = reqs.iter().map(|r| r.constraint.as_str()).collect();
+            !satisfiable(&constraints)
+        })
+        .map(|(dep, reqs)| Conflict {
+            dependency: dep.clone(),
+            requirements: reqs.clone(),
+        })
+        .collect()
+}
+
+/// `deps conflicts --json` output.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ConflictReport {
+    pub contract_id: String,
+    pub dependencies_checked: usize,
+    pub conflicts: Vec<Conflict>,
+}
+
+pub async fn conflicts(
+    api_url: &str,
+    contract_id: &str,
+    max_depth: usize,
+    json: bool,
+) -> Result<()> {
+    let client = reqwest::Client::new();
+    let tree = fetch_tree(&client, api_url, contract_id, max_depth).await?;
+    let requirements = collect_requirements(contract_id, &tree);
+    let conflicts = find_conflicts(&requirements);
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(
+            &ConflictReport {
+                contract_id: contract_id.to_string(),
+                dependencies_checked: requirements.len(),
+                conflicts: conflicts.clone(),
+            },
+            crate::output::Format::Json,
+        )?;
+    } else {
+        println!("\n{}", "Dependency Conflicts:".bold().cyan());
+        println!("{}", "=".repeat(80).cyan());
+
+        for conflict in &conflicts {
+            println!("\n  {} {}", "✗".red(), conflict.dependency.bold());
+            for req in &conflict.requirements {
+                println!(
+                    "      {} required by {}",
+                    req.constraint.cyan(),
+                    req.required_by.join(" → ")
+                );
+            }
+        }
+
+        println!("\n{}", "=".repeat(80).cyan());
+        if conflicts.is_empty() {
+            println!(
+                "{}\n",
+                format!("✓ No conflicts across {} dependencies", requirements.len())
+                    .green()
+                    .bold()
+            );
+        }
+    }
+
+    if !conflicts.is_empty() {
+        anyhow::bail!("{} dependency version conflict(s) found", conflicts.len());
+    }
+    Ok(())
+}
+
+/// A dependency reached anywhere in the tree, with the first path to it.
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyLicense {
+    pub name: String,
+    pub contract_id: String,
+    pub license: String,
+    pub allowed: bool,
+    pub required_by: Vec<String>,
+}
+
+/// Every distinct dependency in the tree (by name), in first-seen order,
+/// paired with its contract ID and the path that first reached it.
+pub fn unique_dependencies(root: &str, tree: &[Value]) -> Vec<(String, String, Vec<String>)> {
+    fn walk(nodes: &[Value], path: &mut Vec<String>, out: &mut Vec<(String, String, Vec<String>)>) {
+        for node in nodes {
+            let name = node_name(node).to_string();
+            if !out.iter().any(|(n, _, _)| *n == name) {
+                let contract_id = node["contract_id"]
+                    .as_str()
+                    .unwrap_or("unknown")
+                    .to_string();
+                out.push((name.clone(), contract_id, path.clone()));
+            }
+            if let Some(children) = node["dependencies"].as_array() {
+                path.push(name);
+                walk(children, path, out);
+                path.pop();
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(tree, &mut vec![root.to_string()], &mut out);
+    out
+}
+
+/// License registered on a dependency. Unresolved dependencies and lookup
+/// failures come back as [`crate::license::UNKNOWN`].
+async fn fetch_license(client: &reqwest::Client, api_url: &str, contract_id: &str) -> String {
+    if contract_id == "unknown" {
+        return crate::license::UNKNOWN.to_string();
+    }
+    let url = format!("{}/api/contracts/{}", api_url, contract_id);
+    let body: Option<Value> = match client.get(&url).send().await {
+        Ok(resp) if resp.status().is_success() => resp.json().await.ok(),
+        _ => None,
+    };
+    match body {
+        Some(body) => crate::license::of(body.get("contract").unwrap_or(&body)),
+        None => {
+            log::debug!("Could not fetch license for {}", contract_id);
+            crate::license::UNKNOWN.to_string()
+        }
+    }
+}
+
+pub async fn licenses(
+    api_url: &str,
+    contract_id: &str,
+    max_depth: usize,
+    json: bool,
+) -> Result<()> {
+    let client = reqwest::Client::new();
+    let tree = fetch_tree(&client, api_url, contract_id, max_depth).await?;
+    let allowed = crate::config::allowed_licenses()?;
+
+    let mut dependencies = Vec::new();
+    for (name, dep_id, required_by) in unique_dependencies(contract_id, &tree) {
+        let license = fetch_license(&client, api_url, &dep_id).await;
+        dependencies.push(DependencyLicense {
+            allowed: crate::license::is_allowed(&license, &allowed),
+            name,
+            contract_id: dep_id,
+            license,
+            required_by,
+        });
+    }
+
+    let mut summary: BTreeMap<&str, usize> = BTreeMap::new();
+    for dep in &dependencies {
+        *summary.entry(dep.license.as_str()).or_default() += 1;
+    }
+    let disallowed: Vec<&DependencyLicense> = dependencies.iter().filter(|d| !d.allowed).collect();
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(
+            &serde_json::json!({
+                "contract_id": contract_id,
+                "allowlist": allowed,
+                "summary": summary,
+                "dependencies": dependencies,
+                "disallowed": disallowed.len(),
+            }),
+            crate::output::Format::Json,
+        )?;
+    } else {
+        println!("\n{}", "Dependency Licenses:".bold().cyan());
+        println!("{}", "=".repeat(80).cyan());
+
+        if dependencies.is_empty() {
+            println!("{}", "No dependencies found.".yellow());
+        }
+        for (license, count) in &summary {
+            println!("  {:<30} {}", license.bold(), count);
+        }
+
+        if !disallowed.is_empty() {
+            println!("\n  {}", "Not on the allowlist:".red().bold());
+            for dep in &disallowed {
+                println!(
+                    "  {} {} ({}) via {}",
+                    "✗".red(),
+                    dep.name.bold(),
+                    dep.license,
+                    dep.required_by.join(" → ")
+                );
+            }
+        }
+
+        println!("\n{}", "=".repeat(80).cyan());
+        if allowed.is_empty() {
+            println!(
+                "{}\n",
+                "⚠ No [licenses] allowed list configured; nothing was rejected".yellow()
+            );
+        } else if disallowed.is_empty() {
+            println!(
+                "{}\n",
+                format!(
+                    "✓ All {} dependencies use allowed licenses",
+                    dependencies.len()
+                )
+                .green()
+                .bold()
+            );
+        }
+    }
+
+    if !disallowed.is_empty() {
+        anyhow::bail!("{} dependency license(s) not allowed", disallowed.len());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn satisfiable_intersects_ranges() {
+        assert!(satisfiable(&["^1.2.0", "~1.4.1", "*"]));
+        assert!(satisfiable(&["^1.0.0", "1.5.0"]));
+        assert!(!satisfiable(&["^1.0.0", "^2.0.0"]));
+        assert!(!satisfiable(&["~1.2.0", "1.3.0"]));
+        assert!(!satisfiable(&["^0.2.0", "^0.3.0"]));
+        assert!(satisfiable(&[]));
+    }
+
+    #[test]
+    fn conflicts_report_requiring_paths() {
+        let tree = vec![
+            json!({ "name": "token", "constraint_to_parent": "^1.0.0", "dependencies": [] }),
+            json!({
+                "name": "vault",
+                "constraint_to_parent": "^2.1.0",
+                "dependencies": [
+                    { "name": "token", "constraint_to_parent": "^2.0.0", "dependencies": [] }
+                ]
+            }),
+        ];
+        let reqs = collect_requirements("app", &tree);
+        let conflicts = find_conflicts(&reqs);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].dependency, "token");
+        assert_eq!(conflicts[0].requirements[0].required_by, vec!["app"]);
+        assert_eq!(
+            conflicts[0].requirements[1].required_by,
+            vec!["app", "vault"]
+        );
+    }
+
+    #[test]
+    fn unique_dependencies_keeps_first_path() {
+        let tree = vec![
+            json!({ "name": "token", "contract_id": "C1", "dependencies": [] }),
+            json!({
+                "name": "vault",
+                "contract_id": "C2",
+                "dependencies": [
+                    { "name": "token", "contract_id": "C1", "dependencies": [] },
+                    { "name": "oracle", "dependencies": [] }
+                ]
+            }),
+        ];
+        let deps = unique_dependencies("app", &tree);
+        let names: Vec<&str> = deps.iter().map(|(n, _, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["token", "vault", "oracle"]);
+        assert_eq!(deps[0].2, vec!["app"]);
+        assert_eq!(deps[2].1, "unknown");
+        assert_eq!(deps[2].2, vec!["app", "vault"]);
+    }
+
+    #[test]
+    fn validate_tree_reports_cycles_and_depth() {
+        // app → a → b → c → a
+        let cyclic = vec![json!({
+            "name": "a",
+            "dependencies": [{
+                "name": "b",
+                "dependencies": [{
+                    "name": "c",
+                    "dependencies": [{
+                        "name": "a",
+                        "dependencies": [{ "name": "b", "dependencies": [] }]
+                    }]
+                }]
+            }]
+        })];
+        let err = validate_tree("app", &cyclic, DEFAULT_MAX_DEPTH)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("a → b → c → a"), "{}", err);
+
+        let chain = vec![json!({
+            "name": "a",
+            "dependencies": [{
+                "name": "b",
+                "dependencies": [{ "name": "c", "dependencies": [] }]
+            }]
+        })];
+        assert!(validate_tree("app", &chain, 3).is_ok());
+        let err = validate_tree("app", &chain, 2).unwrap_err().to_string();
+        assert!(err.contains("--max-depth 2"), "{}", err);
+    }
+}
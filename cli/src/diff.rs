@@ -0,0 +1,574 @@
+//! Line and file-tree diffing shared by commands that compare text content.
+
+use std::collections::BTreeMap;
+
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::Value;
+
+/// One line of an edit script turning `old` into `new`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineOp<'a> {
+    Equal(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Compute a minimal line edit script between `old` and `new` (LCS based).
+pub fn line_diff<'a>(old: &'a str, new: &'a str) -> Vec<LineOp<'a>> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] = length of the LCS of a[i..] and b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(a.len().max(b.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            ops.push(LineOp::Equal(a[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(LineOp::Removed(a[i]));
+            i += 1;
+        } else {
+            ops.push(LineOp::Added(b[j]));
+            j += 1;
+        }
+    }
+    ops.extend(a[i..].iter().map(|l| LineOp::Removed(l)));
+    ops.extend(b[j..].iter().map(|l| LineOp::Added(l)));
+    ops
+}
+
+/// Count of `(added, removed)` lines in an edit script.
+pub fn line_stats(ops: &[LineOp]) -> (usize, usize) {
+    ops.iter().fold((0, 0), |(add, del), op| match op {
+        LineOp::Added(_) => (add + 1, del),
+        LineOp::Removed(_) => (add, del + 1),
+        LineOp::Equal(_) => (add, del),
+    })
+}
+
+/// Render an edit script as unified-diff hunks with `context` lines around
+/// each change. Returns an empty string when there are no changes.
+pub fn unified(old_label: &str, new_label: &str, ops: &[LineOp], context: usize) -> String {
+    let changed: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| !matches!(op, LineOp::Equal(_)))
+        .map(|(i, _)| i)
+        .collect();
+    if changed.is_empty() {
+        return String::new();
+    }
+
+    // Group changes whose context windows overlap into hunks.
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &i in &changed {
+        let start = i.saturating_sub(context);
+        let end = (i + context + 1).min(ops.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut out = format!("--- {}\n+++ {}\n", old_label, new_label);
+    for (start, end) in hunks {
+        // Line numbers at the start of the hunk.
+        let (mut old_line, mut new_line) = (1, 1);
+        for op in &ops[..start] {
+            match op {
+                LineOp::Equal(_) => {
+                    old_line += 1;
+                    new_line += 1;
+                }
+                LineOp::Removed(_) => old_line += 1,
+                LineOp::Added(_) => new_line += 1,
+            }
+        }
+        let hunk = &ops[start..end];
+        let old_len = hunk
+            .iter()
+            .filter(|op| !matches!(op, LineOp::Added(_)))
+            .count();
+        let new_len = hunk
+            .iter()
+            .filter(|op| !matches!(op, LineOp::Removed(_)))
+            .count();
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_line, old_len, new_line, new_len
+        ));
+        for op in hunk {
+            match op {
+                LineOp::Equal(l) => out.push_str(&format!(" {}\n", l)),
+                LineOp::Removed(l) => out.push_str(&format!("-{}\n", l)),
+                LineOp::Added(l) => out.push_str(&format!("+{}\n", l)),
+            }
+        }
+    }
+    out
+}
+
+/// Print a unified diff with added/removed lines coloured.
+pub fn print_unified(diff: &str) {
+    for line in diff.lines() {
+        if line.starts_with("+++") || line.starts_with("---") {
+            println!("{}", line.bold());
+        } else if line.starts_with("@@") {
+            println!("{}", line.cyan());
+        } else if line.starts_with('+') {
+            println!("{}", line.green());
+        } else if line.starts_with('-') {
+            println!("{}", line.red());
+        } else {
+            println!("{}", line);
+        }
+    }
+}
+
+/// Result of a three-way line merge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Merge {
+    pub text: String,
+    /// Number of regions where both sides changed the base differently.
+    pub conflicts: usize,
+}
+
+/// Per-base-line view of an edit script: whether each base line survives and
+/// which lines are inserted before each position (`0..=base.len()`).
+///
+/// Merging walks "slots" alternating between insertion gaps and base lines:
+/// slot `2k` is the gap before base line `k`, slot `2k + 1` is the line itself.
+struct Alignment<'a> {
+    keep: Vec<bool>,
+    inserts: Vec<Vec<&'a str>>,
+}
+
+fn align<'a>(base_len: usize, ops: &[LineOp<'a>]) -> Alignment<'a> {
+    let mut keep = vec![false; base_len];
+    let mut inserts = vec![Vec::new(); base_len + 1];
+    let mut idx = 0;
+    for op in ops {
+        match op {
+            LineOp::Equal(_) => {
+                keep[idx] = true;
+                idx += 1;
+            }
+            LineOp::Removed(_) => idx += 1,
+            LineOp::Added(l) => inserts[idx].push(*l),
+        }
+    }
+    Alignment { keep, inserts }
+}
+
+impl Alignment<'_> {
+    fn changed(&self, slot: usize) -> bool {
+        if slot.is_multiple_of(2) {
+            !self.inserts[slot / 2].is_empty()
+        } else {
+            !self.keep[slot / 2]
+        }
+    }
+
+    fn side<'b>(&'b self, base: &[&'b str], slots: std::ops::Range<usize>) -> Vec<&'b str> {
+        let mut out = Vec::new();
+        for slot in slots {
+            if slot % 2 == 0 {
+                out.extend(self.inserts[slot / 2].iter().copied());
+            } else if self.keep[slot / 2] {
+                out.push(base[slot / 2]);
+            }
+        }
+        out
+    }
+}
+
+/// Three-way merge of `ours` and `theirs` against their common `base`.
+///
+/// Regions changed on only one side take that side; regions changed
+/// identically on both sides are kept once; anything else is emitted between
+/// git-style conflict markers labelled with `ours_label` / `theirs_label`.
+pub fn merge3(base: &str, ours: &str, theirs: &str, ours_label: &str, theirs_label: &str) -> Merge {
+    let base_lines: Vec<&str> = base.lines().collect();
+    let o = align(base_lines.len(), &line_diff(base, ours));
+    let t = align(base_lines.len(), &line_diff(base, theirs));
+    let slots = base_lines.len() * 2 + 1;
+
+    let mut text = String::new();
+    let mut push = |line: &str| {
+        text.push_str(line);
+        text.push('\n');
+    };
+    let mut conflicts = 0;
+    let mut slot = 0;
+    while slot < slots {
+        if !o.changed(slot) && !t.changed(slot) {
+            if slot % 2 == 1 {
+                push(base_lines[slot / 2]);
+            }
+            slot += 1;
+            continue;
+        }
+
+        let mut end = slot;
+        while end < slots && (o.changed(end) || t.changed(end)) {
+            end += 1;
+        }
+        let original: Vec<&str> = (slot..end)
+            .filter(|s| s % 2 == 1)
+            .map(|s| base_lines[s / 2])
+            .collect();
+        let mine = o.side(&base_lines, slot..end);
+        let other = t.side(&base_lines, slot..end);
+
+        if mine == original || mine == other {
+            other.iter().for_each(|l| push(l));
+        } else if other == original {
+            mine.iter().for_each(|l| push(l));
+        } else {
+            conflicts += 1;
+            push(&format!("<<<<<<< {}", ours_label));
+            mine.iter().for_each(|l| push(l));
+            push("=======");
+            other.iter().for_each(|l| push(l));
+            push(&format!(">>>>>>> {}", theirs_label));
+        }
+        slot = end;
+    }
+
+    if !(theirs.ends_with('\n') || ours.ends_with('\n')) {
+        text.pop();
+    }
+    Merge { text, conflicts }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileStatus {
+    Added,
+    Removed,
+    Modified,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileChange {
+    pub path: String,
+    pub status: FileStatus,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+/// File-level comparison of two path → content maps. Modified files carry a
+/// unified diff.
+pub fn diff_trees(
+    base: &BTreeMap<String, String>,
+    current: &BTreeMap<String, String>,
+) -> Vec<FileChange> {
+    let mut paths: Vec<&String> = base.keys().chain(current.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let old = base.get(path).map(String::as_str);
+            let new = current.get(path).map(String::as_str);
+            let ops = line_diff(old.unwrap_or(""), new.unwrap_or(""));
+            let (lines_added, lines_removed) = line_stats(&ops);
+            let status = match (old, new) {
+                (None, _) => FileStatus::Added,
+                (_, None) => FileStatus::Removed,
+                (Some(o), Some(n)) if o == n => FileStatus::Unchanged,
+                _ => FileStatus::Modified,
+            };
+            let diff = (status != FileStatus::Unchanged)
+                .then(|| unified(&format!("a/{}", path), &format!("b/{}", path), &ops, 3));
+            FileChange {
+                path: path.clone(),
+                status,
+                lines_added,
+                lines_removed,
+                diff,
+            }
+        })
+        .collect()
+}
+
+/// How one public method differs between two contract ABIs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InterfaceChangeKind {
+    Added,
+    Removed,
+    SignatureChanged,
+}
+
+/// Semver component a change requires bumping, ordered by severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl std::fmt::Display for Bump {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Bump::Patch => "patch",
+            Bump::Minor => "minor",
+            Bump::Major => "major",
+        })
+    }
+}
+
+impl InterfaceChangeKind {
+    /// Removing a method or changing its parameters or return type breaks
+    /// existing callers; adding one doesn't.
+    pub fn bump(self) -> Bump {
+        match self {
+            InterfaceChangeKind::Added => Bump::Minor,
+            InterfaceChangeKind::Removed | InterfaceChangeKind::SignatureChanged => Bump::Major,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InterfaceChange {
+    pub method: String,
+    pub kind: InterfaceChangeKind,
+    /// Signature before the change; absent for added methods.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old: Option<String>,
+    /// Signature after the change; absent for removed methods.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new: Option<String>,
+}
+
+/// `name(param: type, ...) -> returns`, the part of a method callers
+/// depend on.
+fn method_signature(method: &Value) -> String {
+    let returns: Vec<String> = method["outputs"]
+        .as_array()
+        .map(|outputs| outputs.iter().map(crate::invoke::type_name).collect())
+        .unwrap_or_default();
+    let call = crate::invoke::signature(method);
+    if returns.is_empty() {
+        call
+    } else {
+        format!("{} -> {}", call, returns.join(", "))
+    }
+}
+
+/// Method-level comparison of two ABIs, sorted by method name. Unchanged
+/// methods are left out.
+pub fn interface_diff(old: &[Value], new: &[Value]) -> Vec<InterfaceChange> {
+    let methods = |abi: &[Value]| -> BTreeMap<String, String> {
+        abi.iter()
+            .filter(|item| item["type"] == "function")
+            .filter_map(|f| Some((f["name"].as_str()?.to_string(), method_signature(f))))
+            .collect()
+    };
+    let (old, new) = (methods(old), methods(new));
+    let mut names: Vec<&String> = old.keys().chain(new.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let (before, after) = (old.get(name), new.get(name));
+            let kind = match (before, after) {
+                (None, _) => InterfaceChangeKind::Added,
+                (_, None) => InterfaceChangeKind::Removed,
+                (Some(b), Some(a)) if b != a => InterfaceChangeKind::SignatureChanged,
+                _ => return None,
+            };
+            Some(InterfaceChange {
+                method: name.clone(),
+                kind,
+                old: before.cloned(),
+                new: after.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// The bump covering every change; `Patch` when the interface is unchanged.
+pub fn required_bump(changes: &[InterfaceChange]) -> Bump {
+    changes
+        .iter()
+        .map(|c| c.kind.bump())
+        .max()
+        .unwrap_or(Bump::Patch)
+}
+
+/// `version` bumped by `bump`, keeping a leading `v`. `None` unless the
+/// version is a plain `MAJOR.MINOR.PATCH`.
+pub fn next_version(version: &str, bump: Bump) -> Option<String> {
+    let (prefix, core) = match version.strip_prefix('v') {
+        Some(rest) => ("v", rest),
+        None => ("", version),
+    };
+    let parts: Vec<u64> = core
+        .split('.')
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    let [major, minor, patch] = parts[..] else {
+        return None;
+    };
+    let (major, minor, patch) = match bump {
+        Bump::Major => (major + 1, 0, 0),
+        Bump::Minor => (major, minor + 1, 0),
+        Bump::Patch => (major, minor, patch + 1),
+    };
+    Some(format!("{}{}.{}.{}", prefix, major, minor, patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_diff_finds_minimal_edit() {
+        let ops = line_diff("a\nb\nc\n", "a\nx\nc\nd\n");
+        assert_eq!(
+            ops,
+            vec![
+                LineOp::Equal("a"),
+                LineOp::Removed("b"),
+                LineOp::Added("x"),
+                LineOp::Equal("c"),
+                LineOp::Added("d"),
+            ]
+        );
+        assert_eq!(line_stats(&ops), (2, 1));
+    }
+
+    #[test]
+    fn unified_output_has_hunk_headers() {
+        let old = (1..=20)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let new = old.replace("10", "ten");
+        let diff = unified("a/f", "b/f", &line_diff(&old, &new), 2);
+        assert!(diff.starts_with("--- a/f\n+++ b/f\n"));
+        assert!(diff.contains("@@ -8,5 +8,5 @@"));
+        assert!(diff.contains("-10\n+ten\n"));
+        assert!(unified("a", "b", &line_diff("x", "x"), 3).is_empty());
+    }
+
+    #[test]
+    fn merge3_combines_independent_edits_and_flags_conflicts() {
+        let base = "a\nb\nc\nd\ne\n";
+        let ours = "a\nB\nc\nd\ne\n";
+        let theirs = "a\nb\nc\nd\nE\nf\n";
+        let merged = merge3(base, ours, theirs, "project", "template");
+        assert_eq!(merged.conflicts, 0);
+        assert_eq!(merged.text, "a\nB\nc\nd\nE\nf\n");
+
+        let conflicting = merge3(base, "a\nX\nc\nd\ne\n", "a\nY\nc\nd\ne\n", "ours", "theirs");
+        assert_eq!(conflicting.conflicts, 1);
+        assert!(conflicting
+            .text
+            .contains("<<<<<<< ours\nX\n=======\nY\n>>>>>>> theirs\n"));
+    }
+
+    #[test]
+    fn tree_diff_classifies_files() {
+        let base = BTreeMap::from([
+            ("keep.rs".to_string(), "same".to_string()),
+            ("edit.rs".to_string(), "old".to_string()),
+            ("gone.rs".to_string(), "x".to_string()),
+        ]);
+        let current = BTreeMap::from([
+            ("keep.rs".to_string(), "same".to_string()),
+            ("edit.rs".to_string(), "new".to_string()),
+            ("new.rs".to_string(), "y".to_string()),
+        ]);
+        let statuses: Vec<(String, FileStatus)> = diff_trees(&base, &current)
+            .into_iter()
+            .map(|c| (c.path, c.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("edit.rs".to_string(), FileStatus::Modified),
+                ("gone.rs".to_string(), FileStatus::Removed),
+                ("keep.rs".to_string(), FileStatus::Unchanged),
+                ("new.rs".to_string(), FileStatus::Added),
+            ]
+        );
+    }
+
+    #[test]
+    fn interface_changes_map_to_semver_bumps() {
+        let method = |name: &str, ty: &str, ret: &str| {
+            serde_json::json!({
+                "type": "function",
+                "name": name,
+                "inputs": [{ "name": "amount", "value": { "type": ty } }],
+                "outputs": [{ "type": ret }],
+            })
+        };
+        let old = vec![
+            method("balance", "u32", "i128"),
+            method("burn", "i128", "void"),
+            method("mint", "i128", "void"),
+        ];
+        let compatible = vec![
+            method("balance", "u32", "i128"),
+            method("burn", "i128", "void"),
+            method("mint", "i128", "void"),
+            method("pause", "bool", "void"),
+        ];
+        let changes = interface_diff(&old, &compatible);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, InterfaceChangeKind::Added);
+        assert_eq!(required_bump(&changes), Bump::Minor);
+
+        let breaking = vec![
+            method("balance", "u32", "u128"),
+            method("mint", "i128", "void"),
+        ];
+        let kinds: Vec<(String, InterfaceChangeKind)> = interface_diff(&old, &breaking)
+            .into_iter()
+            .map(|c| (c.method, c.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("balance".to_string(), InterfaceChangeKind::SignatureChanged),
+                ("burn".to_string(), InterfaceChangeKind::Removed),
+            ]
+        );
+        assert_eq!(required_bump(&interface_diff(&old, &breaking)), Bump::Major);
+        assert_eq!(required_bump(&interface_diff(&old, &old)), Bump::Patch);
+
+        assert_eq!(next_version("1.4.2", Bump::Major).as_deref(), Some("2.0.0"));
+        assert_eq!(
+            next_version("v1.4.2", Bump::Minor).as_deref(),
+            Some("v1.5.0")
+        );
+        assert_eq!(next_version("1.4.2", Bump::Patch).as_deref(), Some("1.4.3"));
+        assert_eq!(next_version("1.4.2-rc.1", Bump::Patch), None);
+        assert_eq!(next_version("1.4", Bump::Patch), None);
+    }
+}
@@ -1,50 +1,317 @@
 use std::fs::{self, File};
 use std::io::{BufReader, BufWriter, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, TimeZone, Utc};
 use flate2::write::GzEncoder;
 use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use tar::Builder;
 
 use crate::io_utils::{compute_sha256_streaming, BUF_SIZE};
 use crate::manifest::{ExportManifest, ManifestEntry};
 
+/// Fixed gzip level, so the same input always compresses to the same bytes
+/// regardless of flate2's default. The gzip header itself carries no mtime
+/// or file name.
+const COMPRESSION_LEVEL: u32 = 6;
+
+/// `sha256sum`-style listing of every packaged file, next to
+/// `manifest.json`, which `import` checks the extracted files against.
+pub const CHECKSUMS_FILE: &str = "manifest.sha256";
+
+/// First bytes of an archive encrypted by `export --encrypt`; the trailing
+/// digit is the format version.
+pub const ENCRYPTED_MAGIC: &[u8; 8] = b"SRXENC01";
+pub(crate) const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+/// Magic, Argon2 salt and AES-GCM nonce, all authenticated with the payload.
+pub const ENCRYPTED_HEADER_LEN: usize = ENCRYPTED_MAGIC.len() + SALT_LEN + NONCE_LEN;
+
+/// Where an encrypted export of `output` is written: `<output>.enc`.
+pub fn encrypted_path(output: &Path) -> PathBuf {
+    let mut name = output.as_os_str().to_owned();
+    if output.extension().and_then(|e| e.to_str()) != Some("enc") {
+        name.push(".enc");
+    }
+    PathBuf::from(name)
+}
+
+const EMPTY_PASSPHRASE: &str = "The passphrase can't be empty";
+
+/// Value parser for `--passphrase` and `SOROBAN_EXPORT_PASSPHRASE`.
+pub fn parse_passphrase(s: &str) -> Result<String, String> {
+    if s.is_empty() {
+        return Err(EMPTY_PASSPHRASE.to_string());
+    }
+    Ok(s.to_string())
+}
+
+/// AES-256 key for `passphrase`, stretched with Argon2id.
+pub(crate) fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    if passphrase.is_empty() {
+        anyhow::bail!(EMPTY_PASSPHRASE);
+    }
+    let mut key = [0u8; 32];
+    argon2::Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow::anyhow!("Failed to derive encryption key: {}", e))?;
+    Ok(key)
+}
+
+/// Encrypt the archive at `input` into `output` with AES-256-GCM, under a
+/// key derived from `passphrase` with a fresh random salt.
+pub fn encrypt_file(input: &Path, output: &Path, passphrase: &str) -> Result<()> {
+    use aes_gcm::aead::{Aead, KeyInit, Payload};
+    use rand::RngCore;
+
+    let plaintext =
+        fs::read(input).with_context(|| format!("Failed to read {}", input.display()))?;
+    let mut header = ENCRYPTED_MAGIC.to_vec();
+    let mut salt_and_nonce = [0u8; SALT_LEN + NONCE_LEN];
+    rand::rngs::OsRng.fill_bytes(&mut salt_and_nonce);
+    header.extend_from_slice(&salt_and_nonce);
+
+    let key = derive_key(passphrase, &salt_and_nonce[..SALT_LEN])?;
+    let cipher = aes_gcm::Aes256Gcm::new(&key.into());
+    let ciphertext = cipher
+        .encrypt(
+            aes_gcm::Nonce::from_slice(&salt_and_nonce[SALT_LEN..]),
+            Payload {
+                msg: &plaintext,
+                aad: &header,
+            },
+        )
+        .map_err(|_| anyhow::anyhow!("Failed to encrypt {}", input.display()))?;
+
+    let mut file = BufWriter::new(File::create(output)?);
+    file.write_all(&header)?;
+    file.write_all(&ciphertext)?;
+    file.flush()?;
+    Ok(())
+}
+
+/// Ask for a passphrase on the terminal without echoing it; with
+/// `confirm`, ask twice and require both to match.
+pub fn prompt_passphrase(confirm: bool) -> Result<String> {
+    let passphrase =
+        rpassword::prompt_password("Archive passphrase: ").context("Failed to read passphrase")?;
+    if passphrase.is_empty() {
+        anyhow::bail!(EMPTY_PASSPHRASE);
+    }
+    if confirm {
+        let again = rpassword::prompt_password("Repeat passphrase: ")
+            .context("Failed to read passphrase")?;
+        if again != passphrase {
+            anyhow::bail!("Passphrases don't match");
+        }
+    }
+    Ok(passphrase)
+}
+
+/// Timestamp written into reproducible archives: `SOURCE_DATE_EPOCH` when
+/// set, otherwise the Unix epoch.
+pub fn reproducible_timestamp() -> DateTime<Utc> {
+    std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|s| s.trim().parse::<i64>().ok())
+        .and_then(|secs| Utc.timestamp_opt(secs, 0).single())
+        .unwrap_or_default()
+}
+
+fn new_manifest(
+    contract_id: &str,
+    name: &str,
+    network: &str,
+    pinned: Option<DateTime<Utc>>,
+) -> ExportManifest {
+    let mut manifest = ExportManifest::new(contract_id.into(), name.into(), network.into());
+    if let Some(at) = pinned {
+        manifest.exported_at = at;
+        for entry in &mut manifest.audit_trail {
+            entry.timestamp = at;
+        }
+    }
+    manifest
+}
+
+/// Build a registry export archive. With `reproducible`, every timestamp
+/// (file mtimes, manifest and audit trail) is pinned to
+/// [`reproducible_timestamp`], so identical inputs give byte-identical
+/// archives.
 pub fn create_archive(
     contract_dir: &Path,
     output_path: &Path,
     contract_id: &str,
     name: &str,
     network: &str,
+    reproducible: bool,
 ) -> Result<()> {
     let tmp_dir = tempfile::tempdir().context("failed to create temp dir")?;
     let inner_path = tmp_dir.path().join("contract.tar.gz");
 
-    let mut manifest = ExportManifest::new(contract_id.into(), name.into(), network.into());
+    let pinned = reproducible.then(reproducible_timestamp);
+    let mut manifest = new_manifest(contract_id, name, network, pinned);
 
-    build_inner_archive(contract_dir, &inner_path, &mut manifest)?;
+    build_inner_archive(contract_dir, &inner_path, &mut manifest, pinned)?;
     manifest.sha256 = compute_sha256_streaming(&inner_path)?;
 
     let manifest_path = tmp_dir.path().join("manifest.json");
     let manifest_json = serde_json::to_vec_pretty(&manifest)?;
     fs::write(&manifest_path, &manifest_json)?;
 
-    build_outer_archive(output_path, &manifest_path, &inner_path)?;
+    let checksums_path = tmp_dir.path().join(CHECKSUMS_FILE);
+    write_checksums(contract_dir, &manifest, &checksums_path)?;
+
+    build_outer_archive(
+        output_path,
+        &manifest_path,
+        &checksums_path,
+        &inner_path,
+        pinned,
+    )?;
 
     Ok(())
 }
 
+/// Write [`CHECKSUMS_FILE`] for the files `manifest` lists from `source_dir`.
+fn write_checksums(source_dir: &Path, manifest: &ExportManifest, path: &Path) -> Result<()> {
+    let mut listing = String::new();
+    for entry in &manifest.contents {
+        let digest = compute_sha256_streaming(&source_dir.join(&entry.path))?;
+        listing.push_str(&format!("{}  {}\n", digest, entry.path));
+    }
+    fs::write(path, listing)?;
+    Ok(())
+}
+
+/// A contract going into a multi-contract bundle.
+#[derive(Debug, Clone)]
+pub struct BundleMember {
+    pub id: String,
+    /// The contract's registry record.
+    pub record: Value,
+    /// Sources to package, when there are any for this contract.
+    pub source_dir: Option<PathBuf>,
+}
+
+/// `bundle.json`, the index at the top of a bundle archive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleIndex {
+    pub schema_version: String,
+    pub exported_at: DateTime<Utc>,
+    pub contracts: Vec<BundleEntry>,
+    /// Contracts left out under `--continue-on-error`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<SkippedContract>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleEntry {
+    pub id: String,
+    pub name: String,
+    pub network: String,
+    /// Directory holding this contract's files, `contracts/<id>`.
+    pub path: String,
+    /// Digest of `contract.tar.gz`; `None` when no sources were packaged.
+    pub sha256: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkippedContract {
+    pub id: String,
+    pub error: String,
+}
+
+/// Build one archive holding several contracts. Each gets its own
+/// `contracts/<id>/` directory with its registry record (`metadata.json`)
+/// and, when it has sources, the same `manifest.json`, `manifest.sha256`
+/// and `contract.tar.gz` a single export contains. `bundle.json` indexes them.
+pub fn create_bundle(
+    members: &[BundleMember],
+    skipped: Vec<SkippedContract>,
+    output_path: &Path,
+    reproducible: bool,
+) -> Result<BundleIndex> {
+    let tmp_dir = tempfile::tempdir().context("failed to create temp dir")?;
+    let pinned = reproducible.then(reproducible_timestamp);
+
+    let mut index = BundleIndex {
+        schema_version: "1.0".into(),
+        exported_at: pinned.unwrap_or_else(Utc::now),
+        contracts: Vec::with_capacity(members.len()),
+        skipped,
+    };
+    // Per contract: (archive path, file on disk) pairs, in archive order.
+    let mut files: Vec<(String, PathBuf)> = Vec::new();
+
+    for (i, member) in members.iter().enumerate() {
+        let record = member.record.get("contract").unwrap_or(&member.record);
+        let name = record["name"].as_str().unwrap_or(&member.id).to_string();
+        let network = record["network"].as_str().unwrap_or_default().to_string();
+        let prefix = format!("contracts/{}", member.id);
+        // Indexed rather than named by ID, which may not be a safe file name.
+        let work = tmp_dir.path().join(i.to_string());
+        fs::create_dir(&work)?;
+
+        let metadata_path = work.join("metadata.json");
+        fs::write(&metadata_path, serde_json::to_vec_pretty(&member.record)?)?;
+        files.push((format!("{}/metadata.json", prefix), metadata_path));
+
+        let mut sha256 = None;
+        if let Some(source_dir) = &member.source_dir {
+            let inner_path = work.join("contract.tar.gz");
+            let mut manifest = new_manifest(&member.id, &name, &network, pinned);
+            build_inner_archive(source_dir, &inner_path, &mut manifest, pinned)?;
+            manifest.sha256 = compute_sha256_streaming(&inner_path)?;
+            sha256 = Some(manifest.sha256.clone());
+
+            let manifest_path = work.join("manifest.json");
+            fs::write(&manifest_path, serde_json::to_vec_pretty(&manifest)?)?;
+            files.push((format!("{}/manifest.json", prefix), manifest_path));
+            let checksums_path = work.join(CHECKSUMS_FILE);
+            write_checksums(source_dir, &manifest, &checksums_path)?;
+            files.push((format!("{}/{}", prefix, CHECKSUMS_FILE), checksums_path));
+            files.push((format!("{}/contract.tar.gz", prefix), inner_path));
+        }
+
+        index.contracts.push(BundleEntry {
+            id: member.id.clone(),
+            name,
+            network,
+            path: prefix,
+            sha256,
+        });
+    }
+
+    let index_path = tmp_dir.path().join("bundle.json");
+    fs::write(&index_path, serde_json::to_vec_pretty(&index)?)?;
+
+    let file = BufWriter::new(File::create(output_path)?);
+    let encoder = GzEncoder::new(file, Compression::new(COMPRESSION_LEVEL));
+    let mut builder = Builder::new(encoder);
+    append_file_streaming(&mut builder, &index_path, "bundle.json", pinned)?;
+    for (name, path) in &files {
+        append_file_streaming(&mut builder, path, name, pinned)?;
+    }
+    builder.into_inner()?.finish()?;
+
+    Ok(index)
+}
+
 fn build_inner_archive(
     source_dir: &Path,
     archive_path: &Path,
     manifest: &mut ExportManifest,
+    pinned: Option<DateTime<Utc>>,
 ) -> Result<()> {
     let file = BufWriter::new(File::create(archive_path)?);
-    let encoder = GzEncoder::new(file, Compression::default());
+    let encoder = GzEncoder::new(file, Compression::new(COMPRESSION_LEVEL));
     let mut builder = Builder::new(encoder);
 
-    walk_and_append(&mut builder, source_dir, source_dir, manifest)?;
+    walk_and_append(&mut builder, source_dir, source_dir, manifest, pinned)?;
 
     let encoder = builder.into_inner()?;
     encoder.finish()?;
@@ -56,28 +323,34 @@ fn walk_and_append<W: Write>(
     base: &Path,
     dir: &Path,
     manifest: &mut ExportManifest,
+    pinned: Option<DateTime<Utc>>,
 ) -> Result<()> {
-    let entries = fs::read_dir(dir).with_context(|| format!("reading {}", dir.display()))?;
+    // Directory listing order is filesystem-dependent; sort for stable output.
+    let mut entries = fs::read_dir(dir)
+        .with_context(|| format!("reading {}", dir.display()))?
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|e| e.file_name());
 
     for entry in entries {
-        let entry = entry?;
         let path = entry.path();
         let rel = path.strip_prefix(base).unwrap_or(&path);
 
         if path.is_dir() {
-            walk_and_append(builder, base, &path, manifest)?;
+            walk_and_append(builder, base, &path, manifest, pinned)?;
         } else {
             let metadata = entry.metadata()?;
-            let modified: DateTime<Utc> = metadata
-                .modified()
-                .ok()
-                .and_then(|t| {
-                    t.duration_since(std::time::UNIX_EPOCH).ok().and_then(|d| {
-                        Utc.timestamp_opt(d.as_secs() as i64, d.subsec_nanos())
-                            .single()
+            let modified: DateTime<Utc> = pinned.unwrap_or_else(|| {
+                metadata
+                    .modified()
+                    .ok()
+                    .and_then(|t| {
+                        t.duration_since(std::time::UNIX_EPOCH).ok().and_then(|d| {
+                            Utc.timestamp_opt(d.as_secs() as i64, d.subsec_nanos())
+                                .single()
+                        })
                     })
-                })
-                .unwrap_or_else(Utc::now);
+                    .unwrap_or_else(Utc::now)
+            });
 
             manifest.contents.push(ManifestEntry {
                 path: rel.to_string_lossy().replace('\\', "/"),
@@ -88,6 +361,9 @@ fn walk_and_append<W: Write>(
             let mut header = tar::Header::new_gnu();
             header.set_size(metadata.len());
             header.set_mode(0o644);
+            if let Some(at) = pinned {
+                header.set_mtime(at.timestamp().max(0) as u64);
+            }
             header.set_cksum();
 
             let f = BufReader::new(File::open(&path)?);
@@ -100,14 +376,17 @@ fn walk_and_append<W: Write>(
 fn build_outer_archive(
     output_path: &Path,
     manifest_path: &Path,
+    checksums_path: &Path,
     inner_archive_path: &Path,
+    pinned: Option<DateTime<Utc>>,
 ) -> Result<()> {
     let file = BufWriter::new(File::create(output_path)?);
-    let encoder = GzEncoder::new(file, Compression::default());
+    let encoder = GzEncoder::new(file, Compression::new(COMPRESSION_LEVEL));
     let mut builder = Builder::new(encoder);
 
-    append_file_streaming(&mut builder, manifest_path, "manifest.json")?;
-    append_file_streaming(&mut builder, inner_archive_path, "contract.tar.gz")?;
+    append_file_streaming(&mut builder, manifest_path, "manifest.json", pinned)?;
+    append_file_streaming(&mut builder, checksums_path, CHECKSUMS_FILE, pinned)?;
+    append_file_streaming(&mut builder, inner_archive_path, "contract.tar.gz", pinned)?;
 
     let encoder = builder.into_inner()?;
     encoder.finish()?;
@@ -118,14 +397,249 @@ fn append_file_streaming<W: Write>(
     builder: &mut Builder<W>,
     file_path: &Path,
     archive_name: &str,
+    pinned: Option<DateTime<Utc>>,
 ) -> Result<()> {
     let metadata = fs::metadata(file_path)?;
     let mut header = tar::Header::new_gnu();
     header.set_size(metadata.len());
     header.set_mode(0o644);
+    if let Some(at) = pinned {
+        header.set_mtime(at.timestamp().max(0) as u64);
+    }
     header.set_cksum();
 
     let reader = BufReader::with_capacity(BUF_SIZE, File::open(file_path)?);
     builder.append_data(&mut header, archive_name, reader)?;
     Ok(())
 }
+
+/// Package a contract as an OCI artifact in image-layout tar form, with
+/// separate layers for wasm binaries, registry metadata, docs and source.
+#[cfg(feature = "oci")]
+pub fn create_oci_archive(
+    contract_dir: &Path,
+    output_path: &Path,
+    contract_id: &str,
+    name: &str,
+    network: &str,
+    reproducible: bool,
+) -> Result<()> {
+    use crate::oci::{self, Descriptor, ImageIndex, ImageManifest};
+    use std::collections::BTreeMap;
+
+    let tmp_dir = tempfile::tempdir().context("failed to create temp dir")?;
+    let source_path = tmp_dir.path().join("contract.tar.gz");
+
+    let pinned = reproducible.then(reproducible_timestamp);
+    let mut manifest = new_manifest(contract_id, name, network, pinned);
+    build_inner_archive(contract_dir, &source_path, &mut manifest, pinned)?;
+    manifest.sha256 = compute_sha256_streaming(&source_path)?;
+
+    let mut blobs: Vec<Vec<u8>> = Vec::new();
+    let mut layers: Vec<Descriptor> = Vec::new();
+
+    for wasm in collect_files(contract_dir, &|p| {
+        p.extension().is_some_and(|e| e == "wasm")
+            && !p.components().any(|c| c.as_os_str() == "deps")
+    })? {
+        let bytes = fs::read(&wasm)?;
+        let title = wasm
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "contract.wasm".into());
+        layers.push(Descriptor::for_blob(oci::MEDIA_TYPE_WASM, &bytes).titled(&title));
+        blobs.push(bytes);
+    }
+
+    let metadata = serde_json::to_vec_pretty(&manifest)?;
+    layers.push(Descriptor::for_blob(oci::MEDIA_TYPE_METADATA, &metadata).titled("manifest.json"));
+    blobs.push(metadata);
+
+    let docs = collect_files(contract_dir, &|p| {
+        let rel = p.strip_prefix(contract_dir).unwrap_or(p);
+        rel.starts_with("docs")
+            || (rel.components().count() == 1 && p.extension().is_some_and(|e| e == "md"))
+    })?;
+    if !docs.is_empty() {
+        let docs_path = tmp_dir.path().join("docs.tar.gz");
+        build_file_archive(contract_dir, &docs, &docs_path, pinned)?;
+        let bytes = fs::read(&docs_path)?;
+        layers.push(Descriptor::for_blob(oci::MEDIA_TYPE_DOCS, &bytes).titled("docs.tar.gz"));
+        blobs.push(bytes);
+    }
+
+    let source = fs::read(&source_path)?;
+    layers.push(Descriptor::for_blob(oci::MEDIA_TYPE_SOURCE, &source).titled("contract.tar.gz"));
+    blobs.push(source);
+
+    let config = b"{}".to_vec();
+    let image = ImageManifest {
+        schema_version: 2,
+        media_type: oci::MEDIA_TYPE_MANIFEST.into(),
+        artifact_type: oci::ARTIFACT_TYPE.into(),
+        config: Descriptor::for_blob(oci::MEDIA_TYPE_EMPTY, &config),
+        layers,
+        annotations: BTreeMap::from([(
+            oci::ANNOTATION_CREATED.to_string(),
+            manifest.exported_at.to_rfc3339(),
+        )]),
+    };
+    blobs.push(config);
+
+    let image_bytes = serde_json::to_vec_pretty(&image)?;
+    let mut image_desc = Descriptor::for_blob(oci::MEDIA_TYPE_MANIFEST, &image_bytes);
+    image_desc
+        .annotations
+        .insert(oci::ANNOTATION_REF_NAME.into(), contract_id.into());
+    blobs.push(image_bytes);
+
+    let index = ImageIndex {
+        schema_version: 2,
+        media_type: oci::MEDIA_TYPE_INDEX.into(),
+        manifests: vec![image_desc],
+    };
+
+    let file = BufWriter::new(File::create(output_path)?);
+    let mut builder = Builder::new(file);
+    append_bytes(
+        &mut builder,
+        oci::LAYOUT_FILE,
+        &serde_json::to_vec(&serde_json::json!({ "imageLayoutVersion": oci::LAYOUT_VERSION }))?,
+    )?;
+    append_bytes(&mut builder, oci::INDEX_FILE, &serde_json::to_vec_pretty(&index)?)?;
+    let mut written = std::collections::HashSet::new();
+    for blob in &blobs {
+        let digest = oci::digest(blob);
+        if written.insert(digest.clone()) {
+            append_bytes(&mut builder, &oci::blob_path(&digest), blob)?;
+        }
+    }
+    builder.into_inner()?.flush()?;
+
+    Ok(())
+}
+
+#[cfg(feature = "oci")]
+fn collect_files(dir: &Path, keep: &dyn Fn(&Path) -> bool) -> Result<Vec<std::path::PathBuf>> {
+    let mut out = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("reading {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            out.extend(collect_files(&path, keep)?);
+        } else if keep(&path) {
+            out.push(path);
+        }
+    }
+    out.sort();
+    Ok(out)
+}
+
+#[cfg(feature = "oci")]
+fn build_file_archive(
+    base: &Path,
+    files: &[std::path::PathBuf],
+    archive_path: &Path,
+    pinned: Option<DateTime<Utc>>,
+) -> Result<()> {
+    let file = BufWriter::new(File::create(archive_path)?);
+    let mut builder = Builder::new(GzEncoder::new(file, Compression::new(COMPRESSION_LEVEL)));
+    for path in files {
+        let rel = path.strip_prefix(base).unwrap_or(path);
+        let name = rel.to_string_lossy().replace('\\', "/");
+        append_file_streaming(&mut builder, path, &name, pinned)?;
+    }
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+#[cfg(feature = "oci")]
+fn append_bytes<W: Write>(builder: &mut Builder<W>, name: &str, bytes: &[u8]) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder.append_data(&mut header, name, bytes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reproducible_exports_are_byte_identical() {
+        let src = tempfile::tempdir().expect("create source dir");
+        fs::create_dir(src.path().join("src")).expect("create src/");
+        fs::write(src.path().join("src/lib.rs"), b"pub fn hello() {}").expect("write lib.rs");
+        fs::write(src.path().join("Cargo.toml"), b"[package]").expect("write Cargo.toml");
+        fs::write(src.path().join("README.md"), b"# demo").expect("write README.md");
+
+        let work = tempfile::tempdir().expect("create work dir");
+        let export = |name: &str, reproducible: bool| {
+            let path = work.path().join(name);
+            create_archive(src.path(), &path, "CABC", "demo", "testnet", reproducible)
+                .expect("create archive");
+            fs::read(path).expect("read archive")
+        };
+
+        let first = export("a.tar.gz", true);
+        assert_eq!(first, export("b.tar.gz", true));
+        assert_ne!(export("c.tar.gz", false), export("d.tar.gz", false));
+    }
+
+    #[test]
+    fn bundles_keep_each_contract_under_its_own_directory() {
+        let src = tempfile::tempdir().expect("create source dir");
+        fs::write(src.path().join("lib.rs"), b"pub fn hello() {}").expect("write lib.rs");
+
+        let members = [
+            BundleMember {
+                id: "CAAA".into(),
+                record: serde_json::json!({ "name": "vault", "network": "testnet" }),
+                source_dir: Some(src.path().to_path_buf()),
+            },
+            BundleMember {
+                id: "CBBB".into(),
+                record: serde_json::json!({ "contract": { "name": "oracle", "network": "mainnet" } }),
+                source_dir: None,
+            },
+        ];
+        let skipped = vec![SkippedContract {
+            id: "CCCC".into(),
+            error: "404 Not Found".into(),
+        }];
+        let work = tempfile::tempdir().expect("create work dir");
+        let output = work.path().join("bundle.tar.gz");
+        let index = create_bundle(&members, skipped, &output, true).expect("create bundle");
+        assert_eq!(index.contracts[1].name, "oracle");
+        assert!(index.contracts[0].sha256.is_some() && index.contracts[1].sha256.is_none());
+
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(
+            File::open(&output).expect("open bundle"),
+        ));
+        let mut names = Vec::new();
+        let mut stored_index = None;
+        for entry in archive.entries().expect("read entries") {
+            let entry = entry.expect("read entry");
+            let name = entry.path().expect("entry path").display().to_string();
+            if name == "bundle.json" {
+                stored_index = Some(serde_json::from_reader::<_, BundleIndex>(entry).unwrap());
+            }
+            names.push(name);
+        }
+        assert_eq!(
+            names,
+            [
+                "bundle.json",
+                "contracts/CAAA/metadata.json",
+                "contracts/CAAA/manifest.json",
+                "contracts/CAAA/manifest.sha256",
+                "contracts/CAAA/contract.tar.gz",
+                "contracts/CBBB/metadata.json",
+            ]
+        );
+        let stored_index = stored_index.expect("bundle.json present");
+        assert_eq!(stored_index, index);
+        assert_eq!(stored_index.skipped[0].id, "CCCC");
+    }
+}
@@ -0,0 +1,424 @@
+//! `health`: confirm a registered contract is live on-chain and callable.
+//!
+//! Soroban archives contract instances and code once their TTL runs out, at
+//! which point calls fail until the entries are restored. These checks catch
+//! that (and missing deployments) before users do.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Warn when fewer ledgers than this remain before archival (~1 day at 5s/ledger).
+pub const DEFAULT_MIN_TTL_LEDGERS: u64 = 17_280;
+/// Approximate ledger close time, for turning TTLs into wall-clock estimates.
+const LEDGER_CLOSE_SECS: u64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Healthy,
+    Warning,
+    Unhealthy,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct HealthCheck {
+    pub name: String,
+    pub status: HealthStatus,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl_ledgers: Option<u64>,
+}
+
+/// Classify an entry's remaining TTL against the current ledger.
+pub fn ttl_status(
+    live_until: Option<u64>,
+    latest: u64,
+    min_ttl: u64,
+) -> (HealthStatus, Option<u64>) {
+    match live_until {
+        Some(until) if until < latest => (HealthStatus::Unhealthy, Some(0)),
+        Some(until) if until - latest < min_ttl => (HealthStatus::Warning, Some(until - latest)),
+        Some(until) => (HealthStatus::Healthy, Some(until - latest)),
+        // Older RPC versions omit TTLs; existence is all we can confirm.
+        None => (HealthStatus::Healthy, None),
+    }
+}
+
+fn ttl_check(name: &str, live_until: Option<u64>, latest: u64, min_ttl: u64) -> HealthCheck {
+    let (status, ttl) = ttl_status(live_until, latest, min_ttl);
+    let detail = match (status, ttl) {
+        (HealthStatus::Unhealthy, _) => format!(
+            "archived (expired at ledger {}), restore before use",
+            live_until.unwrap_or_default()
+        ),
+        (HealthStatus::Warning, Some(t)) => {
+            format!("live, but only {} ledgers until archival", t)
+        }
+        (_, Some(t)) => format!("live, {} ledgers until archival", t),
+        (_, None) => "live (TTL not reported by RPC)".to_string(),
+    };
+    HealthCheck {
+        name: name.to_string(),
+        status,
+        detail,
+        ttl_ledgers: ttl,
+    }
+}
+
+pub async fn run(
+    api_url: &str,
+    contract_id: &str,
+    method: Option<String>,
+    json: bool,
+) -> Result<()> {
+    let client = reqwest::Client::new();
+    let settings = crate::config::health_settings()?;
+    let min_ttl = settings.min_ttl_ledgers.unwrap_or(DEFAULT_MIN_TTL_LEDGERS);
+    let method = method.or(settings.method);
+
+    let (_, address, network, endpoint) =
+        crate::onchain::resolve_contract(&client, api_url, contract_id).await?;
+
+    let latest = crate::rpc::latest_ledger(&client, &endpoint).await?;
+    let mut checks = Vec::new();
+
+    match crate::rpc::fetch_contract_entries(&client, &endpoint, &address).await? {
+        None => checks.push(HealthCheck {
+            name: "instance".to_string(),
+            status: HealthStatus::Unhealthy,
+            detail: "contract instance not found on-chain".to_string(),
+            ttl_ledgers: None,
+        }),
+        Some(entries) => {
+            checks.push(ttl_check(
+                "instance",
+                entries.instance.live_until_ledger_seq,
+                latest,
+                min_ttl,
+            ));
+            match (&entries.wasm_hash, &entries.code) {
+                (Some(_), Some(code)) => checks.push(ttl_check(
+                    "wasm code",
+                    code.live_until_ledger_seq,
+                    latest,
+                    min_ttl,
+                )),
+                (Some(hash), None) => checks.push(HealthCheck {
+                    name: "wasm code".to_string(),
+                    status: HealthStatus::Unhealthy,
+                    detail: format!("code entry {} not found", hash),
+                    ttl_ledgers: None,
+                }),
+                // Built-in executables (e.g. the Stellar Asset Contract) have no code entry.
+                (None, _) => {}
+            }
+        }
+    }
+
+    let reachable = checks.iter().all(|c| c.status != HealthStatus::Unhealthy);
+    if let Some(method) = method.as_deref().filter(|_| reachable) {
+        let check =
+            match crate::rpc::simulate_invoke(&client, &endpoint, &address, method, vec![]).await {
+                Ok(sim) => match sim.error {
+                    Some(err) => HealthCheck {
+                        name: format!("call {}()", method),
+                        status: HealthStatus::Unhealthy,
+                        detail: err,
+                        ttl_ledgers: None,
+                    },
+                    None => HealthCheck {
+                        name: format!("call {}()", method),
+                        status: HealthStatus::Healthy,
+                        detail: format!("returned {}", sim.result.unwrap_or(Value::Null)),
+                        ttl_ledgers: None,
+                    },
+                },
+                Err(e) => HealthCheck {
+                    name: format!("call {}()", method),
+                    status: HealthStatus::Unhealthy,
+                    detail: e.to_string(),
+                    ttl_ledgers: None,
+                },
+            };
+        checks.push(check);
+    }
+
+    let overall = checks
+        .iter()
+        .map(|c| c.status)
+        .max()
+        .unwrap_or(HealthStatus::Healthy);
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(
+            &HealthReport {
+                contract_id: address.clone(),
+                network: network.clone(),
+                latest_ledger: latest,
+                status: overall,
+                checks,
+            },
+            crate::output::Format::Json,
+        )?;
+    } else {
+        println!("\n{}", "Contract Health".bold().cyan());
+        println!("{}", "=".repeat(80).cyan());
+        println!("  {}: {}", "Contract".bold(), address.bright_black());
+        println!("  {}: {}", "Network".bold(), network.bright_blue());
+        println!("  {}: {}\n", "Latest ledger".bold(), latest);
+
+        for check in &checks {
+            let icon = match check.status {
+                HealthStatus::Healthy => "✓".green(),
+                HealthStatus::Warning => "⚠".yellow(),
+                HealthStatus::Unhealthy => "✗".red(),
+            };
+            println!("  {} {:<14} {}", icon, check.name.bold(), check.detail);
+        }
+
+        println!("\n{}", "=".repeat(80).cyan());
+        match overall {
+            HealthStatus::Healthy => println!("{}\n", "✓ Contract is healthy".green().bold()),
+            HealthStatus::Warning => println!(
+                "{}\n",
+                "⚠ Contract is live but needs attention".yellow().bold()
+            ),
+            HealthStatus::Unhealthy => {}
+        }
+    }
+
+    if overall == HealthStatus::Unhealthy {
+        anyhow::bail!("Contract {} is not healthy", address);
+    }
+    Ok(())
+}
+
+/// Rough wall-clock time for `ledgers` ledgers, e.g. `~3d 4h`.
+pub fn approx_duration(ledgers: u64) -> String {
+    let secs = ledgers * LEDGER_CLOSE_SECS;
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3_600, secs % 3_600 / 60);
+    match (days, hours) {
+        (0, 0) => format!("~{}m", minutes),
+        (0, h) => format!("~{}h {}m", h, minutes),
+        (d, h) => format!("~{}d {}h", d, h),
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TtlEntryReport {
+    pub entry: String,
+    pub found: bool,
+    pub live_until_ledger: Option<u64>,
+    pub ledgers_remaining: Option<u64>,
+    pub approx_time: Option<String>,
+    pub status: HealthStatus,
+}
+
+/// `ttl --json` output, one per contract.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TtlReport {
+    pub contract_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_ledger: Option<u64>,
+    pub entries: Vec<TtlEntryReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// `health --json` output.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct HealthReport {
+    pub contract_id: String,
+    pub network: String,
+    pub latest_ledger: u64,
+    pub status: HealthStatus,
+    pub checks: Vec<HealthCheck>,
+}
+
+/// TTLs of one registered contract's instance, code and `storage_keys`.
+pub async fn ttl_report(
+    client: &reqwest::Client,
+    api_url: &str,
+    contract_id: &str,
+    storage_keys: &[String],
+    min_ttl: u64,
+) -> Result<TtlReport> {
+    let (_, address, network, endpoint) =
+        crate::onchain::resolve_contract(client, api_url, contract_id).await?;
+    let Some(ttl) = crate::rpc::contract_ttl(client, &endpoint, &address, storage_keys).await?
+    else {
+        anyhow::bail!("contract instance {} not found on-chain", address);
+    };
+
+    let entries = ttl
+        .entries
+        .into_iter()
+        .map(|e| {
+            let (status, remaining) = if e.found {
+                ttl_status(e.live_until_ledger, ttl.latest_ledger, min_ttl)
+            } else {
+                (HealthStatus::Unhealthy, None)
+            };
+            TtlEntryReport {
+                entry: e.entry,
+                found: e.found,
+                live_until_ledger: e.live_until_ledger,
+                ledgers_remaining: remaining,
+                approx_time: remaining.map(approx_duration),
+                status,
+            }
+        })
+        .collect();
+
+    Ok(TtlReport {
+        contract_id: contract_id.to_string(),
+        address: Some(address),
+        network: Some(network),
+        latest_ledger: Some(ttl.latest_ledger),
+        entries,
+        error: None,
+    })
+}
+
+/// Read contract IDs from a file, one per line; blank lines and `#` comments
+/// are ignored.
+pub fn read_contract_ids_file(path: &str) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read contract IDs file: {}", path))?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Report instance, code and storage TTLs for one or more contracts.
+///
+/// Exits non-zero when any entry is archived or missing, and — with
+/// `warn_below` — when any entry has fewer ledgers remaining than that.
+pub async fn ttl(
+    api_url: &str,
+    contract_ids: Vec<String>,
+    storage_keys: Vec<String>,
+    warn_below: Option<u64>,
+    json: bool,
+) -> Result<()> {
+    if contract_ids.is_empty() {
+        anyhow::bail!("No contract IDs given");
+    }
+    let client = reqwest::Client::new();
+    let min_ttl = warn_below.unwrap_or(DEFAULT_MIN_TTL_LEDGERS);
+
+    let mut reports = Vec::with_capacity(contract_ids.len());
+    for id in &contract_ids {
+        let report = match ttl_report(&client, api_url, id, &storage_keys, min_ttl).await {
+            Ok(r) => r,
+            Err(e) => TtlReport {
+                contract_id: id.clone(),
+                address: None,
+                network: None,
+                latest_ledger: None,
+                entries: Vec::new(),
+                error: Some(e.to_string()),
+            },
+        };
+        reports.push(report);
+    }
+
+    let failing = reports
+        .iter()
+        .filter(|r| {
+            r.error.is_some()
+                || r.entries.iter().any(|e| {
+                    e.status == HealthStatus::Unhealthy
+                        || (warn_below.is_some() && e.status == HealthStatus::Warning)
+                })
+        })
+        .count();
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(&reports, crate::output::Format::Json)?;
+    } else {
+        println!("\n{}", "Contract TTL".bold().cyan());
+        println!("{}", "=".repeat(80).cyan());
+        for report in &reports {
+            println!(
+                "\n  {} {}",
+                report.contract_id.bold(),
+                report
+                    .network
+                    .as_deref()
+                    .map(|n| format!("({})", n))
+                    .unwrap_or_default()
+                    .bright_black()
+            );
+            if let Some(err) = &report.error {
+                println!("    {} {}", "✗".red(), err);
+                continue;
+            }
+            for e in &report.entries {
+                let icon = match e.status {
+                    HealthStatus::Healthy => "✓".green(),
+                    HealthStatus::Warning => "⚠".yellow(),
+                    HealthStatus::Unhealthy => "✗".red(),
+                };
+                let detail = match (e.found, e.ledgers_remaining, &e.approx_time) {
+                    (false, _, _) => "not found (archived or never written)".to_string(),
+                    (true, Some(0), _) => "archived".to_string(),
+                    (true, Some(n), Some(t)) => format!("{} ledgers ({})", n, t),
+                    _ => "TTL not reported by RPC".to_string(),
+                };
+                println!("    {} {:<16} {}", icon, e.entry, detail);
+            }
+        }
+        println!("\n{}", "=".repeat(80).cyan());
+    }
+
+    if failing > 0 {
+        anyhow::bail!(
+            "{} of {} contract(s) have archived, missing or low-TTL entries",
+            failing,
+            reports.len()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ttl_thresholds() {
+        assert_eq!(
+            ttl_status(Some(90), 100, 10),
+            (HealthStatus::Unhealthy, Some(0))
+        );
+        assert_eq!(
+            ttl_status(Some(105), 100, 10),
+            (HealthStatus::Warning, Some(5))
+        );
+        assert_eq!(
+            ttl_status(Some(500), 100, 10),
+            (HealthStatus::Healthy, Some(400))
+        );
+        assert_eq!(ttl_status(None, 100, 10), (HealthStatus::Healthy, None));
+    }
+
+    #[test]
+    fn approx_duration_formats() {
+        assert_eq!(approx_duration(12), "~1m");
+        assert_eq!(approx_duration(1_000), "~1h 23m");
+        assert_eq!(approx_duration(17_280 * 3), "~3d 0h");
+    }
+}
@@ -0,0 +1,274 @@
+//! Shared HTTP helpers: retries with backoff, rate-limit (`429`) handling
+//! and idempotency keys for mutating registry requests.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use sha2::{Digest, Sha256};
+
+/// Header the registry API uses to deduplicate retried writes.
+pub const IDEMPOTENCY_HEADER: &str = "Idempotency-Key";
+
+/// Bearer token for authenticated registry calls.
+pub const API_TOKEN_ENV: &str = "SOROBAN_REGISTRY_API_TOKEN";
+
+/// Longest `Retry-After` we are willing to sit out; beyond this the 429 is
+/// returned to the caller instead.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(120);
+
+/// Cleared by the global `--no-wait-on-429` flag.
+static WAIT_ON_429: AtomicBool = AtomicBool::new(true);
+
+pub fn set_wait_on_429(wait: bool) {
+    WAIT_ON_429.store(wait, Ordering::Relaxed);
+}
+
+pub fn waits_on_429() -> bool {
+    WAIT_ON_429.load(Ordering::Relaxed)
+}
+
+/// Parse a `Retry-After` header: either delay-seconds or an HTTP-date
+/// (relative to `now`). Dates in the past mean "retry immediately".
+pub fn parse_retry_after(value: &str, now: chrono::DateTime<chrono::Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (at.with_timezone(&chrono::Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+fn retry_after(resp: &reqwest::Response) -> Option<Duration> {
+    let value = resp.headers().get(reqwest::header::RETRY_AFTER)?;
+    parse_retry_after(value.to_str().ok()?, chrono::Utc::now())
+}
+
+/// Registry client with the configured timeout (`[defaults] timeout`) and,
+/// when [`API_TOKEN_ENV`] is set, an `Authorization: Bearer` header.
+pub fn client() -> Result<reqwest::Client> {
+    let timeout = crate::config::resolve_runtime_config(None, None, None)?.timeout;
+    let mut headers = reqwest::header::HeaderMap::new();
+    if let Some(token) = std::env::var(API_TOKEN_ENV).ok().filter(|t| !t.is_empty()) {
+        let mut value = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
+            .with_context(|| format!("{} contains invalid header characters", API_TOKEN_ENV))?;
+        value.set_sensitive(true);
+        headers.insert(reqwest::header::AUTHORIZATION, value);
+    }
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(timeout))
+        .default_headers(headers)
+        .build()
+        .context("Failed to build HTTP client")
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before `attempt` (1-based); doubles each time up to `max_delay`.
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(2));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+/// Derive a stable idempotency key for one logical operation.
+///
+/// The key depends only on the operation name and its canonical JSON
+/// payload, so re-running the same command after a timeout reuses the key
+/// and the server can recognise the duplicate.
+pub fn idempotency_key(operation: &str, payload: &serde_json::Value) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(operation.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(canonical_json(payload).as_bytes());
+    let digest = hasher.finalize();
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    uuid::Builder::from_sha1_bytes(bytes)
+        .into_uuid()
+        .hyphenated()
+        .to_string()
+}
+
+/// Serialise `value` with object keys sorted, independent of map ordering.
+fn canonical_json(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let fields: Vec<String> = keys
+                .into_iter()
+                .map(|k| {
+                    format!(
+                        "{}:{}",
+                        serde_json::Value::from(k.as_str()),
+                        canonical_json(&map[k])
+                    )
+                })
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        serde_json::Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Whether a failed send is worth retrying (the request may never have
+/// reached the server, or the server asked us to come back).
+fn is_retryable_error(err: &reqwest::Error) -> bool {
+    err.is_timeout() || err.is_connect() || err.is_request()
+}
+
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    matches!(status.as_u16(), 408 | 502 | 503 | 504)
+}
+
+/// Send a request built by `build`, retrying transient failures according to
+/// `policy`. When `idempotency_key` is set it is attached to every attempt so
+/// retried writes are deduplicated server-side.
+pub async fn send_with_retry<F>(
+    build: F,
+    idempotency_key: Option<&str>,
+    policy: RetryPolicy,
+) -> Result<reqwest::Response>
+where
+    F: Fn() -> reqwest::RequestBuilder,
+{
+    let mut attempt = 1;
+    loop {
+        let mut request = build();
+        if let Some(key) = idempotency_key {
+            request = request.header(IDEMPOTENCY_HEADER, key);
+        }
+
+        let outcome = request.send().await;
+
+        if let Ok(resp) = &outcome {
+            if resp.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+                && attempt < policy.max_attempts
+                && WAIT_ON_429.load(Ordering::Relaxed)
+            {
+                let delay = retry_after(resp).unwrap_or_else(|| policy.delay_for(attempt + 1));
+                if delay <= MAX_RETRY_AFTER {
+                    attempt += 1;
+                    log::debug!(
+                        "Rate limited (429); waiting {:.1}s before retry {}/{}",
+                        delay.as_secs_f64(),
+                        attempt,
+                        policy.max_attempts
+                    );
+                    tokio::time::sleep(delay).await;
+                    continue;
+                }
+                log::debug!(
+                    "Rate limited (429); Retry-After {}s exceeds the {}s limit",
+                    delay.as_secs(),
+                    MAX_RETRY_AFTER.as_secs()
+                );
+            }
+        }
+
+        let retry_reason = match &outcome {
+            Ok(resp) if is_retryable_status(resp.status()) => Some(resp.status().to_string()),
+            Ok(_) => None,
+            Err(e) if is_retryable_error(e) => Some(e.to_string()),
+            Err(_) => None,
+        };
+
+        match retry_reason {
+            Some(reason) if attempt < policy.max_attempts => {
+                attempt += 1;
+                let delay = policy.delay_for(attempt);
+                eprintln!(
+                    "  {} {} — retry {}/{} in {:.1}s",
+                    "↻".yellow(),
+                    reason,
+                    attempt,
+                    policy.max_attempts,
+                    delay.as_secs_f64()
+                );
+                tokio::time::sleep(delay).await;
+            }
+            _ => {
+                return outcome
+                    .with_context(|| format!("request failed after {} attempt(s)", attempt))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn idempotency_key_is_stable_per_operation() {
+        let payload = json!({ "contract_id": "C1", "wasm_hash": "abc" });
+        let a = idempotency_key("migrate", &payload);
+        let b = idempotency_key(
+            "migrate",
+            &json!({ "wasm_hash": "abc", "contract_id": "C1" }),
+        );
+        assert_eq!(a, b);
+        assert_ne!(a, idempotency_key("publish", &payload));
+        assert_ne!(
+            a,
+            idempotency_key("migrate", &json!({ "contract_id": "C2" }))
+        );
+        assert_eq!(a.len(), 36);
+    }
+
+    #[test]
+    fn retry_after_accepts_seconds_and_http_dates() {
+        let now = chrono::DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(parse_retry_after("30", now), Some(Duration::from_secs(30)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:45 GMT", now),
+            Some(Duration::from_secs(45))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        let policy = RetryPolicy {
+            max_attempts: 6,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(350),
+        };
+        assert_eq!(policy.delay_for(2), Duration::from_millis(100));
+        assert_eq!(policy.delay_for(3), Duration::from_millis(200));
+        assert_eq!(policy.delay_for(4), Duration::from_millis(350));
+    }
+}
@@ -1,29 +1,662 @@
 use std::fs::{self, File};
 use std::io::BufReader;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
-use chrono::Utc;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::{Map, Value};
 
-use crate::io_utils::{compute_sha256_streaming, extract_tar_gz};
+use crate::batch_register::RegisterManifest;
+use crate::io_utils::{compute_sha256_streaming, extract_tar_gz, Conflict};
 use crate::manifest::{AuditEntry, ExportManifest};
 
-pub fn extract_and_verify(archive_path: &Path, output_dir: &Path) -> Result<ExportManifest> {
-    let tmp_dir = tempfile::tempdir().context("failed to create temp dir")?;
+pub mod cargo;
+pub mod registry;
+
+/// Machine-readable account of everything an import did.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ImportSummary {
+    pub archive: String,
+    pub output_dir: String,
+    pub files_extracted: usize,
+    pub bytes_extracted: u64,
+    /// Files that didn't exist in the output directory before.
+    pub files_created: usize,
+    /// Existing files replaced under `import --force`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files_overwritten: Vec<String>,
+    /// Existing files kept under `import --merge`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files_skipped: Vec<String>,
+    pub contracts_restored: Vec<RestoredContract>,
+    pub verification: VerificationResult,
+    /// Contract ID the archive was exported under, when re-homed with
+    /// `import --contract-id`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rehomed_from: Option<String>,
+    #[serde(skip)]
+    pub manifest: ExportManifest,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RestoredContract {
+    pub contract_id: String,
+    pub name: String,
+    pub network: String,
+    pub exported_at: DateTime<Utc>,
+}
 
-    extract_tar_gz(archive_path, tmp_dir.path())?;
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct VerificationResult {
+    pub algorithm: &'static str,
+    pub expected: String,
+    pub computed: String,
+    pub passed: bool,
+    pub manifest_entries: usize,
+    /// Extracted files checked against the archive's `manifest.sha256`;
+    /// zero for archives exported without one.
+    pub files_verified: usize,
+    /// Set by `import --skip-verify`: nothing was checked.
+    pub skipped: bool,
+}
+
+/// Unpack a registry export into `work_dir` and check the inner archive
+/// against the manifest digest, unless `skip_verify`. Returns the manifest,
+/// the inner archive path and the computed digest.
+fn open_archive(
+    archive_path: &Path,
+    work_dir: &Path,
+    skip_verify: bool,
+) -> Result<(ExportManifest, PathBuf, String)> {
+    extract_tar_gz(archive_path, work_dir, Conflict::Overwrite)?;
 
-    let manifest_path = tmp_dir.path().join("manifest.json");
-    let inner_path = tmp_dir.path().join("contract.tar.gz");
+    let manifest_path = work_dir.join("manifest.json");
+    let inner_path = work_dir.join("contract.tar.gz");
 
     if !manifest_path.exists() || !inner_path.exists() {
         bail!("invalid archive: missing manifest.json or contract.tar.gz");
     }
 
-    let mut manifest: ExportManifest =
+    let manifest: ExportManifest =
         serde_json::from_reader(BufReader::new(File::open(&manifest_path)?))?;
 
     let computed_hash = compute_sha256_streaming(&inner_path)?;
+    if computed_hash != manifest.sha256 && !skip_verify {
+        bail!(
+            "integrity check failed: expected {} got {}",
+            manifest.sha256,
+            computed_hash
+        );
+    }
+    Ok((manifest, inner_path, computed_hash))
+}
+
+/// Check the files under `dir` against a `manifest.sha256` listing, except
+/// the `unwritten` ones an import merged around. Returns how many were
+/// checked and the paths that are missing or differ.
+pub fn verify_checksums(
+    listing: &str,
+    dir: &Path,
+    unwritten: &[String],
+) -> Result<(usize, Vec<String>)> {
+    let mut checked = 0;
+    let mut mismatched = Vec::new();
+    for line in listing.lines().filter(|l| !l.trim().is_empty()) {
+        let Some((expected, path)) = line.split_once("  ") else {
+            bail!(
+                "invalid {}: bad line '{}'",
+                crate::export::CHECKSUMS_FILE,
+                line
+            );
+        };
+        let relative = Path::new(path);
+        if relative.is_absolute()
+            || relative
+                .components()
+                .any(|c| matches!(c, std::path::Component::ParentDir))
+        {
+            bail!(
+                "invalid {}: path '{}' escapes the archive",
+                crate::export::CHECKSUMS_FILE,
+                path
+            );
+        }
+        if unwritten.iter().any(|p| p == path) {
+            continue;
+        }
+        checked += 1;
+        let file = dir.join(relative);
+        if !file.is_file() {
+            mismatched.push(format!("{} (missing)", path));
+        } else if compute_sha256_streaming(&file)? != expected {
+            mismatched.push(path.to_string());
+        }
+    }
+    Ok((checked, mismatched))
+}
+
+pub fn extract_and_verify(
+    archive_path: &Path,
+    output_dir: &Path,
+    skip_verify: bool,
+    conflict: Conflict,
+) -> Result<ImportSummary> {
+    let tmp_dir = tempfile::tempdir().context("failed to create temp dir")?;
+    let (mut manifest, inner_path, computed_hash) =
+        open_archive(archive_path, tmp_dir.path(), skip_verify)?;
+
+    manifest.audit_trail.push(AuditEntry {
+        action: "import_verified".into(),
+        timestamp: Utc::now(),
+        actor: "soroban-registry-cli".into(),
+    });
+
+    fs::create_dir_all(output_dir)?;
+    let stats = extract_tar_gz(&inner_path, output_dir, conflict)?;
+
+    let checksums_path = tmp_dir.path().join(crate::export::CHECKSUMS_FILE);
+    let mut files_verified = 0;
+    if !skip_verify && checksums_path.exists() {
+        let (checked, mismatched) = verify_checksums(
+            &fs::read_to_string(&checksums_path)?,
+            output_dir,
+            &stats.skipped,
+        )?;
+        if !mismatched.is_empty() {
+            bail!(
+                "integrity check failed: {} of {} extracted file(s) don't match {}:\n  {}",
+                mismatched.len(),
+                checked,
+                crate::export::CHECKSUMS_FILE,
+                mismatched.join("\n  ")
+            );
+        }
+        files_verified = checked;
+    }
+
+    manifest.audit_trail.push(AuditEntry {
+        action: "import_extracted".into(),
+        timestamp: Utc::now(),
+        actor: "soroban-registry-cli".into(),
+    });
+
+    Ok(ImportSummary {
+        archive: archive_path.display().to_string(),
+        output_dir: output_dir.display().to_string(),
+        files_extracted: stats.files,
+        bytes_extracted: stats.bytes,
+        files_created: stats.created,
+        files_overwritten: stats.overwritten,
+        files_skipped: stats.skipped,
+        contracts_restored: vec![RestoredContract {
+            contract_id: manifest.contract_id.clone(),
+            name: manifest.name.clone(),
+            network: manifest.network.clone(),
+            exported_at: manifest.exported_at,
+        }],
+        verification: VerificationResult {
+            algorithm: "sha256",
+            expected: manifest.sha256.clone(),
+            passed: computed_hash == manifest.sha256,
+            computed: computed_hash,
+            manifest_entries: manifest.contents.len(),
+            files_verified,
+            skipped: skip_verify,
+        },
+        rehomed_from: None,
+        manifest,
+    })
+}
+
+/// Parse a trusted key file: one base64 Ed25519 public key per line, with
+/// blank lines and `#` comments ignored.
+pub fn load_trusted_keys(path: &Path) -> Result<Vec<VerifyingKey>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read trusted keys {}", path.display()))?;
+    let keys = content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|line| {
+            let bytes: [u8; 32] = BASE64
+                .decode(line)
+                .ok()
+                .and_then(|b| b.try_into().ok())
+                .with_context(|| {
+                    format!("Invalid trusted key '{}' (expected base64, 32 bytes)", line)
+                })?;
+            VerifyingKey::from_bytes(&bytes)
+                .map_err(|_| anyhow::anyhow!("Trusted key '{}' is not a valid Ed25519 key", line))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if keys.is_empty() {
+        bail!("No keys found in {}", path.display());
+    }
+    Ok(keys)
+}
+
+/// Check the signature embedded in an export manifest: it must be present,
+/// unexpired, made by one of `trusted` and valid over
+/// [`ExportManifest::signing_message`]. Returns the signing address.
+pub fn verify_signature(manifest: &ExportManifest, trusted: &[VerifyingKey]) -> Result<String> {
+    let info = manifest
+        .signature
+        .as_ref()
+        .context("archive is not signed")?;
+    if info.algorithm != "ed25519" {
+        bail!("unsupported signature algorithm: {}", info.algorithm);
+    }
+    if info.expires_at.is_some_and(|at| at < Utc::now()) {
+        bail!("signature expired");
+    }
+
+    let key = BASE64
+        .decode(info.public_key.trim())
+        .ok()
+        .and_then(|b| <[u8; 32]>::try_from(b).ok())
+        .and_then(|b| VerifyingKey::from_bytes(&b).ok())
+        .context("signature has an invalid public key")?;
+    if !trusted.contains(&key) {
+        bail!("signed by an untrusted key ({})", info.signing_address);
+    }
+
+    let signature = BASE64
+        .decode(info.signature.trim())
+        .ok()
+        .and_then(|b| <[u8; 64]>::try_from(b).ok())
+        .map(|b| Signature::from_bytes(&b))
+        .context("signature is not valid base64 Ed25519")?;
+    key.verify(&manifest.signing_message(), &signature)
+        .map_err(|_| anyhow::anyhow!("signature does not match archive contents"))?;
+    Ok(info.signing_address.clone())
+}
+
+/// Integrity and signature check of one registry export, without
+/// extracting its contents anywhere permanent.
+pub fn verify_archive(
+    archive_path: &Path,
+    trusted: &[VerifyingKey],
+) -> Result<(ExportManifest, String)> {
+    let tmp_dir = tempfile::tempdir().context("failed to create temp dir")?;
+    let (manifest, _, _) = open_archive(archive_path, tmp_dir.path(), false)?;
+    let signer = verify_signature(&manifest, trusted)?;
+    Ok((manifest, signer))
+}
+
+/// Re-home an imported contract under `new_id`: the manifest, the restored
+/// contract records and any extracted text file mentioning the old ID are
+/// rewritten. The archive signature is dropped, since it certified the old
+/// ID. Returns how many extracted files were updated.
+pub fn rehome(summary: &mut ImportSummary, output_dir: &Path, new_id: &str) -> Result<usize> {
+    stellar_strkey::Contract::from_string(new_id).map_err(|_| {
+        anyhow::anyhow!("Invalid contract ID '{}' (expected a C... address)", new_id)
+    })?;
+    let old_id = summary.manifest.contract_id.clone();
+    if old_id == new_id {
+        bail!("Archive is already exported under {}", new_id);
+    }
+
+    let mut files_updated = 0;
+    if !old_id.is_empty() {
+        for entry in &summary.manifest.contents {
+            let path = output_dir.join(&entry.path);
+            let Ok(text) = fs::read_to_string(&path) else {
+                continue;
+            };
+            if text.contains(&old_id) {
+                fs::write(&path, text.replace(&old_id, new_id))
+                    .with_context(|| format!("Failed to update {}", path.display()))?;
+                files_updated += 1;
+            }
+        }
+    }
+
+    for restored in &mut summary.contracts_restored {
+        if restored.contract_id == old_id {
+            restored.contract_id = new_id.to_string();
+        }
+    }
+    summary.manifest.contract_id = new_id.to_string();
+    summary.manifest.signature = None;
+    summary.manifest.audit_trail.push(AuditEntry {
+        action: format!("import_rehomed_from:{}", old_id),
+        timestamp: Utc::now(),
+        actor: "soroban-registry-cli".into(),
+    });
+    summary.rehomed_from = Some(old_id);
+    Ok(files_updated)
+}
+
+/// External metadata formats `import-metadata` maps from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataFormat {
+    /// A contract crate's Cargo.toml, including `[package.metadata.soroban]`
+    Cargo,
+    /// A JSON or YAML export from another registry
+    Registry,
+}
+
+impl MetadataFormat {
+    /// `*.toml` is a Cargo manifest; any other file a registry export.
+    /// stdin has no name to go by.
+    pub fn detect(source: &str) -> Option<Self> {
+        if crate::io_utils::is_stdin(source) {
+            return None;
+        }
+        let toml = Path::new(source).extension().is_some_and(|e| e == "toml");
+        Some(if toml {
+            MetadataFormat::Cargo
+        } else {
+            MetadataFormat::Registry
+        })
+    }
+}
+
+/// External metadata mapped onto a `batch-register` manifest.
+#[derive(Debug, Serialize)]
+pub struct MetadataImport {
+    pub manifest: RegisterManifest,
+    /// Source fields with no registry equivalent, e.g. `package.version`.
+    pub unmapped: Vec<String>,
+    /// Manifest fields the source didn't provide; they must be filled in
+    /// before the manifest can be registered.
+    pub missing: Vec<String>,
+}
+
+impl MetadataImport {
+    fn new(manifest: RegisterManifest, unmapped: Vec<String>) -> Self {
+        let mut missing = Vec::new();
+        for (i, entry) in manifest.contracts.iter().enumerate() {
+            if entry.contract_id.is_empty() {
+                missing.push(format!("contracts[{}].contract_id", i));
+            }
+            if entry.name.is_empty() {
+                missing.push(format!("contracts[{}].name", i));
+            }
+            if entry.network.is_none() && manifest.network.is_none() {
+                missing.push(format!("contracts[{}].network", i));
+            }
+        }
+        MetadataImport {
+            manifest,
+            unmapped,
+            missing,
+        }
+    }
+}
+
+/// Reads fields out of one source object, remembering which ones were
+/// mapped so the rest can be reported.
+struct Fields<'a> {
+    object: &'a Map<String, Value>,
+    prefix: String,
+    used: Vec<&'a str>,
+}
+
+impl<'a> Fields<'a> {
+    fn new(object: &'a Map<String, Value>, prefix: impl Into<String>) -> Self {
+        Fields {
+            object,
+            prefix: prefix.into(),
+            used: Vec::new(),
+        }
+    }
+
+    /// The first of `keys` holding a non-empty string.
+    fn string(&mut self, keys: &[&str]) -> Option<String> {
+        let object = self.object;
+        keys.iter().find_map(|key| {
+            let (key, value) = object.get_key_value(*key)?;
+            let value = value.as_str().map(str::trim).filter(|v| !v.is_empty())?;
+            self.used.push(key);
+            Some(value.to_string())
+        })
+    }
+
+    /// The first of `keys` holding a list of strings or a comma-separated
+    /// string.
+    fn list(&mut self, keys: &[&str]) -> Option<Vec<String>> {
+        let object = self.object;
+        keys.iter().find_map(|key| {
+            let (key, value) = object.get_key_value(*key)?;
+            let items: Vec<String> = match value {
+                Value::Array(items) => items
+                    .iter()
+                    .filter_map(|i| i.as_str())
+                    .map(|i| i.trim().to_string())
+                    .collect(),
+                Value::String(items) => items.split(',').map(|i| i.trim().to_string()).collect(),
+                _ => return None,
+            };
+            let items: Vec<String> = items.into_iter().filter(|i| !i.is_empty()).collect();
+            if items.is_empty() {
+                return None;
+            }
+            self.used.push(key);
+            Some(items)
+        })
+    }
+
+    /// Keys that weren't mapped, other than `skip`, as `prefix.key`.
+    fn unmapped(&self, skip: &[&str]) -> Vec<String> {
+        self.object
+            .keys()
+            .filter(|k| !self.used.contains(&k.as_str()) && !skip.contains(&k.as_str()))
+            .map(|k| {
+                if self.prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{}.{}", self.prefix, k)
+                }
+            })
+            .collect()
+    }
+}
+
+/// `import-metadata`: map `source` onto a `batch-register` manifest, write
+/// it to `output` (JSON for `*.json`, YAML otherwise) or print it, and
+/// report what couldn't be mapped.
+pub fn import_metadata(
+    source: &str,
+    format: Option<MetadataFormat>,
+    output: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let format = format
+        .or_else(|| MetadataFormat::detect(source))
+        .context("Can't detect the format of stdin; pass --format")?;
+    let content = crate::io_utils::read_input_to_string(source)
+        .with_context(|| format!("Failed to read {}", source))?;
+    let import = match format {
+        MetadataFormat::Cargo => cargo::map(&content),
+        MetadataFormat::Registry => registry::map(&content),
+    }
+    .with_context(|| format!("Failed to import metadata from {}", source))?;
+
+    let rendered = if output.is_some_and(|o| o.ends_with(".json")) {
+        serde_json::to_string_pretty(&import.manifest)? + "\n"
+    } else {
+        serde_yaml::to_string(&import.manifest)?
+    };
+    if let Some(output) = output {
+        fs::write(output, &rendered).with_context(|| format!("Failed to write {}", output))?;
+    }
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(&import, crate::output::Format::Json)?;
+        return Ok(());
+    }
+
+    println!("\n{}", "Metadata Import".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    println!("{} {}", "Source:".bold(), source);
+    for entry in &import.manifest.contracts {
+        let id = if entry.contract_id.is_empty() {
+            "no contract ID"
+        } else {
+            &entry.contract_id
+        };
+        println!(
+            "  {} {} {}",
+            "✓".green(),
+            entry.name.bold(),
+            id.bright_black()
+        );
+    }
+    for field in &import.unmapped {
+        println!("  {} {} {}", "⚠".yellow(), "Unmapped:".yellow(), field);
+    }
+    for field in &import.missing {
+        println!("  {} {} {}", "✗".red(), "Missing:".red(), field);
+    }
+    println!("{}", "=".repeat(80).cyan());
+
+    match output {
+        Some(output) => {
+            println!("{} Manifest written to {}", "✓".green(), output.bold());
+            if import.missing.is_empty() {
+                println!("{} soroban-registry batch-register {}", "→".cyan(), output);
+            } else {
+                println!(
+                    "{} Fill in the missing fields before running batch-register",
+                    "→".cyan()
+                );
+            }
+        }
+        None => print!("\n{}", rendered),
+    }
+    Ok(())
+}
+
+/// True when `archive_path` was written by `export --encrypt`.
+pub fn is_encrypted_archive(archive_path: &Path) -> Result<bool> {
+    use std::io::Read;
+
+    let mut magic = [0u8; 8];
+    let mut file = File::open(archive_path)?;
+    let read = file.read(&mut magic)?;
+    Ok(read == magic.len() && &magic == crate::export::ENCRYPTED_MAGIC)
+}
+
+/// Decrypt an `export --encrypt` archive into `output`. A wrong passphrase
+/// is reported as such rather than surfacing later as a bad gzip stream.
+pub fn decrypt_archive(archive_path: &Path, output: &Path, passphrase: &str) -> Result<()> {
+    use aes_gcm::aead::{Aead, KeyInit, Payload};
+
+    let bytes = fs::read(archive_path)
+        .with_context(|| format!("Failed to read {}", archive_path.display()))?;
+    if bytes.len() < crate::export::ENCRYPTED_HEADER_LEN {
+        bail!("invalid archive: encrypted header is truncated");
+    }
+    let (header, ciphertext) = bytes.split_at(crate::export::ENCRYPTED_HEADER_LEN);
+    let (salt, nonce) =
+        header[crate::export::ENCRYPTED_MAGIC.len()..].split_at(crate::export::SALT_LEN);
+
+    let key = crate::export::derive_key(passphrase, salt)?;
+    let cipher = aes_gcm::Aes256Gcm::new(&key.into());
+    let plaintext = cipher
+        .decrypt(
+            aes_gcm::Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: header,
+            },
+        )
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "Failed to decrypt {}: wrong passphrase, or the archive was modified",
+                archive_path.display()
+            )
+        })?;
+    fs::write(output, plaintext)
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+    Ok(())
+}
+
+/// True when `archive_path` is an uncompressed OCI image-layout tarball
+/// rather than a gzipped registry export.
+#[cfg(feature = "oci")]
+pub fn is_oci_archive(archive_path: &Path) -> Result<bool> {
+    use std::io::Read;
+
+    let mut magic = [0u8; 2];
+    let mut file = File::open(archive_path)?;
+    if file.read(&mut magic)? == 2 && magic == [0x1f, 0x8b] {
+        return Ok(false);
+    }
+
+    let mut archive = tar::Archive::new(BufReader::new(File::open(archive_path)?));
+    for entry in archive.entries()? {
+        if entry?.path()?.as_os_str() == crate::oci::LAYOUT_FILE {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Import a contract packaged with `export --format oci`, verifying every
+/// blob digest against the OCI manifest before restoring the source layer.
+#[cfg(feature = "oci")]
+pub fn extract_oci_archive(
+    archive_path: &Path,
+    output_dir: &Path,
+    conflict: Conflict,
+) -> Result<ImportSummary> {
+    use crate::io_utils::extract_tar;
+    use crate::oci::{self, Descriptor, ImageIndex, ImageManifest};
+
+    let tmp_dir = tempfile::tempdir().context("failed to create temp dir")?;
+    extract_tar(archive_path, tmp_dir.path(), Conflict::Overwrite)?;
+
+    let read_blob = |desc: &Descriptor| -> Result<Vec<u8>> {
+        let bytes = fs::read(tmp_dir.path().join(oci::blob_path(&desc.digest)))
+            .with_context(|| format!("missing blob {}", desc.digest))?;
+        let computed = oci::digest(&bytes);
+        if computed != desc.digest || bytes.len() as u64 != desc.size {
+            bail!(
+                "integrity check failed for {}: got {} ({} bytes)",
+                desc.digest,
+                computed,
+                bytes.len()
+            );
+        }
+        Ok(bytes)
+    };
+
+    let index: ImageIndex = serde_json::from_slice(
+        &fs::read(tmp_dir.path().join(oci::INDEX_FILE))
+            .context("invalid OCI layout: missing index.json")?,
+    )?;
+    let image_desc = index
+        .manifests
+        .first()
+        .context("invalid OCI layout: index.json lists no manifests")?;
+    let image: ImageManifest = serde_json::from_slice(&read_blob(image_desc)?)?;
+    if image.artifact_type != oci::ARTIFACT_TYPE {
+        bail!(
+            "unsupported OCI artifact type: {} (expected {})",
+            image.artifact_type,
+            oci::ARTIFACT_TYPE
+        );
+    }
+
+    let layer = |media_type: &str| image.layers.iter().find(|l| l.media_type == media_type);
+
+    let metadata_desc = layer(oci::MEDIA_TYPE_METADATA).context("artifact has no metadata layer")?;
+    let mut manifest: ExportManifest = serde_json::from_slice(&read_blob(metadata_desc)?)?;
+
+    let source_desc = layer(oci::MEDIA_TYPE_SOURCE).context("artifact has no source layer")?;
+    let source = read_blob(source_desc)?;
+    let source_path = tmp_dir.path().join("contract.tar.gz");
+    fs::write(&source_path, &source)?;
+    let computed_hash = compute_sha256_streaming(&source_path)?;
     if computed_hash != manifest.sha256 {
         bail!(
             "integrity check failed: expected {} got {}",
@@ -39,7 +672,26 @@ pub fn extract_and_verify(archive_path: &Path, output_dir: &Path) -> Result<Expo
     });
 
     fs::create_dir_all(output_dir)?;
-    extract_tar_gz(&inner_path, output_dir)?;
+    let mut stats = extract_tar_gz(&source_path, output_dir, conflict)?;
+
+    for wasm in image.layers.iter().filter(|l| l.media_type == oci::MEDIA_TYPE_WASM) {
+        let bytes = read_blob(wasm)?;
+        let name = Path::new(wasm.title().unwrap_or("contract.wasm"))
+            .file_name()
+            .map(|n| n.to_os_string())
+            .unwrap_or_else(|| "contract.wasm".into());
+        let dest = output_dir.join(name);
+        if !dest.exists() {
+            fs::write(&dest, &bytes)?;
+            stats.files += 1;
+            stats.created += 1;
+            stats.bytes += bytes.len() as u64;
+        }
+    }
+    // Docs are also part of the source layer; only their integrity is checked here.
+    if let Some(docs) = layer(oci::MEDIA_TYPE_DOCS) {
+        read_blob(docs)?;
+    }
 
     manifest.audit_trail.push(AuditEntry {
         action: "import_extracted".into(),
@@ -47,5 +699,190 @@ pub fn extract_and_verify(archive_path: &Path, output_dir: &Path) -> Result<Expo
         actor: "soroban-registry-cli".into(),
     });
 
-    Ok(manifest)
+    Ok(ImportSummary {
+        archive: archive_path.display().to_string(),
+        output_dir: output_dir.display().to_string(),
+        files_extracted: stats.files,
+        bytes_extracted: stats.bytes,
+        files_created: stats.created,
+        files_overwritten: stats.overwritten,
+        files_skipped: stats.skipped,
+        contracts_restored: vec![RestoredContract {
+            contract_id: manifest.contract_id.clone(),
+            name: manifest.name.clone(),
+            network: manifest.network.clone(),
+            exported_at: manifest.exported_at,
+        }],
+        verification: VerificationResult {
+            algorithm: "sha256",
+            expected: manifest.sha256.clone(),
+            computed: computed_hash,
+            passed: true,
+            manifest_entries: manifest.contents.len(),
+            files_verified: 0,
+            skipped: false,
+        },
+        rehomed_from: None,
+        manifest,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_accounts_for_extracted_files() {
+        let src = tempfile::tempdir().expect("create source dir");
+        fs::write(src.path().join("lib.rs"), b"pub fn hello() {}").expect("write lib.rs");
+        fs::write(src.path().join("Cargo.toml"), b"[package]").expect("write Cargo.toml");
+
+        let work = tempfile::tempdir().expect("create work dir");
+        let archive = work.path().join("contract.tar.gz");
+        crate::export::create_archive(src.path(), &archive, "CABC", "demo", "testnet", false)
+            .expect("create archive");
+
+        let out = work.path().join("out");
+        let summary =
+            extract_and_verify(&archive, &out, false, Conflict::Refuse).expect("import archive");
+
+        assert_eq!(summary.files_extracted, 2);
+        assert_eq!(summary.bytes_extracted, 17 + 9);
+        assert_eq!(summary.contracts_restored.len(), 1);
+        assert_eq!(summary.contracts_restored[0].contract_id, "CABC");
+        assert!(summary.verification.passed);
+        assert_eq!(summary.verification.expected, summary.verification.computed);
+        assert_eq!(summary.verification.files_verified, 2);
+
+        fs::write(out.join("lib.rs"), b"pub fn tampered() {}").expect("tamper lib.rs");
+        fs::remove_file(out.join("Cargo.toml")).expect("remove Cargo.toml");
+        let listing = format!(
+            "{}  lib.rs\n{}  Cargo.toml\n",
+            compute_sha256_streaming(&src.path().join("lib.rs")).unwrap(),
+            compute_sha256_streaming(&src.path().join("Cargo.toml")).unwrap()
+        );
+        let (checked, mismatched) = verify_checksums(&listing, &out, &[]).unwrap();
+        assert_eq!(checked, 2);
+        assert_eq!(mismatched, ["lib.rs", "Cargo.toml (missing)"]);
+        assert!(verify_checksums("abc  ../etc/passwd", &out, &[]).is_err());
+    }
+
+    #[test]
+    fn encrypted_archives_need_the_right_passphrase() {
+        let src = tempfile::tempdir().expect("create source dir");
+        fs::write(src.path().join("lib.rs"), b"pub fn hello() {}").expect("write lib.rs");
+
+        let work = tempfile::tempdir().expect("create work dir");
+        let archive = work.path().join("contract.tar.gz");
+        crate::export::create_archive(src.path(), &archive, "CABC", "demo", "testnet", false)
+            .expect("create archive");
+        let sealed = crate::export::encrypted_path(&archive);
+        assert_eq!(sealed, work.path().join("contract.tar.gz.enc"));
+        assert!(crate::export::encrypt_file(&archive, &sealed, "").is_err());
+        assert!(crate::export::parse_passphrase("").is_err());
+        crate::export::encrypt_file(&archive, &sealed, "hunter2").expect("encrypt archive");
+        assert!(is_encrypted_archive(&sealed).unwrap());
+        assert!(!is_encrypted_archive(&archive).unwrap());
+
+        let opened = work.path().join("opened.tar.gz");
+        let err = decrypt_archive(&sealed, &opened, "hunter3").unwrap_err();
+        assert!(err.to_string().contains("wrong passphrase"));
+        decrypt_archive(&sealed, &opened, "hunter2").expect("decrypt archive");
+        assert_eq!(fs::read(&opened).unwrap(), fs::read(&archive).unwrap());
+        let summary =
+            extract_and_verify(&opened, &work.path().join("out"), false, Conflict::Refuse).unwrap();
+        assert_eq!(summary.files_extracted, 1);
+    }
+
+    #[test]
+    fn rehome_rewrites_ids_in_metadata_and_sources() {
+        let old_id = format!("C{}", "A".repeat(55));
+        let new_id = stellar_strkey::Contract([7u8; 32])
+            .to_string()
+            .as_str()
+            .to_owned();
+
+        let src = tempfile::tempdir().expect("create source dir");
+        fs::write(src.path().join("lib.rs"), b"pub fn hello() {}").expect("write lib.rs");
+        fs::write(src.path().join("contract-id"), format!("{}\n", old_id)).expect("write id");
+
+        let work = tempfile::tempdir().expect("create work dir");
+        let archive = work.path().join("contract.tar.gz");
+        crate::export::create_archive(src.path(), &archive, &old_id, "demo", "testnet", false)
+            .expect("create archive");
+        let out = work.path().join("out");
+        let mut summary =
+            extract_and_verify(&archive, &out, false, Conflict::Refuse).expect("import archive");
+
+        assert!(rehome(&mut summary, &out, "not-a-contract").is_err());
+        assert_eq!(rehome(&mut summary, &out, &new_id).unwrap(), 1);
+        assert_eq!(summary.manifest.contract_id, new_id);
+        assert_eq!(summary.contracts_restored[0].contract_id, new_id);
+        assert_eq!(summary.rehomed_from.as_deref(), Some(old_id.as_str()));
+        assert_eq!(
+            fs::read_to_string(out.join("contract-id")).unwrap(),
+            format!("{}\n", new_id)
+        );
+    }
+
+    #[test]
+    fn embedded_signatures_must_come_from_trusted_keys() {
+        use crate::manifest::PackageSignatureInfo;
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        let other = SigningKey::from_bytes(&[3u8; 32]).verifying_key();
+        let mut manifest = ExportManifest::new("CABC".into(), "demo".into(), "testnet".into());
+        manifest.sha256 = "ab".repeat(32);
+        let sign = |manifest: &ExportManifest| PackageSignatureInfo {
+            signature: BASE64.encode(key.sign(&manifest.signing_message()).to_bytes()),
+            signing_address: "GDEMO".into(),
+            public_key: BASE64.encode(key.verifying_key().to_bytes()),
+            algorithm: "ed25519".into(),
+            signed_at: Utc::now(),
+            expires_at: None,
+            key_fingerprint: None,
+        };
+
+        let err = verify_signature(&manifest, &[key.verifying_key()]).unwrap_err();
+        assert!(err.to_string().contains("not signed"));
+
+        let signed = manifest.clone().with_signature(sign(&manifest));
+        assert_eq!(
+            verify_signature(&signed, &[other, key.verifying_key()]).unwrap(),
+            "GDEMO"
+        );
+        assert!(verify_signature(&signed, &[other]).is_err());
+
+        let mut tampered = signed.clone();
+        tampered.sha256 = "cd".repeat(32);
+        assert!(verify_signature(&tampered, &[key.verifying_key()]).is_err());
+
+        let mut expired = signed;
+        expired.signature.as_mut().unwrap().expires_at =
+            Some(Utc::now() - chrono::Duration::days(1));
+        assert!(verify_signature(&expired, &[key.verifying_key()]).is_err());
+    }
+
+    #[cfg(feature = "oci")]
+    #[test]
+    fn oci_round_trip_restores_source_and_wasm() {
+        let src = tempfile::tempdir().expect("create source dir");
+        fs::write(src.path().join("lib.rs"), b"pub fn hello() {}").expect("write lib.rs");
+        fs::write(src.path().join("README.md"), b"# demo").expect("write README");
+        fs::write(src.path().join("demo.wasm"), b"\0asm\x01\0\0\0").expect("write wasm");
+
+        let work = tempfile::tempdir().expect("create work dir");
+        let archive = work.path().join("contract.oci.tar");
+        crate::export::create_oci_archive(src.path(), &archive, "CABC", "demo", "testnet", false)
+            .expect("create OCI archive");
+        assert!(is_oci_archive(&archive).expect("inspect archive"));
+
+        let out = work.path().join("out");
+        let summary =
+            extract_oci_archive(&archive, &out, Conflict::Refuse).expect("import OCI archive");
+        assert_eq!(summary.contracts_restored[0].contract_id, "CABC");
+        assert!(out.join("lib.rs").exists());
+        assert!(out.join("demo.wasm").exists());
+    }
 }
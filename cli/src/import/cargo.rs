@@ -0,0 +1,117 @@
+//! Metadata from a contract crate's Cargo.toml.
+//!
+//! `[package]` supplies the name, description, keywords and repository;
+//! `[package.metadata.soroban]` can override them and add what Cargo has no
+//! field for (contract ID, network, category, wasm hash). Only `[package]`
+//! is treated as metadata; dependencies and build settings are ignored.
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+
+use super::{Fields, MetadataImport};
+use crate::batch_register::{ManifestEntry, RegisterManifest};
+
+pub fn map(content: &str) -> Result<MetadataImport> {
+    let manifest: toml::Value = content.parse().context("Invalid Cargo.toml")?;
+    let manifest = serde_json::to_value(manifest)?;
+    let package = manifest
+        .get("package")
+        .and_then(Value::as_object)
+        .context("Cargo.toml has no [package] table")?;
+    let metadata = package
+        .get("metadata")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    let empty = Map::new();
+    let soroban_table = metadata
+        .get("soroban")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+
+    let mut package_fields = Fields::new(package, "package");
+    let mut soroban = Fields::new(soroban_table, "package.metadata.soroban");
+
+    // Both sides are always read so a shadowed [package] value isn't
+    // reported as unmapped.
+    let name = soroban.string(&["name"]);
+    let crate_name = package_fields.string(&["name"]);
+    let description = soroban.string(&["description"]);
+    let crate_description = package_fields.string(&["description"]);
+    let tags = soroban.list(&["tags"]);
+    let keywords = package_fields.list(&["keywords"]);
+    let source_url = soroban.string(&["source_url"]);
+    let repository = package_fields.string(&["repository"]);
+
+    let entry = ManifestEntry {
+        contract_id: soroban.string(&["contract_id"]).unwrap_or_default(),
+        name: name.or(crate_name).unwrap_or_default(),
+        network: soroban.string(&["network"]),
+        description: description.or(crate_description),
+        category: soroban.string(&["category"]),
+        tags: tags.or(keywords),
+        wasm_hash: soroban.string(&["wasm_hash"]),
+        source_url: source_url.or(repository),
+    };
+
+    let mut unmapped = package_fields.unmapped(&["metadata"]);
+    unmapped.extend(
+        metadata
+            .keys()
+            .filter(|k| *k != "soroban")
+            .map(|k| format!("package.metadata.{}", k)),
+    );
+    unmapped.extend(soroban.unmapped(&[]));
+
+    Ok(MetadataImport::new(
+        RegisterManifest {
+            publisher: None,
+            network: None,
+            contracts: vec![entry],
+        },
+        unmapped,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_package_and_soroban_metadata() {
+        let import = map(r#"
+[package]
+name = "token"
+version = "0.3.0"
+description = "Fungible token"
+keywords = ["sep-41", "token"]
+repository = "https://github.com/acme/token"
+
+[package.metadata.soroban]
+contract_id = "CABC"
+category = "defi"
+audit = "2024-Q1"
+
+[dependencies]
+soroban-sdk = "21"
+"#)
+        .unwrap();
+
+        let entry = &import.manifest.contracts[0];
+        assert_eq!(entry.name, "token");
+        assert_eq!(entry.contract_id, "CABC");
+        assert_eq!(entry.category.as_deref(), Some("defi"));
+        assert_eq!(entry.tags.as_ref().unwrap(), &["sep-41", "token"]);
+        assert_eq!(
+            entry.source_url.as_deref(),
+            Some("https://github.com/acme/token")
+        );
+        assert_eq!(
+            import.unmapped,
+            ["package.version", "package.metadata.soroban.audit"]
+        );
+        assert_eq!(import.missing, ["contracts[0].network"]);
+
+        assert!(map("[lib]\ncrate-type = [\"cdylib\"]\n").is_err());
+    }
+}
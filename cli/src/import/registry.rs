@@ -0,0 +1,116 @@
+//! Metadata exported from another contract registry, as JSON or YAML.
+//!
+//! Accepts a list of contract records, an object holding one under
+//! `contracts`, `items` or `data`, or a single record. Common spellings of
+//! each field (`address` for the contract ID, `keywords` for tags,
+//! `repository` for the source URL, ...) are recognised.
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+
+use super::{Fields, MetadataImport};
+use crate::batch_register::{ManifestEntry, RegisterManifest};
+
+/// Keys an export may keep its list of records under.
+const LIST_KEYS: &[&str] = &["contracts", "items", "data"];
+
+pub fn map(content: &str) -> Result<MetadataImport> {
+    // YAML is a superset of JSON, so this reads both.
+    let export: Value =
+        serde_yaml::from_str(content).context("Registry export is not valid JSON or YAML")?;
+
+    let mut unmapped = Vec::new();
+    let mut publisher = None;
+    let mut network = None;
+    let (records, list_key) = match &export {
+        Value::Array(records) => (records.iter().collect::<Vec<_>>(), Some(String::new())),
+        Value::Object(object) => {
+            match LIST_KEYS
+                .iter()
+                .find_map(|k| Some((object.get(*k)?.as_array()?, *k)))
+            {
+                Some((records, key)) => {
+                    let mut top = Fields::new(object, "");
+                    publisher = top.string(&["publisher", "publisher_address"]);
+                    network = top.string(&["network"]);
+                    unmapped.extend(top.unmapped(&[key]));
+                    (records.iter().collect(), Some(key.to_string()))
+                }
+                None => (vec![&export], None),
+            }
+        }
+        _ => bail!("Registry export must be a contract record or a list of them"),
+    };
+    if records.is_empty() {
+        bail!("Registry export contains no contracts");
+    }
+
+    let mut contracts = Vec::with_capacity(records.len());
+    for (i, record) in records.into_iter().enumerate() {
+        let prefix = match &list_key {
+            Some(key) => format!("{}[{}]", key, i),
+            None => String::new(),
+        };
+        let record = record
+            .as_object()
+            .with_context(|| format!("{} is not a contract record", prefix))?;
+        let mut fields = Fields::new(record, prefix);
+        contracts.push(ManifestEntry {
+            contract_id: fields
+                .string(&["contract_id", "contractId", "address", "contract_address"])
+                .unwrap_or_default(),
+            name: fields.string(&["name", "title"]).unwrap_or_default(),
+            network: fields.string(&["network"]),
+            description: fields.string(&["description", "summary"]),
+            category: fields.string(&["category"]),
+            tags: fields.list(&["tags", "keywords"]),
+            wasm_hash: fields.string(&["wasm_hash", "wasmHash", "hash"]),
+            source_url: fields.string(&["source_url", "sourceUrl", "repository", "repo"]),
+        });
+        unmapped.extend(fields.unmapped(&[]));
+    }
+
+    Ok(MetadataImport::new(
+        RegisterManifest {
+            publisher,
+            network,
+            contracts,
+        },
+        unmapped,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_records_and_reports_the_rest() {
+        let import = map(r#"{
+            "network": "testnet",
+            "exported_at": "2024-05-01",
+            "items": [
+                { "address": "CA1", "title": "Token", "keywords": "sep-41, token", "stars": 4 },
+                { "contractId": "CB2", "name": "DEX", "network": "mainnet" }
+            ]
+        }"#)
+        .unwrap();
+
+        let contracts = &import.manifest.contracts;
+        assert_eq!(contracts.len(), 2);
+        assert_eq!(contracts[0].contract_id, "CA1");
+        assert_eq!(contracts[0].name, "Token");
+        assert_eq!(contracts[0].tags.as_ref().unwrap(), &["sep-41", "token"]);
+        assert_eq!(contracts[1].network.as_deref(), Some("mainnet"));
+        assert_eq!(import.manifest.network.as_deref(), Some("testnet"));
+        assert_eq!(import.unmapped, ["exported_at", "items[0].stars"]);
+        assert!(import.missing.is_empty());
+
+        let single = map("name: Vault\n").unwrap();
+        assert_eq!(
+            single.missing,
+            ["contracts[0].contract_id", "contracts[0].network"]
+        );
+        assert!(map("[]").is_err());
+    }
+}
@@ -0,0 +1,402 @@
+//! `invoke`: call a registered contract's method through RPC simulation,
+//! with arguments typed from the contract's registered ABI.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde_json::Value;
+use stellar_xdr::curr::{
+    Duration, Int128Parts, ScBytes, ScMap, ScMapEntry, ScString, ScVal, ScVec, TimePoint,
+    UInt128Parts,
+};
+
+/// The ABI entry for function `name`.
+pub fn find_method<'a>(abi: &'a [Value], name: &str) -> Result<&'a Value> {
+    let functions = || abi.iter().filter(|i| i["type"] == "function");
+    functions()
+        .find(|f| f["name"].as_str() == Some(name))
+        .with_context(|| {
+            let available: Vec<&str> = functions().filter_map(|f| f["name"].as_str()).collect();
+            format!(
+                "Contract has no method '{}' (available: {})",
+                name,
+                available.join(", ")
+            )
+        })
+}
+
+fn inputs(method: &Value) -> Vec<(&str, &Value)> {
+    method["inputs"]
+        .as_array()
+        .map(|inputs| {
+            inputs
+                .iter()
+                .map(|i| (i["name"].as_str().unwrap_or("?"), &i["value"]))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn type_name(ty: &Value) -> String {
+    let name = ty["type"].as_str().unwrap_or("?");
+    match name.to_ascii_lowercase().as_str() {
+        "vec" | "option" => format!("{}<{}>", name, type_name(&ty["element"])),
+        "map" => format!("map<{}, {}>", type_name(&ty["key"]), type_name(&ty["val"])),
+        "bytesn" => format!("BytesN<{}>", ty["n"].as_u64().unwrap_or(0)),
+        _ => name.to_string(),
+    }
+}
+
+/// `name(param: type, ...)` for messages.
+pub fn signature(method: &Value) -> String {
+    let params: Vec<String> = inputs(method)
+        .iter()
+        .map(|(name, ty)| format!("{}: {}", name, type_name(ty)))
+        .collect();
+    format!(
+        "{}({})",
+        method["name"].as_str().unwrap_or("?"),
+        params.join(", ")
+    )
+}
+
+/// Encode command-line arguments for `method`. Each argument is either
+/// `name=value` or a bare value filling the next unnamed parameter in ABI
+/// order. Composite values (vec, map, option) are given as JSON.
+pub fn encode_args(method: &Value, args: &[String]) -> Result<Vec<ScVal>> {
+    let params = inputs(method);
+    let mut values: Vec<Option<&str>> = vec![None; params.len()];
+    let mut positional = Vec::new();
+
+    for arg in args {
+        let named = arg
+            .split_once('=')
+            .and_then(|(name, value)| Some((params.iter().position(|(p, _)| *p == name)?, value)));
+        match named {
+            Some((index, value)) => {
+                if values[index].replace(value).is_some() {
+                    bail!("Argument '{}' given more than once", params[index].0);
+                }
+            }
+            None => positional.push(arg.as_str()),
+        }
+    }
+    let mut positional = positional.into_iter();
+    for slot in values.iter_mut().filter(|v| v.is_none()) {
+        *slot = positional.next();
+    }
+    if positional.next().is_some() {
+        bail!("Too many arguments for {}", signature(method));
+    }
+
+    let missing: Vec<&str> = params
+        .iter()
+        .zip(&values)
+        .filter(|(_, v)| v.is_none())
+        .map(|((name, _), _)| *name)
+        .collect();
+    if !missing.is_empty() {
+        bail!(
+            "Missing argument(s) {} for {}",
+            missing.join(", "),
+            signature(method)
+        );
+    }
+
+    params
+        .iter()
+        .zip(values)
+        .map(|((name, ty), raw)| {
+            encode_arg(raw.unwrap_or_default(), ty)
+                .with_context(|| format!("Invalid value for '{}' ({})", name, type_name(ty)))
+        })
+        .collect()
+}
+
+fn encode_arg(raw: &str, ty: &Value) -> Result<ScVal> {
+    let kind = ty["type"].as_str().unwrap_or_default().to_ascii_lowercase();
+    let value = match kind.as_str() {
+        "vec" | "map" | "option" | "bool" => {
+            serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+        }
+        _ => Value::String(raw.to_string()),
+    };
+    encode(&value, ty)
+}
+
+fn parse_int<T: std::str::FromStr>(value: &Value) -> Result<T>
+where
+    T::Err: std::fmt::Display,
+{
+    let text = match value {
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(n) => n.to_string(),
+        other => bail!("expected an integer, got {}", other),
+    };
+    text.parse::<T>()
+        .map_err(|e| anyhow::anyhow!("{}: {}", text, e))
+}
+
+fn text(value: &Value) -> Result<&str> {
+    value
+        .as_str()
+        .with_context(|| format!("expected a string, got {}", value))
+}
+
+fn hex_bytes(value: &Value) -> Result<Vec<u8>> {
+    let s = text(value)?;
+    hex::decode(s.trim_start_matches("0x")).context("expected hex-encoded bytes")
+}
+
+/// Encode a JSON value as the `ScVal` of ABI type `ty`.
+pub fn encode(value: &Value, ty: &Value) -> Result<ScVal> {
+    let kind = ty["type"].as_str().unwrap_or_default().to_ascii_lowercase();
+    Ok(match kind.as_str() {
+        "bool" => match value {
+            Value::Bool(b) => ScVal::Bool(*b),
+            Value::String(s) if s == "true" || s == "false" => ScVal::Bool(s == "true"),
+            other => bail!("expected true or false, got {}", other),
+        },
+        "u32" => ScVal::U32(parse_int(value)?),
+        "i32" => ScVal::I32(parse_int(value)?),
+        "u64" => ScVal::U64(parse_int(value)?),
+        "i64" => ScVal::I64(parse_int(value)?),
+        "timepoint" => ScVal::Timepoint(TimePoint(parse_int(value)?)),
+        "duration" => ScVal::Duration(Duration(parse_int(value)?)),
+        "u128" => {
+            let n: u128 = parse_int(value)?;
+            ScVal::U128(UInt128Parts {
+                hi: (n >> 64) as u64,
+                lo: n as u64,
+            })
+        }
+        "i128" => {
+            let n: i128 = parse_int(value)?;
+            ScVal::I128(Int128Parts {
+                hi: (n >> 64) as i64,
+                lo: n as u64,
+            })
+        }
+        "symbol" => crate::rpc::symbol(text(value)?)?,
+        "string" => ScVal::String(ScString(
+            text(value)?
+                .as_bytes()
+                .to_vec()
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("string too long"))?,
+        )),
+        "address" => ScVal::Address(crate::rpc::sc_address(text(value)?)?),
+        "bytes" | "bytesn" => {
+            let bytes = hex_bytes(value)?;
+            if let Some(n) = ty["n"].as_u64().filter(|_| kind == "bytesn") {
+                if bytes.len() as u64 != n {
+                    bail!("expected {} bytes, got {}", n, bytes.len());
+                }
+            }
+            ScVal::Bytes(ScBytes(
+                bytes
+                    .try_into()
+                    .map_err(|_| anyhow::anyhow!("bytes too long"))?,
+            ))
+        }
+        "option" => match value {
+            Value::Null => ScVal::Void,
+            Value::String(s) if s == "null" => ScVal::Void,
+            inner => encode(inner, &ty["element"])?,
+        },
+        "vec" => {
+            let items = value.as_array().context("expected a JSON array")?;
+            let encoded = items
+                .iter()
+                .map(|item| encode(item, &ty["element"]))
+                .collect::<Result<Vec<_>>>()?;
+            ScVal::Vec(Some(ScVec(
+                encoded
+                    .try_into()
+                    .map_err(|_| anyhow::anyhow!("too many elements"))?,
+            )))
+        }
+        "map" => {
+            // JSON object keys come out sorted, which is the host's order for
+            // symbol and string keys.
+            let object = value.as_object().context("expected a JSON object")?;
+            let entries = object
+                .iter()
+                .map(|(k, v)| {
+                    Ok(ScMapEntry {
+                        key: encode(&Value::String(k.clone()), &ty["key"])?,
+                        val: encode(v, &ty["val"])?,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            ScVal::Map(Some(ScMap(
+                entries
+                    .try_into()
+                    .map_err(|_| anyhow::anyhow!("too many entries"))?,
+            )))
+        }
+        "void" => ScVal::Void,
+        other => bail!(
+            "arguments of type '{}' can't be given on the command line",
+            other
+        ),
+    })
+}
+
+/// `invoke --simulate`: run the call through RPC simulation and show the
+/// decoded result and resource cost. Nothing is submitted.
+pub async fn invoke(
+    api_url: &str,
+    contract_id: &str,
+    method: &str,
+    args: &[String],
+    simulate: bool,
+    json: bool,
+) -> Result<()> {
+    if !simulate {
+        bail!(
+            "Submitting invocations isn't supported; rerun with --simulate to preview the call \
+             without sending a transaction"
+        );
+    }
+
+    let client = reqwest::Client::new();
+    let (contract, address, network, endpoint) =
+        crate::onchain::resolve_contract(&client, api_url, contract_id).await?;
+    let uuid = contract["id"].as_str().unwrap_or(contract_id);
+
+    let abi = crate::recommend::fetch_abi(&client, api_url, uuid).await;
+    let (encoded, call) = match abi.as_ref().and_then(Value::as_array) {
+        Some(abi) => {
+            let spec = find_method(abi, method)?;
+            (encode_args(spec, args)?, signature(spec))
+        }
+        None if args.is_empty() => (Vec::new(), format!("{}()", method)),
+        None => bail!(
+            "No ABI registered for {}; arguments can't be typed without one",
+            contract_id
+        ),
+    };
+
+    let simulation =
+        crate::rpc::simulate_invoke(&client, &endpoint, &address, method, encoded).await?;
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(
+            &serde_json::json!({
+                "contract_id": address,
+                "network": network,
+                "method": method,
+                "args": args,
+                "simulation": simulation,
+            }),
+            crate::output::Format::Json,
+        )?;
+        return Ok(());
+    }
+
+    println!("\n{}", "Simulated Invocation".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    println!(
+        "  {}: {} {}",
+        "Contract".bold(),
+        contract["name"].as_str().unwrap_or("Unknown"),
+        address.bright_black()
+    );
+    println!("  {}: {}", "Network".bold(), network.bright_blue());
+    println!("  {}: {}", "Call".bold(), call);
+    if !args.is_empty() {
+        println!("  {}: {}", "Args".bold(), args.join(" "));
+    }
+    println!();
+
+    match (&simulation.error, &simulation.result) {
+        (Some(err), _) => println!("  {} {}", "✗ Call failed:".red().bold(), err),
+        (None, Some(result)) => println!(
+            "  {} {}",
+            "✓ Returns:".green().bold(),
+            serde_json::to_string_pretty(result)?
+        ),
+        (None, None) => println!("  {} (no return value)", "✓".green()),
+    }
+
+    if let Some(resources) = &simulation.resources {
+        println!("\n  {}", "Resources:".bold());
+        println!("    Instructions: {}", resources.instructions);
+        println!(
+            "    Read:         {} bytes ({} entries)",
+            resources.disk_read_bytes, resources.read_entries
+        );
+        println!(
+            "    Write:        {} bytes ({} entries)",
+            resources.write_bytes, resources.write_entries
+        );
+    }
+    if let Some(fee) = &simulation.min_resource_fee {
+        println!("    Min resource fee: {} stroops", fee);
+    }
+
+    println!(
+        "\n  {} Simulation only; nothing was submitted.",
+        "→".bright_black()
+    );
+    println!("{}", "=".repeat(80).cyan());
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn encodes_named_and_positional_args_from_abi() {
+        let abi = vec![json!({
+            "type": "function",
+            "name": "transfer",
+            "inputs": [
+                { "name": "to", "value": { "type": "Address" } },
+                { "name": "amount", "value": { "type": "i128" } },
+                { "name": "memo", "value": { "type": "option", "element": { "type": "symbol" } } },
+                { "name": "ids", "value": { "type": "vec", "element": { "type": "u32" } } }
+            ]
+        })];
+        let method = find_method(&abi, "transfer").unwrap();
+        assert!(find_method(&abi, "mint")
+            .unwrap_err()
+            .to_string()
+            .contains("available: transfer"));
+        assert_eq!(
+            signature(method),
+            "transfer(to: Address, amount: i128, memo: option<symbol>, ids: vec<u32>)"
+        );
+
+        let to = stellar_strkey::Contract([1u8; 32])
+            .to_string()
+            .as_str()
+            .to_owned();
+        let args: Vec<String> = vec![
+            "amount=-170141183460469231731687303715884105728".into(),
+            to,
+            "null".into(),
+            "[1, 2]".into(),
+        ];
+        let encoded = encode_args(method, &args).unwrap();
+        assert!(matches!(encoded[0], ScVal::Address(_)));
+        assert_eq!(
+            encoded[1],
+            ScVal::I128(Int128Parts {
+                hi: i64::MIN,
+                lo: 0
+            })
+        );
+        assert_eq!(encoded[2], ScVal::Void);
+        assert_eq!(crate::rpc::scval_to_json(&encoded[3]), json!([1, 2]));
+
+        let err = encode_args(method, &args[..2]).unwrap_err().to_string();
+        assert!(err.contains("Missing argument(s) memo, ids"));
+        let bad = vec!["GBAD".to_string(), "1".into(), "null".into(), "[]".into()];
+        assert!(encode_args(method, &bad).is_err());
+    }
+}
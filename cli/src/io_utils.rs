@@ -1,14 +1,47 @@
 use std::fs::File;
 use std::io::{BufReader, BufWriter, Read, Write};
-use std::path::Path;
+use std::path::{Component, Path};
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use flate2::read::GzDecoder;
 use sha2::{Digest, Sha256};
 use tar::Archive;
 
 pub const BUF_SIZE: usize = 65536;
 
+/// Path argument meaning "read from stdin".
+pub const STDIN: &str = "-";
+
+pub fn is_stdin(path_or_dash: &str) -> bool {
+    path_or_dash == STDIN
+}
+
+/// Read an input argument as raw bytes: the file at the given path, or all of
+/// stdin when it is `-`.
+pub fn read_input(path_or_dash: &str) -> Result<Vec<u8>> {
+    if is_stdin(path_or_dash) {
+        let mut buf = Vec::new();
+        std::io::stdin()
+            .lock()
+            .read_to_end(&mut buf)
+            .context("Failed to read from stdin")?;
+        return Ok(buf);
+    }
+    std::fs::read(path_or_dash).with_context(|| format!("Failed to read {}", path_or_dash))
+}
+
+/// Like [`read_input`], for UTF-8 text inputs.
+pub fn read_input_to_string(path_or_dash: &str) -> Result<String> {
+    let bytes = read_input(path_or_dash)?;
+    String::from_utf8(bytes).with_context(|| {
+        if is_stdin(path_or_dash) {
+            "stdin is not valid UTF-8".to_string()
+        } else {
+            format!("{} is not valid UTF-8", path_or_dash)
+        }
+    })
+}
+
 /// Compute SHA256 hash of a file using streaming to handle large files.
 pub fn compute_sha256_streaming(path: &Path) -> Result<String> {
     let mut reader = BufReader::with_capacity(BUF_SIZE, File::open(path)?);
@@ -26,32 +59,255 @@ pub fn compute_sha256_streaming(path: &Path) -> Result<String> {
     Ok(format!("{:x}", hasher.finalize()))
 }
 
+/// What to do with files already in the directory being extracted into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Conflict {
+    /// Refuse a non-empty directory up front (see [`check_output_dir`]).
+    #[default]
+    Refuse,
+    /// Replace existing files.
+    Overwrite,
+    /// Keep existing files and only write the ones that are missing.
+    Merge,
+}
+
+/// Fail when `dir` already has something in it and `conflict` says to
+/// refuse.
+pub fn check_output_dir(dir: &Path, conflict: Conflict) -> Result<()> {
+    if conflict != Conflict::Refuse {
+        return Ok(());
+    }
+    let occupied = match std::fs::read_dir(dir) {
+        Ok(mut entries) => entries.next().is_some(),
+        Err(_) => false,
+    };
+    if occupied {
+        bail!(
+            "{} is not empty; pass --force to overwrite its files or --merge to keep them",
+            dir.display()
+        );
+    }
+    Ok(())
+}
+
+/// What [`extract_tar_gz`] wrote to disk.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExtractStats {
+    /// Files written, new or overwritten.
+    pub files: usize,
+    pub bytes: u64,
+    pub created: usize,
+    /// Paths, relative to the destination, that replaced existing files.
+    pub overwritten: Vec<String>,
+    /// Paths left alone because they already existed ([`Conflict::Merge`]).
+    pub skipped: Vec<String>,
+}
+
 /// Extract a gzipped tar archive to a destination directory.
-pub fn extract_tar_gz(archive_path: &Path, dest: &Path) -> Result<()> {
+pub fn extract_tar_gz(
+    archive_path: &Path,
+    dest: &Path,
+    conflict: Conflict,
+) -> Result<ExtractStats> {
+    let reader = BufReader::with_capacity(BUF_SIZE, File::open(archive_path)?);
+    extract_entries(Archive::new(GzDecoder::new(reader)), dest, conflict)
+}
+
+/// Extract an uncompressed tar archive to a destination directory.
+#[cfg_attr(not(feature = "oci"), allow(dead_code))]
+pub fn extract_tar(archive_path: &Path, dest: &Path, conflict: Conflict) -> Result<ExtractStats> {
     let reader = BufReader::with_capacity(BUF_SIZE, File::open(archive_path)?);
-    let decoder = GzDecoder::new(reader);
-    let mut archive = Archive::new(decoder);
+    extract_entries(Archive::new(reader), dest, conflict)
+}
+
+/// True when `path`, relative to the extraction root, stays inside it.
+fn stays_within(path: &Path) -> bool {
+    let mut depth = 0usize;
+    for component in path.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => depth -= 1,
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return false,
+        }
+    }
+    true
+}
+
+fn extract_entries<R: Read>(
+    mut archive: Archive<R>,
+    dest: &Path,
+    conflict: Conflict,
+) -> Result<ExtractStats> {
+    let mut stats = ExtractStats::default();
+    std::fs::create_dir_all(dest)?;
+    let root = dest.canonicalize()?;
 
     for entry in archive.entries()? {
         let mut entry = entry?;
         let path = entry.path()?.into_owned();
+        let name = path.to_string_lossy().replace('\\', "/");
+        // No `..` at all in entry names, even ones that would end up inside.
+        if path.is_absolute() || path.components().any(|c| c == Component::ParentDir) {
+            bail!(
+                "archive entry '{}' points outside the output directory",
+                name
+            );
+        }
         let dest_path = dest.join(&path);
+        let kind = entry.header().entry_type();
+
+        if kind.is_dir() {
+            std::fs::create_dir_all(&dest_path)?;
+            continue;
+        }
+        if kind.is_symlink() || kind.is_hard_link() {
+            let target = entry
+                .link_name()?
+                .with_context(|| format!("archive link '{}' has no target", name))?;
+            // Symlinks resolve from their own directory, hard links from the root.
+            let resolved = if kind.is_symlink() {
+                path.parent().unwrap_or(Path::new("")).join(&target)
+            } else {
+                target.to_path_buf()
+            };
+            if !stays_within(&resolved) {
+                bail!(
+                    "archive link '{}' -> '{}' points outside the output directory",
+                    name,
+                    target.display()
+                );
+            }
+        }
 
         if let Some(parent) = dest_path.parent() {
             std::fs::create_dir_all(parent)?;
+            // A symlinked directory, from the archive or already on disk,
+            // must not carry writes out of the destination.
+            if !parent.canonicalize()?.starts_with(&root) {
+                bail!(
+                    "archive entry '{}' would be written outside the output directory",
+                    name
+                );
+            }
         }
 
-        let mut out = BufWriter::new(File::create(&dest_path)?);
-        let mut buf = vec![0u8; BUF_SIZE];
-        loop {
-            let n = entry.read(&mut buf)?;
-            if n == 0 {
-                break;
+        let existed = dest_path.symlink_metadata().is_ok();
+        if existed {
+            match conflict {
+                Conflict::Merge => {
+                    stats.skipped.push(name);
+                    continue;
+                }
+                // Removed rather than truncated, so an existing symlink is
+                // replaced instead of written through.
+                Conflict::Overwrite | Conflict::Refuse => std::fs::remove_file(&dest_path)
+                    .with_context(|| format!("Failed to replace {}", dest_path.display()))?,
             }
-            out.write_all(&buf[..n])?;
         }
-        out.flush()?;
+
+        if kind.is_symlink() || kind.is_hard_link() {
+            entry.unpack(&dest_path)?;
+        } else {
+            let mut out = BufWriter::new(File::create(&dest_path)?);
+            let mut buf = vec![0u8; BUF_SIZE];
+            loop {
+                let n = entry.read(&mut buf)?;
+                if n == 0 {
+                    break;
+                }
+                out.write_all(&buf[..n])?;
+                stats.bytes += n as u64;
+            }
+            out.flush()?;
+        }
+        stats.files += 1;
+        if existed {
+            stats.overwritten.push(name);
+        } else {
+            stats.created += 1;
+        }
     }
 
-    Ok(())
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_input_reads_files_and_recognises_dash() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("manifest.yaml");
+        std::fs::write(&path, "contracts: []\n").unwrap();
+        let path = path.to_str().unwrap();
+        assert_eq!(read_input_to_string(path).unwrap(), "contracts: []\n");
+        assert!(is_stdin("-"));
+        assert!(!is_stdin(path));
+        assert!(read_input("/nonexistent/file").is_err());
+    }
+
+    fn tar_gz(entries: &[(&str, tar::EntryType, &str)]) -> tempfile::NamedTempFile {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let encoder =
+            flate2::write::GzEncoder::new(file.reopen().unwrap(), flate2::Compression::fast());
+        let mut builder = tar::Builder::new(encoder);
+        for (name, kind, data) in entries {
+            // Names are set raw: the builder itself refuses `..`.
+            let mut header = tar::Header::new_old();
+            header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_entry_type(*kind);
+            header.set_mode(0o644);
+            if kind.is_symlink() {
+                header.as_old_mut().linkname[..data.len()].copy_from_slice(data.as_bytes());
+                header.set_size(0);
+                header.set_cksum();
+                builder.append(&header, std::io::empty()).unwrap();
+            } else {
+                header.set_size(data.len() as u64);
+                header.set_cksum();
+                builder.append(&header, data.as_bytes()).unwrap();
+            }
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+        file
+    }
+
+    #[test]
+    fn extraction_resolves_conflicts_and_stays_inside_dest() {
+        use tar::EntryType::{Regular, Symlink};
+
+        let archive = tar_gz(&[("a.txt", Regular, "new a"), ("sub/b.txt", Regular, "new b")]);
+        let dest = tempfile::tempdir().unwrap();
+        std::fs::write(dest.path().join("a.txt"), "old a").unwrap();
+        assert!(check_output_dir(dest.path(), Conflict::Refuse).is_err());
+        assert!(check_output_dir(&dest.path().join("sub"), Conflict::Refuse).is_ok());
+
+        let merged = extract_tar_gz(archive.path(), dest.path(), Conflict::Merge).unwrap();
+        assert_eq!(merged.created, 1);
+        assert_eq!(merged.skipped, ["a.txt"]);
+        assert_eq!(
+            std::fs::read_to_string(dest.path().join("a.txt")).unwrap(),
+            "old a"
+        );
+
+        let forced = extract_tar_gz(archive.path(), dest.path(), Conflict::Overwrite).unwrap();
+        assert_eq!(forced.overwritten, ["a.txt", "sub/b.txt"]);
+        assert_eq!(
+            std::fs::read_to_string(dest.path().join("a.txt")).unwrap(),
+            "new a"
+        );
+
+        for evil in [
+            tar_gz(&[("../evil.txt", Regular, "x")]),
+            tar_gz(&[("link", Symlink, "../../etc/passwd")]),
+            tar_gz(&[("sub/link", Symlink, "/etc/passwd")]),
+        ] {
+            let dest = tempfile::tempdir().unwrap();
+            assert!(extract_tar_gz(evil.path(), dest.path(), Conflict::Overwrite).is_err());
+        }
+        assert!(stays_within(Path::new("sub/../a.txt")));
+        assert!(!stays_within(Path::new("sub/../../a.txt")));
+    }
 }
@@ -0,0 +1,61 @@
+//! JSON Schemas for the CLI's structured (`--json`) output.
+//!
+//! Schemas are generated from the same types the commands serialise, so they
+//! cannot drift from what is actually printed.
+
+use anyhow::Result;
+use clap::ValueEnum;
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+
+/// Commands whose `--json` output has a published schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SchemaCommand {
+    Health,
+    Ttl,
+    Import,
+    BatchRegister,
+    BatchVerify,
+    DepsConflicts,
+}
+
+impl SchemaCommand {
+    pub fn schema(self) -> RootSchema {
+        match self {
+            SchemaCommand::Health => schema_for!(crate::health::HealthReport),
+            SchemaCommand::Ttl => schema_for!(Vec<crate::health::TtlReport>),
+            SchemaCommand::Import => schema_for!(crate::import::ImportSummary),
+            SchemaCommand::BatchRegister => {
+                schema_for!(crate::batch_register::RegistrationSummary)
+            }
+            SchemaCommand::BatchVerify => schema_for!(crate::batch_verify::BatchVerifyResponse),
+            SchemaCommand::DepsConflicts => schema_for!(crate::deps::ConflictReport),
+        }
+    }
+}
+
+pub fn print(command: SchemaCommand) -> Result<()> {
+    crate::output::Emitter::current().emit(&command.schema(), crate::output::Format::Json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_command_has_an_object_schema() {
+        for command in SchemaCommand::value_variants() {
+            let schema = serde_json::to_value(command.schema()).unwrap();
+            assert!(
+                schema["$schema"].is_string(),
+                "{:?} schema missing $schema",
+                command
+            );
+        }
+
+        let health = serde_json::to_value(SchemaCommand::Health.schema()).unwrap();
+        let required = health["required"].as_array().unwrap();
+        assert!(required.iter().any(|f| f == "checks"));
+        assert_eq!(health["definitions"]["HealthStatus"]["enum"][0], "healthy");
+    }
+}
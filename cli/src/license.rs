@@ -0,0 +1,66 @@
+//! SPDX license expressions as registered on contracts, and allowlist checks.
+
+use serde_json::Value;
+
+/// Shown for contracts that have no license registered.
+pub const UNKNOWN: &str = "UNKNOWN";
+
+/// The license registered on a contract, or [`UNKNOWN`].
+pub fn of(contract: &Value) -> String {
+    contract["license"]
+        .as_str()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .unwrap_or(UNKNOWN)
+        .to_string()
+}
+
+/// Whether an SPDX expression is acceptable under `allowed`. An `OR`
+/// expression passes if any alternative does; an `AND` expression only if
+/// every term does. Identifiers compare case-insensitively. An empty
+/// allowlist accepts everything.
+pub fn is_allowed(expression: &str, allowed: &[String]) -> bool {
+    if allowed.is_empty() {
+        return true;
+    }
+    let permitted = |id: &str| allowed.iter().any(|a| a.eq_ignore_ascii_case(id));
+    let expression = expression.replace(['(', ')'], " ");
+    let words: Vec<&str> = expression.split_whitespace().collect();
+    words
+        .split(|w| w.eq_ignore_ascii_case("OR"))
+        .any(|alternative| {
+            let terms: Vec<&[&str]> = alternative
+                .split(|w| w.eq_ignore_ascii_case("AND"))
+                .collect();
+            !alternative.is_empty()
+                && terms.iter().all(|term| match term {
+                    [id] => permitted(id),
+                    // `<id> WITH <exception>`: the base license decides.
+                    [id, with, _] if with.eq_ignore_ascii_case("WITH") => permitted(id),
+                    _ => false,
+                })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn evaluates_spdx_expressions_against_allowlist() {
+        let allowed = vec!["MIT".to_string(), "apache-2.0".to_string()];
+        assert!(is_allowed("MIT", &allowed));
+        assert!(is_allowed("Apache-2.0", &allowed));
+        assert!(is_allowed("GPL-3.0-only OR MIT", &allowed));
+        assert!(is_allowed("(MIT AND Apache-2.0)", &allowed));
+        assert!(is_allowed("Apache-2.0 WITH LLVM-exception", &allowed));
+        assert!(!is_allowed("MIT AND GPL-3.0-only", &allowed));
+        assert!(!is_allowed(UNKNOWN, &allowed));
+        assert!(is_allowed("GPL-3.0-only", &[]));
+
+        assert_eq!(of(&json!({ "license": " MIT " })), "MIT");
+        assert_eq!(of(&json!({ "license": "" })), UNKNOWN);
+        assert_eq!(of(&json!({})), UNKNOWN);
+    }
+}
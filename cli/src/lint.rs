@@ -0,0 +1,620 @@
+//! `lint`: common metadata and interface smells in a contract's source.
+//!
+//! Each check is a [`LintRule`] with its own ID and default severity. Rules
+//! only see exported methods, meaning the `pub fn`s of a `#[contractimpl]`
+//! block, or every `fn` when it implements a trait. Source is scanned line by
+//! line rather than fully parsed, so a check that needs data flow (such as an
+//! event published from a helper) can report a false positive. Those are
+//! warnings by default; `--deny <rule>` makes a rule fail the run.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn glyph(self) -> colored::ColoredString {
+        match self {
+            Severity::Warning => "⚠".yellow(),
+            Severity::Error => "✗".red(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Finding {
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub file: String,
+    /// 1-based; `None` for findings about the contract as a whole.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    pub name: String,
+    pub ty: String,
+}
+
+/// An exported contract method.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Method {
+    pub name: String,
+    /// 1-based line of the `fn`.
+    pub line: usize,
+    pub documented: bool,
+    /// Parameters other than the `Env`.
+    pub params: Vec<Param>,
+    /// Everything between the method's braces, comments stripped.
+    pub body: String,
+}
+
+pub struct SourceFile {
+    pub path: String,
+    pub text: String,
+    pub methods: Vec<Method>,
+}
+
+impl SourceFile {
+    pub fn parse(path: impl Into<String>, text: String) -> Self {
+        let methods = exported_methods(&text);
+        SourceFile {
+            path: path.into(),
+            text,
+            methods,
+        }
+    }
+
+    fn finding(&self, rule: &dyn LintRule, method: &Method, message: String) -> Finding {
+        Finding {
+            rule: rule.id(),
+            severity: rule.severity(),
+            file: self.path.clone(),
+            line: Some(method.line),
+            message,
+        }
+    }
+}
+
+pub trait LintRule {
+    /// Name accepted by `--deny`.
+    fn id(&self) -> &'static str;
+    fn severity(&self) -> Severity;
+    fn check(&self, files: &[SourceFile]) -> Vec<Finding>;
+}
+
+/// Every exported method should say what it does; the ABI carries the doc
+/// comment to bindings and the registry.
+pub struct MissingDocs;
+
+impl LintRule for MissingDocs {
+    fn id(&self) -> &'static str {
+        "missing-docs"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, files: &[SourceFile]) -> Vec<Finding> {
+        files
+            .iter()
+            .flat_map(|file| {
+                file.methods
+                    .iter()
+                    .filter(|m| !m.documented)
+                    .map(move |m| file.finding(self, m, format!("`{}` has no doc comment", m.name)))
+            })
+            .collect()
+    }
+}
+
+/// Method and parameter names that say nothing about what they hold or do.
+pub struct GenericNames;
+
+const GENERIC_METHODS: &[&str] = &[
+    "do", "run", "execute", "exec", "handle", "process", "call", "action", "func", "update",
+    "data", "main", "foo", "bar",
+];
+const GENERIC_PARAMS: &[&str] = &[
+    "data", "value", "val", "arg", "args", "input", "param", "params", "tmp", "temp", "x", "a", "b",
+];
+
+impl LintRule for GenericNames {
+    fn id(&self) -> &'static str {
+        "generic-name"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, files: &[SourceFile]) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for file in files {
+            for method in &file.methods {
+                if GENERIC_METHODS.contains(&method.name.as_str()) {
+                    findings.push(file.finding(
+                        self,
+                        method,
+                        format!("method name `{}` is too generic", method.name),
+                    ));
+                }
+                for param in &method.params {
+                    if GENERIC_PARAMS.contains(&param.name.as_str()) {
+                        findings.push(file.finding(
+                            self,
+                            method,
+                            format!(
+                                "parameter `{}` of `{}` is too generic",
+                                param.name, method.name
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+        findings
+    }
+}
+
+/// Collection and byte-string arguments any caller can make arbitrarily
+/// large. Not reported when the method checks the argument's `len()`.
+pub struct UnboundedInput;
+
+const UNBOUNDED_TYPES: &[&str] = &["Vec", "Map", "Bytes", "String"];
+
+impl LintRule for UnboundedInput {
+    fn id(&self) -> &'static str {
+        "unbounded-input"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, files: &[SourceFile]) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for file in files {
+            for method in &file.methods {
+                for param in &method.params {
+                    let outer = param.ty.trim_start_matches('&').trim();
+                    let outer = outer.split('<').next().unwrap_or_default().trim();
+                    let outer = outer.rsplit("::").next().unwrap_or_default();
+                    let checked = method.body.contains(&format!("{}.len()", param.name));
+                    if UNBOUNDED_TYPES.contains(&outer) && !checked {
+                        findings.push(file.finding(
+                            self,
+                            method,
+                            format!(
+                                "`{}: {}` in `{}` has no length check",
+                                param.name, param.ty, method.name
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+        findings
+    }
+}
+
+/// Contracts should fail with a `#[contracterror]` enum rather than bare
+/// panics, so callers get a stable error code.
+pub struct MissingErrorEnum;
+
+impl LintRule for MissingErrorEnum {
+    fn id(&self) -> &'static str {
+        "missing-error-enum"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, files: &[SourceFile]) -> Vec<Finding> {
+        let Some(file) = files.iter().find(|f| !f.methods.is_empty()) else {
+            return Vec::new();
+        };
+        if files.iter().any(|f| f.text.contains("#[contracterror")) {
+            return Vec::new();
+        }
+        vec![Finding {
+            rule: self.id(),
+            severity: self.severity(),
+            file: file.path.clone(),
+            line: None,
+            message: "no #[contracterror] enum is defined".to_string(),
+        }]
+    }
+}
+
+/// Methods that write storage without publishing an event leave indexers
+/// with no way to follow the change.
+pub struct MissingEvents;
+
+impl LintRule for MissingEvents {
+    fn id(&self) -> &'static str {
+        "missing-events"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, files: &[SourceFile]) -> Vec<Finding> {
+        files
+            .iter()
+            .flat_map(|file| {
+                file.methods
+                    .iter()
+                    .filter(|m| {
+                        let writes = m.body.contains("storage()")
+                            && (m.body.contains(".set(") || m.body.contains(".remove("));
+                        writes && !m.body.contains("events()") && !m.body.contains(".publish(")
+                    })
+                    .map(move |m| {
+                        file.finding(
+                            self,
+                            m,
+                            format!("`{}` changes state but emits no event", m.name),
+                        )
+                    })
+            })
+            .collect()
+    }
+}
+
+pub fn rules() -> Vec<Box<dyn LintRule>> {
+    vec![
+        Box::new(MissingDocs),
+        Box::new(GenericNames),
+        Box::new(UnboundedInput),
+        Box::new(MissingErrorEnum),
+        Box::new(MissingEvents),
+    ]
+}
+
+/// Run every rule, raising the ones in `deny` to errors. Findings are
+/// ordered by file and line.
+pub fn lint(files: &[SourceFile], deny: &[String]) -> Result<Vec<Finding>> {
+    let rules = rules();
+    if let Some(unknown) = deny
+        .iter()
+        .find(|d| !rules.iter().any(|r| r.id() == d.as_str()))
+    {
+        let known: Vec<&str> = rules.iter().map(|r| r.id()).collect();
+        bail!(
+            "Unknown lint rule '{}' (expected one of: {})",
+            unknown,
+            known.join(", ")
+        );
+    }
+
+    let mut findings: Vec<Finding> = rules
+        .iter()
+        .flat_map(|rule| rule.check(files))
+        .map(|mut finding| {
+            if deny.iter().any(|d| d == finding.rule) {
+                finding.severity = Severity::Error;
+            }
+            finding
+        })
+        .collect();
+    findings.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    Ok(findings)
+}
+
+/// `contract_path` itself, or every `.rs` file under it (skipping `target`).
+fn source_files(contract_path: &Path) -> Result<Vec<PathBuf>> {
+    if contract_path.is_file() {
+        return Ok(vec![contract_path.to_path_buf()]);
+    }
+    let mut found = Vec::new();
+    let mut pending = vec![contract_path.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                if path.file_name().and_then(|n| n.to_str()) != Some("target") {
+                    pending.push(path);
+                }
+            } else if path.extension().and_then(|e| e.to_str()) == Some("rs") {
+                found.push(path);
+            }
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+pub fn run(contract_path: &str, deny: &[String], json: bool) -> Result<()> {
+    let root = Path::new(contract_path);
+    let mut files = Vec::new();
+    for path in source_files(root)? {
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read contract source: {}", path.display()))?;
+        files.push(SourceFile::parse(path.display().to_string(), text));
+    }
+    if files.iter().all(|f| f.methods.is_empty()) {
+        bail!(
+            "No #[contractimpl] methods found in {}; lint reads Rust source, not WASM",
+            contract_path
+        );
+    }
+
+    let findings = lint(&files, deny)?;
+    let denied = findings
+        .iter()
+        .filter(|f| f.severity == Severity::Error)
+        .count();
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(
+            &serde_json::json!({
+                "contract": contract_path,
+                "methods": files.iter().map(|f| f.methods.len()).sum::<usize>(),
+                "passed": denied == 0,
+                "findings": findings,
+            }),
+            crate::output::Format::Json,
+        )?;
+    } else {
+        println!("\n{}", "Contract Lint".bold().cyan());
+        println!("{}", "=".repeat(80).cyan());
+        if findings.is_empty() {
+            println!("  {} No issues found", "✓".green());
+        }
+        for finding in &findings {
+            let location = match finding.line {
+                Some(line) => format!("{}:{}", finding.file, line),
+                None => finding.file.clone(),
+            };
+            println!(
+                "  {} {} {} {}",
+                finding.severity.glyph(),
+                location.bright_black(),
+                format!("[{}]", finding.rule).bold(),
+                finding.message
+            );
+        }
+        println!(
+            "\n  {} finding(s), {} denied",
+            findings.len(),
+            denied.to_string().bold()
+        );
+    }
+
+    if denied > 0 {
+        bail!("{} denied lint finding(s)", denied);
+    }
+    Ok(())
+}
+
+/// `code` up to any line comment.
+fn strip_comment(line: &str) -> &str {
+    match line.find("//") {
+        Some(i) => &line[..i],
+        None => line,
+    }
+}
+
+fn brace_delta(code: &str) -> isize {
+    code.chars()
+        .map(|c| match c {
+            '{' => 1,
+            '}' => -1,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Name of the method declared on `code`, if it's exported: `pub fn` in an
+/// inherent impl, any `fn` in a trait impl.
+fn exported_fn(code: &str, trait_impl: bool) -> Option<String> {
+    let rest = match code.strip_prefix("pub fn ") {
+        Some(rest) => rest,
+        None if trait_impl => code.strip_prefix("fn ")?,
+        None => return None,
+    };
+    let name = rest.split(['(', '<']).next()?.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+fn exported_methods(text: &str) -> Vec<Method> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut methods = Vec::new();
+    let mut depth = 0isize;
+    let mut contractimpl = false;
+    // Depth inside the current #[contractimpl] block, and whether it
+    // implements a trait.
+    let mut block: Option<(isize, bool)> = None;
+    let mut i = 0;
+    while i < lines.len() {
+        let code = strip_comment(lines[i]).trim();
+        if code.starts_with("#[contractimpl") {
+            contractimpl = true;
+        } else if contractimpl && code.starts_with("impl") {
+            block = Some((depth + 1, code.contains(" for ")));
+            contractimpl = false;
+        }
+
+        if let Some((inner, trait_impl)) = block {
+            if depth == inner {
+                if let Some(name) = exported_fn(code, trait_impl) {
+                    let (method, end) = parse_method(&lines, i, name);
+                    methods.push(method);
+                    i = end + 1;
+                    continue;
+                }
+            }
+        }
+
+        depth += brace_delta(code);
+        if matches!(block, Some((inner, _)) if depth < inner && code.contains('}')) {
+            block = None;
+        }
+        i += 1;
+    }
+    methods
+}
+
+/// The method starting on `lines[start]`, and the index of its last line.
+fn parse_method(lines: &[&str], start: usize, name: String) -> (Method, usize) {
+    let mut text = String::new();
+    let mut balance = 0;
+    let mut end = start;
+    for (j, line) in lines.iter().enumerate().skip(start) {
+        let code = strip_comment(line);
+        text.push_str(code);
+        text.push('\n');
+        balance += brace_delta(code);
+        end = j;
+        if balance <= 0 && code.contains('}') {
+            break;
+        }
+    }
+    let (signature, body) = text.split_once('{').unwrap_or((&text, ""));
+
+    let documented = lines[..start]
+        .iter()
+        .rev()
+        .map(|l| l.trim())
+        .take_while(|l| l.starts_with("///") || l.starts_with("#["))
+        .any(|l| l.starts_with("///") || l.starts_with("#[doc"));
+
+    let method = Method {
+        name,
+        line: start + 1,
+        documented,
+        params: params(signature),
+        body: body.to_string(),
+    };
+    (method, end)
+}
+
+/// `name: Type` pairs from a signature, without the `Env`.
+fn params(signature: &str) -> Vec<Param> {
+    let Some(open) = signature.find('(') else {
+        return Vec::new();
+    };
+    let mut params = Vec::new();
+    let mut nesting = 0;
+    let mut current = String::new();
+    for c in signature[open + 1..].chars() {
+        match c {
+            '(' | '<' | '[' => nesting += 1,
+            ')' if nesting == 0 => break,
+            ')' | '>' | ']' => nesting -= 1,
+            ',' if nesting == 0 => {
+                params.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    params.push(current);
+
+    params
+        .iter()
+        .filter_map(|p| {
+            let (name, ty) = p.split_once(':')?;
+            let name = name.trim().trim_start_matches("mut ").trim();
+            let ty = ty.split_whitespace().collect::<Vec<_>>().join(" ");
+            (!ty.ends_with("Env")).then(|| Param {
+                name: name.to_string(),
+                ty,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: &str = r#"
+#[contract]
+pub struct Registry;
+
+fn helper(data: Vec<u32>) {}
+
+#[contractimpl]
+impl Registry {
+    /// Store a name.
+    pub fn set_name(env: Env, name: String) {
+        if name.len() > 32 { panic!("too long") }
+        env.storage().instance().set(&NAME, &name);
+        env.events().publish((NAME,), name);
+    }
+
+    pub fn process(env: Env, data: Vec<Address>,
+                   owner: Address) {
+        env.storage().persistent().set(&owner, &data);
+    }
+
+    fn internal(env: Env) {}
+}
+
+#[contractimpl]
+impl Token for Registry {
+    #[allow(unused)]
+    /// Balance of `id`.
+    fn balance(env: Env, id: Address) -> i128 { 0 }
+}
+"#;
+
+    #[test]
+    fn rules_report_exported_methods_and_deny_raises_them() {
+        let file = SourceFile::parse("src/lib.rs", CONTRACT.to_string());
+        let names: Vec<&str> = file.methods.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["set_name", "process", "balance"]);
+        assert_eq!(
+            file.methods[1].params,
+            [
+                Param {
+                    name: "data".into(),
+                    ty: "Vec<Address>".into()
+                },
+                Param {
+                    name: "owner".into(),
+                    ty: "Address".into()
+                },
+            ]
+        );
+
+        let findings = lint(&[file], &["missing-events".into()]).unwrap();
+        let found: Vec<(&str, Option<usize>, Severity)> = findings
+            .iter()
+            .map(|f| (f.rule, f.line, f.severity))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("missing-error-enum", None, Severity::Warning),
+                ("missing-docs", Some(16), Severity::Warning),
+                ("generic-name", Some(16), Severity::Warning),
+                ("generic-name", Some(16), Severity::Warning),
+                ("unbounded-input", Some(16), Severity::Warning),
+                ("missing-events", Some(16), Severity::Error),
+            ]
+        );
+
+        let files = [SourceFile::parse("src/lib.rs", CONTRACT.to_string())];
+        assert!(lint(&files, &["no-such-rule".into()]).is_err());
+    }
+}
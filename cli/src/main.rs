@@ -1,36 +1,78 @@
 #![allow(unused_variables)]
 
+mod activity;
+mod analytics;
 mod analyze;
+mod audit;
 mod backup;
 mod batch_register;
 mod batch_verify;
+mod changelog;
 mod cicd;
 mod commands;
+mod concurrency;
 mod config;
+mod context;
+mod contract_docs;
 mod contract_verify;
 mod contracts;
 mod conversions;
 mod coverage;
+mod custom_fields;
 mod dashboard;
+mod deps;
+mod diff;
 mod events;
 mod export;
 mod formal_verification;
 mod fuzz;
+mod health;
+mod http;
 mod import;
 mod incident;
+mod invoke;
 mod io_utils;
+mod json_schema;
+mod license;
+mod lint;
 mod manifest;
+mod metadata_schema;
 mod migration;
 mod multisig;
 mod network;
+#[cfg(feature = "oci")]
+mod oci;
+mod onchain;
+mod output;
 mod package_signing;
+mod pagination;
 mod patch;
+mod preflight;
 mod profiler;
+mod progress;
+mod proto;
+mod provenance;
+mod publish_checks;
+mod publisher;
+mod query;
+mod ranking;
+mod raw;
+mod recommend;
+mod relations;
 mod release_notes;
+mod rpc;
+mod signals;
+mod signing;
 mod sla;
+mod standards;
 mod table_format;
+mod tags;
+mod template;
 mod test_framework;
 mod track_deployment;
+mod wasm;
+mod watch;
+mod watchlist;
 mod webhook;
 mod wizard;
 mod shell;
@@ -41,16 +83,16 @@ use clap::{Parser, Subcommand};
 use colored::Colorize;
 use patch::Severity;
 
+/// Environment variable `--api-url` falls back to.
+pub const API_URL_ENV: &str = "SOROBAN_REGISTRY_API_URL";
+pub const DEFAULT_API_URL: &str = "http://localhost:3001";
+
 /// Soroban Registry CLI — discover, publish, verify, and deploy Soroban contracts
 #[derive(Debug, Parser)]
 #[command(name = "soroban-registry", version, about, long_about = None)]
 pub struct Cli {
     /// Registry API URL
-    #[arg(
-        long,
-        env = "SOROBAN_REGISTRY_API_URL",
-        default_value = "http://localhost:3001"
-    )]
+    #[arg(long, env = API_URL_ENV, default_value = DEFAULT_API_URL)]
     pub api_url: String,
 
     /// Stellar network to use (mainnet | testnet | futurenet)
@@ -61,6 +103,72 @@ pub struct Cli {
     #[arg(long, short = 'v', global = true)]
     pub verbose: bool,
 
+    /// Disable colored output (also honoured via the NO_COLOR environment variable)
+    #[arg(long, global = true)]
+    pub no_color: bool,
+
+    /// Render tables with plain ASCII borders instead of Unicode box-drawing
+    #[arg(long, global = true)]
+    pub ascii: bool,
+
+    /// Cut table cells and long fields to this many characters with an
+    /// ellipsis (default: a third of the terminal width; no limit when
+    /// output is piped)
+    #[arg(long, global = true, value_name = "N")]
+    pub max_field_width: Option<usize>,
+
+    /// Show full field values, however long
+    #[arg(long, global = true, conflicts_with = "max_field_width")]
+    pub no_truncate: bool,
+
+    /// Write the result of read commands to this file instead of stdout
+    /// (JSON, or YAML for a .yaml/.yml path); logs stay on stderr
+    #[arg(long, global = true, value_name = "PATH")]
+    pub output_file: Option<String>,
+
+    /// Send the result of read commands to stdout (default), file:<path>,
+    /// or http:<url> (POSTed, with retries) instead
+    #[arg(long, global = true, value_name = "SINK", conflicts_with = "output_file")]
+    pub sink: Option<String>,
+
+    /// Print the structured result of read commands in this format
+    /// (overrides --json and a command's own --format; an --output-file
+    /// extension still wins). `protobuf` writes length-delimited messages
+    /// and is supported by list and search
+    #[arg(long, global = true, value_enum, value_name = "FORMAT")]
+    pub output_format: Option<output::Format>,
+
+    /// Print only the value at this RFC 6901 JSON Pointer in the command's
+    /// structured result (e.g. /wasm_hash); strings are printed unquoted
+    #[arg(long, global = true, value_name = "POINTER")]
+    pub extract: Option<String>,
+
+    /// Send the result of read commands to <format>:<destination>;
+    /// repeatable, each target gets the same result (e.g. --emit text:stdout
+    /// --emit json:result.json). Formats: text, json, yaml, protobuf
+    /// (list and search only); destinations: stdout, a path or an http(s) URL
+    #[arg(
+        long = "emit",
+        global = true,
+        value_name = "FORMAT:DEST",
+        conflicts_with_all = ["output_file", "sink", "output_format"]
+    )]
+    pub emit: Vec<String>,
+
+    /// Fail immediately on HTTP 429 instead of waiting out `Retry-After`
+    #[arg(long, global = true)]
+    pub no_wait_on_429: bool,
+
+    /// Print each contract reference as it's resolved to a registry ID or
+    /// on-chain address, before the command acts on it
+    #[arg(long, global = true)]
+    pub resolve_aliases: bool,
+
+    /// Signing backend for commands that sign: file:<path>, env[:<VAR>]
+    /// or keyring[:<name>]
+    #[arg(long, value_name = "BACKEND", env = "SOROBAN_REGISTRY_SIGNER")]
+    pub signer: Option<String>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -70,7 +178,8 @@ pub enum Commands {
     /// Search for contracts in the registry
     Search {
         /// Search query
-        query: String,
+        #[arg(required_unless_present = "near")]
+        query: Option<String>,
         /// Only show verified contracts
         #[arg(long)]
         verified_only: bool,
@@ -83,12 +192,59 @@ pub enum Commands {
         /// Maximum number of results to return
         #[arg(long, default_value = "20")]
         limit: usize,
+        /// Page of results to show, counting from 1 (--limit results per page)
+        #[arg(long, conflicts_with_all = ["offset", "near"])]
+        page: Option<usize>,
         /// Number of results to skip (for pagination)
-        #[arg(long, default_value = "0")]
-        offset: usize,
+        #[arg(long)]
+        offset: Option<usize>,
         /// Output results as machine-readable JSON
         #[arg(long)]
         json: bool,
+        /// Output format: table, json, csv or yaml
+        #[arg(long, value_enum, default_value_t = output::OutputFormat::Table, conflicts_with_all = ["json", "near"])]
+        format: output::OutputFormat,
+        /// Show each result's relevance score, and sort by it unless --sort
+        /// is given
+        #[arg(long)]
+        show_score: bool,
+        /// Client-side filter expression, e.g. "downloads>1000 AND tags contains defi"
+        #[arg(long)]
+        filter: Option<String>,
+        /// Only show contracts with at least one published audit report
+        #[arg(long)]
+        audited_only: bool,
+        /// Recommend contracts similar to this one (shared tags, category
+        /// and ABI methods) instead of running a text query
+        #[arg(long, value_name = "CONTRACT_ID", conflicts_with_all = ["query", "show_score", "filter"])]
+        near: Option<String>,
+        /// Only show contracts registered by this publisher (UUID or Stellar address)
+        #[arg(long, value_name = "ACCOUNT", conflicts_with = "near")]
+        publisher: Option<String>,
+        /// Only output these fields (comma-separated); implies JSON output
+        #[arg(long, value_name = "FIELDS", conflicts_with = "near")]
+        fields: Option<String>,
+        /// Read the field projection from a file (one or more per line, `#` comments)
+        #[arg(long, value_name = "PATH", conflicts_with = "near")]
+        fields_from_file: Option<String>,
+        /// Embed each result's full ABI; implies JSON output. Combine with the
+        /// global --output-file to save a self-contained comparison dataset
+        #[arg(long, conflicts_with = "near")]
+        with_abi: bool,
+        /// Only show contracts invoked at least this many times (registry
+        /// usage stats, or contract events over RPC when it has none)
+        #[arg(long, value_name = "N", conflicts_with = "near")]
+        min_invocations: Option<u64>,
+        /// Only show contracts active within this window, e.g. 12h, 7d
+        #[arg(long, value_name = "DURATION", value_parser = activity::parse_window, conflicts_with = "near")]
+        active_since: Option<chrono::Duration>,
+        /// Sort results by this key (name is case-insensitive); ties are
+        /// ordered by contract ID
+        #[arg(long, value_enum, conflicts_with = "near")]
+        sort: Option<ranking::SortKey>,
+        /// Sort direction for --sort
+        #[arg(long, value_enum, default_value_t = ranking::SortOrder::Desc, conflicts_with = "near")]
+        order: ranking::SortOrder,
     },
 
     /// Get detailed information about a contract
@@ -96,13 +252,36 @@ pub enum Commands {
         /// Contract registry identifier (UUID, contract address, or name)
         contract_id: String,
 
-        /// Output format (text, json, yaml)
-        #[arg(long, short = 'f', default_value = "text")]
-        format: String,
+        /// Output format: table, json or yaml. JSON prints only the contract
+        /// record (or an `{"error": ...}` object) so it can be piped
+        #[arg(long, short = 'f', value_enum, default_value_t = output::OutputFormat::Table)]
+        format: output::OutputFormat,
 
         /// Highlight a specific ABI method
         #[arg(long)]
         highlight_method: Option<String>,
+
+        /// Print only the key fields on a single line
+        #[arg(long, conflicts_with = "wide")]
+        compact: bool,
+
+        /// Print everything, including versions and raw metadata
+        #[arg(long)]
+        wide: bool,
+
+        /// Only output these fields, dotted for nested ones (e.g.
+        /// metadata.name,abi); implies JSON output unless --format yaml
+        #[arg(long, value_name = "FIELDS")]
+        fields: Option<String>,
+
+        /// Read the field projection from a file (one or more per line, `#` comments)
+        #[arg(long, value_name = "PATH")]
+        fields_from_file: Option<String>,
+
+        /// Also look up the deployed wasm hash over RPC and flag drift from
+        /// the registered one (one extra RPC round trip)
+        #[arg(long)]
+        check_chain: bool,
     },
 
     /// Publish a new contract to the registry
@@ -111,9 +290,9 @@ pub enum Commands {
         #[arg(long)]
         contract_id: String,
 
-        /// Human-readable contract name
+        /// Human-readable contract name (required unless set in --manifest)
         #[arg(long)]
-        name: String,
+        name: Option<String>,
 
         /// Optional description
         #[arg(long)]
@@ -131,9 +310,15 @@ pub enum Commands {
         #[arg(long)]
         tags: Option<String>,
 
-        /// Publisher Stellar address
+        /// Publisher Stellar address (required unless set in --manifest)
         #[arg(long)]
-        publisher: String,
+        publisher: Option<String>,
+
+        /// Read name, description, category, tags and publisher from a TOML
+        /// or JSON file (`-` for stdin); flags given alongside it override
+        /// its values
+        #[arg(long, value_name = "PATH")]
+        manifest: Option<std::path::PathBuf>,
 
         /// Path to contract project directory for preflight testing
         #[arg(long, default_value = ".")]
@@ -154,6 +339,66 @@ pub enum Commands {
         /// Skip pre-submission contract tests
         #[arg(long)]
         skip_tests: bool,
+
+        /// Reject the publish unless all required metadata is present
+        /// (description, category, tags, license, README by default;
+        /// configurable via `[publish] strict_required` in the config file)
+        #[arg(long)]
+        strict: bool,
+
+        /// Link a published audit report as `<url>,<auditor>,<YYYY-MM-DD>` (repeatable)
+        #[arg(long = "audit", value_name = "URL,AUDITOR,DATE")]
+        audits: Vec<String>,
+
+        /// Attach a custom metadata field as `key=value` (repeatable). Shown by
+        /// `info` and filterable with e.g. `--filter "custom_fields.owner=alice"`
+        #[arg(long = "meta", value_name = "KEY=VALUE")]
+        meta: Vec<String>,
+
+        /// Run every pre-publish check (manifest, metadata, contract ID, wasm
+        /// validity and size, duplicates, standards conformance) and only
+        /// publish if all pass
+        #[arg(long)]
+        preflight: bool,
+
+        /// Publish even if preflight checks fail
+        #[arg(long, requires = "preflight")]
+        force: bool,
+
+        /// Shrink the built wasm first (strip debug sections, remove dead
+        /// code, run wasm-opt if installed) and report the size reduction
+        #[arg(long)]
+        optimize: bool,
+
+        /// Contract this one replaces (repeatable)
+        #[arg(long, value_name = "ID")]
+        supersedes: Vec<String>,
+
+        /// Contract this one was forked from (repeatable)
+        #[arg(long, value_name = "ID")]
+        forks: Vec<String>,
+
+        /// Related contract (repeatable)
+        #[arg(long, value_name = "ID")]
+        related: Vec<String>,
+
+        /// Reject the publish unless its metadata (custom fields included)
+        /// validates against this JSON Schema (JSON or YAML); defaults to
+        /// `[publish] metadata_schema` in the config file
+        #[arg(long, value_name = "PATH")]
+        metadata_schema: Option<std::path::PathBuf>,
+
+        /// Build and print the request payload without publishing it
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Built wasm whose SHA-256 is sent with the metadata
+        #[arg(long, value_name = "PATH")]
+        wasm: Option<std::path::PathBuf>,
+
+        /// Abort unless the --wasm file hashes to this (hex SHA-256)
+        #[arg(long, value_name = "SHA256", requires = "wasm")]
+        expected_hash: Option<String>,
     },
 
     /// List recent contracts
@@ -161,9 +406,24 @@ pub enum Commands {
         /// Maximum number of contracts to show
         #[arg(long, default_value = "10")]
         limit: usize,
+        /// Page of contracts to show, counting from 1 (--limit contracts per page)
+        #[arg(long, conflicts_with = "offset")]
+        page: Option<usize>,
+        /// Number of contracts to skip (for pagination)
+        #[arg(long)]
+        offset: Option<usize>,
         /// Output results as machine-readable JSON
         #[arg(long)]
         json: bool,
+        /// Client-side filter expression, e.g. "downloads>1000 AND tags contains defi"
+        #[arg(long)]
+        filter: Option<String>,
+        /// Only output these fields (comma-separated); implies JSON output
+        #[arg(long, value_name = "FIELDS")]
+        fields: Option<String>,
+        /// Read the field projection from a file (one or more per line, `#` comments)
+        #[arg(long, value_name = "PATH")]
+        fields_from_file: Option<String>,
     },
 
     /// Launch an interactive, real-time terminal dashboard
@@ -190,6 +450,62 @@ pub enum Commands {
         json: bool,
     },
 
+    /// Compare the public interfaces (ABIs) of two registered contracts
+    Diff {
+        /// Contract to compare from (registry UUID or contract ID)
+        old_id: String,
+        /// Contract to compare to (registry UUID or contract ID)
+        new_id: String,
+        /// Classify each change as breaking or compatible and recommend a semver bump
+        #[arg(long)]
+        semantic: bool,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Add or remove a typed relationship between two registered contracts
+    Link {
+        /// Contract the relationship starts from (registry UUID, contract ID or name)
+        from: String,
+        /// How `from` relates to `to`
+        #[arg(value_enum)]
+        relation: relations::Relation,
+        /// Referenced contract (registry UUID, contract ID or name)
+        to: String,
+        /// Remove the relationship instead of adding it
+        #[arg(long)]
+        remove: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Deprecate a contract and list the registered contracts that depend on it
+    Deprecate {
+        /// Contract to deprecate (registry UUID, contract ID or name)
+        contract_id: String,
+        /// When the contract retires: YYYY-MM-DD or an RFC 3339 timestamp
+        #[arg(long, value_name = "DATE")]
+        retire_at: String,
+        /// Contract that replaces it
+        #[arg(long, required_unless_present = "migration_guide")]
+        replacement: Option<String>,
+        /// URL of a migration guide for dependents
+        #[arg(long, value_name = "URL")]
+        migration_guide: Option<String>,
+        /// Free-form note shown alongside the deprecation
+        #[arg(long)]
+        notes: Option<String>,
+        /// Have the registry notify publishers of dependent contracts
+        /// (subscriptions and contract.deprecated webhooks)
+        #[arg(long)]
+        notify: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Contract state migration assistant
     Migrate {
         #[command(subcommand)]
@@ -210,37 +526,155 @@ pub enum Commands {
 
     /// Export a contract archive (.tar.gz)
     Export {
-        /// Contract registry ID (UUID)
-        #[arg(long)]
-        id: String,
+        /// Contract registry ID (UUID); repeat it or pass a comma-separated
+        /// list to bundle several contracts into one archive
+        #[arg(long = "id", required = true, value_delimiter = ',')]
+        ids: Vec<String>,
 
         /// Output archive path
         #[arg(long, default_value = "contract-export.tar.gz")]
         output: String,
 
-        /// Path to contract source directory
+        /// Path to contract source directory; for a bundle, each contract's
+        /// sources are read from `<dir>/<id>` when it exists
         #[arg(long, default_value = ".")]
         contract_dir: String,
+
+        /// Archive format: tar.gz (registry export) or oci (OCI image layout)
+        #[arg(long, default_value = "tar.gz")]
+        format: String,
+
+        /// Produce a byte-identical archive for identical inputs: sorted
+        /// entries, fixed compression, and all timestamps pinned to
+        /// SOURCE_DATE_EPOCH (or the Unix epoch)
+        #[arg(long)]
+        reproducible: bool,
+
+        /// In a bundle, skip contracts that can't be fetched (with a
+        /// warning) instead of aborting the whole export
+        #[arg(long)]
+        continue_on_error: bool,
+
+        /// Encrypt the archive with AES-256-GCM, writing `<output>.enc`
+        #[arg(long)]
+        encrypt: bool,
+
+        /// Passphrase for --encrypt; prompted for when not given
+        #[arg(
+            long,
+            env = "SOROBAN_EXPORT_PASSPHRASE",
+            hide_env_values = true,
+            value_name = "PASSPHRASE",
+            value_parser = export::parse_passphrase
+        )]
+        passphrase: Option<String>,
+    },
+
+    /// Verify the embedded signatures of every export archive in a directory
+    VerifyArchives {
+        /// Directory containing `.tar.gz` / `.tgz` export archives
+        dir: String,
+
+        /// File of trusted base64 Ed25519 public keys, one per line
+        #[arg(long)]
+        trusted_keys: String,
+
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
     },
 
-    /// Import a contract from an archive
+    /// Import a contract from an archive (registry export or OCI layout)
     Import {
-        /// Path to the archive file
+        /// Path to the archive file, or `-` to read it from stdin
         archive: String,
 
         /// Directory to extract into
         #[arg(long, default_value = "./imported")]
         output_dir: String,
+
+        /// Print the import summary as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Re-home the contract under a new contract ID, rewriting the ID in
+        /// the imported metadata and sources
+        #[arg(long, value_name = "NEW_ID")]
+        contract_id: Option<String>,
+
+        /// Publish the re-homed contract to the registry after importing.
+        /// Publishing reports its progress as text, so this can't be
+        /// combined with --json
+        #[arg(long, requires_all = ["contract_id", "publisher"], conflicts_with = "json")]
+        republish: bool,
+
+        /// Publisher address used with --republish
+        #[arg(long)]
+        publisher: Option<String>,
+
+        /// Passphrase of an encrypted archive; prompted for when not given
+        #[arg(
+            long,
+            env = "SOROBAN_EXPORT_PASSPHRASE",
+            hide_env_values = true,
+            value_name = "PASSPHRASE",
+            value_parser = export::parse_passphrase
+        )]
+        passphrase: Option<String>,
+
+        /// Don't check the archive digest or the extracted files against
+        /// `manifest.sha256` (for archives known to be altered on purpose)
+        #[arg(long)]
+        skip_verify: bool,
+
+        /// Extract into a non-empty --output-dir, overwriting existing files
+        #[arg(long, conflicts_with = "merge")]
+        force: bool,
+
+        /// Extract into a non-empty --output-dir, keeping existing files and
+        /// only writing the ones that are missing
+        #[arg(long)]
+        merge: bool,
+    },
+
+    /// Map metadata from a Cargo.toml or another registry's export into a
+    /// batch-register manifest, reporting fields that couldn't be mapped
+    ImportMetadata {
+        /// Cargo.toml, registry export (JSON or YAML), or `-` for stdin
+        source: String,
+
+        /// Source format; detected from the file name when omitted
+        #[arg(long, value_enum)]
+        format: Option<import::MetadataFormat>,
+
+        /// Write the manifest here (JSON for *.json, YAML otherwise)
+        /// instead of printing it
+        #[arg(long, short = 'o')]
+        output: Option<String>,
+
+        /// Print the mapping result as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Generate documentation from a contract WASM
     Doc {
-        /// Path to contract WASM file
-        contract_path: String,
+        /// Path to contract WASM file or ABI JSON file
+        #[arg(required_unless_present = "spec")]
+        contract_path: Option<String>,
+
+        /// Document an exported contract spec (the JSON interface, e.g. from
+        /// `soroban contract bindings json`) instead, without its source
+        #[arg(long, value_name = "PATH", conflicts_with = "contract_path")]
+        spec: Option<std::path::PathBuf>,
 
         /// Output directory
         #[arg(long, default_value = "docs")]
         output: String,
+
+        /// Write `index.md`, or a single `index.html` rendered from it
+        #[arg(long, value_enum, default_value_t = contract_docs::DocFormat::Markdown)]
+        format: contract_docs::DocFormat,
     },
 
     /// Generate OpenAPI 3.0 spec from contract ABI
@@ -258,7 +692,31 @@ pub enum Commands {
     },
 
     /// Launch the interactive setup wizard
-    Wizard {},
+    #[command(args_conflicts_with_subcommands = true)]
+    Wizard {
+        /// Guided flow to run instead of contract deployment
+        #[command(subcommand)]
+        flow: Option<WizardCommands>,
+
+        /// Continue the last interrupted wizard session
+        #[arg(long)]
+        resume: bool,
+
+        /// YAML or JSON file of answers keyed by prompt id (network, signer,
+        /// wasm_path, params, max_fee, dry_run, execute); only the prompts
+        /// it doesn't answer are asked
+        #[arg(long, value_name = "FILE")]
+        answers: Option<std::path::PathBuf>,
+
+        /// Never prompt: use defaults for unanswered prompts that have one and
+        /// fail on the rest
+        #[arg(long)]
+        non_interactive: bool,
+
+        /// Record this run's answers to FILE for replay with --answers
+        #[arg(long, value_name = "FILE")]
+        dump_answers: Option<std::path::PathBuf>,
+    },
 
     /// Launch the interactive shell
     Shell {
@@ -267,15 +725,11 @@ pub enum Commands {
         network: Option<String>,
     },
 
-    /// Show command history
+    /// Show, clear or export the local command history
     History {
-        /// Filter by search term
-        #[arg(long)]
-        search: Option<String>,
-
-        /// Maximum number of entries to show
-        #[arg(long, default_value = "20")]
-        limit: usize,
+        /// What to do with the history (default: show)
+        #[command(subcommand)]
+        action: Option<HistoryCommands>,
     },
 
     /// Security patch management
@@ -314,6 +768,69 @@ pub enum Commands {
         minimize: bool,
     },
 
+    /// Disassemble contract wasm to WAT text
+    Disasm {
+        /// Path to the contract .wasm file
+        contract_path: String,
+
+        /// Only disassemble this exported function
+        #[arg(long)]
+        function: Option<String>,
+
+        /// Write the disassembly to a file instead of stdout
+        #[arg(long, short = 'o')]
+        output: Option<String>,
+    },
+
+    /// Shrink contract wasm: strip debug and name sections, stub out
+    /// unreachable functions, and run wasm-opt if installed. Soroban
+    /// metadata is preserved.
+    Optimize {
+        /// Path to the contract .wasm file
+        wasm: String,
+
+        /// Where to write the result (default: <name>.optimized.wasm)
+        #[arg(long, short = 'o')]
+        output: Option<String>,
+
+        /// Output the size report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show which host capabilities a contract uses (storage, crypto, auth,
+    /// calls, ...) and flag privileged or unusual imports
+    Capabilities {
+        /// Path to the contract .wasm file
+        contract_path: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Call a contract method; with --simulate, preview the decoded result
+    /// and resource cost through RPC simulation without submitting
+    Invoke {
+        /// Contract registry identifier (UUID, contract address, or name)
+        contract_id: String,
+
+        /// Method to call
+        method: String,
+
+        /// Arguments as `name=value` or positional values in ABI order;
+        /// vec, map and option values are JSON
+        args: Vec<String>,
+
+        /// Simulate the call instead of submitting a transaction
+        #[arg(long)]
+        simulate: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Profile contract execution performance
     Profile {
         /// Path to contract file
@@ -342,7 +859,7 @@ pub enum Commands {
 
     /// Run integration tests
     Test {
-        /// Optional path to scenario test file (YAML or JSON)
+        /// Optional path to scenario test file (YAML or JSON), or `-` for stdin
         ///
         /// If omitted, auto-detects and runs contract project tests.
         test_file: Option<String>,
@@ -374,6 +891,32 @@ pub enum Commands {
         /// Minimum required coverage percentage (0-100)
         #[arg(long, default_value_t = 0.0)]
         coverage_threshold: f64,
+
+        /// Re-run tests whenever the contract source or test file changes
+        ///
+        /// Coverage is skipped in watch mode unless a coverage requirement is set.
+        #[arg(long)]
+        watch: bool,
+
+        /// Seed for randomized inputs; a random seed is chosen and printed
+        /// when omitted, and reported on failure so the run can be replayed
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Compare per-method coverage against this baseline file and fail
+        /// if a changed method lost coverage; the file is written from the
+        /// current run when it doesn't exist yet
+        #[arg(long, value_name = "FILE", requires = "test_file")]
+        coverage_baseline: Option<String>,
+
+        /// Resolve the test file and list the cases that would run, in
+        /// order, without executing any
+        #[arg(long, requires = "test_file", conflicts_with = "watch")]
+        dry_run: bool,
+
+        /// Run every step, ignoring results cached from earlier passing runs
+        #[arg(long)]
+        no_cache: bool,
     },
 
     /// SLA compliance monitoring
@@ -454,10 +997,12 @@ pub enum Commands {
         expires_at: Option<String>,
     },
 
-    /// Verify a signed contract package
+    /// Verify a signed contract package, or with --source attest that a
+    /// contract was built from a specific git commit
     Verify {
         /// Path to the package file to verify
-        package: String,
+        #[arg(required_unless_present = "source")]
+        package: Option<String>,
 
         /// Contract ID
         #[arg(long)]
@@ -470,6 +1015,48 @@ pub enum Commands {
         /// Signature (base64, optional - will lookup from registry if not provided)
         #[arg(long)]
         signature: Option<String>,
+
+        /// Rebuild this source (<git-url>@<commit>) and check that it produces
+        /// the registered and deployed wasm hash; the attestation is recorded
+        /// under ~/.soroban-registry/provenance
+        #[arg(long, value_name = "GIT_URL@COMMIT")]
+        source: Option<String>,
+    },
+
+    /// Submit a contract's source and build instructions for verification review
+    RequestVerification {
+        /// Contract ID (on-chain address)
+        contract_id: String,
+
+        /// URL of the source repository
+        #[arg(long)]
+        source_repo: String,
+
+        /// Command that reproduces the deployed WASM from the source
+        #[arg(long)]
+        build_command: String,
+
+        /// Commit the deployed build was made from
+        #[arg(long)]
+        commit: Option<String>,
+
+        /// Compiler (rustc / soroban-sdk) version used for the build
+        #[arg(long, default_value = "stable")]
+        compiler_version: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check the progress of a contract's verification request
+    VerificationStatus {
+        /// Contract ID
+        contract_id: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Verify a contract binary against an Ed25519 signature locally
@@ -506,6 +1093,59 @@ pub enum Commands {
         action: ContractCommands,
     },
 
+    /// Compare a contract's live on-chain storage with its registered config
+    CompareOnchain {
+        /// Contract registry ID
+        contract_id: String,
+
+        /// Comma-separated storage keys to compare (default: `[compare_onchain] keys`
+        /// from the config file, then every registered expected key)
+        #[arg(long)]
+        keys: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check that a contract is live on-chain (not archived) and callable
+    Health {
+        /// Contract registry ID
+        contract_id: String,
+
+        /// Read-only method to invoke as a liveness probe (default: `[health] method`
+        /// from the config file)
+        #[arg(long)]
+        method: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Report how long a contract's ledger entries stay live before archival
+    Ttl {
+        /// Contract registry ID
+        #[arg(required_unless_present = "contract_ids_file")]
+        contract_id: Option<String>,
+
+        /// File listing contract IDs to check, one per line
+        #[arg(long, conflicts_with = "contract_id")]
+        contract_ids_file: Option<String>,
+
+        /// Comma-separated persistent storage keys to include
+        #[arg(long)]
+        keys: Option<String>,
+
+        /// Exit non-zero when any entry has fewer ledgers than this remaining
+        #[arg(long)]
+        warn_below: Option<u64>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Verify multiple contracts in a single atomic batch (all succeed or all rollback)
     BatchVerify {
         /// Comma-separated list of contract IDs to verify.
@@ -528,6 +1168,20 @@ pub enum Commands {
         action: WebhookCommands,
     },
 
+    /// Generate a Keep-a-Changelog CHANGELOG.md from a contract's version history
+    Changelog {
+        /// Contract ID (registry UUID, name or on-chain address)
+        contract_id: String,
+
+        /// File to write, or `-` for stdout
+        #[arg(long, short = 'o', default_value = "CHANGELOG.md")]
+        output: String,
+
+        /// Only include versions newer than this one
+        #[arg(long)]
+        since_version: Option<String>,
+    },
+
     /// Auto-generate and manage release notes for contract versions
     ReleaseNotes {
         #[command(subcommand)]
@@ -540,6 +1194,12 @@ pub enum Commands {
         action: CicdCommands,
     },
 
+    /// Contract project templates
+    Template {
+        #[command(subcommand)]
+        action: TemplateCommands,
+    },
+
     /// Check the status of supported Stellar networks
     Network {
         #[command(subcommand)]
@@ -548,7 +1208,7 @@ pub enum Commands {
 
     /// Register multiple contracts from a YAML or JSON manifest file
     BatchRegister {
-        /// Path to the manifest file (.yaml, .yml, or .json)
+        /// Path to the manifest file (.yaml, .yml, or .json), or `-` for stdin
         #[arg(long)]
         manifest: String,
 
@@ -558,29 +1218,191 @@ pub enum Commands {
 
         /// Validate all entries and show what would be registered without submitting
         #[arg(long)]
-        dry_run: bool,
+        dry_run: bool,
+
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Number of registrations in flight at once (1-16), or `auto` to
+        /// start low and adapt to the registry's latency and errors
+        #[arg(long, default_value = "1")]
+        concurrency: concurrency::Concurrency,
+
+        /// Print timing statistics (latency distribution, effective
+        /// concurrency, throughput) when done
+        #[arg(long, conflicts_with = "dry_run")]
+        concurrency_report: bool,
+    },
+
+    /// Run advanced analysis on a deployed contract (#530)
+    Analyze {
+        /// On-chain contract ID to analyse
+        contract_id: String,
+
+        /// Stellar network (mainnet | testnet | futurenet)
+        #[arg(long, default_value = "testnet")]
+        network: String,
+
+        /// Report format: text (default), json, yaml
+        #[arg(long, default_value = "text")]
+        report_format: String,
+
+        /// Write the report to a file instead of stdout
+        #[arg(long, short = 'o')]
+        output: Option<String>,
+    },
+
+    /// Canonicalize tags and categories across all contracts (admin)
+    NormalizeTags {
+        /// Show the before/after report without applying anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Apply without the confirmation prompt
+        #[arg(long, short)]
+        yes: bool,
+
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Build the registry's tag co-occurrence graph (JSON, or DOT for a
+    /// .dot/.gv output path)
+    TagGraph {
+        /// Write the graph to this file instead of stdout
+        #[arg(long, short = 'o')]
+        output: Option<String>,
+    },
+
+    /// Send an arbitrary authenticated request to the registry API
+    Raw {
+        /// HTTP method (GET, POST, PUT, PATCH, DELETE, ...)
+        method: String,
+
+        /// Path relative to the API URL, e.g. /api/contracts?page=2
+        path: String,
+
+        /// JSON request body: inline, @<file>, or - for stdin
+        #[arg(long)]
+        body: Option<String>,
+    },
+
+    /// Check a contract's ABI against a standard interface such as SEP-41
+    Conforms {
+        /// Path to the contract WASM or ABI JSON
+        contract_path: String,
+
+        /// Bundled standard to check against (e.g. sep-41)
+        #[arg(long, required_unless_present = "standard_file")]
+        standard: Option<String>,
+
+        /// Custom standard definition (TOML, or JSON for a .json path)
+        #[arg(long, conflicts_with = "standard")]
+        standard_file: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check a contract's source for common metadata and interface smells
+    Lint {
+        /// Contract source file, or a crate directory to scan for .rs files
+        contract_path: String,
+
+        /// Fail when this rule reports anything (repeatable), e.g. missing-docs
+        #[arg(long = "deny", value_name = "RULE")]
+        deny: Vec<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show what commands will target: API URL, network and passphrase,
+    /// the identity behind the API token, and active modes
+    #[command(alias = "whoami")]
+    Context {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print the JSON Schema of a command's `--json` output
+    JsonSchema {
+        /// Command whose output schema to print
+        #[arg(value_enum)]
+        command: json_schema::SchemaCommand,
+    },
+
+    /// List every contract a publisher has registered, with portfolio stats
+    Publisher {
+        /// Publisher UUID or Stellar address
+        account: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Keep a personal list of contracts and check on them together
+    Watchlist {
+        #[command(subcommand)]
+        action: WatchlistCommands,
+    },
+}
+
+/// Sub-commands for the `watchlist` group
+#[derive(Debug, Subcommand)]
+pub enum WatchlistCommands {
+    /// Start watching a contract
+    Add {
+        /// Contract ID (registry UUID or C... address)
+        contract_id: String,
+    },
+
+    /// Stop watching a contract
+    Remove { contract_id: String },
+
+    /// List watched contracts
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
 
-        /// Output results as machine-readable JSON
+    /// Show version, TTL and pending patches for every watched contract
+    Status {
+        /// Output as JSON
         #[arg(long)]
         json: bool,
     },
+}
 
-    /// Run advanced analysis on a deployed contract (#530)
-    Analyze {
-        /// On-chain contract ID to analyse
-        contract_id: String,
-
-        /// Stellar network (mainnet | testnet | futurenet)
-        #[arg(long, default_value = "testnet")]
-        network: String,
+/// Sub-commands for the `template` group
+#[derive(Debug, Subcommand)]
+pub enum TemplateCommands {
+    /// Show how a cloned project has diverged from its template
+    Diff {
+        /// Project directory created by `template clone`
+        #[arg(default_value = ".")]
+        project_dir: String,
 
-        /// Report format: text (default), json, yaml
-        #[arg(long, default_value = "text")]
-        report_format: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Merge upstream template changes into a cloned project
+    Update {
+        /// Project directory created by `template clone`
+        #[arg(default_value = ".")]
+        project_dir: String,
 
-        /// Write the report to a file instead of stdout
-        #[arg(long, short = 'o')]
-        output: Option<String>,
+        /// Template version to update to (defaults to the latest)
+        #[arg(long)]
+        to: Option<String>,
     },
 }
 
@@ -593,6 +1415,12 @@ pub enum NetworkCommands {
         #[arg(long)]
         json: bool,
     },
+    /// Show the resolved network, its passphrase and default RPC URL
+    Info {
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 /// Sub-commands for the `release-notes` group
@@ -874,19 +1702,79 @@ pub enum MultisigCommands {
         signature_data: Option<String>,
     },
 
+    /// Sign every pending proposal that is awaiting your signature
+    SignAll {
+        #[arg(long)]
+        signer: String,
+        /// Maximum number of pending proposals to consider
+        #[arg(long, default_value = "100")]
+        limit: usize,
+        /// Skip the batch confirmation prompt
+        #[arg(long, short)]
+        yes: bool,
+        /// Print timing statistics (latency distribution, effective
+        /// concurrency, throughput) when done
+        #[arg(long)]
+        concurrency_report: bool,
+    },
+
     /// Execute an approved deployment proposal
     Execute { proposal_id: String },
 
+    /// Cancel a proposal that hasn't been executed (proposer or policy admin only)
+    CancelProposal {
+        proposal_id: String,
+        /// Address of the proposer or policy admin cancelling it
+        #[arg(long)]
+        canceller: String,
+    },
+
     /// Show full info for a proposal (signatures, policy, status)
     Info { proposal_id: String },
 
     /// List deployment proposals
     ListProposals {
+        /// Only show proposals with this status (pending, approved, executed,
+        /// expired, rejected or cancelled)
         #[arg(long)]
         status: Option<String>,
         #[arg(long, default_value = "20")]
         limit: usize,
     },
+
+    /// Export a policy and its proposals (with signatures) to a portable file
+    Export {
+        policy_id: String,
+        /// File to write the snapshot to
+        #[arg(long, short)]
+        output: String,
+    },
+
+    /// Recreate an exported policy and its open proposals on this registry
+    Import {
+        /// Snapshot written by `multisig export`
+        input: String,
+    },
+
+    /// Write a proposal and the exact bytes to sign to a file, for signers
+    /// without registry access (hardware wallets, air-gapped machines)
+    ExportProposal {
+        #[arg(long)]
+        proposal_id: String,
+        /// File to write the signing payload to
+        #[arg(long, short)]
+        output: String,
+    },
+
+    /// Submit a signature collected offline for a proposal
+    ImportSignature {
+        #[arg(long)]
+        proposal_id: String,
+        /// Signature file (proposal_id, payload_hash, signer_address,
+        /// signature_data)
+        #[arg(long)]
+        file: String,
+    },
 }
 
 /// Sub-commands for the `incident` group
@@ -910,6 +1798,45 @@ pub enum IncidentCommands {
     },
 }
 
+/// Sub-commands for the `history` group
+#[derive(Debug, Subcommand)]
+pub enum HistoryCommands {
+    /// Show command history
+    Show {
+        /// Filter by search term
+        #[arg(long)]
+        search: Option<String>,
+
+        /// Maximum number of entries to show
+        #[arg(long, default_value = "20")]
+        limit: usize,
+
+        /// Summarize the history instead: most used commands, success and
+        /// failure rates, most touched contracts, and activity over time
+        #[arg(long, conflicts_with = "search")]
+        stats: bool,
+    },
+    /// Delete the local history
+    Clear {
+        /// Skip the confirmation prompt
+        #[arg(long, short)]
+        yes: bool,
+    },
+    /// Write the full history, with timestamps and arguments, as JSON
+    Export {
+        /// File to write
+        #[arg(long, short = 'o')]
+        output: std::path::PathBuf,
+    },
+}
+
+/// Guided flows for the `wizard` command
+#[derive(Debug, Subcommand)]
+pub enum WizardCommands {
+    /// Design a multi-sig policy step by step and create it
+    MultisigPolicy,
+}
+
 /// Sub-commands for the `patch` group
 #[derive(Debug, Subcommand)]
 pub enum PatchCommands {
@@ -924,6 +1851,23 @@ pub enum PatchCommands {
         #[arg(long, default_value = "100")]
         rollout: u8,
     },
+    /// List published patches, and which of your contracts each affects
+    List {
+        /// Only patches of this severity (critical|high|medium|low)
+        #[arg(long)]
+        severity: Option<String>,
+        /// Only patches targeting this version (wasm hash)
+        #[arg(long)]
+        version: Option<String>,
+        /// Check this publisher's contracts (UUID or Stellar address)
+        /// instead of your own: `[defaults] publisher` in the config file,
+        /// else the API token's subject
+        #[arg(long)]
+        publisher: Option<String>,
+        /// Output format: table, json or yaml
+        #[arg(long, short = 'f', value_enum, default_value_t = output::OutputFormat::Table)]
+        format: output::OutputFormat,
+    },
     /// Notify subscribers about a patch
     Notify {
         #[arg(long)]
@@ -935,9 +1879,60 @@ pub enum PatchCommands {
         contract_id: String,
         #[arg(long)]
         patch_id: String,
+        /// Apply even if the contract is outside the patch's rollout cohort,
+        /// or its current build can't be read (the patch then can't be
+        /// rolled back)
+        #[arg(long)]
+        force: bool,
+    },
+    /// Raise a patch's rollout percentage, widening its cohort
+    Promote {
+        #[arg(long)]
+        patch_id: String,
+        /// New rollout percentage (1-100), above the current one
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=100))]
+        rollout: u8,
+    },
+    /// Restore the WASM a contract ran before a patch was applied to it
+    Rollback {
+        #[arg(long)]
+        contract_id: String,
+        #[arg(long)]
+        patch_id: String,
+        /// Show the hash that would be restored without rolling back
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Verify a patch's wasm hash against a downloaded build
+    Verify {
+        #[arg(long)]
+        patch_id: String,
+        /// Download the wasm from this URL instead of the registry
+        #[arg(long)]
+        wasm_url: Option<String>,
+    },
+    /// Preview a phased rollout: contracts patched per stage and the
+    /// estimated completion time
+    SimulateRollout {
+        /// Stages as <percent>%[:<soak>], comma-separated, e.g. 10%:2h,50%:1d,100%
+        #[arg(long)]
+        schedule: String,
+        /// Count the contracts this patch affects
+        #[arg(long, required_unless_present = "contracts")]
+        patch_id: Option<String>,
+        /// Number of affected contracts, instead of counting them for --patch-id
+        #[arg(long, conflicts_with = "patch_id")]
+        contracts: Option<usize>,
+        /// Output the projection as JSON
+        #[arg(long)]
+        json: bool,
     },
     /// Manage contract dependencies
     Deps {
+        /// Maximum dependency depth to traverse before treating the graph as malformed
+        #[arg(long, global = true, default_value_t = deps::DEFAULT_MAX_DEPTH)]
+        max_depth: usize,
+
         #[command(subcommand)]
         command: DepsCommands,
     },
@@ -950,6 +1945,25 @@ pub enum DepsCommands {
         /// Contract ID
         contract_id: String,
     },
+    /// Find dependencies whose version requirements cannot all be satisfied
+    Conflicts {
+        /// Contract ID
+        contract_id: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Report the licenses of the whole dependency tree, failing on any not
+    /// in the `[licenses] allowed` list of the config file
+    Licenses {
+        /// Contract ID
+        contract_id: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -1020,7 +2034,8 @@ pub enum WebhookCommands {
 
         /// Comma-separated list of events to subscribe to.
         /// Valid: contract.published, contract.verified,
-        ///        contract.failed_verification, version.created
+        ///        contract.failed_verification, version.created,
+        ///        contract.deprecated
         #[arg(long)]
         events: String,
 
@@ -1096,8 +2111,39 @@ pub enum MigrateCommands {
     Validate { old_id: String, new_id: String },
     /// Apply migration and record history
     Apply { old_id: String, new_id: String },
-    /// Rollback a migration by migration ID
+    /// Undo a local `migrate apply` by migration ID, restoring its
+    /// snapshots (to re-deploy a contract's previous wasm, use
+    /// `migrate run --rollback`)
     Rollback { migration_id: String },
+    /// Upgrade a registered contract to a new WASM, optionally as a canary
+    Run {
+        /// Contract ID
+        contract_id: String,
+        /// Path to the new contract WASM
+        #[arg(required_unless_present = "rollback")]
+        wasm_path: Option<String>,
+        /// Route only this percentage of resolutions to the new version
+        /// (finish with `migrate promote`)
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..100))]
+        canary: Option<u8>,
+        /// Re-deploy the WASM of the version before the current one instead
+        /// of a new build, and record it in `migrate history`
+        #[arg(long, conflicts_with_all = ["wasm_path", "canary", "require_tests"])]
+        rollback: bool,
+        /// Validate locally without contacting the registry; with
+        /// --rollback, only look up and print the revert target
+        #[arg(long)]
+        dry_run: bool,
+        /// Force the mocked migration step to fail
+        #[arg(long, hide = true)]
+        simulate_fail: bool,
+        /// Run this test scenario against the new WASM first and abort the
+        /// migration unless it passes
+        #[arg(long, value_name = "TEST_FILE")]
+        require_tests: Option<String>,
+    },
+    /// Complete a canary rollout started by `migrate run --canary`
+    Promote { canary_id: String },
     /// Show migration history
     History {
         #[arg(long, default_value = "20")]
@@ -1106,7 +2152,7 @@ pub enum MigrateCommands {
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> Result<std::process::ExitCode> {
     let cli = Cli::parse();
 
     // ── Initialise logger ─────────────────────────────────────────────────────
@@ -1119,10 +2165,54 @@ async fn main() -> Result<()> {
         .format_module_path(cli.verbose) // show module path only in verbose
         .init();
 
+    // ── Terminal capabilities ─────────────────────────────────────────────────
+    // Dumb terminals (CI logs, bare consoles) get neither colors nor Unicode.
+    let dumb_terminal = table_format::is_dumb_terminal();
+    if cli.no_color || dumb_terminal || std::env::var_os("NO_COLOR").is_some() {
+        colored::control::set_override(false);
+    }
+    table_format::set_ascii(cli.ascii || dumb_terminal);
+    table_format::set_max_field_width(if cli.no_truncate {
+        None
+    } else {
+        cli.max_field_width.or_else(table_format::default_field_width)
+    });
+    output::set_output_path(cli.output_file.as_deref());
+    output::set_sink(cli.sink.as_deref())?;
+    output::set_format(cli.output_format);
+    output::set_extract(cli.extract.as_deref())?;
+    output::set_emit_targets(&cli.emit)?;
+    context::set_resolve_aliases(cli.resolve_aliases);
+    http::set_wait_on_429(!cli.no_wait_on_429);
+
     log::debug!("Verbose mode enabled");
     log::debug!("API URL: {}", cli.api_url);
 
-    handle_command(cli).await
+    let json_errors = reports_errors_as_json(&cli.command);
+    match handle_command(cli).await {
+        Err(err) if json_errors => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(
+                    &serde_json::json!({ "error": format!("{:#}", err) })
+                )?
+            );
+            Ok(std::process::ExitCode::FAILURE)
+        }
+        result => result.map(|()| std::process::ExitCode::SUCCESS),
+    }
+}
+
+/// Commands whose failures are printed on stdout as `{"error": ...}`, so
+/// scripts reading their JSON always get an object back.
+fn reports_errors_as_json(command: &Commands) -> bool {
+    matches!(
+        command,
+        Commands::Info {
+            format: output::OutputFormat::Json,
+            ..
+        }
+    )
 }
 
 pub async fn handle_command(cli: Cli) -> Result<()> {
@@ -1139,11 +2229,101 @@ pub async fn handle_command(cli: Cli) -> Result<()> {
             }
             let network: commands::Network = net_str.parse().unwrap();
             
-            dispatch_command(cli, network, cfg_network).await
+            match mutation_in(&cli.command) {
+                Some(mutation) => {
+                    signals::guard(&mutation, dispatch_command(cli, network, cfg_network)).await
+                }
+                None => dispatch_command(cli, network, cfg_network).await,
+            }
         }
     }
 }
 
+/// The registry mutation `command` performs, if any. These run under
+/// [`signals::guard`] so an interrupt can't cut a write off mid-request.
+fn mutation_in(command: &Commands) -> Option<signals::Mutation> {
+    use signals::Mutation;
+
+    let mutation = match command {
+        Commands::Publish {
+            contract_id,
+            dry_run: false,
+            ..
+        } => Mutation::new(
+            "publish",
+            format!("soroban-registry info {}", contract_id),
+        ),
+        Commands::Migrate { action } => match action {
+            MigrateCommands::Run {
+                contract_id,
+                dry_run: false,
+                ..
+            } => Mutation::new(
+                "migration",
+                format!("soroban-registry info {}", contract_id),
+            ),
+            MigrateCommands::Promote { .. } => Mutation::new(
+                "canary promotion",
+                "soroban-registry migrate history".to_string(),
+            ),
+            _ => return None,
+        },
+        Commands::Import {
+            contract_id: Some(contract_id),
+            republish: true,
+            ..
+        } => Mutation::new(
+            "re-publish",
+            format!("soroban-registry info {}", contract_id),
+        ),
+        Commands::Deprecate { contract_id, .. } => Mutation::new(
+            "deprecation",
+            format!("soroban-registry info {}", contract_id),
+        ),
+        Commands::BatchRegister { dry_run: false, .. } => Mutation::new(
+            "batch registration",
+            "soroban-registry list".to_string(),
+        ),
+        Commands::NormalizeTags { dry_run: false, .. } => Mutation::new(
+            "tag normalization",
+            "soroban-registry normalize-tags --dry-run".to_string(),
+        ),
+        Commands::Patch { action } => match action {
+            PatchCommands::Create { .. } => Mutation::new("patch creation", None),
+            PatchCommands::Promote { .. } => Mutation::new("patch promotion", None),
+            PatchCommands::Apply { contract_id, .. } => Mutation::new(
+                "patch rollout",
+                format!("soroban-registry info {}", contract_id),
+            ),
+            PatchCommands::Rollback {
+                contract_id,
+                dry_run: false,
+                ..
+            } => Mutation::new(
+                "patch rollback",
+                format!("soroban-registry info {}", contract_id),
+            ),
+            _ => return None,
+        },
+        Commands::Multisig { action } => match action {
+            MultisigCommands::CreatePolicy { .. } => Mutation::new("policy creation", None),
+            MultisigCommands::CreateProposal { .. }
+            | MultisigCommands::Sign { .. }
+            | MultisigCommands::SignAll { .. }
+            | MultisigCommands::Execute { .. }
+            | MultisigCommands::CancelProposal { .. }
+            | MultisigCommands::ImportSignature { .. }
+            | MultisigCommands::Import { .. } => Mutation::new(
+                "multisig update",
+                "soroban-registry multisig list-proposals".to_string(),
+            ),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(mutation)
+}
+
 pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network: crate::config::Network) -> Result<()> {
     log::debug!("Network: {:?}", network);
 
@@ -1160,18 +2340,52 @@ pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network:
             network: filter_networks,
             category,
             limit,
+            page,
             offset,
             json,
+            format,
+            show_score,
+            filter,
+            audited_only,
+            near,
+            publisher,
+            fields,
+            fields_from_file,
+            with_abi,
+            min_invocations,
+            active_since,
+            sort,
+            order,
         } => {
+            if let Some(reference) = near {
+                log::debug!("Command: search --near | reference={}", reference);
+                recommend::near(
+                    &cli.api_url,
+                    &reference,
+                    verified_only,
+                    category.as_deref(),
+                    limit,
+                    json,
+                )
+                .await?;
+                return Ok(());
+            }
+            let query = query.unwrap_or_default();
+            let offset = pagination::offset(page, offset, limit)?;
+            let fields =
+                output::Projection::from_args(fields.as_deref(), fields_from_file.as_deref())?;
             let networks_vec: Vec<String> = filter_networks
                 .map(|n| n.split(',').map(|s| s.trim().to_string()).collect())
                 .unwrap_or_default();
             log::debug!(
-                "Command: search | query={:?} verified_only={} networks={:?} category={:?}",
+                "Command: search | query={:?} verified_only={} networks={:?} category={:?} publisher={:?} sort={:?} order={:?}",
                 query,
                 verified_only,
                 networks_vec,
-                category
+                category,
+                publisher,
+                sort,
+                order
             );
             commands::search(
                 &cli.api_url,
@@ -1183,26 +2397,59 @@ pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network:
                 limit,
                 offset,
                 json,
+                show_score,
+                filter.as_deref(),
+                audited_only,
+                publisher.as_deref(),
+                fields.as_ref(),
+                with_abi,
+                activity::ActivityFilter::new(min_invocations, active_since).as_ref(),
+                sort,
+                order,
+                format,
             )
             .await?;
         }
+        Commands::Publisher { account, json } => {
+            log::debug!("Command: publisher | account={}", account);
+            publisher::portfolio(&cli.api_url, &account, json).await?;
+        }
         Commands::Info {
             contract_id,
             format,
             highlight_method,
+            compact,
+            wide,
+            fields,
+            fields_from_file,
+            check_chain,
         } => {
+            let fields =
+                output::Projection::from_args(fields.as_deref(), fields_from_file.as_deref())?;
+            let display = if compact {
+                commands::InfoDisplay::Compact
+            } else if wide {
+                commands::InfoDisplay::Wide
+            } else {
+                commands::InfoDisplay::Standard
+            };
             log::debug!(
-                "Command: info | contract_id={} format={} highlight={:?}",
+                "Command: info | contract_id={} format={:?} highlight={:?} display={:?} check_chain={}",
                 contract_id,
                 format,
-                highlight_method
+                highlight_method,
+                display,
+                check_chain
             );
             commands::info(
                 &cli.api_url,
                 &contract_id,
-                &format,
+                format,
                 highlight_method.as_deref(),
                 cfg_network,
+                display,
+                fields.as_ref(),
+                check_chain,
             )
             .await?;
         }
@@ -1214,42 +2461,111 @@ pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network:
             category,
             tags,
             publisher,
+            manifest: manifest_path,
             contract_path,
             test_command,
             require_coverage,
             coverage_threshold,
             skip_tests,
+            strict,
+            audits,
+            meta,
+            preflight,
+            force,
+            optimize,
+            supersedes,
+            forks,
+            related,
+            metadata_schema,
+            dry_run,
+            wasm,
+            expected_hash,
         } => {
-            let tags_vec = tags
-                .map(|t| t.split(',').map(|s| s.trim().to_string()).collect())
-                .unwrap_or_default();
+            let relations = relations::from_publish_args(&supersedes, &forks, &related);
+            let audits = audits
+                .iter()
+                .map(|a| audit::parse(a))
+                .collect::<Result<Vec<_>>>()?;
+            let custom_fields = custom_fields::parse(&meta)?;
+            let flags = manifest::PublishManifest {
+                name,
+                description,
+                category,
+                tags: tags.map(|t| t.split(',').map(|s| s.trim().to_string()).collect()),
+                publisher,
+            };
+            let metadata = match &manifest_path {
+                Some(path) => manifest::PublishManifest::load(path)?.overridden_by(flags),
+                None => flags,
+            }
+            .resolve()?;
             log::debug!(
-                "Command: publish | contract_id={} name={} tags={:?}",
+                "Command: publish | contract_id={} name={} tags={:?} manifest={:?} dry_run={}",
                 contract_id,
-                name,
-                tags_vec
+                metadata.name,
+                metadata.tags,
+                manifest_path,
+                dry_run
             );
             commands::publish(
                 &cli.api_url,
-                &contract_id,
-                &name,
-                description.as_deref(),
-                network,
-                category.as_deref(),
-                tags_vec,
-                &publisher,
-                false,
-                &contract_path,
-                test_command.as_deref(),
-                require_coverage,
-                coverage_threshold,
-                skip_tests,
+                commands::PublishParams {
+                    contract_id: &contract_id,
+                    name: &metadata.name,
+                    description: metadata.description.as_deref(),
+                    network,
+                    category: metadata.category.as_deref(),
+                    tags: metadata.tags,
+                    publisher: &metadata.publisher,
+                    is_cicd: false,
+                    contract_path: &contract_path,
+                    test_command: test_command.as_deref(),
+                    require_coverage,
+                    coverage_threshold,
+                    skip_tests,
+                    strict,
+                    audits: &audits,
+                    custom_fields: &custom_fields,
+                    preflight,
+                    force,
+                    optimize,
+                    relations: &relations,
+                    metadata_schema: metadata_schema.as_deref(),
+                    dry_run,
+                    wasm: wasm.as_deref(),
+                    expected_hash: expected_hash.as_deref(),
+                },
             )
             .await?;
         }
-        Commands::List { limit, json } => {
-            log::debug!("Command: list | limit={}", limit);
-            commands::list(&cli.api_url, limit, network, json).await?;
+        Commands::List {
+            limit,
+            page,
+            offset,
+            json,
+            filter,
+            fields,
+            fields_from_file,
+        } => {
+            let offset = pagination::offset(page, offset, limit)?;
+            log::debug!(
+                "Command: list | limit={} offset={} filter={:?}",
+                limit,
+                offset,
+                filter
+            );
+            let fields =
+                output::Projection::from_args(fields.as_deref(), fields_from_file.as_deref())?;
+            commands::list(
+                &cli.api_url,
+                limit,
+                offset,
+                network,
+                json,
+                filter.as_deref(),
+                fields.as_ref(),
+            )
+            .await?;
         }
         Commands::Dashboard {
             refresh_rate,
@@ -1278,6 +2594,64 @@ pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network:
             log::debug!("Command: breaking-changes | old={} new={}", old_id, new_id);
             commands::breaking_changes(&cli.api_url, &old_id, &new_id, json).await?;
         }
+        Commands::Diff {
+            old_id,
+            new_id,
+            semantic,
+            json,
+        } => {
+            log::debug!(
+                "Command: diff | old={} new={} semantic={}",
+                old_id,
+                new_id,
+                semantic
+            );
+            commands::interface_diff(&cli.api_url, &old_id, &new_id, semantic, json).await?;
+        }
+        Commands::Link {
+            from,
+            relation,
+            to,
+            remove,
+            json,
+        } => {
+            log::debug!(
+                "Command: link | from={} relation={:?} to={} remove={}",
+                from,
+                relation,
+                to,
+                remove
+            );
+            relations::link(&cli.api_url, &from, relation, &to, remove, json).await?;
+        }
+        Commands::Deprecate {
+            contract_id,
+            retire_at,
+            replacement,
+            migration_guide,
+            notes,
+            notify,
+            json,
+        } => {
+            log::debug!(
+                "Command: deprecate | contract_id={} retire_at={} replacement={:?} notify={}",
+                contract_id,
+                retire_at,
+                replacement,
+                notify
+            );
+            commands::deprecate(
+                &cli.api_url,
+                &contract_id,
+                &retire_at,
+                replacement.as_deref(),
+                migration_guide.as_deref(),
+                notes.as_deref(),
+                notify,
+                json,
+            )
+            .await?;
+        }
         Commands::UpgradeAnalyze { old, new, json } => {
             log::debug!("Command: upgrade analyze | old={} new={}", old, new);
             commands::upgrade_analyze(&cli.api_url, &old, &new, json).await?;
@@ -1337,36 +2711,175 @@ pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network:
                 log::debug!("Command: migrate history | limit={}", limit);
                 migration::history(limit)?;
             }
+            MigrateCommands::Run {
+                contract_id,
+                rollback: true,
+                dry_run,
+                simulate_fail,
+                ..
+            } => {
+                log::debug!(
+                    "Command: migrate run --rollback | contract_id={} dry_run={}",
+                    contract_id,
+                    dry_run
+                );
+                commands::migrate_rollback(&cli.api_url, &contract_id, simulate_fail, dry_run)
+                    .await?;
+            }
+            MigrateCommands::Run {
+                contract_id,
+                wasm_path,
+                canary,
+                dry_run,
+                simulate_fail,
+                require_tests,
+                ..
+            } => {
+                let Some(wasm_path) = wasm_path else {
+                    anyhow::bail!("migrate run needs a WASM path or --rollback");
+                };
+                log::debug!(
+                    "Command: migrate run | contract_id={} wasm_path={} canary={:?} require_tests={:?}",
+                    contract_id,
+                    wasm_path,
+                    canary,
+                    require_tests
+                );
+                commands::migrate(
+                    &cli.api_url,
+                    &contract_id,
+                    &wasm_path,
+                    simulate_fail,
+                    dry_run,
+                    canary,
+                    require_tests.as_deref(),
+                )
+                .await?;
+            }
+            MigrateCommands::Promote { canary_id } => {
+                log::debug!("Command: migrate promote | canary_id={}", canary_id);
+                commands::migrate_promote(&cli.api_url, &canary_id).await?;
+            }
         },
+        Commands::VerifyArchives {
+            dir,
+            trusted_keys,
+            json,
+        } => {
+            log::debug!(
+                "Command: verify-archives | dir={} trusted_keys={}",
+                dir,
+                trusted_keys
+            );
+            commands::verify_archives(&dir, &trusted_keys, json)?;
+        }
         Commands::Export {
-            id,
+            ids,
             output,
             contract_dir,
+            format,
+            reproducible,
+            continue_on_error,
+            encrypt,
+            passphrase,
         } => {
-            log::debug!("Command: export | id={} output={}", id, output);
-            commands::export(&cli.api_url, &id, &output, &contract_dir).await?;
+            log::debug!(
+                "Command: export | ids={:?} output={} format={} reproducible={} encrypt={}",
+                ids,
+                output,
+                format,
+                reproducible,
+                encrypt
+            );
+            let passphrase = match (encrypt, passphrase) {
+                (false, _) => None,
+                (true, Some(passphrase)) => Some(passphrase),
+                (true, None) => Some(export::prompt_passphrase(true)?),
+            };
+            commands::export(
+                &cli.api_url,
+                &ids,
+                &output,
+                &contract_dir,
+                &format,
+                reproducible,
+                continue_on_error,
+                passphrase.as_deref(),
+            )
+            .await?;
         }
         Commands::Import {
             archive,
             output_dir,
+            json,
+            contract_id,
+            republish,
+            publisher,
+            passphrase,
+            skip_verify,
+            force,
+            merge,
         } => {
             log::debug!(
-                "Command: import | archive={} output_dir={}",
+                "Command: import | archive={} output_dir={} json={} contract_id={:?} republish={} skip_verify={} force={} merge={}",
                 archive,
-                output_dir
+                output_dir,
+                json,
+                contract_id,
+                republish,
+                skip_verify,
+                force,
+                merge
+            );
+            let conflict = if force {
+                io_utils::Conflict::Overwrite
+            } else if merge {
+                io_utils::Conflict::Merge
+            } else {
+                io_utils::Conflict::Refuse
+            };
+            commands::import(
+                &cli.api_url,
+                &archive,
+                network,
+                &output_dir,
+                json,
+                contract_id.as_deref(),
+                publisher.as_deref().filter(|_| republish),
+                passphrase.as_deref(),
+                skip_verify,
+                conflict,
+            )
+            .await?;
+        }
+        Commands::ImportMetadata {
+            source,
+            format,
+            output,
+            json,
+        } => {
+            log::debug!(
+                "Command: import-metadata | source={} format={:?} output={:?}",
+                source,
+                format,
+                output
             );
-            commands::import(&cli.api_url, &archive, network, &output_dir).await?;
+            import::import_metadata(&source, format, output.as_deref(), json)?;
         }
         Commands::Doc {
             contract_path,
+            spec,
             output,
+            format,
         } => {
             log::debug!(
-                "Command: doc | contract_path={} output={}",
+                "Command: doc | contract_path={:?} spec={:?} output={} format={:?}",
                 contract_path,
-                output
+                spec,
+                output,
+                format
             );
-            commands::doc(&contract_path, &output)?;
+            commands::doc(contract_path.as_deref(), spec.as_deref(), &output, format)?;
         }
         Commands::Openapi {
             contract_path,
@@ -1381,14 +2894,66 @@ pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network:
             );
             commands::openapi(&contract_path, &output, &format)?;
         }
-        Commands::Wizard {} => {
-            log::debug!("Command: wizard");
-            wizard::run(&cli.api_url).await?;
-        }
-        Commands::History { search, limit } => {
-            log::debug!("Command: history | search={:?} limit={}", search, limit);
-            wizard::show_history(search.as_deref(), limit)?;
-        }
+        Commands::Wizard {
+            flow,
+            resume,
+            answers,
+            non_interactive,
+            dump_answers,
+        } => match flow {
+            Some(WizardCommands::MultisigPolicy) => {
+                log::debug!("Command: wizard multisig-policy");
+                wizard::multisig_policy(&cli.api_url).await?;
+            }
+            None => {
+                log::debug!(
+                    "Command: wizard | resume={} answers={:?} non_interactive={} dump_answers={:?}",
+                    resume,
+                    answers,
+                    non_interactive,
+                    dump_answers
+                );
+                wizard::run(
+                    &cli.api_url,
+                    resume,
+                    answers.as_deref(),
+                    non_interactive,
+                    dump_answers.as_deref(),
+                )
+                .await?;
+            }
+        },
+        Commands::History { action } => match action.unwrap_or(HistoryCommands::Show {
+            search: None,
+            limit: 20,
+            stats: false,
+        }) {
+            HistoryCommands::Show {
+                search,
+                limit,
+                stats,
+            } => {
+                log::debug!(
+                    "Command: history show | search={:?} limit={} stats={}",
+                    search,
+                    limit,
+                    stats
+                );
+                if stats {
+                    wizard::show_history_stats()?;
+                } else {
+                    wizard::show_history(search.as_deref(), limit)?;
+                }
+            }
+            HistoryCommands::Clear { yes } => {
+                log::debug!("Command: history clear | yes={}", yes);
+                wizard::clear_history(yes)?;
+            }
+            HistoryCommands::Export { output } => {
+                log::debug!("Command: history export | output={}", output.display());
+                wizard::export_history(&output)?;
+            }
+        },
         Commands::Incident { action } => match action {
             IncidentCommands::Trigger {
                 contract_id,
@@ -1425,24 +2990,108 @@ pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network:
                 );
                 commands::patch_create(&cli.api_url, &version, &hash, sev, rollout).await?;
             }
+            PatchCommands::List {
+                severity,
+                version,
+                publisher,
+                format,
+            } => {
+                let severity = severity.map(|s| s.parse::<Severity>()).transpose()?;
+                log::debug!(
+                    "Command: patch list | severity={:?} version={:?} publisher={:?} format={:?}",
+                    severity,
+                    version,
+                    publisher,
+                    format
+                );
+                commands::patch_list(
+                    &cli.api_url,
+                    severity,
+                    version.as_deref(),
+                    publisher.as_deref(),
+                    format,
+                )
+                .await?;
+            }
             PatchCommands::Notify { patch_id } => {
                 log::debug!("Command: patch notify | patch_id={}", patch_id);
                 commands::patch_notify(&cli.api_url, &patch_id).await?;
             }
+            PatchCommands::Verify { patch_id, wasm_url } => {
+                log::debug!(
+                    "Command: patch verify | patch_id={} wasm_url={:?}",
+                    patch_id,
+                    wasm_url
+                );
+                commands::patch_verify(&cli.api_url, &patch_id, wasm_url.as_deref()).await?;
+            }
             PatchCommands::Apply {
                 contract_id,
                 patch_id,
+                force,
+            } => {
+                log::debug!(
+                    "Command: patch apply | contract_id={} patch_id={} force={}",
+                    contract_id,
+                    patch_id,
+                    force
+                );
+                commands::patch_apply(&cli.api_url, &contract_id, &patch_id, force).await?;
+            }
+            PatchCommands::Promote { patch_id, rollout } => {
+                log::debug!(
+                    "Command: patch promote | patch_id={} rollout={}",
+                    patch_id,
+                    rollout
+                );
+                commands::patch_promote(&cli.api_url, &patch_id, rollout).await?;
+            }
+            PatchCommands::Rollback {
+                contract_id,
+                patch_id,
+                dry_run,
             } => {
                 log::debug!(
-                    "Command: patch apply | contract_id={} patch_id={}",
+                    "Command: patch rollback | contract_id={} patch_id={} dry_run={}",
                     contract_id,
-                    patch_id
+                    patch_id,
+                    dry_run
                 );
-                commands::patch_apply(&cli.api_url, &contract_id, &patch_id).await?;
+                commands::patch_rollback(&cli.api_url, &contract_id, &patch_id, dry_run).await?;
             }
-            PatchCommands::Deps { command } => match command {
+            PatchCommands::SimulateRollout {
+                schedule,
+                patch_id,
+                contracts,
+                json,
+            } => {
+                log::debug!(
+                    "Command: patch simulate-rollout | schedule={} patch_id={:?} contracts={:?}",
+                    schedule,
+                    patch_id,
+                    contracts
+                );
+                let schedule = schedule.parse::<patch::RolloutSchedule>()?;
+                commands::patch_simulate_rollout(
+                    &cli.api_url,
+                    &schedule,
+                    patch_id.as_deref(),
+                    contracts,
+                    json,
+                )
+                .await?;
+            }
+            PatchCommands::Deps { max_depth, command } => match command {
                 DepsCommands::List { contract_id } => {
-                    commands::deps_list(&cli.api_url, &contract_id).await?;
+                    commands::deps_list(&cli.api_url, &contract_id, max_depth).await?;
+                }
+                DepsCommands::Conflicts { contract_id, json } => {
+                    log::debug!("Command: deps conflicts | contract_id={}", contract_id);
+                    deps::conflicts(&cli.api_url, &contract_id, max_depth, json).await?;
+                }
+                DepsCommands::Licenses { contract_id, json } => {
+                    log::debug!("Command: deps licenses | contract_id={}", contract_id);
+                    deps::licenses(&cli.api_url, &contract_id, max_depth, json).await?;
                 }
             },
         },
@@ -1505,11 +3154,35 @@ pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network:
                 signature_data,
             } => {
                 log::debug!("Command: multisig sign | proposal_id={}", proposal_id);
+                let key = cli.signer.as_deref().map(signing::from_spec).transpose()?;
                 multisig::sign_proposal(
                     &cli.api_url,
                     &proposal_id,
                     &signer,
                     signature_data.as_deref(),
+                    key.as_deref(),
+                )
+                .await?;
+            }
+            MultisigCommands::SignAll {
+                signer,
+                limit,
+                yes,
+                concurrency_report,
+            } => {
+                log::debug!(
+                    "Command: multisig sign-all | signer={} limit={}",
+                    signer,
+                    limit
+                );
+                let key = cli.signer.as_deref().map(signing::from_spec).transpose()?;
+                multisig::sign_all(
+                    &cli.api_url,
+                    &signer,
+                    limit,
+                    yes,
+                    key.as_deref(),
+                    concurrency_report,
                 )
                 .await?;
             }
@@ -1517,6 +3190,17 @@ pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network:
                 log::debug!("Command: multisig execute | proposal_id={}", proposal_id);
                 multisig::execute_proposal(&cli.api_url, &proposal_id).await?;
             }
+            MultisigCommands::CancelProposal {
+                proposal_id,
+                canceller,
+            } => {
+                log::debug!(
+                    "Command: multisig cancel-proposal | proposal_id={} canceller={}",
+                    proposal_id,
+                    canceller
+                );
+                multisig::cancel_proposal(&cli.api_url, &proposal_id, &canceller).await?;
+            }
             MultisigCommands::Info { proposal_id } => {
                 log::debug!("Command: multisig info | proposal_id={}", proposal_id);
                 multisig::proposal_info(&cli.api_url, &proposal_id).await?;
@@ -1529,6 +3213,37 @@ pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network:
                 );
                 multisig::list_proposals(&cli.api_url, status.as_deref(), limit).await?;
             }
+            MultisigCommands::Export { policy_id, output } => {
+                log::debug!(
+                    "Command: multisig export | policy_id={} output={}",
+                    policy_id,
+                    output
+                );
+                multisig::export_policy(&cli.api_url, &policy_id, &output).await?;
+            }
+            MultisigCommands::Import { input } => {
+                log::debug!("Command: multisig import | input={}", input);
+                multisig::import_policy(&cli.api_url, &input).await?;
+            }
+            MultisigCommands::ExportProposal {
+                proposal_id,
+                output,
+            } => {
+                log::debug!(
+                    "Command: multisig export-proposal | proposal_id={} output={}",
+                    proposal_id,
+                    output
+                );
+                multisig::export_proposal(&cli.api_url, &proposal_id, &output).await?;
+            }
+            MultisigCommands::ImportSignature { proposal_id, file } => {
+                log::debug!(
+                    "Command: multisig import-signature | proposal_id={} file={}",
+                    proposal_id,
+                    file
+                );
+                multisig::import_signature(&cli.api_url, &proposal_id, &file).await?;
+            }
         },
         Commands::Fuzz {
             contract_path,
@@ -1576,6 +3291,55 @@ pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network:
                 recommendations,
             )?;
         }
+        Commands::Disasm {
+            contract_path,
+            function,
+            output,
+        } => {
+            log::debug!(
+                "Command: disasm | contract_path={} function={:?} output={:?}",
+                contract_path,
+                function,
+                output
+            );
+            wasm::disasm(&contract_path, function.as_deref(), output.as_deref())?;
+        }
+        Commands::Optimize { wasm, output, json } => {
+            log::debug!(
+                "Command: optimize | wasm={} output={:?} json={}",
+                wasm,
+                output,
+                json
+            );
+            wasm::optimize_file(&wasm, output.as_deref(), json)?;
+        }
+        Commands::Capabilities {
+            contract_path,
+            json,
+        } => {
+            log::debug!(
+                "Command: capabilities | contract_path={} json={}",
+                contract_path,
+                json
+            );
+            wasm::capabilities(&contract_path, json)?;
+        }
+        Commands::Invoke {
+            contract_id,
+            method,
+            args,
+            simulate,
+            json,
+        } => {
+            log::debug!(
+                "Command: invoke | contract_id={} method={} args={:?} simulate={}",
+                contract_id,
+                method,
+                args,
+                simulate
+            );
+            invoke::invoke(&cli.api_url, &contract_id, &method, &args, simulate, json).await?;
+        }
         Commands::Test {
             test_file,
             contract_path,
@@ -1585,25 +3349,80 @@ pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network:
             verbose,
             require_coverage,
             coverage_threshold,
+            watch,
+            seed,
+            coverage_baseline,
+            dry_run,
+            no_cache,
         } => {
-            if let Some(test_file) = test_file {
-                commands::run_tests(
-                    &test_file,
-                    contract_path.as_deref(),
-                    junit.as_deref(),
-                    coverage,
-                    verbose,
-                )
-                .await?;
+            // Resolved once so every --watch re-run uses the same seed.
+            let seed = test_framework::resolve_seed(seed);
+            if dry_run {
+                let test_file = test_file.as_deref().unwrap_or_default();
+                log::debug!(
+                    "Command: test --dry-run | test_file={} contract_path={:?}",
+                    test_file,
+                    contract_path
+                );
+                commands::plan_tests(test_file, contract_path.as_deref(), seed)?;
+                return Ok(());
+            }
+            let coverage = if watch {
+                require_coverage || coverage_threshold > 0.0
             } else {
-                commands::run_contract_tests(
+                coverage
+            };
+            let run_once = || {
+                let test_file = test_file.clone();
+                let contract_path = contract_path.clone();
+                let test_command = test_command.clone();
+                let junit = junit.clone();
+                let coverage_baseline = coverage_baseline.clone();
+                async move {
+                    if let Some(test_file) = test_file {
+                        commands::run_tests(
+                            &test_file,
+                            contract_path.as_deref(),
+                            junit.as_deref(),
+                            coverage,
+                            verbose,
+                            seed,
+                            coverage_baseline.as_deref(),
+                            !no_cache,
+                        )
+                        .await
+                    } else {
+                        commands::run_contract_tests(
+                            contract_path.as_deref().unwrap_or("."),
+                            test_command.as_deref(),
+                            require_coverage,
+                            coverage_threshold,
+                            coverage,
+                            seed,
+                        )
+                        .await
+                    }
+                }
+            };
+
+            if watch {
+                if test_file.as_deref().is_some_and(io_utils::is_stdin) {
+                    anyhow::bail!("--watch cannot re-read a test file from stdin");
+                }
+                log::debug!(
+                    "Command: test --watch | contract_path={:?} test_file={:?}",
+                    contract_path,
+                    test_file
+                );
+                let mut paths = vec![std::path::PathBuf::from(
                     contract_path.as_deref().unwrap_or("."),
-                    test_command.as_deref(),
-                    require_coverage,
-                    coverage_threshold,
-                    coverage,
-                )
-                .await?;
+                )];
+                if let Some(ref f) = test_file {
+                    paths.push(std::path::PathBuf::from(f));
+                }
+                watch::run(paths, run_once).await?;
+            } else {
+                run_once().await?;
             }
         }
         Commands::Sla { action } => match action {
@@ -1725,20 +3544,27 @@ pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network:
             contract_id,
             version,
             signature,
+            source,
         } => {
             log::debug!(
-                "Command: verify | package={} contract_id={}",
+                "Command: verify | package={:?} contract_id={} source={:?}",
                 package,
-                contract_id
+                contract_id,
+                source
             );
-            package_signing::verify_package(
-                &cli.api_url,
-                &package,
-                &contract_id,
-                version.as_deref(),
-                signature.as_deref(),
-            )
-            .await?;
+            if let Some(package) = package {
+                package_signing::verify_package(
+                    &cli.api_url,
+                    &package,
+                    &contract_id,
+                    version.as_deref(),
+                    signature.as_deref(),
+                )
+                .await?;
+            }
+            if let Some(source) = source {
+                provenance::attest(&cli.api_url, &contract_id, &source).await?;
+            }
         }
         Commands::VerifyContract {
             wasm_path,
@@ -1871,6 +3697,55 @@ pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network:
                 contract_verify::run(&cli.api_url, &address, &network, json).await?;
             }
         },
+        Commands::CompareOnchain {
+            contract_id,
+            keys,
+            json,
+        } => {
+            let keys_vec: Vec<String> = keys
+                .map(|k| k.split(',').map(|s| s.trim().to_string()).collect())
+                .unwrap_or_default();
+            log::debug!(
+                "Command: compare-onchain | contract_id={} keys={:?}",
+                contract_id,
+                keys_vec
+            );
+            onchain::compare(&cli.api_url, &contract_id, keys_vec, json).await?;
+        }
+        Commands::Health {
+            contract_id,
+            method,
+            json,
+        } => {
+            log::debug!(
+                "Command: health | contract_id={} method={:?}",
+                contract_id,
+                method
+            );
+            health::run(&cli.api_url, &contract_id, method, json).await?;
+        }
+        Commands::Ttl {
+            contract_id,
+            contract_ids_file,
+            keys,
+            warn_below,
+            json,
+        } => {
+            let contract_ids = match contract_ids_file {
+                Some(path) => health::read_contract_ids_file(&path)?,
+                None => contract_id.into_iter().collect(),
+            };
+            let keys_vec: Vec<String> = keys
+                .map(|k| k.split(',').map(|s| s.trim().to_string()).collect())
+                .unwrap_or_default();
+            log::debug!(
+                "Command: ttl | contracts={} keys={:?} warn_below={:?}",
+                contract_ids.len(),
+                keys_vec,
+                warn_below
+            );
+            health::ttl(&cli.api_url, contract_ids, keys_vec, warn_below, json).await?;
+        }
         // ── Release Notes commands ───────────────────────────────────────────
         Commands::ReleaseNotes { action } => match action {
             ReleaseNotesCommands::Generate {
@@ -1987,11 +3862,29 @@ pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network:
         },
 
         // ── Network commands (issue #523) ────────────────────────────────────
+        Commands::Template { action } => match action {
+            TemplateCommands::Diff { project_dir, json } => {
+                log::debug!("Command: template diff | project_dir={}", project_dir);
+                template::diff(&cli.api_url, &project_dir, json).await?;
+            }
+            TemplateCommands::Update { project_dir, to } => {
+                log::debug!(
+                    "Command: template update | project_dir={} to={:?}",
+                    project_dir,
+                    to
+                );
+                commands::template_update(&cli.api_url, &project_dir, to.as_deref()).await?;
+            }
+        },
         Commands::Network { action } => match action {
             NetworkCommands::Status { json } => {
                 log::debug!("Command: network status");
                 network::status(json).await?;
             }
+            NetworkCommands::Info { json } => {
+                log::debug!("Command: network info | network={}", cfg_network);
+                network::info(cfg_network, json)?;
+            }
         },
 
         // ── Advanced contract analysis (issue #530) ─────────────────────────
@@ -2023,12 +3916,15 @@ pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network:
             publisher,
             dry_run,
             json,
+            concurrency,
+            concurrency_report,
         } => {
             log::debug!(
-                "Command: batch-register | manifest={} dry_run={} publisher={:?}",
+                "Command: batch-register | manifest={} dry_run={} publisher={:?} concurrency={}",
                 manifest,
                 dry_run,
-                publisher
+                publisher,
+                concurrency
             );
             batch_register::run_batch_register(
                 &cli.api_url,
@@ -2036,9 +3932,119 @@ pub async fn dispatch_command(cli: Cli, network: commands::Network, cfg_network:
                 publisher.as_deref(),
                 dry_run,
                 json,
+                concurrency,
+                concurrency_report,
+            )
+            .await?;
+        }
+        Commands::RequestVerification {
+            contract_id,
+            source_repo,
+            build_command,
+            commit,
+            compiler_version,
+            json,
+        } => {
+            log::debug!(
+                "Command: request-verification | contract_id={} source_repo={} commit={:?}",
+                contract_id,
+                source_repo,
+                commit
+            );
+            commands::request_verification(
+                &cli.api_url,
+                &contract_id,
+                &source_repo,
+                &build_command,
+                commit.as_deref(),
+                &compiler_version,
+                json,
             )
             .await?;
         }
+        Commands::VerificationStatus { contract_id, json } => {
+            log::debug!("Command: verification-status | contract_id={}", contract_id);
+            commands::verification_status(&cli.api_url, &contract_id, json).await?;
+        }
+        Commands::Changelog {
+            contract_id,
+            output,
+            since_version,
+        } => {
+            log::debug!(
+                "Command: changelog | contract_id={} output={} since_version={:?}",
+                contract_id,
+                output,
+                since_version
+            );
+            changelog::generate(&cli.api_url, &contract_id, &output, since_version.as_deref())
+                .await?;
+        }
+        Commands::NormalizeTags { dry_run, yes, json } => {
+            log::debug!("Command: normalize-tags | dry_run={} yes={}", dry_run, yes);
+            tags::normalize(&cli.api_url, dry_run, yes, json).await?;
+        }
+        Commands::Watchlist { action } => match action {
+            WatchlistCommands::Add { contract_id } => {
+                log::debug!("Command: watchlist add | contract_id={}", contract_id);
+                watchlist::add(&contract_id)?;
+            }
+            WatchlistCommands::Remove { contract_id } => {
+                log::debug!("Command: watchlist remove | contract_id={}", contract_id);
+                watchlist::remove(&contract_id)?;
+            }
+            WatchlistCommands::List { json } => {
+                log::debug!("Command: watchlist list");
+                watchlist::list(json)?;
+            }
+            WatchlistCommands::Status { json } => {
+                log::debug!("Command: watchlist status");
+                watchlist::status(&cli.api_url, json).await?;
+            }
+        },
+        Commands::TagGraph { output } => {
+            log::debug!("Command: tag-graph | output={:?}", output);
+            analytics::tag_graph(&cli.api_url, output.as_deref()).await?;
+        }
+        Commands::Raw { method, path, body } => {
+            log::debug!("Command: raw | method={} path={}", method, path);
+            raw::run(&cli.api_url, &method, &path, body.as_deref()).await?;
+        }
+        Commands::Conforms {
+            contract_path,
+            standard,
+            standard_file,
+            json,
+        } => {
+            log::debug!(
+                "Command: conforms | contract_path={} standard={:?} standard_file={:?}",
+                contract_path,
+                standard,
+                standard_file
+            );
+            standards::conforms(
+                &contract_path,
+                standard.as_deref(),
+                standard_file.as_deref(),
+                json,
+            )?;
+        }
+        Commands::Lint {
+            contract_path,
+            deny,
+            json,
+        } => {
+            log::debug!("Command: lint | contract_path={} deny={:?}", contract_path, deny);
+            lint::run(&contract_path, &deny, json)?;
+        }
+        Commands::Context { json } => {
+            log::debug!("Command: context | json={}", json);
+            context::show(&cli.api_url, cli.network.as_deref(), cfg_network, json)?;
+        }
+        Commands::JsonSchema { command } => {
+            log::debug!("Command: json-schema | command={:?}", command);
+            json_schema::print(command)?;
+        }
     }
 
     Ok(())
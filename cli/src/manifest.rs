@@ -1,5 +1,8 @@
 #![allow(dead_code)]
 
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -60,6 +63,12 @@ impl ExportManifest {
         }
     }
 
+    /// Bytes an archive signature covers: the contract and the digest of
+    /// the packaged contents.
+    pub fn signing_message(&self) -> Vec<u8> {
+        format!("{}:{}", self.contract_id, self.sha256).into_bytes()
+    }
+
     pub fn with_signature(mut self, sig_info: PackageSignatureInfo) -> Self {
         self.signature = Some(sig_info);
         self.audit_trail.push(AuditEntry {
@@ -70,3 +79,150 @@ impl ExportManifest {
         self
     }
 }
+
+/// Contract metadata for `publish --manifest`, read from TOML or JSON
+/// (by the `.json` extension), or from stdin for `-`. Flags given on the
+/// command line win.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PublishManifest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub publisher: Option<String>,
+}
+
+/// Metadata `publish` submits once the manifest and flags are merged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishMetadata {
+    pub name: String,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+    pub publisher: String,
+}
+
+impl PublishManifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let path_str = path.to_string_lossy();
+        let content = crate::io_utils::read_input_to_string(&path_str)
+            .with_context(|| format!("Failed to read publish manifest {}", path.display()))?;
+        if crate::io_utils::is_stdin(&path_str) {
+            return Self::parse_stdin(&content);
+        }
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let manifest = if is_json {
+            serde_json::from_str(&content).map_err(anyhow::Error::from)
+        } else {
+            toml::from_str(&content).map_err(anyhow::Error::from)
+        };
+        manifest.with_context(|| format!("Invalid publish manifest {}", path.display()))
+    }
+
+    /// stdin has no extension to go by: YAML (which covers JSON) is tried
+    /// first, as for `batch-register`, then TOML.
+    fn parse_stdin(content: &str) -> Result<Self> {
+        serde_yaml::from_str(content)
+            .or_else(|_| toml::from_str(content))
+            .context("Invalid publish manifest on stdin (expected YAML, JSON or TOML)")
+    }
+
+    /// Fields set in `flags` replace the manifest's.
+    pub fn overridden_by(self, flags: PublishManifest) -> Self {
+        PublishManifest {
+            name: flags.name.or(self.name),
+            description: flags.description.or(self.description),
+            category: flags.category.or(self.category),
+            tags: flags.tags.or(self.tags),
+            publisher: flags.publisher.or(self.publisher),
+        }
+    }
+
+    /// Fails listing every required field that is still unset.
+    pub fn resolve(self) -> Result<PublishMetadata> {
+        let present = |v: &Option<String>| v.as_deref().is_some_and(|s| !s.trim().is_empty());
+        let missing: Vec<&str> = [("name", &self.name), ("publisher", &self.publisher)]
+            .into_iter()
+            .filter(|(_, v)| !present(v))
+            .map(|(field, _)| field)
+            .collect();
+        if !missing.is_empty() {
+            bail!(
+                "missing required publish field(s): {}; pass {} or set {} in the --manifest file",
+                missing.join(", "),
+                missing
+                    .iter()
+                    .map(|f| format!("--{}", f))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                if missing.len() == 1 { "it" } else { "them" }
+            );
+        }
+        Ok(PublishMetadata {
+            name: self.name.unwrap_or_default(),
+            description: self.description,
+            category: self.category,
+            tags: self
+                .tags
+                .unwrap_or_default()
+                .into_iter()
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect(),
+            publisher: self.publisher.unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_override_the_publish_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let toml_path = dir.path().join("publish.toml");
+        std::fs::write(
+            &toml_path,
+            "name = \"vault\"\ncategory = \"defi\"\ntags = [\"yield\", \" amm \"]\n",
+        )
+        .unwrap();
+        let manifest = PublishManifest::load(&toml_path).unwrap();
+        assert_eq!(manifest.name.as_deref(), Some("vault"));
+
+        let err = manifest.clone().resolve().unwrap_err().to_string();
+        assert!(err.contains("missing required publish field(s): publisher;"));
+
+        let flags = PublishManifest {
+            name: Some("vault-v2".into()),
+            publisher: Some("GABC".into()),
+            ..Default::default()
+        };
+        let merged = manifest.overridden_by(flags).resolve().unwrap();
+        assert_eq!(merged.name, "vault-v2");
+        assert_eq!(merged.category.as_deref(), Some("defi"));
+        assert_eq!(merged.tags, ["yield", "amm"]);
+
+        let json_path = dir.path().join("publish.json");
+        std::fs::write(&json_path, r#"{"publisher": "GABC", "license": "MIT"}"#).unwrap();
+        assert!(PublishManifest::load(&json_path).is_err());
+        std::fs::write(&json_path, r#"{"publisher": "GABC"}"#).unwrap();
+        let err = PublishManifest::load(&json_path)
+            .unwrap()
+            .resolve()
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("missing required publish field(s): name;"));
+
+        let piped = PublishManifest::parse_stdin("name: vault\ntags: [yield]\n").unwrap();
+        assert_eq!(piped.tags, Some(vec!["yield".to_string()]));
+        let piped = PublishManifest::parse_stdin(r#"{"name": "vault"}"#).unwrap();
+        assert_eq!(piped.name.as_deref(), Some("vault"));
+        let piped = PublishManifest::parse_stdin("name = \"vault\"\n").unwrap();
+        assert_eq!(piped.name.as_deref(), Some("vault"));
+        assert!(PublishManifest::parse_stdin("license: MIT\n").is_err());
+    }
+}
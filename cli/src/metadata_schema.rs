@@ -0,0 +1,132 @@
+//! Organisation metadata policy for `publish`, written as a JSON Schema.
+//!
+//! The schema comes from `--metadata-schema` or `[publish] metadata_schema`
+//! in the config file, as JSON or YAML. It is checked against the payload
+//! `publish` is about to submit (`name`, `description`, `category`, `tags`,
+//! `custom_fields`, ...), and every violation is reported with its JSON
+//! Pointer path before the submission is rejected.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// JSON Pointer to the offending value, e.g. `/custom_fields/owner`;
+    /// empty for the payload as a whole.
+    pub path: String,
+    pub message: String,
+}
+
+pub fn load(path: &Path) -> Result<jsonschema::Validator> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read metadata schema {}", path.display()))?;
+    // YAML is a superset of JSON, so this reads both.
+    let schema: Value = serde_yaml::from_str(&content)
+        .with_context(|| format!("{} is not valid JSON or YAML", path.display()))?;
+    jsonschema::validator_for(&schema)
+        .map_err(|e| anyhow::anyhow!("Invalid metadata schema {}: {}", path.display(), e))
+}
+
+/// Top-level nulls are fields `publish` wasn't given, so they're dropped
+/// first and `required` catches them.
+pub fn check(validator: &jsonschema::Validator, metadata: &Value) -> Vec<Violation> {
+    let mut metadata = metadata.clone();
+    if let Value::Object(fields) = &mut metadata {
+        fields.retain(|_, v| !v.is_null());
+    }
+    let violations = validator
+        .iter_errors(&metadata)
+        .map(|e| Violation {
+            path: e.instance_path.to_string(),
+            message: e.to_string(),
+        })
+        .collect();
+    violations
+}
+
+/// Print any violations and fail if there are some.
+pub fn enforce(schema_path: &Path, metadata: &Value) -> Result<()> {
+    let validator = load(schema_path)?;
+    let violations = check(&validator, metadata);
+    if violations.is_empty() {
+        println!(
+            "{} Metadata conforms to {}",
+            "✓".green(),
+            schema_path.display()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "\n{} {}",
+        "Metadata schema violations:".bold().red(),
+        schema_path.display().to_string().bright_black()
+    );
+    for v in &violations {
+        let path = if v.path.is_empty() { "/" } else { &v.path };
+        println!("  {} {}: {}", "✗".red(), path.bold(), v.message);
+    }
+    bail!(
+        "publish rejected: metadata violates {} ({} error(s))",
+        schema_path.display(),
+        violations.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reports_violations_by_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema_path = dir.path().join("policy.yaml");
+        fs::write(
+            &schema_path,
+            r#"
+type: object
+required: [description, custom_fields]
+properties:
+  category: { enum: [defi, nft, dao] }
+  custom_fields:
+    type: object
+    required: [owner]
+    properties:
+      owner: { type: string, pattern: "^team-" }
+"#,
+        )
+        .unwrap();
+        let validator = load(&schema_path).unwrap();
+
+        let good = json!({
+            "name": "vault",
+            "description": "Yield vault",
+            "category": "defi",
+            "custom_fields": { "owner": "team-core" },
+        });
+        assert!(check(&validator, &good).is_empty());
+        assert!(enforce(&schema_path, &good).is_ok());
+
+        let bad = json!({
+            "name": "vault",
+            "description": null,
+            "category": "games",
+            "custom_fields": { "owner": "alice" },
+        });
+        let mut paths: Vec<_> = check(&validator, &bad)
+            .into_iter()
+            .map(|v| v.path)
+            .collect();
+        paths.sort();
+        assert_eq!(paths, ["", "/category", "/custom_fields/owner"]);
+        assert!(enforce(&schema_path, &bad).is_err());
+
+        fs::write(&schema_path, "type: 12").unwrap();
+        assert!(load(&schema_path).is_err());
+    }
+}
@@ -205,6 +205,16 @@ pub fn apply(old_id: &str, new_id: &str) -> Result<()> {
 
 pub fn rollback(migration_id: &str) -> Result<()> {
     let records = read_history()?;
+    if records
+        .iter()
+        .any(|r| r.id == migration_id && r.action == "redeploy_rollback")
+    {
+        bail!(
+            "{} is a `migrate run --rollback` re-deployment, not a local apply; \
+             re-deploy the wanted version with `migrate run` instead",
+            migration_id
+        );
+    }
     let record = records
         .into_iter()
         .rev()
@@ -252,6 +262,38 @@ pub fn rollback(migration_id: &str) -> Result<()> {
     Ok(())
 }
 
+/// Record a `migrate run --rollback` in the local history. It re-deploys a
+/// contract's previous wasm through the registry, unlike [`rollback`], which
+/// restores the snapshots of a local `migrate apply`, so it is kept under
+/// its own action and [`rollback`] never picks it up.
+pub fn record_redeploy_rollback(
+    migration_id: &str,
+    contract_id: &str,
+    from_hash: &str,
+    to_hash: &str,
+    to_version: Option<&str>,
+) -> Result<()> {
+    append_history(MigrationRecord {
+        id: migration_id.to_string(),
+        action: "redeploy_rollback".to_string(),
+        timestamp: Utc::now().to_rfc3339(),
+        status: "success".to_string(),
+        old_id: Some(contract_id.to_string()),
+        new_id: None,
+        diff: None,
+        warnings: vec![format!(
+            "Re-deployed wasm {} (version {}) in place of {}",
+            to_hash,
+            to_version.unwrap_or("?"),
+            from_hash
+        )],
+        before_state: Some(serde_json::json!({ "wasm_hash": from_hash })),
+        after_state: Some(serde_json::json!({ "wasm_hash": to_hash })),
+        backup_old_snapshot: None,
+        backup_new_snapshot: None,
+    })
+}
+
 pub fn history(limit: usize) -> Result<()> {
     let records = read_history()?;
     println!("\n{}", "Migration History".bold().cyan());
@@ -1,14 +1,72 @@
 // cli/src/multisig.rs
 // CLI functions for Multi-Signature Contract Deployment (issue #47)
 
+use std::collections::HashSet;
+
 use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, Utc};
 use colored::Colorize;
+use ed25519_dalek::Verifier;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
+use stellar_xdr::curr::{
+    DecoratedSignature, Hash, Limits, ReadXdr, Signature as XdrSignature, SignatureHint,
+    TransactionEnvelope, TransactionSignaturePayload, TransactionSignaturePayloadTaggedTransaction,
+    WriteXdr,
+};
+
+use crate::signing::{self, Signer};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Create a new multi-sig policy
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Length of a `G...` account address.
+const ACCOUNT_ADDRESS_LEN: usize = 56;
+
+/// Check a policy's threshold and signers before they reach the registry,
+/// returning the trimmed signer list. Every problem is reported at once.
+pub fn validate_policy(threshold: u32, signers: &[String]) -> Result<Vec<String>> {
+    let mut errors = Vec::new();
+    let mut trimmed: Vec<String> = Vec::new();
+    for (i, signer) in signers.iter().enumerate() {
+        let signer = signer.trim();
+        if signer.is_empty() {
+            errors.push(format!("signer {} is blank", i + 1));
+        } else if trimmed.iter().any(|s| s == signer) {
+            errors.push(format!("signer {} is listed more than once", signer));
+        } else {
+            if !signer.starts_with('G')
+                || signer.len() != ACCOUNT_ADDRESS_LEN
+                || stellar_strkey::ed25519::PublicKey::from_string(signer).is_err()
+            {
+                errors.push(format!(
+                    "signer {} is not a Stellar account address (G..., {} characters)",
+                    signer, ACCOUNT_ADDRESS_LEN
+                ));
+            }
+            trimmed.push(signer.to_string());
+        }
+    }
+
+    if threshold == 0 {
+        errors.push("threshold must be at least 1".to_string());
+    } else if threshold as usize > trimmed.len() {
+        errors.push(format!(
+            "threshold {} is more than the {} distinct signer(s)",
+            threshold,
+            trimmed.len()
+        ));
+    }
+
+    if !errors.is_empty() {
+        let msg = errors.join("\n  ");
+        anyhow::bail!("Validation failed:\n  {}", msg);
+    }
+    Ok(trimmed)
+}
+
 pub async fn create_policy(
     api_url: &str,
     name: &str,
@@ -17,6 +75,7 @@ pub async fn create_policy(
     expiry_secs: Option<u32>,
     created_by: &str,
 ) -> Result<()> {
+    let signers = validate_policy(threshold, &signers)?;
     let client = reqwest::Client::new();
     let url = format!("{}/api/multisig/policies", api_url);
 
@@ -171,13 +230,149 @@ pub async fn create_proposal(
 // Sign a proposal
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Bytes a signer approves for a proposal that carries no transaction. The
+/// [`payload_hash`] ties the approval to the proposal's contents, not just
+/// its id.
+pub fn approval_message(proposal_id: &str, payload_hash: &str, signer_address: &str) -> Vec<u8> {
+    format!("{}:{}:{}", proposal_id, payload_hash, signer_address).into_bytes()
+}
+
+/// Hash of the envelope's transaction on the network with `passphrase`:
+/// what Stellar checks the envelope's signatures against.
+fn transaction_hash(envelope_xdr: &str, passphrase: &str) -> Result<[u8; 32]> {
+    use sha2::{Digest, Sha256};
+
+    let envelope = TransactionEnvelope::from_xdr_base64(envelope_xdr, Limits::none())
+        .context("Proposal transaction is not a valid envelope")?;
+    let tagged_transaction = match envelope {
+        TransactionEnvelope::Tx(e) => TransactionSignaturePayloadTaggedTransaction::Tx(e.tx),
+        TransactionEnvelope::TxFeeBump(e) => {
+            TransactionSignaturePayloadTaggedTransaction::TxFeeBump(e.tx)
+        }
+        TransactionEnvelope::TxV0(_) => {
+            anyhow::bail!("Proposal transaction is a legacy v0 envelope, which can't be signed")
+        }
+    };
+    let payload = TransactionSignaturePayload {
+        network_id: Hash(Sha256::digest(passphrase).into()),
+        tagged_transaction,
+    };
+    Ok(Sha256::digest(payload.to_xdr(Limits::none())?).into())
+}
+
+/// Bytes `signer_address` signs to approve `proposal` (the `proposal`
+/// object of the registry's proposal detail). A proposal that carries its
+/// deployment transaction is signed over the transaction hash, so the
+/// signatures can go straight into the envelope; any other proposal over
+/// its [`approval_message`].
+pub fn signing_payload(proposal: &serde_json::Value, signer_address: &str) -> Result<Vec<u8>> {
+    let proposal_id = proposal["id"]
+        .as_str()
+        .context("Proposal response missing id")?;
+    let Some(transaction) = proposal["transaction_xdr"].as_str() else {
+        return Ok(approval_message(
+            proposal_id,
+            &payload_hash(proposal)?,
+            signer_address,
+        ));
+    };
+    let network: crate::config::Network = proposal["network"]
+        .as_str()
+        .context("Proposal response missing network")?
+        .parse()?;
+    let hash = transaction_hash(transaction, crate::config::network_passphrase(network))?;
+    Ok(hash.to_vec())
+}
+
+/// Signature to submit for `proposal`: produced by the `--signer` backend
+/// when one is configured, otherwise whatever was passed explicitly.
+fn resolve_signature(
+    signer: Option<&dyn Signer>,
+    proposal: &serde_json::Value,
+    signer_address: &str,
+    signature_data: Option<&str>,
+) -> Result<Option<String>> {
+    match (signer, signature_data) {
+        (Some(_), Some(_)) => {
+            anyhow::bail!("Pass either --signature-data or a --signer backend, not both")
+        }
+        (Some(signer), None) => {
+            let signature = signer.sign(&signing_payload(proposal, signer_address)?)?;
+            Ok(Some(signing::encode_signature(&signature)))
+        }
+        (None, data) => Ok(data.map(str::to_string)),
+    }
+}
+
 pub async fn sign_proposal(
     api_url: &str,
     proposal_id: &str,
     signer_address: &str,
     signature_data: Option<&str>,
+    signer: Option<&dyn Signer>,
 ) -> Result<()> {
+    println!("\n{}", "Signing proposal...".bold().cyan());
+    println!("  Proposal: {}", proposal_id.bright_black());
+    println!("  Signer:   {}", signer_address.bright_magenta());
+    if let Some(signer) = signer {
+        println!(
+            "  Key:      {} ({})",
+            signer.describe().bright_black(),
+            signing::encode_public_key(&signer.public_key()).bright_black()
+        );
+    }
+
     let client = reqwest::Client::new();
+    let detail = fetch_proposal_detail(&client, api_url, proposal_id).await?;
+    let signature = resolve_signature(signer, &detail["proposal"], signer_address, signature_data)?;
+    let body = submit_signature(
+        &client,
+        api_url,
+        proposal_id,
+        signer_address,
+        signature.as_deref(),
+    )
+    .await?;
+
+    print_signature_recorded(&body);
+    Ok(())
+}
+
+/// Report the API's answer to a recorded signature.
+fn print_signature_recorded(body: &serde_json::Value) {
+    println!("{}", "✓ Signature recorded!".green().bold());
+
+    let collected = body["signatures_collected"].as_i64().unwrap_or(0);
+    let needed = body["signatures_needed"].as_i64().unwrap_or(0);
+    let threshold_met = body["threshold_met"].as_bool().unwrap_or(false);
+    let proposal_status = body["proposal_status"].as_str().unwrap_or("pending");
+
+    if threshold_met {
+        println!(
+            "  {} Threshold reached! Proposal status: {}",
+            "🎉".bold(),
+            proposal_status.green().bold()
+        );
+    } else {
+        println!(
+            "  Signatures: {}/{} collected — {} more needed",
+            collected,
+            collected + needed,
+            needed.to_string().yellow().bold()
+        );
+    }
+    println!("  Status: {}", proposal_status.yellow());
+    println!();
+}
+
+/// POST a signature for one proposal and return the API's response body.
+async fn submit_signature(
+    client: &reqwest::Client,
+    api_url: &str,
+    proposal_id: &str,
+    signer_address: &str,
+    signature_data: Option<&str>,
+) -> Result<serde_json::Value> {
     let url = format!("{}/api/contracts/{}/sign", api_url, proposal_id);
 
     let payload = json!({
@@ -185,10 +380,6 @@ pub async fn sign_proposal(
         "signature_data": signature_data,
     });
 
-    println!("\n{}", "Signing proposal...".bold().cyan());
-    println!("  Proposal: {}", proposal_id.bright_black());
-    println!("  Signer:   {}", signer_address.bright_magenta());
-
     let response = client
         .post(&url)
         .json(&payload)
@@ -204,29 +395,195 @@ pub async fn sign_proposal(
         anyhow::bail!("API error ({}): {}", status, err);
     }
 
-    println!("{}", "✓ Signature recorded!".green().bold());
+    Ok(body)
+}
 
-    let collected = body["signatures_collected"].as_i64().unwrap_or(0);
-    let needed = body["signatures_needed"].as_i64().unwrap_or(0);
-    let threshold_met = body["threshold_met"].as_bool().unwrap_or(false);
-    let proposal_status = body["proposal_status"].as_str().unwrap_or("pending");
+// ─────────────────────────────────────────────────────────────────────────────
+// Sign every pending proposal awaiting a signer
+// ─────────────────────────────────────────────────────────────────────────────
 
-    if threshold_met {
+/// Maximum number of signature requests in flight at once.
+const SIGN_ALL_CONCURRENCY: usize = 4;
+
+async fn fetch_proposal_detail(
+    client: &reqwest::Client,
+    api_url: &str,
+    proposal_id: &str,
+) -> Result<serde_json::Value> {
+    let url = format!("{}/api/contracts/{}/proposal", api_url, proposal_id);
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to fetch proposal info")?;
+
+    if !response.status().is_success() {
+        let err = response.text().await?;
+        anyhow::bail!("API error: {}", err);
+    }
+
+    Ok(response.json().await?)
+}
+
+/// True when `signer` belongs to the proposal's policy and has not signed yet.
+fn awaits_signer(detail: &serde_json::Value, signer: &str) -> bool {
+    let is_member = detail["policy"]["signer_addresses"]
+        .as_array()
+        .map(|a| a.iter().any(|s| s.as_str() == Some(signer)))
+        .unwrap_or(false);
+    let already_signed = detail["signatures"]
+        .as_array()
+        .map(|a| a.iter().any(|s| s["signer_address"].as_str() == Some(signer)))
+        .unwrap_or(false);
+    is_member && !already_signed
+}
+
+pub async fn sign_all(
+    api_url: &str,
+    signer: &str,
+    limit: usize,
+    yes: bool,
+    key: Option<&dyn Signer>,
+    concurrency_report: bool,
+) -> Result<()> {
+    let client = reqwest::Client::new();
+    let url = format!(
+        "{}/api/multisig/proposals?status=pending&limit={}",
+        api_url, limit
+    );
+
+    println!("\n{}", "Finding proposals awaiting your signature...".bold().cyan());
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to list proposals")?;
+
+    if !response.status().is_success() {
+        let err = response.text().await?;
+        anyhow::bail!("API error: {}", err);
+    }
+
+    let data: serde_json::Value = response.json().await?;
+    let items = data["items"].as_array().cloned().unwrap_or_default();
+
+    let mut pending = Vec::new();
+    let mut skipped = 0usize;
+    for p in &items {
+        let Some(id) = p["id"].as_str() else { continue };
+        let detail = fetch_proposal_detail(&client, api_url, id).await?;
+        if awaits_signer(&detail, signer) {
+            pending.push(p.clone());
+        } else {
+            skipped += 1;
+        }
+    }
+
+    if pending.is_empty() {
         println!(
-            "  {} Threshold reached! Proposal status: {}",
-            "🎉".bold(),
-            proposal_status.green().bold()
+            "{}",
+            "\n  No proposals are awaiting your signature.\n".green()
         );
-    } else {
+        return Ok(());
+    }
+
+    println!("{}", "=".repeat(70).cyan());
+    for (i, p) in pending.iter().enumerate() {
         println!(
-            "  Signatures: {}/{} collected — {} more needed",
-            collected,
-            collected + needed,
-            needed.to_string().yellow().bold()
+            "  {}. {} [{}] {}",
+            i + 1,
+            p["contract_name"].as_str().unwrap_or("Unknown").bold(),
+            p["network"].as_str().unwrap_or("?").bright_blue(),
+            p["id"].as_str().unwrap_or("?").bright_black()
         );
     }
-    println!("  Status: {}", proposal_status.yellow());
-    println!();
+    println!("{}", "=".repeat(70).cyan());
+    if skipped > 0 {
+        println!(
+            "  {} Skipping {} proposal(s) already signed or outside your policies",
+            "→".bright_black(),
+            skipped
+        );
+    }
+
+    if !yes
+        && !crate::wizard::confirm(
+            &format!("Sign {} proposal(s) as {}?", pending.len(), signer),
+            false,
+        )?
+    {
+        println!("{}", "Aborted.".yellow());
+        return Ok(());
+    }
+
+    let mut jobs = Vec::with_capacity(pending.len());
+    for p in &pending {
+        let id = p["id"].as_str().unwrap_or_default().to_string();
+        let name = p["contract_name"].as_str().unwrap_or("Unknown").to_string();
+        let signature = resolve_signature(key, p, signer, None)?;
+        let client = client.clone();
+        let api_url = api_url.to_string();
+        let signer = signer.to_string();
+        jobs.push(async move {
+            let result =
+                submit_signature(&client, &api_url, &id, &signer, signature.as_deref()).await;
+            (id, name, result)
+        });
+    }
+
+    let mut signed = 0usize;
+    let mut failed = 0usize;
+    let (_, timing) = crate::concurrency::run_bounded(SIGN_ALL_CONCURRENCY, jobs, |_, done| {
+        let (id, name, result) = done;
+        match result {
+            Ok(body) => {
+                signed += 1;
+                let status = body["proposal_status"].as_str().unwrap_or("pending");
+                let note = if body["threshold_met"].as_bool().unwrap_or(false) {
+                    "threshold reached".green().to_string()
+                } else {
+                    format!(
+                        "{} more needed",
+                        body["signatures_needed"].as_i64().unwrap_or(0)
+                    )
+                };
+                println!(
+                    "  {} {} ({}) — {} [{}]",
+                    "✓".green(),
+                    name.bold(),
+                    id.bright_black(),
+                    note,
+                    status.yellow()
+                );
+            }
+            Err(e) => {
+                failed += 1;
+                println!(
+                    "  {} {} ({}) — {}",
+                    "✗".red(),
+                    name.bold(),
+                    id.bright_black(),
+                    e.to_string().red()
+                );
+            }
+        }
+    })
+    .await?;
+
+    println!(
+        "\n{} signed, {} failed, {} skipped\n",
+        signed.to_string().green(),
+        failed.to_string().red(),
+        skipped
+    );
+    if concurrency_report {
+        timing.report().print();
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{} signature(s) failed", failed);
+    }
 
     Ok(())
 }
@@ -235,6 +592,133 @@ pub async fn sign_proposal(
 // Execute a proposal
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Check that `encoded` is `address`'s Ed25519 signature over its
+/// [`signing_payload`] for `proposal`, returning the signer's key and the
+/// raw signature.
+fn verify_approval(
+    proposal: &serde_json::Value,
+    address: &str,
+    encoded: &str,
+) -> Result<(stellar_strkey::ed25519::PublicKey, [u8; 64])> {
+    let public_key = stellar_strkey::ed25519::PublicKey::from_string(address)
+        .map_err(|_| anyhow::anyhow!("Signer {} is not a valid G... address", address))?;
+    let bytes: [u8; 64] = BASE64
+        .decode(encoded)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .with_context(|| {
+            format!(
+                "Signature from {} is not a base64 Ed25519 signature",
+                address
+            )
+        })?;
+
+    let key = ed25519_dalek::VerifyingKey::from_bytes(&public_key.0)
+        .with_context(|| format!("Signer {} has an invalid public key", address))?;
+    key.verify(
+        &signing_payload(proposal, address)?,
+        &ed25519_dalek::Signature::from_bytes(&bytes),
+    )
+    .map_err(|_| anyhow::anyhow!("Signature from {} does not verify", address))?;
+    Ok((public_key, bytes))
+}
+
+/// Assemble a proposal's approvals into the signature set for its
+/// transaction.
+///
+/// Signatures are taken in approval order when the policy enforces one, and
+/// in the policy's signer order otherwise. Each must come from a policy
+/// signer and verify against its [`signing_payload`]. Exactly the
+/// required number is returned, since Stellar rejects transactions that
+/// carry extra signatures.
+pub fn aggregate_signatures(proposal: &serde_json::Value) -> Result<Vec<DecoratedSignature>> {
+    let policy = &proposal["policy"];
+    let signers: Vec<&str> = policy["signer_addresses"]
+        .as_array()
+        .map(|a| a.iter().filter_map(|s| s.as_str()).collect())
+        .unwrap_or_default();
+    let threshold = proposal["proposal"]["required_approvals"]
+        .as_u64()
+        .or_else(|| policy["threshold"].as_u64())
+        .context("Proposal response missing approval threshold")? as usize;
+
+    let mut approvals: Vec<&serde_json::Value> = proposal["signatures"]
+        .as_array()
+        .map(|a| {
+            a.iter()
+                .filter(|s| s["decision"].as_str().is_none_or(|d| d == "approved"))
+                .collect()
+        })
+        .unwrap_or_default();
+    if policy["ordered_approvals"].as_bool().unwrap_or(false) {
+        approvals.sort_by_key(|s| s["step_index"].as_i64().unwrap_or(i64::MAX));
+    } else {
+        approvals.sort_by_key(|s| {
+            signers
+                .iter()
+                .position(|a| s["signer_address"].as_str() == Some(*a))
+                .unwrap_or(usize::MAX)
+        });
+    }
+
+    let mut assembled = Vec::with_capacity(threshold);
+    for approval in approvals.into_iter().take(threshold) {
+        let address = approval["signer_address"]
+            .as_str()
+            .context("Signature missing signer_address")?;
+        if !signers.contains(&address) {
+            anyhow::bail!(
+                "{} signed but is not a signer of the proposal's policy",
+                address
+            );
+        }
+        let encoded = approval["signature_data"]
+            .as_str()
+            .with_context(|| format!("{} approved without submitting a signature", address))?;
+        let (public_key, bytes) = verify_approval(&proposal["proposal"], address, encoded)?;
+
+        let mut hint = [0u8; 4];
+        hint.copy_from_slice(&public_key.0[28..]);
+        assembled.push(DecoratedSignature {
+            hint: SignatureHint(hint),
+            signature: XdrSignature(bytes.to_vec().try_into()?),
+        });
+    }
+
+    if assembled.len() < threshold {
+        anyhow::bail!(
+            "Only {} valid signature(s) collected; the policy requires {}",
+            assembled.len(),
+            threshold
+        );
+    }
+    Ok(assembled)
+}
+
+/// Attach `signatures` to the proposal's transaction, if it carries one,
+/// and return the signed envelope as base64 XDR. The signatures are over
+/// the transaction hash (see [`signing_payload`]), so the envelope is ready
+/// to submit.
+fn signed_envelope(
+    proposal: &serde_json::Value,
+    signatures: Vec<DecoratedSignature>,
+) -> Result<Option<String>> {
+    let Some(unsigned) = proposal["proposal"]["transaction_xdr"].as_str() else {
+        return Ok(None);
+    };
+    let mut envelope = TransactionEnvelope::from_xdr_base64(unsigned, Limits::none())
+        .context("Proposal transaction is not a valid envelope")?;
+    let slot = match &mut envelope {
+        TransactionEnvelope::TxV0(e) => &mut e.signatures,
+        TransactionEnvelope::Tx(e) => &mut e.signatures,
+        TransactionEnvelope::TxFeeBump(e) => &mut e.signatures,
+    };
+    *slot = signatures
+        .try_into()
+        .context("Too many signatures for one transaction envelope")?;
+    Ok(Some(envelope.to_xdr_base64(Limits::none())?))
+}
+
 pub async fn execute_proposal(api_url: &str, proposal_id: &str) -> Result<()> {
     let client = reqwest::Client::new();
     let url = format!("{}/api/contracts/{}/execute", api_url, proposal_id);
@@ -242,8 +726,27 @@ pub async fn execute_proposal(api_url: &str, proposal_id: &str) -> Result<()> {
     println!("\n{}", "Executing deployment proposal...".bold().cyan());
     println!("  Proposal: {}", proposal_id.bright_black());
 
+    let detail = fetch_proposal_detail(&client, api_url, proposal_id).await?;
+    let signatures = aggregate_signatures(&detail)
+        .with_context(|| format!("Cannot execute proposal {}", proposal_id))?;
+    println!("  Signatures: {} assembled", signatures.len());
+
+    let payload = json!({
+        "signatures": signatures
+            .iter()
+            .map(|s| {
+                json!({
+                    "hint": hex::encode(s.hint.0),
+                    "signature": BASE64.encode(s.signature.0.as_slice()),
+                })
+            })
+            .collect::<Vec<_>>(),
+        "signed_envelope_xdr": signed_envelope(&detail, signatures)?,
+    });
+
     let response = client
         .post(&url)
+        .json(&payload)
         .send()
         .await
         .context("Failed to execute proposal")?;
@@ -277,6 +780,84 @@ pub async fn execute_proposal(api_url: &str, proposal_id: &str) -> Result<()> {
     Ok(())
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Cancel a proposal
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Turn a rejected cancel request into an error naming the reason. The
+/// registry decides who may cancel; 403 and 409 are the refusals it gives.
+fn cancel_error(
+    status: reqwest::StatusCode,
+    body: &serde_json::Value,
+    proposal_id: &str,
+    canceller: &str,
+) -> anyhow::Error {
+    let message = body["message"].as_str().unwrap_or("unknown error");
+    match status {
+        reqwest::StatusCode::FORBIDDEN => anyhow::anyhow!(
+            "{} may not cancel proposal {}: only its proposer or a policy admin can ({})",
+            canceller,
+            proposal_id,
+            message
+        ),
+        reqwest::StatusCode::CONFLICT => anyhow::anyhow!(
+            "Proposal {} can no longer be cancelled: {}",
+            proposal_id,
+            message
+        ),
+        _ => anyhow::anyhow!("API error ({}): {}", status, message),
+    }
+}
+
+pub async fn cancel_proposal(api_url: &str, proposal_id: &str, canceller: &str) -> Result<()> {
+    let client = reqwest::Client::new();
+    let url = format!("{}/api/contracts/{}/cancel", api_url, proposal_id);
+
+    println!("\n{}", "Cancelling deployment proposal...".bold().cyan());
+    println!("  Proposal: {}", proposal_id.bright_black());
+    println!("  Canceller: {}", canceller.bright_magenta());
+
+    let detail = fetch_proposal_detail(&client, api_url, proposal_id).await?;
+    match detail["proposal"]["status"].as_str() {
+        Some("executed") => anyhow::bail!(
+            "Proposal {} was already executed and can't be cancelled",
+            proposal_id
+        ),
+        Some("cancelled") => anyhow::bail!("Proposal {} is already cancelled", proposal_id),
+        _ => {}
+    }
+
+    let response = client
+        .post(&url)
+        .json(&json!({ "canceller": canceller }))
+        .send()
+        .await
+        .context("Failed to cancel proposal")?;
+
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+
+    if !status.is_success() {
+        return Err(cancel_error(status, &body, proposal_id, canceller));
+    }
+
+    println!("{}", "✓ Proposal cancelled".green().bold());
+    println!(
+        "  {}: {}",
+        "Status".bold(),
+        body["status"]
+            .as_str()
+            .unwrap_or("cancelled")
+            .bright_black()
+    );
+    if let Some(at) = body["cancelled_at"].as_str() {
+        println!("  {}: {}", "Cancelled at".bold(), at);
+    }
+    println!();
+
+    Ok(())
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Get proposal info
 // ─────────────────────────────────────────────────────────────────────────────
@@ -310,6 +891,7 @@ pub async fn proposal_info(api_url: &str, proposal_id: &str) -> Result<()> {
         "approved" => status.green().bold(),
         "executed" => status.bright_green().bold(),
         "expired" | "rejected" => status.red().bold(),
+        "cancelled" => status.bright_black().bold(),
         _ => status.yellow().bold(),
     };
 
@@ -445,6 +1027,7 @@ pub async fn list_proposals(
             "approved" => status.green(),
             "executed" => status.bright_green(),
             "expired" | "rejected" => status.red(),
+            "cancelled" => status.bright_black(),
             _ => status.yellow(),
         };
 
@@ -476,3 +1059,847 @@ pub async fn list_proposals(
 
     Ok(())
 }
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Export / import a policy and its proposals
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Layout version of `multisig export` files.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// Proposals scanned for the policy on export (the list endpoint's maximum).
+const EXPORT_SCAN_LIMIT: usize = 100;
+
+/// A policy with its proposals and collected signatures, as written by
+/// `multisig export`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicySnapshot {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    /// Registry the snapshot was taken from.
+    pub source: String,
+    pub policy: SnapshotPolicy,
+    pub proposals: Vec<SnapshotProposal>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotPolicy {
+    pub id: String,
+    pub name: String,
+    pub threshold: u32,
+    pub signer_addresses: Vec<String>,
+    pub expiry_seconds: Option<u32>,
+    pub created_by: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotProposal {
+    pub id: String,
+    pub contract_name: String,
+    pub contract_id: String,
+    pub wasm_hash: String,
+    pub network: String,
+    pub description: Option<String>,
+    pub proposer: String,
+    pub status: String,
+    pub expires_at: Option<String>,
+    #[serde(default)]
+    pub signatures: Vec<SnapshotSignature>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotSignature {
+    pub signer_address: String,
+    pub signature_data: Option<String>,
+    pub signed_at: Option<String>,
+}
+
+impl SnapshotProposal {
+    fn from_detail(detail: &serde_json::Value) -> Result<Self> {
+        let mut proposal = detail["proposal"].clone();
+        proposal["signatures"] = detail["signatures"].clone();
+        if proposal["signatures"].is_null() {
+            proposal["signatures"] = json!([]);
+        }
+        serde_json::from_value(proposal).context("Unexpected proposal format")
+    }
+
+    /// Only proposals still collecting signatures or awaiting execution are
+    /// recreated on import; the rest are history.
+    fn is_open(&self) -> bool {
+        matches!(self.status.as_str(), "pending" | "approved")
+    }
+}
+
+impl PolicySnapshot {
+    /// Reject snapshots written in another format version, and ones whose
+    /// signatures don't line up with the policy: every signer must belong to
+    /// it, sign a proposal at most once, and any signature present must
+    /// verify against that proposal.
+    pub fn validate(&self) -> Result<()> {
+        if self.format_version != SNAPSHOT_FORMAT_VERSION {
+            anyhow::bail!(
+                "Unsupported snapshot format version {} (this CLI reads version {})",
+                self.format_version,
+                SNAPSHOT_FORMAT_VERSION
+            );
+        }
+        let signers = &self.policy.signer_addresses;
+        let unique: HashSet<&str> = signers.iter().map(String::as_str).collect();
+        if unique.len() != signers.len() {
+            anyhow::bail!("Policy {} lists a signer more than once", self.policy.id);
+        }
+        if self.policy.threshold == 0 || self.policy.threshold as usize > signers.len() {
+            anyhow::bail!(
+                "Policy {} requires {} of {} signers",
+                self.policy.id,
+                self.policy.threshold,
+                signers.len()
+            );
+        }
+
+        for proposal in &self.proposals {
+            let mut seen = HashSet::new();
+            for signature in &proposal.signatures {
+                let address = signature.signer_address.as_str();
+                if !unique.contains(address) {
+                    anyhow::bail!(
+                        "Proposal {} was signed by {}, who is not a signer of policy {}",
+                        proposal.id,
+                        address,
+                        self.policy.id
+                    );
+                }
+                if !seen.insert(address) {
+                    anyhow::bail!(
+                        "Proposal {} has two signatures from {}",
+                        proposal.id,
+                        address
+                    );
+                }
+                if let Some(encoded) = &signature.signature_data {
+                    verify_approval(&serde_json::to_value(proposal)?, address, encoded)
+                        .with_context(|| format!("Proposal {}", proposal.id))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// POST `payload` and return the created record.
+async fn create(
+    client: &reqwest::Client,
+    url: &str,
+    payload: &serde_json::Value,
+) -> Result<serde_json::Value> {
+    let response = client
+        .post(url)
+        .json(payload)
+        .send()
+        .await
+        .context("Failed to reach registry API")?;
+    if !response.status().is_success() {
+        let err = response.text().await?;
+        anyhow::bail!("API error: {}", err);
+    }
+    Ok(response.json().await?)
+}
+
+/// Snapshot a policy and its proposals (with signatures) to `output`.
+///
+/// The registry only exposes a policy through its proposals, so the policy
+/// must have at least one among the latest proposals.
+pub async fn export_policy(api_url: &str, policy_id: &str, output: &str) -> Result<()> {
+    let client = reqwest::Client::new();
+    let url = format!(
+        "{}/api/multisig/proposals?limit={}",
+        api_url, EXPORT_SCAN_LIMIT
+    );
+
+    println!("\n{}", "Exporting multi-sig policy...".bold().cyan());
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to list proposals")?;
+    if !response.status().is_success() {
+        let err = response.text().await?;
+        anyhow::bail!("API error: {}", err);
+    }
+    let data: serde_json::Value = response.json().await?;
+    let items = data["items"].as_array().cloned().unwrap_or_default();
+
+    let mut policy = None;
+    let mut proposals = Vec::new();
+    for item in items
+        .iter()
+        .filter(|p| p["policy_id"].as_str() == Some(policy_id))
+    {
+        let Some(id) = item["id"].as_str() else {
+            continue;
+        };
+        let detail = fetch_proposal_detail(&client, api_url, id).await?;
+        if policy.is_none() {
+            policy = Some(
+                serde_json::from_value::<SnapshotPolicy>(detail["policy"].clone())
+                    .context("Unexpected policy format")?,
+            );
+        }
+        proposals.push(SnapshotProposal::from_detail(&detail)?);
+    }
+    let Some(policy) = policy else {
+        anyhow::bail!(
+            "No proposals found for policy {} among the latest {}; a policy can only be exported through its proposals",
+            policy_id,
+            EXPORT_SCAN_LIMIT
+        );
+    };
+
+    let snapshot = PolicySnapshot {
+        format_version: SNAPSHOT_FORMAT_VERSION,
+        exported_at: Utc::now(),
+        source: api_url.to_string(),
+        policy,
+        proposals,
+    };
+    std::fs::write(output, serde_json::to_string_pretty(&snapshot)?)
+        .with_context(|| format!("Failed to write {}", output))?;
+
+    let signatures: usize = snapshot.proposals.iter().map(|p| p.signatures.len()).sum();
+    println!("{}", "✓ Policy exported!".green().bold());
+    println!("  {}: {}", "Policy".bold(), snapshot.policy.name);
+    println!("  {}: {}", "Proposals".bold(), snapshot.proposals.len());
+    println!("  {}: {}", "Signatures".bold(), signatures);
+    println!("  {}: {}", "File".bold(), output);
+    println!();
+    Ok(())
+}
+
+/// Recreate an exported policy and its open proposals on this registry.
+///
+/// Approvals sign the proposal ID, which the target registry assigns anew,
+/// so signatures are checked against the snapshot but not replayed: the
+/// listed signers re-approve the recreated proposals.
+pub async fn import_policy(api_url: &str, input: &str) -> Result<()> {
+    let raw =
+        std::fs::read_to_string(input).with_context(|| format!("Failed to read {}", input))?;
+    let snapshot: PolicySnapshot = serde_json::from_str(&raw)
+        .with_context(|| format!("{} is not a multisig export", input))?;
+    snapshot.validate()?;
+
+    println!("\n{}", "Importing multi-sig policy...".bold().cyan());
+    println!(
+        "  Source: {} (exported {})",
+        snapshot.source.bright_black(),
+        snapshot.exported_at.format("%Y-%m-%d %H:%M UTC")
+    );
+
+    let client = reqwest::Client::new();
+    let policy = &snapshot.policy;
+    let created = create(
+        &client,
+        &format!("{}/api/multisig/policies", api_url),
+        &json!({
+            "name": policy.name,
+            "threshold": policy.threshold,
+            "signer_addresses": policy.signer_addresses,
+            "expiry_seconds": policy.expiry_seconds,
+            "created_by": policy.created_by,
+        }),
+    )
+    .await
+    .context("Failed to create policy")?;
+    let new_policy_id = created["id"]
+        .as_str()
+        .context("Policy response missing id")?;
+    println!(
+        "{} Policy {} → {}",
+        "✓".green(),
+        policy.id.bright_black(),
+        new_policy_id.bold()
+    );
+
+    let mut skipped = 0usize;
+    for proposal in &snapshot.proposals {
+        if !proposal.is_open() {
+            skipped += 1;
+            continue;
+        }
+        let created = create(
+            &client,
+            &format!("{}/api/contracts/deploy-proposal", api_url),
+            &json!({
+                "contract_name": proposal.contract_name,
+                "contract_id": proposal.contract_id,
+                "wasm_hash": proposal.wasm_hash,
+                "network": proposal.network,
+                "policy_id": new_policy_id,
+                "proposer": proposal.proposer,
+                "description": proposal.description,
+            }),
+        )
+        .await
+        .with_context(|| format!("Failed to recreate proposal {}", proposal.id))?;
+        println!(
+            "{} Proposal {} → {}",
+            "✓".green(),
+            proposal.id.bright_black(),
+            created["id"].as_str().unwrap_or("?").bold()
+        );
+        if !proposal.signatures.is_empty() {
+            let signers: Vec<&str> = proposal
+                .signatures
+                .iter()
+                .map(|s| s.signer_address.as_str())
+                .collect();
+            println!(
+                "    {} Needs re-approval from: {}",
+                "→".bright_black(),
+                signers.join(", ")
+            );
+        }
+    }
+    if skipped > 0 {
+        println!(
+            "  {} Skipped {} closed (executed, expired, rejected or cancelled) proposal(s)",
+            "→".bright_black(),
+            skipped
+        );
+    }
+    println!();
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Offline signing
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Layout version of `multisig export-proposal` files.
+pub const OFFLINE_FORMAT_VERSION: u32 = 1;
+
+/// Everything an air-gapped signer needs to review and approve a proposal,
+/// as written by `multisig export-proposal`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OfflineProposal {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    /// Registry the proposal was exported from.
+    pub source: String,
+    pub proposal: SnapshotProposal,
+    pub policy: SnapshotPolicy,
+    /// Hex SHA-256 of the proposal fields being approved. It is part of each
+    /// signing request's message, and the signature file carries it back so
+    /// a changed proposal is reported as such on import.
+    pub payload_hash: String,
+    /// One per policy signer who hasn't signed yet.
+    pub signing_requests: Vec<SigningRequest>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SigningRequest {
+    pub signer_address: String,
+    /// Hex of the exact bytes to sign with the signer's Ed25519 key.
+    pub message_hex: String,
+}
+
+/// A signature collected offline, as read by `multisig import-signature`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OfflineSignature {
+    pub proposal_id: String,
+    /// `payload_hash` from the exported proposal that was signed.
+    pub payload_hash: String,
+    pub signer_address: String,
+    /// Base64 Ed25519 signature over the signer's `message_hex` bytes.
+    pub signature_data: String,
+}
+
+/// Hash of what a signer actually reviews: the proposal and the contract,
+/// wasm and network it would deploy.
+fn payload_hash(proposal: &serde_json::Value) -> Result<String> {
+    use sha2::{Digest, Sha256};
+
+    let fields = ["id", "contract_name", "contract_id", "wasm_hash", "network"]
+        .iter()
+        .map(|field| {
+            proposal[field]
+                .as_str()
+                .with_context(|| format!("Proposal response missing {}", field))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(hex::encode(Sha256::digest(fields.join("\n"))))
+}
+
+impl OfflineSignature {
+    /// Check the signature against the proposal as the registry has it now:
+    /// same proposal, unchanged since export, from a policy signer who hasn't
+    /// signed yet, and valid for that signer's approval message.
+    fn check(&self, proposal_id: &str, detail: &serde_json::Value) -> Result<()> {
+        if self.proposal_id != proposal_id {
+            anyhow::bail!(
+                "Signature is for proposal {}, not {}",
+                self.proposal_id,
+                proposal_id
+            );
+        }
+        let current = SnapshotProposal::from_detail(detail)?;
+        if !current.is_open() {
+            anyhow::bail!(
+                "Proposal {} is {} and no longer takes signatures",
+                proposal_id,
+                current.status
+            );
+        }
+        if self.payload_hash != payload_hash(&detail["proposal"])? {
+            anyhow::bail!(
+                "Payload hash mismatch: proposal {} changed since it was exported; export it again and re-sign",
+                proposal_id
+            );
+        }
+        if !awaits_signer(detail, &self.signer_address) {
+            anyhow::bail!(
+                "{} is not a signer of this proposal's policy or has already signed it",
+                self.signer_address
+            );
+        }
+        verify_approval(
+            &detail["proposal"],
+            &self.signer_address,
+            &self.signature_data,
+        )?;
+        Ok(())
+    }
+}
+
+/// Write a proposal and the bytes each outstanding signer must sign to
+/// `output`, for signing on a machine without registry access.
+pub async fn export_proposal(api_url: &str, proposal_id: &str, output: &str) -> Result<()> {
+    let client = reqwest::Client::new();
+
+    println!(
+        "\n{}",
+        "Exporting proposal for offline signing...".bold().cyan()
+    );
+
+    let detail = fetch_proposal_detail(&client, api_url, proposal_id).await?;
+    let proposal = SnapshotProposal::from_detail(&detail)?;
+    if !proposal.is_open() {
+        anyhow::bail!(
+            "Proposal {} is {} and no longer takes signatures",
+            proposal_id,
+            proposal.status
+        );
+    }
+    let policy: SnapshotPolicy =
+        serde_json::from_value(detail["policy"].clone()).context("Unexpected policy format")?;
+    let signing_requests = policy
+        .signer_addresses
+        .iter()
+        .filter(|address| awaits_signer(&detail, address))
+        .map(|address| {
+            Ok(SigningRequest {
+                signer_address: address.clone(),
+                message_hex: hex::encode(signing_payload(&detail["proposal"], address)?),
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    let export = OfflineProposal {
+        format_version: OFFLINE_FORMAT_VERSION,
+        exported_at: Utc::now(),
+        source: api_url.to_string(),
+        payload_hash: payload_hash(&detail["proposal"])?,
+        proposal,
+        policy,
+        signing_requests,
+    };
+    std::fs::write(output, serde_json::to_string_pretty(&export)?)
+        .with_context(|| format!("Failed to write {}", output))?;
+
+    println!("{}", "✓ Proposal exported!".green().bold());
+    println!("  {}: {}", "Contract".bold(), export.proposal.contract_name);
+    println!(
+        "  {}: {}",
+        "WASM Hash".bold(),
+        export.proposal.wasm_hash.bright_black()
+    );
+    println!(
+        "  {}: {}",
+        "Network".bold(),
+        export.proposal.network.bright_blue()
+    );
+    println!(
+        "  {}: {}",
+        "Payload hash".bold(),
+        export.payload_hash.bright_black()
+    );
+    println!(
+        "  {}: {} signer(s)",
+        "Awaiting".bold(),
+        export.signing_requests.len()
+    );
+    println!("  {}: {}", "File".bold(), output);
+    println!();
+    Ok(())
+}
+
+/// Submit a signature collected offline once it checks out against the
+/// proposal's current state.
+pub async fn import_signature(api_url: &str, proposal_id: &str, file: &str) -> Result<()> {
+    let raw = std::fs::read_to_string(file).with_context(|| format!("Failed to read {}", file))?;
+    let signature: OfflineSignature = serde_json::from_str(&raw)
+        .with_context(|| format!("{} is not an offline signature file", file))?;
+
+    println!("\n{}", "Importing offline signature...".bold().cyan());
+    println!("  Proposal: {}", proposal_id.bright_black());
+    println!("  Signer:   {}", signature.signer_address.bright_magenta());
+
+    let client = reqwest::Client::new();
+    let detail = fetch_proposal_detail(&client, api_url, proposal_id).await?;
+    signature.check(proposal_id, &detail)?;
+    println!("  {} Payload hash and signature verified", "✓".green());
+
+    let body = submit_signature(
+        &client,
+        api_url,
+        proposal_id,
+        &signature.signer_address,
+        Some(&signature.signature_data),
+    )
+    .await?;
+    print_signature_recorded(&body);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_validation_reports_every_problem() {
+        let address = |seed: u8| {
+            let key = ed25519_dalek::SigningKey::from_bytes(&[seed; 32]);
+            stellar_strkey::ed25519::PublicKey(key.verifying_key().to_bytes())
+                .to_string()
+                .as_str()
+                .to_owned()
+        };
+        let (a, b) = (address(1), address(2));
+
+        let signers = validate_policy(2, &[format!(" {} ", a), b.clone()]).unwrap();
+        assert_eq!(signers, [a.clone(), b.clone()]);
+
+        let err = validate_policy(
+            3,
+            &[a.clone(), a.clone(), " ".to_string(), "GSHORT".to_string()],
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("is listed more than once"));
+        assert!(err.contains("signer 3 is blank"));
+        assert!(err.contains("GSHORT is not a Stellar account address"));
+        assert!(err.contains("threshold 3 is more than the 2 distinct signer(s)"));
+
+        let err = validate_policy(0, &[a]).unwrap_err().to_string();
+        assert!(err.contains("threshold must be at least 1"));
+    }
+
+    #[test]
+    fn awaits_signer_skips_non_members_and_existing_signatures() {
+        let detail = json!({
+            "policy": { "signer_addresses": ["GA", "GB"] },
+            "signatures": [{ "signer_address": "GA" }],
+        });
+        assert!(!awaits_signer(&detail, "GA"));
+        assert!(awaits_signer(&detail, "GB"));
+        assert!(!awaits_signer(&detail, "GC"));
+    }
+
+    #[test]
+    fn cancel_refusals_name_the_reason() {
+        let body = json!({ "message": "not allowed" });
+        let err = cancel_error(reqwest::StatusCode::FORBIDDEN, &body, "p1", "GB");
+        assert!(err
+            .to_string()
+            .contains("only its proposer or a policy admin can"));
+        let err = cancel_error(reqwest::StatusCode::CONFLICT, &json!({}), "p1", "GA");
+        assert!(err.to_string().contains("can no longer be cancelled"));
+        let err = cancel_error(reqwest::StatusCode::BAD_GATEWAY, &body, "p1", "GA");
+        assert!(err.to_string().starts_with("API error (502"));
+    }
+
+    /// Proposal object as the registry returns it for a testnet deployment.
+    fn deploy_proposal(id: &str) -> serde_json::Value {
+        json!({
+            "id": id, "contract_name": "token", "contract_id": "c1",
+            "wasm_hash": "ab".repeat(32), "network": "testnet",
+        })
+    }
+
+    #[test]
+    fn signer_backend_signs_the_approval_message() {
+        use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+        use ed25519_dalek::Verifier;
+
+        let key = signing::KeySigner::new(ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]), "test");
+        let proposal = deploy_proposal("p1");
+        let encoded = resolve_signature(Some(&key), &proposal, "GA", None)
+            .unwrap()
+            .unwrap();
+        let bytes: [u8; 64] = BASE64.decode(encoded).unwrap().try_into().unwrap();
+        let signature = ed25519_dalek::Signature::from_bytes(&bytes);
+        assert!(key
+            .public_key()
+            .verify(
+                &approval_message("p1", &payload_hash(&proposal).unwrap(), "GA"),
+                &signature,
+            )
+            .is_ok());
+
+        assert!(resolve_signature(Some(&key), &proposal, "GA", Some("manual")).is_err());
+        assert_eq!(
+            resolve_signature(None, &proposal, "GA", Some("manual")).unwrap(),
+            Some("manual".to_string())
+        );
+    }
+
+    #[test]
+    fn aggregation_orders_by_policy_and_enforces_threshold() {
+        let keys: Vec<signing::KeySigner> = (1u8..=3)
+            .map(|i| {
+                signing::KeySigner::new(ed25519_dalek::SigningKey::from_bytes(&[i; 32]), "test")
+            })
+            .collect();
+        let addresses: Vec<String> = keys
+            .iter()
+            .map(|k| {
+                stellar_strkey::ed25519::PublicKey(k.public_key().to_bytes())
+                    .to_string()
+                    .as_str()
+                    .to_owned()
+            })
+            .collect();
+        let mut proposal = deploy_proposal("p1");
+        proposal["required_approvals"] = json!(2);
+        let approval = |i: usize| {
+            json!({
+                "signer_address": addresses[i],
+                "signature_data": resolve_signature(Some(&keys[i]), &proposal, &addresses[i], None)
+                    .unwrap(),
+                "decision": "approved",
+            })
+        };
+        let detail = |signatures: Vec<serde_json::Value>| {
+            json!({
+                "proposal": proposal,
+                "policy": { "signer_addresses": addresses, "threshold": 2 },
+                "signatures": signatures,
+            })
+        };
+
+        // Signed out of order by all three: the first two policy signers win.
+        let assembled =
+            aggregate_signatures(&detail(vec![approval(2), approval(0), approval(1)])).unwrap();
+        assert_eq!(assembled.len(), 2);
+        assert_eq!(assembled[0].hint.0, keys[0].public_key().to_bytes()[28..]);
+        assert_eq!(assembled[1].hint.0, keys[1].public_key().to_bytes()[28..]);
+
+        let err = aggregate_signatures(&detail(vec![approval(1)])).unwrap_err();
+        assert!(err.to_string().contains("Only 1 valid signature(s)"));
+
+        let mut forged = approval(0);
+        forged["signature_data"] = approval(1)["signature_data"].clone();
+        let err = aggregate_signatures(&detail(vec![forged, approval(1)])).unwrap_err();
+        assert!(err.to_string().contains("does not verify"));
+    }
+
+    #[test]
+    fn transaction_proposals_are_signed_over_the_network_hash() {
+        use stellar_xdr::curr::{
+            Memo, MuxedAccount, Preconditions, SequenceNumber, Transaction, TransactionExt,
+            TransactionV1Envelope, Uint256,
+        };
+
+        let key =
+            signing::KeySigner::new(ed25519_dalek::SigningKey::from_bytes(&[6u8; 32]), "test");
+        let address = stellar_strkey::ed25519::PublicKey(key.public_key().to_bytes())
+            .to_string()
+            .as_str()
+            .to_owned();
+        let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256(key.public_key().to_bytes())),
+                fee: 100,
+                seq_num: SequenceNumber(1),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: Default::default(),
+                ext: TransactionExt::V0,
+            },
+            signatures: Default::default(),
+        });
+        let xdr = envelope.to_xdr_base64(Limits::none()).unwrap();
+        let proposal = json!({
+            "id": "p1", "network": "testnet", "transaction_xdr": xdr, "required_approvals": 1,
+        });
+
+        let payload = signing_payload(&proposal, &address).unwrap();
+        let testnet = transaction_hash(&xdr, crate::config::TESTNET_PASSPHRASE).unwrap();
+        assert_eq!(payload, testnet);
+        assert_ne!(
+            testnet,
+            transaction_hash(&xdr, crate::config::MAINNET_PASSPHRASE).unwrap()
+        );
+
+        let detail = json!({
+            "proposal": proposal,
+            "policy": { "signer_addresses": [&address], "threshold": 1 },
+            "signatures": [{
+                "signer_address": &address,
+                "signature_data": resolve_signature(Some(&key), &proposal, &address, None)
+                    .unwrap(),
+            }],
+        });
+        let signatures = aggregate_signatures(&detail).unwrap();
+        let signed = signed_envelope(&detail, signatures).unwrap().unwrap();
+        let TransactionEnvelope::Tx(signed) =
+            TransactionEnvelope::from_xdr_base64(signed, Limits::none()).unwrap()
+        else {
+            panic!("expected a v1 envelope");
+        };
+        let signature: [u8; 64] = signed.signatures[0]
+            .signature
+            .0
+            .to_vec()
+            .try_into()
+            .unwrap();
+        assert!(key
+            .public_key()
+            .verify(&testnet, &ed25519_dalek::Signature::from_bytes(&signature))
+            .is_ok());
+    }
+
+    #[test]
+    fn snapshot_validation_checks_version_and_signer_consistency() {
+        let key =
+            signing::KeySigner::new(ed25519_dalek::SigningKey::from_bytes(&[4u8; 32]), "test");
+        let signer = stellar_strkey::ed25519::PublicKey(key.public_key().to_bytes())
+            .to_string()
+            .as_str()
+            .to_owned();
+        let signature = |address: &str| SnapshotSignature {
+            signer_address: address.to_string(),
+            signature_data: resolve_signature(Some(&key), &deploy_proposal("p1"), address, None)
+                .unwrap(),
+            signed_at: None,
+        };
+        let snapshot = |signatures: Vec<SnapshotSignature>| PolicySnapshot {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            exported_at: Utc::now(),
+            source: "https://registry.example".to_string(),
+            policy: SnapshotPolicy {
+                id: "pol".to_string(),
+                name: "core".to_string(),
+                threshold: 1,
+                signer_addresses: vec![signer.clone(), "GOTHER".to_string()],
+                expiry_seconds: None,
+                created_by: "GADMIN".to_string(),
+            },
+            proposals: vec![SnapshotProposal {
+                id: "p1".to_string(),
+                contract_name: "token".to_string(),
+                contract_id: "c1".to_string(),
+                wasm_hash: "ab".repeat(32),
+                network: "testnet".to_string(),
+                description: None,
+                proposer: "GADMIN".to_string(),
+                status: "pending".to_string(),
+                expires_at: None,
+                signatures,
+            }],
+        };
+
+        let valid = snapshot(vec![signature(&signer)]);
+        valid.validate().unwrap();
+        let round_trip: PolicySnapshot =
+            serde_json::from_str(&serde_json::to_string(&valid).unwrap()).unwrap();
+        assert_eq!(round_trip, valid);
+
+        let mut future = valid.clone();
+        future.format_version = SNAPSHOT_FORMAT_VERSION + 1;
+        assert!(future.validate().is_err());
+
+        let err = snapshot(vec![signature("GSTRANGER")])
+            .validate()
+            .unwrap_err();
+        assert!(err.to_string().contains("not a signer of policy"));
+        assert!(snapshot(vec![signature(&signer), signature(&signer)])
+            .validate()
+            .is_err());
+
+        // A signature made for a different proposal doesn't carry over.
+        let mut moved = valid.clone();
+        moved.proposals[0].id = "p2".to_string();
+        assert!(moved.validate().is_err());
+    }
+
+    #[test]
+    fn offline_signatures_must_match_the_current_proposal() {
+        let key =
+            signing::KeySigner::new(ed25519_dalek::SigningKey::from_bytes(&[5u8; 32]), "test");
+        let signer = stellar_strkey::ed25519::PublicKey(key.public_key().to_bytes())
+            .to_string()
+            .as_str()
+            .to_owned();
+        let detail = json!({
+            "proposal": {
+                "id": "p1", "contract_name": "token", "contract_id": "c1",
+                "wasm_hash": "ab".repeat(32), "network": "testnet", "description": null,
+                "proposer": "GADMIN", "status": "pending", "expires_at": null,
+            },
+            "policy": { "signer_addresses": [&signer, "GOTHER"], "threshold": 1 },
+            "signatures": [],
+        });
+        let hash = payload_hash(&detail["proposal"]).unwrap();
+        let signature = OfflineSignature {
+            proposal_id: "p1".to_string(),
+            payload_hash: hash,
+            signer_address: signer.clone(),
+            signature_data: resolve_signature(Some(&key), &detail["proposal"], &signer, None)
+                .unwrap()
+                .unwrap(),
+        };
+        signature.check("p1", &detail).unwrap();
+        assert!(signature.check("p2", &detail).is_err());
+
+        let mut changed = detail.clone();
+        changed["proposal"]["wasm_hash"] = json!("cd".repeat(32));
+        let err = signature.check("p1", &changed).unwrap_err();
+        assert!(err.to_string().contains("Payload hash mismatch"));
+        // Copying the new hash over doesn't help: the signature covers the old one.
+        let replayed = OfflineSignature {
+            payload_hash: payload_hash(&changed["proposal"]).unwrap(),
+            ..signature.clone()
+        };
+        let err = replayed.check("p1", &changed).unwrap_err();
+        assert!(err.to_string().contains("does not verify"));
+
+        let mut signed = detail.clone();
+        signed["signatures"] = json!([{ "signer_address": &signer }]);
+        assert!(signature.check("p1", &signed).is_err());
+
+        let mut cancelled = detail.clone();
+        cancelled["proposal"]["status"] = json!("cancelled");
+        assert!(signature.check("p1", &cancelled).is_err());
+
+        let forged = OfflineSignature {
+            signature_data: resolve_signature(Some(&key), &deploy_proposal("p2"), &signer, None)
+                .unwrap()
+                .unwrap(),
+            ..signature
+        };
+        let err = forged.check("p1", &detail).unwrap_err();
+        assert!(err.to_string().contains("does not verify"));
+    }
+}
@@ -77,6 +77,17 @@ struct NetworkCache {
     cached_at: DateTime<Utc>,
 }
 
+/// Soroban RPC endpoint for a network name, honouring `SOROBAN_RPC_URL`.
+pub fn rpc_endpoint(network: &str) -> Option<String> {
+    if let Ok(url) = std::env::var("SOROBAN_RPC_URL") {
+        return Some(url);
+    }
+    NETWORKS
+        .iter()
+        .find(|n| n.name.eq_ignore_ascii_case(network))
+        .map(|n| n.rpc_endpoint.to_string())
+}
+
 // Stellar RPC JSON-RPC types
 #[derive(Serialize)]
 struct RpcRequest<'a> {
@@ -216,6 +227,60 @@ async fn fetch_close_time(def: &NetworkDef, client: &reqwest::Client) -> Option<
     None
 }
 
+/// Show the resolved network with its passphrase and default endpoints.
+pub fn info(network: crate::config::Network, json: bool) -> Result<()> {
+    use crate::config::Network;
+
+    let name = match network {
+        Network::Auto => Network::Mainnet.to_string(),
+        other => other.to_string(),
+    };
+    let passphrase = crate::config::resolve_passphrase(network)?;
+    let def = NETWORKS.iter().find(|n| n.name == name);
+    let rpc_url = rpc_endpoint(&name);
+    let rpc_overridden = std::env::var_os("SOROBAN_RPC_URL").is_some();
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        return emitter.emit(
+            &serde_json::json!({
+                "network": name,
+                "requested": network.to_string(),
+                "passphrase": passphrase,
+                "rpc_url": rpc_url,
+                "rpc_url_overridden": rpc_overridden,
+                "horizon_url": def.map(|d| d.horizon_endpoint),
+            }),
+            crate::output::Format::Json,
+        );
+    }
+
+    println!("\n{}", "Network".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    let label = if network == Network::Auto {
+        format!("{} (auto)", name)
+    } else {
+        name.clone()
+    };
+    println!("  {:<12} {}", "Network:".bold(), label.bright_blue());
+    println!("  {:<12} {}", "Passphrase:".bold(), passphrase);
+    println!(
+        "  {:<12} {}{}",
+        "RPC URL:".bold(),
+        rpc_url.as_deref().unwrap_or("-"),
+        if rpc_overridden {
+            " (from SOROBAN_RPC_URL)".bright_black().to_string()
+        } else {
+            String::new()
+        }
+    );
+    if let Some(def) = def {
+        println!("  {:<12} {}", "Horizon URL:".bold(), def.horizon_endpoint);
+    }
+    println!();
+    Ok(())
+}
+
 pub async fn status(json: bool) -> Result<()> {
     let client = reqwest::Client::new();
 
@@ -0,0 +1,88 @@
+//! OCI image-layout types for exporting contracts as OCI artifacts.
+//!
+//! An exported artifact is an `oci-archive` style tarball (an OCI image
+//! layout: `oci-layout`, `index.json`, `blobs/sha256/*`) that tools such as
+//! `oras` or `skopeo` can push to any OCI-compliant registry.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+pub const ARTIFACT_TYPE: &str = "application/vnd.soroban.contract.v1";
+pub const MEDIA_TYPE_MANIFEST: &str = "application/vnd.oci.image.manifest.v1+json";
+pub const MEDIA_TYPE_INDEX: &str = "application/vnd.oci.image.index.v1+json";
+pub const MEDIA_TYPE_EMPTY: &str = "application/vnd.oci.empty.v1+json";
+pub const MEDIA_TYPE_WASM: &str = "application/vnd.soroban.contract.wasm.v1";
+pub const MEDIA_TYPE_METADATA: &str = "application/vnd.soroban.contract.metadata.v1+json";
+pub const MEDIA_TYPE_DOCS: &str = "application/vnd.soroban.contract.docs.v1.tar+gzip";
+pub const MEDIA_TYPE_SOURCE: &str = "application/vnd.soroban.contract.source.v1.tar+gzip";
+
+pub const ANNOTATION_TITLE: &str = "org.opencontainers.image.title";
+pub const ANNOTATION_CREATED: &str = "org.opencontainers.image.created";
+pub const ANNOTATION_REF_NAME: &str = "org.opencontainers.image.ref.name";
+
+pub const LAYOUT_FILE: &str = "oci-layout";
+pub const INDEX_FILE: &str = "index.json";
+pub const LAYOUT_VERSION: &str = "1.0.0";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Descriptor {
+    pub media_type: String,
+    pub digest: String,
+    pub size: u64,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageManifest {
+    pub schema_version: u32,
+    pub media_type: String,
+    pub artifact_type: String,
+    pub config: Descriptor,
+    pub layers: Vec<Descriptor>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageIndex {
+    pub schema_version: u32,
+    pub media_type: String,
+    pub manifests: Vec<Descriptor>,
+}
+
+/// `sha256:<hex>` digest of `bytes`.
+pub fn digest(bytes: &[u8]) -> String {
+    format!("sha256:{}", hex::encode(Sha256::digest(bytes)))
+}
+
+/// Path of a blob inside an image layout.
+pub fn blob_path(digest: &str) -> String {
+    format!("blobs/{}", digest.replacen(':', "/", 1))
+}
+
+impl Descriptor {
+    pub fn for_blob(media_type: &str, bytes: &[u8]) -> Self {
+        Self {
+            media_type: media_type.to_string(),
+            digest: digest(bytes),
+            size: bytes.len() as u64,
+            annotations: BTreeMap::new(),
+        }
+    }
+
+    pub fn titled(mut self, title: &str) -> Self {
+        self.annotations
+            .insert(ANNOTATION_TITLE.to_string(), title.to_string());
+        self
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.annotations.get(ANNOTATION_TITLE).map(String::as_str)
+    }
+}
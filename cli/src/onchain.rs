@@ -0,0 +1,206 @@
+//! `compare-onchain`: detect drift between a contract's live storage and the
+//! values registered as its intended configuration.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, Serialize)]
+pub struct KeyComparison {
+    pub key: String,
+    pub expected: Option<Value>,
+    pub actual: Option<Value>,
+    pub status: DriftStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftStatus {
+    Match,
+    Drift,
+    MissingOnChain,
+    NoExpectation,
+}
+
+/// Registered expectations may store numbers or addresses as strings, so a
+/// string expectation matches any value with the same textual form.
+fn values_match(expected: &Value, actual: &Value) -> bool {
+    if expected == actual {
+        return true;
+    }
+    match (expected, actual) {
+        (Value::String(e), Value::String(a)) => e == a,
+        (Value::String(e), other) | (other, Value::String(e)) => {
+            serde_json::from_str::<Value>(e).is_ok_and(|parsed| parsed == *other)
+        }
+        _ => false,
+    }
+}
+
+pub fn classify(expected: Option<&Value>, actual: Option<&Value>) -> DriftStatus {
+    match (expected, actual) {
+        (None, _) => DriftStatus::NoExpectation,
+        (Some(_), None) => DriftStatus::MissingOnChain,
+        (Some(e), Some(a)) if values_match(e, a) => DriftStatus::Match,
+        _ => DriftStatus::Drift,
+    }
+}
+
+/// Look up a registered contract and return its on-chain address, network
+/// and the RPC endpoint serving that network. The endpoint is checked to
+/// really serve that network before anything reads from it.
+pub async fn resolve_contract(
+    client: &reqwest::Client,
+    api_url: &str,
+    contract_id: &str,
+) -> Result<(Value, String, String, String)> {
+    let response = client
+        .get(format!("{}/api/contracts/{}", api_url, contract_id))
+        .send()
+        .await
+        .context("Failed to fetch contract from registry")?;
+    if !response.status().is_success() {
+        anyhow::bail!(
+            "Contract not found: {} ({})",
+            contract_id,
+            response.status()
+        );
+    }
+    let contract: Value = response.json().await?;
+
+    let address = crate::conversions::as_str(&contract["contract_id"], "contract_id")?;
+    let network = contract["network"]
+        .as_str()
+        .unwrap_or("testnet")
+        .to_string();
+    let endpoint = crate::network::rpc_endpoint(&network)
+        .with_context(|| format!("No RPC endpoint known for network {}", network))?;
+    crate::context::echo_resolved(contract_id, &address, &format!("on {}", network));
+
+    let resolved: crate::config::Network = network.parse()?;
+    crate::config::resolve_passphrase(resolved)?;
+    crate::rpc::ensure_network(client, &endpoint, resolved).await?;
+    Ok((contract, address, network, endpoint))
+}
+
+pub async fn compare(
+    api_url: &str,
+    contract_id: &str,
+    keys: Vec<String>,
+    json: bool,
+) -> Result<()> {
+    let client = reqwest::Client::new();
+    let (contract, address, network, endpoint) =
+        resolve_contract(&client, api_url, contract_id).await?;
+
+    let expected = contract
+        .get("expected_state")
+        .or_else(|| contract.pointer("/metadata/expected_state"))
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut keys = if keys.is_empty() {
+        crate::config::compare_onchain_keys()?
+    } else {
+        keys
+    };
+    if keys.is_empty() {
+        keys = expected.keys().cloned().collect();
+    }
+    if keys.is_empty() {
+        anyhow::bail!(
+            "No storage keys to compare: pass --keys, set [compare_onchain] keys in the config file, \
+             or register expected_state for the contract"
+        );
+    }
+
+    let actual = crate::rpc::fetch_contract_storage(&client, &endpoint, &address, &keys).await?;
+
+    let results: Vec<KeyComparison> = keys
+        .iter()
+        .map(|key| {
+            let exp = expected.get(key).cloned();
+            let act = actual.get(key).cloned().flatten();
+            KeyComparison {
+                key: key.clone(),
+                status: classify(exp.as_ref(), act.as_ref()),
+                expected: exp,
+                actual: act,
+            }
+        })
+        .collect();
+
+    let drifted = results
+        .iter()
+        .filter(|r| matches!(r.status, DriftStatus::Drift | DriftStatus::MissingOnChain))
+        .count();
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(
+            &serde_json::json!({
+                "contract_id": address,
+                "network": network,
+                "drift_detected": drifted > 0,
+                "keys": results,
+            }),
+            crate::output::Format::Json,
+        )?;
+    } else {
+        println!("\n{}", "On-chain Config Comparison".bold().cyan());
+        println!("{}", "=".repeat(80).cyan());
+        println!("  {}: {}", "Contract".bold(), address.bright_black());
+        println!("  {}: {}\n", "Network".bold(), network.bright_blue());
+
+        let show = |v: &Option<Value>| match v {
+            Some(Value::String(s)) => s.clone(),
+            Some(other) => other.to_string(),
+            None => "—".to_string(),
+        };
+        for r in &results {
+            let (icon, label) = match r.status {
+                DriftStatus::Match => ("✓".green(), "match".green()),
+                DriftStatus::Drift => ("✗".red(), "DRIFT".red().bold()),
+                DriftStatus::MissingOnChain => ("✗".red(), "missing on-chain".red()),
+                DriftStatus::NoExpectation => ("○".yellow(), "no registered value".yellow()),
+            };
+            println!("  {} {} [{}]", icon, r.key.bold(), label);
+            if r.status != DriftStatus::Match {
+                println!("      expected: {}", show(&r.expected));
+                println!("      actual:   {}", show(&r.actual));
+            }
+        }
+
+        println!("\n{}", "=".repeat(80).cyan());
+        if drifted == 0 {
+            println!("{}\n", "✓ No drift detected".green().bold());
+        }
+    }
+
+    if drifted > 0 {
+        anyhow::bail!("{} key(s) drifted from registered values", drifted);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn classify_handles_stringly_expectations() {
+        assert_eq!(
+            classify(Some(&json!("100")), Some(&json!(100))),
+            DriftStatus::Match
+        );
+        assert_eq!(
+            classify(Some(&json!("GA")), Some(&json!("GB"))),
+            DriftStatus::Drift
+        );
+        assert_eq!(classify(Some(&json!(1)), None), DriftStatus::MissingOnChain);
+        assert_eq!(classify(None, Some(&json!(1))), DriftStatus::NoExpectation);
+    }
+}
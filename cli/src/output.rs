@@ -0,0 +1,944 @@
+//! Destination for the primary result of read commands.
+//!
+//! By default results go to stdout. The global `--output-file <path>`
+//! redirects them to a file instead, so logs and progress (on stderr) never
+//! end up mixed into the saved data. `--sink` generalises this to any
+//! [`Sink`], including an HTTP endpoint the result is POSTed to.
+//!
+//! The global `--output-format json|yaml` picks the serialisation for every
+//! command that has a structured result; `list` and `search` also accept
+//! `protobuf` (see [`crate::proto`]).
+//!
+//! Repeated `--emit <format>:<destination>` flags send the one result to
+//! several [`EmitTarget`]s at once, e.g. a `text` summary to stdout and JSON
+//! to a file.
+//!
+//! Commands that take `--fields` / `--fields-from-file` narrow their result
+//! with a [`Projection`] first. The global `--extract <pointer>` then
+//! replaces the whole result with the single value an RFC 6901 JSON Pointer
+//! selects.
+
+use std::cell::Cell;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Process-wide result destination, set once from the global
+/// `--output-file` or `--sink` flag. Unset means stdout.
+static OUTPUT_SINK: OnceLock<SinkSpec> = OnceLock::new();
+
+/// Process-wide result format, set once from the global `--output-format`
+/// flag.
+static OUTPUT_FORMAT: OnceLock<Format> = OnceLock::new();
+
+/// Process-wide JSON Pointer, set once from the global `--extract` flag.
+static EXTRACT_POINTER: OnceLock<String> = OnceLock::new();
+
+/// Process-wide result targets, set once from the global `--emit` flags.
+static EMIT_TARGETS: OnceLock<Vec<EmitTarget>> = OnceLock::new();
+
+pub fn set_output_path(path: Option<&str>) {
+    if let Some(path) = path {
+        let _ = OUTPUT_SINK.set(SinkSpec::File(PathBuf::from(path)));
+    }
+}
+
+pub fn set_sink(spec: Option<&str>) -> Result<()> {
+    if let Some(spec) = spec {
+        match SinkSpec::parse(spec)? {
+            SinkSpec::Stdout => {}
+            sink => {
+                let _ = OUTPUT_SINK.set(sink);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn set_format(format: Option<Format>) {
+    if let Some(format) = format {
+        let _ = OUTPUT_FORMAT.set(format);
+    }
+}
+
+pub fn set_emit_targets(specs: &[String]) -> Result<()> {
+    if specs.is_empty() {
+        return Ok(());
+    }
+    let targets = specs
+        .iter()
+        .map(|spec| EmitTarget::parse(spec))
+        .collect::<Result<Vec<_>>>()?;
+    if targets
+        .iter()
+        .filter(|t| t.sink == SinkSpec::Stdout)
+        .count()
+        > 1
+    {
+        anyhow::bail!("Only one --emit target can write to stdout");
+    }
+    let _ = EMIT_TARGETS.set(targets);
+    Ok(())
+}
+
+pub fn set_extract(pointer: Option<&str>) -> Result<()> {
+    if let Some(pointer) = pointer {
+        if !pointer.is_empty() && !pointer.starts_with('/') {
+            anyhow::bail!(
+                "Invalid JSON pointer '{}': must be empty or start with '/' (e.g. /wasm_hash)",
+                pointer
+            );
+        }
+        let _ = EXTRACT_POINTER.set(pointer.to_string());
+    }
+    Ok(())
+}
+
+/// The value `pointer` selects in `value`, printed raw: strings without
+/// quotes, other scalars as-is, arrays and objects as JSON.
+pub fn extract(value: &Value, pointer: &str) -> Result<String> {
+    let selected = value
+        .pointer(pointer)
+        .with_context(|| format!("JSON pointer '{}' matched nothing in the result", pointer))?;
+    Ok(match selected {
+        Value::String(s) => s.clone(),
+        Value::Array(_) | Value::Object(_) => serde_json::to_string_pretty(selected)?,
+        scalar => scalar.to_string(),
+    })
+}
+
+/// Serialisation format for structured results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    Json,
+    Yaml,
+    /// Length-delimited protobuf messages (`list` and `search` only)
+    Protobuf,
+    /// Indented plain-text summary, for `--emit text:<destination>`
+    #[value(skip)]
+    Text,
+}
+
+impl Format {
+    /// Format implied by a file extension, if recognised.
+    pub fn from_path(path: &Path) -> Option<Format> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(Format::Json),
+            "yaml" | "yml" => Some(Format::Yaml),
+            "pb" | "binpb" => Some(Format::Protobuf),
+            _ => None,
+        }
+    }
+
+    pub fn render<T: Serialize + ?Sized>(self, value: &T) -> Result<String> {
+        Ok(match self {
+            Format::Json => serde_json::to_string_pretty(value)?,
+            Format::Yaml => serde_yaml::to_string(value)?,
+            Format::Protobuf => {
+                anyhow::bail!("Protobuf output is only available for list and search")
+            }
+            Format::Text => render_text(&serde_json::to_value(value)?),
+        })
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            Format::Yaml => "application/yaml",
+            Format::Protobuf => "application/x-protobuf",
+            Format::Text => "text/plain",
+        }
+    }
+}
+
+/// `--format` of commands that also have a human-readable view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    #[value(alias = "text")]
+    Table,
+    Json,
+    Csv,
+    Yaml,
+}
+
+impl OutputFormat {
+    /// The structured serialisation, `None` for table and CSV output.
+    pub fn structured(self) -> Option<Format> {
+        match self {
+            OutputFormat::Json => Some(Format::Json),
+            OutputFormat::Yaml => Some(Format::Yaml),
+            OutputFormat::Table | OutputFormat::Csv => None,
+        }
+    }
+}
+
+/// Plain-text rendering of a structured result: nested `key: value` lines,
+/// with lists of flat records laid out as a table.
+pub fn render_text(value: &Value) -> String {
+    let mut out = String::new();
+    write_text(&mut out, value, 0);
+    out
+}
+
+fn is_scalar(value: &Value) -> bool {
+    match value {
+        Value::Array(items) => items.iter().all(|v| !v.is_array() && !v.is_object()),
+        Value::Object(map) => map.is_empty(),
+        _ => true,
+    }
+}
+
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => "-".to_string(),
+        Value::Array(items) if items.is_empty() => "-".to_string(),
+        Value::Array(items) => items.iter().map(scalar_text).collect::<Vec<_>>().join(", "),
+        Value::Object(_) => "-".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn write_text(out: &mut String, value: &Value, indent: usize) {
+    let pad = "  ".repeat(indent);
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map {
+                if is_scalar(v) {
+                    out.push_str(&format!("{}{}: {}\n", pad, key, scalar_text(v)));
+                } else {
+                    out.push_str(&format!("{}{}:\n", pad, key));
+                    write_text(out, v, indent + 1);
+                }
+            }
+        }
+        Value::Array(items) => {
+            let records: Option<Vec<&Map<String, Value>>> = items
+                .iter()
+                .map(|v| v.as_object().filter(|m| m.values().all(is_scalar)))
+                .collect();
+            match records {
+                Some(records) => write_table(out, &records, &pad),
+                None => {
+                    for item in items {
+                        if is_scalar(item) {
+                            out.push_str(&format!("{}- {}\n", pad, scalar_text(item)));
+                        } else {
+                            out.push_str(&format!("{}-\n", pad));
+                            write_text(out, item, indent + 1);
+                        }
+                    }
+                }
+            }
+        }
+        scalar => out.push_str(&format!("{}{}\n", pad, scalar_text(scalar))),
+    }
+}
+
+/// Columns are the records' keys: in key order within a record (serde_json
+/// maps are sorted), followed by any keys only later records have.
+fn write_table(out: &mut String, records: &[&Map<String, Value>], pad: &str) {
+    let mut headers: Vec<&str> = Vec::new();
+    for record in records {
+        for key in record.keys() {
+            if !headers.contains(&key.as_str()) {
+                headers.push(key);
+            }
+        }
+    }
+    let rows: Vec<Vec<String>> = records
+        .iter()
+        .map(|r| {
+            headers
+                .iter()
+                .map(|h| r.get(*h).map(scalar_text).unwrap_or_else(|| "-".into()))
+                .collect()
+        })
+        .collect();
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, h)| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain([h.chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{:<width$}", c, width = w))
+            .collect();
+        format!("{}{}\n", pad, padded.join("  ").trim_end())
+    };
+    out.push_str(&line(headers.clone()));
+    out.push_str(&line(
+        widths
+            .iter()
+            .map(|w| "-".repeat(*w))
+            .collect::<Vec<_>>()
+            .iter()
+            .map(String::as_str)
+            .collect(),
+    ));
+    for row in &rows {
+        out.push_str(&line(row.iter().map(String::as_str).collect()));
+    }
+}
+
+/// Delivers a rendered result somewhere.
+pub trait Sink {
+    fn deliver(&self, content: &str, format: Format) -> Result<()>;
+
+    /// Deliver binary content (protobuf) as-is, with no trailing newline.
+    fn deliver_bytes(&self, content: &[u8], format: Format) -> Result<()>;
+}
+
+/// Prints to stdout (the default).
+pub struct StdoutSink;
+
+impl Sink for StdoutSink {
+    fn deliver(&self, content: &str, _format: Format) -> Result<()> {
+        println!("{}", content.trim_end());
+        Ok(())
+    }
+
+    fn deliver_bytes(&self, content: &[u8], _format: Format) -> Result<()> {
+        let mut stdout = std::io::stdout().lock();
+        stdout
+            .write_all(content)
+            .and_then(|_| stdout.flush())
+            .context("Failed to write output to stdout")
+    }
+}
+
+/// Writes to a file, creating its parent directories; confirms on stderr.
+pub struct FileSink {
+    pub path: PathBuf,
+}
+
+impl Sink for FileSink {
+    fn deliver(&self, content: &str, format: Format) -> Result<()> {
+        let mut content = content.to_string();
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        self.deliver_bytes(content.as_bytes(), format)
+    }
+
+    fn deliver_bytes(&self, content: &[u8], _format: Format) -> Result<()> {
+        let path = &self.path;
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write output to {}", path.display()))?;
+        eprintln!("{} Wrote output to {}", "✓".green(), path.display());
+        Ok(())
+    }
+}
+
+/// POSTs to a URL, retrying transient failures; confirms on stderr.
+pub struct HttpSink {
+    pub url: reqwest::Url,
+}
+
+impl Sink for HttpSink {
+    fn deliver(&self, content: &str, format: Format) -> Result<()> {
+        self.deliver_bytes(content.as_bytes(), format)
+    }
+
+    fn deliver_bytes(&self, content: &[u8], format: Format) -> Result<()> {
+        let client = reqwest::Client::new();
+        let post = crate::http::send_with_retry(
+            || {
+                client
+                    .post(self.url.clone())
+                    .header(reqwest::header::CONTENT_TYPE, format.content_type())
+                    .body(content.to_vec())
+            },
+            None,
+            crate::http::RetryPolicy::default(),
+        );
+        let response =
+            block_on(post)?.with_context(|| format!("Failed to POST output to {}", self.url))?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Output sink {} rejected the result: {}",
+                self.url,
+                response.status()
+            );
+        }
+        eprintln!("{} Posted output to {}", "✓".green(), self.url);
+        Ok(())
+    }
+}
+
+/// Run `future` to completion from synchronous code, inside or outside the
+/// command's tokio runtime.
+fn block_on<F: std::future::Future>(future: F) -> Result<F::Output> {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => Ok(tokio::task::block_in_place(|| handle.block_on(future))),
+        Err(_) => Ok(tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .context("Failed to start async runtime")?
+            .block_on(future)),
+    }
+}
+
+/// A `--sink` destination: `stdout`, `file:<path>` or `http:<url>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SinkSpec {
+    Stdout,
+    File(PathBuf),
+    Http(reqwest::Url),
+}
+
+impl SinkSpec {
+    /// `http:` takes a full URL (`http:https://collector/ingest`); a bare
+    /// `http://...` or `https://...` is accepted too.
+    pub fn parse(spec: &str) -> Result<SinkSpec> {
+        if spec == "stdout" {
+            return Ok(SinkSpec::Stdout);
+        }
+        if spec.starts_with("http://") || spec.starts_with("https://") {
+            return SinkSpec::parse(&format!("http:{}", spec));
+        }
+        match spec.split_once(':') {
+            Some(("file", path)) if !path.is_empty() => Ok(SinkSpec::File(PathBuf::from(path))),
+            Some(("http", url)) => {
+                let url = reqwest::Url::parse(url)
+                    .with_context(|| format!("Invalid sink URL '{}'", url))?;
+                if !matches!(url.scheme(), "http" | "https") {
+                    anyhow::bail!("Sink URL must be http or https: {}", url);
+                }
+                Ok(SinkSpec::Http(url))
+            }
+            _ => anyhow::bail!(
+                "Invalid --sink '{}': expected stdout, file:<path> or http:<url>",
+                spec
+            ),
+        }
+    }
+
+    /// Format implied by the destination itself (a file's extension).
+    fn format(&self) -> Option<Format> {
+        match self {
+            SinkSpec::File(path) => Format::from_path(path),
+            _ => None,
+        }
+    }
+
+    pub fn open(&self) -> Box<dyn Sink> {
+        match self {
+            SinkSpec::Stdout => Box::new(StdoutSink),
+            SinkSpec::File(path) => Box::new(FileSink { path: path.clone() }),
+            SinkSpec::Http(url) => Box::new(HttpSink { url: url.clone() }),
+        }
+    }
+}
+
+/// One `--emit <format>:<destination>` target. The format is `text`,
+/// `json`, `yaml` or `protobuf`; the destination is `stdout`, a file path
+/// (optionally `file:`-prefixed) or an `http(s)://` URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmitTarget {
+    pub format: Format,
+    pub sink: SinkSpec,
+}
+
+impl EmitTarget {
+    pub fn parse(spec: &str) -> Result<EmitTarget> {
+        let (format, destination) = spec
+            .split_once(':')
+            .filter(|(_, d)| !d.is_empty())
+            .with_context(|| {
+                format!(
+                    "Invalid --emit '{}': expected <format>:<destination>, e.g. json:result.json",
+                    spec
+                )
+            })?;
+        let format = match format {
+            "text" => Format::Text,
+            other => <Format as clap::ValueEnum>::from_str(other, true).map_err(|_| {
+                anyhow::anyhow!(
+                    "Invalid --emit format '{}': expected text, json, yaml or protobuf",
+                    other
+                )
+            })?,
+        };
+        let sink = if destination == "stdout"
+            || destination.starts_with("file:")
+            || destination.starts_with("http:")
+            || destination.starts_with("https:")
+        {
+            SinkSpec::parse(destination)?
+        } else {
+            SinkSpec::File(PathBuf::from(destination))
+        };
+        Ok(EmitTarget { format, sink })
+    }
+}
+
+/// Writes a command's primary result to stdout or the `--output-file` /
+/// `--sink` destination, or to every `--emit` target.
+#[derive(Debug, Clone, Default)]
+pub struct Emitter {
+    sink: Option<SinkSpec>,
+    format: Option<Format>,
+    extract: Option<String>,
+    targets: Vec<EmitTarget>,
+    /// Set once [`emit_protobuf`](Emitter::emit_protobuf) has served the
+    /// protobuf targets, which [`emit`](Emitter::emit) then skips.
+    protobuf_sent: Cell<bool>,
+}
+
+impl Emitter {
+    /// Emitter honouring the global `--output-file`, `--sink` and
+    /// `--output-format` flags.
+    pub fn current() -> Self {
+        Emitter {
+            sink: OUTPUT_SINK.get().cloned(),
+            format: OUTPUT_FORMAT.get().copied(),
+            extract: EXTRACT_POINTER.get().cloned(),
+            targets: EMIT_TARGETS.get().cloned().unwrap_or_default(),
+            protobuf_sent: Cell::new(false),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn to_targets(targets: Vec<EmitTarget>) -> Self {
+        Emitter {
+            targets,
+            ..Default::default()
+        }
+    }
+
+    pub fn to_file(path: impl Into<PathBuf>) -> Self {
+        Emitter {
+            sink: Some(SinkSpec::File(path.into())),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_format(self, format: Format) -> Self {
+        Emitter {
+            format: Some(format),
+            ..self
+        }
+    }
+
+    /// True when results leave stdout, a format was requested or a value
+    /// is being extracted; commands should then emit structured data even if
+    /// they would otherwise render a human-readable view.
+    pub fn structured(&self) -> bool {
+        self.sink.is_some()
+            || self.format.is_some()
+            || self.extract.is_some()
+            || !self.targets.is_empty()
+    }
+
+    /// Emit `value`. The format is taken from the output file's extension,
+    /// then the global `--output-format`, then the command's own `format`. With
+    /// `--extract` only the selected value is written, raw. File and HTTP
+    /// sinks print a confirmation to stderr.
+    ///
+    /// With `--emit`, `value` is rendered into every target in turn; a
+    /// failing target doesn't stop the others, and the first error is
+    /// returned once all have been tried.
+    pub fn emit<T: Serialize + ?Sized>(&self, value: &T, format: Format) -> Result<()> {
+        if self.targets.is_empty() {
+            let sink = self.sink.clone().unwrap_or(SinkSpec::Stdout);
+            return self.deliver(&sink, value, self.resolve(format));
+        }
+        let mut first_error = None;
+        for target in &self.targets {
+            if target.format == Format::Protobuf && self.protobuf_sent.get() {
+                continue;
+            }
+            if let Err(e) = self.deliver(&target.sink, value, target.format) {
+                first_error.get_or_insert(e);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    fn deliver<T: Serialize + ?Sized>(
+        &self,
+        sink: &SinkSpec,
+        value: &T,
+        format: Format,
+    ) -> Result<()> {
+        let content = match &self.extract {
+            Some(pointer) => extract(&serde_json::to_value(value)?, pointer)?,
+            None => format.render(value)?,
+        };
+        sink.open().deliver(&content, format)
+    }
+
+    /// Whether `format` is among the outputs of a command whose own default
+    /// is `default`.
+    pub fn emits(&self, format: Format, default: Format) -> bool {
+        if self.targets.is_empty() {
+            self.resolve(default) == format
+        } else {
+            self.targets.iter().any(|t| t.format == format)
+        }
+    }
+
+    /// Whether `format` is the only output of a command whose own default
+    /// is `default`.
+    pub fn emits_only(&self, format: Format, default: Format) -> bool {
+        if self.targets.is_empty() {
+            self.resolve(default) == format
+        } else {
+            self.targets.iter().all(|t| t.format == format)
+        }
+    }
+
+    /// The format [`emit`](Emitter::emit) would use for a command whose
+    /// own default is `format`.
+    pub fn resolve(&self, format: Format) -> Format {
+        self.sink
+            .as_ref()
+            .and_then(SinkSpec::format)
+            .unwrap_or_else(|| self.format.unwrap_or(format))
+    }
+
+    /// Emit `messages` as a length-delimited protobuf stream.
+    pub fn emit_protobuf<M: prost::Message>(&self, messages: &[M]) -> Result<()> {
+        if self.extract.is_some() {
+            anyhow::bail!("--extract can't be combined with protobuf output");
+        }
+        let bytes = crate::proto::encode_stream(messages);
+        self.protobuf_sent.set(true);
+        if self.targets.is_empty() {
+            let sink = self.sink.clone().unwrap_or(SinkSpec::Stdout);
+            return sink.open().deliver_bytes(&bytes, Format::Protobuf);
+        }
+        for target in self.targets.iter().filter(|t| t.format == Format::Protobuf) {
+            target.sink.open().deliver_bytes(&bytes, Format::Protobuf)?;
+        }
+        Ok(())
+    }
+}
+
+/// The subset of a result's fields to output, from `--fields` and/or
+/// `--fields-from-file`. Nested fields use dotted paths (`metadata.name`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Projection {
+    fields: Vec<String>,
+}
+
+impl Projection {
+    /// Combine the inline comma-separated list with the file's entries (one
+    /// or more per line, comma-separated; blank lines and `#` comments are
+    /// ignored). `None` when neither was given.
+    pub fn from_args(inline: Option<&str>, file: Option<&str>) -> Result<Option<Projection>> {
+        if inline.is_none() && file.is_none() {
+            return Ok(None);
+        }
+        let from_file = match file {
+            Some(path) => std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read fields file: {}", path))?,
+            None => String::new(),
+        };
+
+        let mut fields: Vec<String> = Vec::new();
+        let lines = from_file.lines().map(|l| l.split('#').next().unwrap_or(""));
+        for field in inline.into_iter().chain(lines).flat_map(|l| l.split(',')) {
+            let field = field.trim();
+            if !field.is_empty() && !fields.iter().any(|f| f == field) {
+                fields.push(field.to_string());
+            }
+        }
+        if fields.is_empty() {
+            anyhow::bail!("Field projection is empty");
+        }
+        Ok(Some(Projection { fields }))
+    }
+
+    /// Fail listing every field whose top-level name isn't one of `known`.
+    pub fn validate(&self, known: &[&str]) -> Result<()> {
+        let unknown: Vec<&str> = self
+            .fields
+            .iter()
+            .map(String::as_str)
+            .filter(|f| !known.contains(&f.split('.').next().unwrap_or(f)))
+            .collect();
+        if !unknown.is_empty() {
+            anyhow::bail!(
+                "Unknown field(s): {} (available: {})",
+                unknown.join(", "),
+                known.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    /// Copy the projected fields of `value` into a new object, keeping their
+    /// nesting. Fields the value lacks come out as `null` so every record
+    /// has the same shape.
+    pub fn apply(&self, value: &Value) -> Value {
+        let mut out = Map::new();
+        for field in &self.fields {
+            let path: Vec<&str> = field.split('.').collect();
+            let found = path
+                .iter()
+                .try_fold(value, |v, key| v.get(*key))
+                .cloned()
+                .unwrap_or(Value::Null);
+            insert_path(&mut out, &path, found);
+        }
+        Value::Object(out)
+    }
+}
+
+fn insert_path(out: &mut Map<String, Value>, path: &[&str], value: Value) {
+    match path {
+        [] => {}
+        [last] => {
+            out.insert(last.to_string(), value);
+        }
+        [first, rest @ ..] => {
+            let child = out
+                .entry(first.to_string())
+                .or_insert_with(|| Value::Object(Map::new()));
+            // A parent that was itself projected already holds the child.
+            if let Value::Object(child) = child {
+                insert_path(child, rest, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn output_format_accepts_text_for_table() {
+        use clap::ValueEnum;
+        assert_eq!(
+            OutputFormat::from_str("text", true).unwrap(),
+            OutputFormat::Table
+        );
+        assert_eq!(OutputFormat::Yaml.structured(), Some(Format::Yaml));
+        assert_eq!(OutputFormat::Csv.structured(), None);
+    }
+
+    #[test]
+    fn file_extension_selects_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let yaml = dir.path().join("out/result.yml");
+        Emitter::to_file(&yaml)
+            .emit(&json!({ "name": "vault" }), Format::Json)
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&yaml).unwrap(), "name: vault\n");
+
+        let other = dir.path().join("result.txt");
+        Emitter::to_file(&other)
+            .emit(&json!([1]), Format::Json)
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&other).unwrap(), "[\n  1\n]\n");
+
+        let forced = dir.path().join("forced.txt");
+        let emitter = Emitter::to_file(&forced).with_format(Format::Yaml);
+        assert!(emitter.structured());
+        emitter.emit(&json!([1]), Format::Json).unwrap();
+        assert_eq!(std::fs::read_to_string(&forced).unwrap(), "- 1\n");
+    }
+
+    #[test]
+    fn protobuf_goes_out_as_bytes_and_only_where_supported() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("contracts.pb");
+        let emitter = Emitter::to_file(&path);
+        assert_eq!(emitter.resolve(Format::Json), Format::Protobuf);
+
+        let contract = crate::proto::Contract {
+            id: "CABC".into(),
+            ..Default::default()
+        };
+        emitter
+            .emit_protobuf(std::slice::from_ref(&contract))
+            .unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(
+            crate::proto::decode_stream::<crate::proto::Contract>(&bytes).unwrap(),
+            [contract]
+        );
+        assert!(emitter.emit(&json!({}), Format::Json).is_err());
+    }
+
+    #[test]
+    fn emit_targets_each_get_the_same_result() {
+        let dir = tempfile::tempdir().unwrap();
+        let json_path = dir.path().join("result.json");
+        let text_path = dir.path().join("summary.txt");
+        let targets = [
+            format!("json:{}", json_path.display()),
+            format!("text:file:{}", text_path.display()),
+        ];
+        let emitter = Emitter::to_targets(
+            targets
+                .iter()
+                .map(|t| EmitTarget::parse(t))
+                .collect::<Result<_>>()
+                .unwrap(),
+        );
+        assert!(emitter.structured());
+        assert!(!emitter.emits(Format::Protobuf, Format::Json));
+
+        let result = json!({
+            "name": "vault",
+            "stats": { "downloads": 42 },
+            "tags": ["defi", "vault"],
+            "versions": [{ "version": "1.0.0", "verified": true }, { "version": "1.1.0" }],
+        });
+        emitter.emit(&result, Format::Json).unwrap();
+        let written: Value =
+            serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(written, result);
+        assert_eq!(
+            std::fs::read_to_string(&text_path).unwrap(),
+            "name: vault
+stats:
+  downloads: 42
+tags: defi, vault
+versions:
+  verified  version
+  --------  -------
+  true      1.0.0
+  -         1.1.0
+"
+        );
+
+        assert_eq!(
+            EmitTarget::parse("yaml:https://collector.example/in").unwrap(),
+            EmitTarget {
+                format: Format::Yaml,
+                sink: SinkSpec::Http(reqwest::Url::parse("https://collector.example/in").unwrap()),
+            }
+        );
+        assert_eq!(
+            EmitTarget::parse("text:stdout").unwrap().sink,
+            SinkSpec::Stdout
+        );
+        assert!(EmitTarget::parse("csv:out.csv").is_err());
+        assert!(EmitTarget::parse("json:").is_err());
+        assert!(set_emit_targets(&["text:stdout".into(), "json:stdout".into()]).is_err());
+
+        let protobuf = Emitter::to_targets(vec![EmitTarget::parse("protobuf:out.pb").unwrap()]);
+        assert!(protobuf.emits_only(Format::Protobuf, Format::Json));
+        assert!(protobuf.emit(&result, Format::Json).is_err());
+    }
+
+    #[test]
+    fn parses_sink_specs() {
+        assert_eq!(SinkSpec::parse("stdout").unwrap(), SinkSpec::Stdout);
+        assert_eq!(
+            SinkSpec::parse("file:out/result.yaml").unwrap(),
+            SinkSpec::File(PathBuf::from("out/result.yaml"))
+        );
+        let url = reqwest::Url::parse("https://collector.example/ingest").unwrap();
+        assert_eq!(
+            SinkSpec::parse("http:https://collector.example/ingest").unwrap(),
+            SinkSpec::Http(url.clone())
+        );
+        assert_eq!(
+            SinkSpec::parse("https://collector.example/ingest").unwrap(),
+            SinkSpec::Http(url)
+        );
+        assert!(SinkSpec::parse("http:ftp://collector.example").is_err());
+        assert!(SinkSpec::parse("file:").is_err());
+        assert!(SinkSpec::parse("kafka:topic").is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sink.json");
+        SinkSpec::parse(&format!("file:{}", path.display()))
+            .unwrap()
+            .open()
+            .deliver("{}", Format::Json)
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{}\n");
+    }
+
+    #[test]
+    fn extract_selects_one_value_by_json_pointer() {
+        let result = json!({
+            "wasm_hash": "abc123",
+            "stats": { "downloads": 42, "verified": true },
+            "tags": ["defi", "a/b"],
+            "a/b": { "x~y": null },
+        });
+        assert_eq!(extract(&result, "/wasm_hash").unwrap(), "abc123");
+        assert_eq!(extract(&result, "/stats/downloads").unwrap(), "42");
+        assert_eq!(extract(&result, "/stats/verified").unwrap(), "true");
+        assert_eq!(extract(&result, "/tags/1").unwrap(), "a/b");
+        assert_eq!(extract(&result, "/a~1b/x~0y").unwrap(), "null");
+        assert_eq!(
+            extract(&result, "/tags").unwrap(),
+            "[\n  \"defi\",\n  \"a/b\"\n]"
+        );
+        assert!(extract(&result, "/missing").is_err());
+        assert!(set_extract(Some("wasm_hash")).is_err());
+    }
+
+    #[test]
+    fn yaml_list_output_round_trips() {
+        // Values YAML would otherwise read as booleans, nulls, numbers or
+        // mappings must come back as the same strings.
+        let result = json!({ "contracts": [
+            { "id": "CABC", "name": "yes", "network": "testnet", "is_verified": true },
+            { "id": "0123", "name": "a: b", "network": "null", "is_verified": false }
+        ]});
+        let yaml = Format::Yaml.render(&result).unwrap();
+        let parsed: serde_json::Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed, result);
+    }
+
+    #[test]
+    fn projection_merges_sources_and_keeps_nesting() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("fields.txt");
+        std::fs::write(&file, "# pipeline columns\nname\nmetadata.owner, id\n\n").unwrap();
+        let projection = Projection::from_args(Some("id,network"), Some(file.to_str().unwrap()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            projection.fields,
+            ["id", "network", "name", "metadata.owner"]
+        );
+
+        assert!(projection
+            .validate(&["id", "name", "network", "metadata"])
+            .is_ok());
+        let err = projection.validate(&["id", "name"]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unknown field(s): network, metadata.owner"));
+
+        let record = json!({ "id": "C1", "name": "vault", "metadata": { "owner": "G1", "x": 1 } });
+        assert_eq!(
+            projection.apply(&record),
+            json!({ "id": "C1", "network": null, "name": "vault", "metadata": { "owner": "G1" } })
+        );
+        assert!(Projection::from_args(None, None).unwrap().is_none());
+        assert!(Projection::from_args(Some(" , "), None).is_err());
+    }
+}
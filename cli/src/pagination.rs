@@ -0,0 +1,46 @@
+//! `--page` / `--offset` paging for `search` and `list`.
+
+use anyhow::{bail, Result};
+
+/// Rows to skip: `(page - 1) * limit` for a 1-based `page`, else `offset`.
+pub fn offset(page: Option<usize>, offset: Option<usize>, limit: usize) -> Result<usize> {
+    match (page, offset) {
+        (Some(_), Some(_)) => bail!("--page and --offset can't be used together"),
+        (Some(0), None) => bail!("--page must be at least 1 (pages are numbered from 1)"),
+        (Some(page), None) => Ok((page - 1) * limit),
+        (None, offset) => Ok(offset.unwrap_or(0)),
+    }
+}
+
+/// Table footer such as `Showing 11–20 of 143`; the total is left out when
+/// the registry doesn't report one.
+pub fn footer(offset: usize, shown: usize, total: Option<u64>) -> String {
+    let range = if shown == 0 {
+        "Showing 0".to_string()
+    } else {
+        format!("Showing {}–{}", offset + 1, offset + shown)
+    };
+    match total {
+        Some(total) => format!("{} of {}", range, total),
+        None => range,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pages_map_to_offsets() {
+        assert_eq!(offset(Some(2), None, 10).unwrap(), 10);
+        assert_eq!(offset(Some(1), None, 10).unwrap(), 0);
+        assert_eq!(offset(None, Some(7), 10).unwrap(), 7);
+        assert_eq!(offset(None, None, 10).unwrap(), 0);
+        assert!(offset(Some(0), None, 10).is_err());
+        assert!(offset(Some(2), Some(5), 10).is_err());
+
+        assert_eq!(footer(10, 10, Some(143)), "Showing 11–20 of 143");
+        assert_eq!(footer(0, 3, None), "Showing 1–3");
+        assert_eq!(footer(140, 0, Some(143)), "Showing 0 of 143");
+    }
+}
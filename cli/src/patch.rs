@@ -1,12 +1,17 @@
 use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
 use std::str::FromStr;
 
-use anyhow::{bail, Result};
-use chrono::{DateTime, Utc};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Ordered most severe first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
     Critical,
@@ -54,6 +59,29 @@ pub struct SecurityPatch {
     pub created_at: DateTime<Utc>,
 }
 
+/// One row of `patch list`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PatchListing {
+    #[serde(flatten)]
+    pub patch: SecurityPatch,
+    /// The publisher's contracts running the patch's target build; `None`
+    /// when no publisher was given to check against.
+    pub affected_contracts: Option<Vec<String>>,
+}
+
+/// IDs of the `contracts` whose current build is the one `patch` replaces.
+pub fn affected_by(patch: &SecurityPatch, contracts: &[serde_json::Value]) -> Vec<String> {
+    contracts
+        .iter()
+        .filter(|c| {
+            c["wasm_hash"]
+                .as_str()
+                .is_some_and(|hash| hash.eq_ignore_ascii_case(patch.target_version.trim()))
+        })
+        .filter_map(|c| c["contract_id"].as_str().map(str::to_string))
+        .collect()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PatchAudit {
     pub id: Uuid,
@@ -62,15 +90,252 @@ pub struct PatchAudit {
     pub applied_at: DateTime<Utc>,
 }
 
+/// File under `~/.soroban-registry` where applied patches are logged.
+const PATCH_LOG_FILE: &str = "patch-log.jsonl";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchAction {
+    Apply,
+    Rollback,
+}
+
+/// One line of the local patch log. The registry doesn't keep the build a
+/// contract ran before a patch, so `patch apply` records it here for
+/// `patch rollback` to restore.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatchLogEntry {
+    pub action: PatchAction,
+    pub contract_id: String,
+    pub patch_id: String,
+    /// The build before this action.
+    pub previous_wasm_hash: String,
+    /// The build after it.
+    pub wasm_hash: String,
+    pub at: DateTime<Utc>,
+}
+
+fn patch_log_path() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Cannot determine home directory")?;
+    Ok(home.join(".soroban-registry").join(PATCH_LOG_FILE))
+}
+
+pub fn read_patch_log() -> Result<Vec<PatchLogEntry>> {
+    let path = patch_log_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read patch log {}", path.display()))?;
+    raw.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Corrupt entry in patch log {}", path.display()))
+        })
+        .collect()
+}
+
+fn append_patch_log(entry: &PatchLogEntry) -> Result<()> {
+    let path = patch_log_path()?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open patch log {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+        .with_context(|| format!("Failed to write patch log {}", path.display()))?;
+    Ok(())
+}
+
+/// The application of `patch_id` to `contract_id` that a rollback would
+/// undo: the latest logged one, unless it was already rolled back.
+///
+/// Fails when a later apply or rollback moved the contract off that
+/// patch's build, since restoring the pre-patch build would silently undo
+/// the later change too.
+pub fn rollback_target<'a>(
+    log: &'a [PatchLogEntry],
+    contract_id: &str,
+    patch_id: &str,
+) -> Result<Option<&'a PatchLogEntry>> {
+    let mut history = log.iter().rev().filter(|e| e.contract_id == contract_id);
+    let Some(latest) = history.clone().next() else {
+        return Ok(None);
+    };
+    let Some(applied) = history
+        .find(|e| e.patch_id == patch_id)
+        .filter(|e| e.action == PatchAction::Apply)
+    else {
+        return Ok(None);
+    };
+    if latest.wasm_hash != applied.wasm_hash {
+        bail!(
+            "contract {} no longer runs the build patch {} applied: patch {} was {} after it \
+             (now {}). Roll that back first",
+            contract_id,
+            patch_id,
+            latest.patch_id,
+            match latest.action {
+                PatchAction::Apply => "applied",
+                PatchAction::Rollback => "rolled back",
+            },
+            latest.wasm_hash
+        );
+    }
+    Ok(Some(applied))
+}
+
+/// Outcome of checking a patch's referenced wasm build.
+#[derive(Debug, Clone)]
+pub struct PatchVerification {
+    pub patch: SecurityPatch,
+    pub wasm_size: usize,
+    pub computed_hash: String,
+    pub hash_matches: bool,
+    /// `None` when the wasm is a valid Soroban module.
+    pub module_error: Option<String>,
+}
+
+impl PatchVerification {
+    pub fn passed(&self) -> bool {
+        self.hash_matches && self.module_error.is_none()
+    }
+}
+
+/// One stage of a phased rollout: patch up to `percent` of the affected
+/// contracts, then hold for `soak` before the next stage starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RolloutStage {
+    pub percent: u8,
+    pub soak: Duration,
+}
+
+/// A phased rollout, written `<percent>%[:<soak>]` per stage and
+/// comma-separated, e.g. `10%:2h,50%:1d,100%`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RolloutSchedule {
+    pub stages: Vec<RolloutStage>,
+}
+
+impl FromStr for RolloutSchedule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut stages: Vec<RolloutStage> = Vec::new();
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (percent, soak) = match part.split_once(':') {
+                Some((percent, soak)) => (percent, Some(soak)),
+                None => (part, None),
+            };
+            let percent: u8 = match percent.trim().trim_end_matches('%').parse() {
+                Ok(p) if (1..=100).contains(&p) => p,
+                _ => bail!(
+                    "invalid rollout stage: {} (expected a percentage from 1 to 100)",
+                    part
+                ),
+            };
+            if let Some(prev) = stages.last() {
+                if percent <= prev.percent {
+                    bail!(
+                        "rollout stages must increase: {}% follows {}%",
+                        percent,
+                        prev.percent
+                    );
+                }
+            }
+            let soak = match soak {
+                Some(soak) => crate::activity::parse_window(soak)
+                    .map_err(|e| anyhow::anyhow!("invalid soak time in {}: {}", part, e))?,
+                None => Duration::zero(),
+            };
+            stages.push(RolloutStage { percent, soak });
+        }
+        if stages.is_empty() {
+            bail!("rollout schedule has no stages (expected e.g. 10%:2h,50%:1d,100%)");
+        }
+        Ok(RolloutSchedule { stages })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StageProjection {
+    pub percent: u8,
+    pub starts_at: DateTime<Utc>,
+    /// Contracts patched in this stage.
+    pub patched: usize,
+    /// Contracts patched so far, this stage included.
+    pub cumulative: usize,
+}
+
+/// Projected timeline of a [`RolloutSchedule`] over a set of affected
+/// contracts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RolloutProjection {
+    pub affected: usize,
+    pub stages: Vec<StageProjection>,
+    /// Affected contracts the schedule never reaches.
+    pub unpatched: usize,
+    /// When the last stage's soak ends.
+    pub completes_at: DateTime<Utc>,
+}
+
+impl RolloutSchedule {
+    /// Stages are assumed to patch their share as soon as they start; each
+    /// then waits out its soak before the next begins.
+    pub fn project(&self, affected: usize, start: DateTime<Utc>) -> RolloutProjection {
+        let mut at = start;
+        let mut cumulative = 0;
+        let mut stages = Vec::with_capacity(self.stages.len());
+        for stage in &self.stages {
+            let quota = PatchManager::rollout_quota(affected, stage.percent);
+            stages.push(StageProjection {
+                percent: stage.percent,
+                starts_at: at,
+                patched: quota - cumulative,
+                cumulative: quota,
+            });
+            cumulative = quota;
+            at += stage.soak;
+        }
+        RolloutProjection {
+            affected,
+            stages,
+            unpatched: affected - cumulative,
+            completes_at: at,
+        }
+    }
+}
+
 pub struct PatchManager;
 
 impl PatchManager {
+    /// Contracts a rollout at `rollout_pct` may patch, rounded up.
+    pub fn rollout_quota(total: usize, rollout_pct: u8) -> usize {
+        (total as f64 * rollout_pct as f64 / 100.0).ceil() as usize
+    }
+
     pub fn check_rollout(applied: usize, total: usize, rollout_pct: u8) -> bool {
         if total == 0 {
             return false;
         }
-        let max_allowed = (total as f64 * rollout_pct as f64 / 100.0).ceil() as usize;
-        applied < max_allowed
+        applied < Self::rollout_quota(total, rollout_pct)
+    }
+
+    /// A contract's fixed place in every staged rollout, 0-99, from a hash
+    /// of its ID. The same contracts go first whatever the patch.
+    pub fn rollout_bucket(contract_id: &str) -> u8 {
+        let digest = Sha256::digest(contract_id.trim().as_bytes());
+        let head = u64::from_be_bytes(digest[..8].try_into().expect("8 bytes"));
+        (head % 100) as u8
+    }
+
+    /// Whether a rollout at `rollout_pct` has reached `contract_id`.
+    pub fn in_cohort(contract_id: &str, rollout_pct: u8) -> bool {
+        Self::rollout_bucket(contract_id) < rollout_pct
     }
 
     pub async fn create(
@@ -132,7 +397,140 @@ impl PatchManager {
         Ok((patch, contracts))
     }
 
-    pub async fn apply(api_url: &str, contract_id: &str, patch_id: &str) -> Result<PatchAudit> {
+    pub async fn get(api_url: &str, patch_id: &str) -> Result<SecurityPatch> {
+        let client = reqwest::Client::new();
+        let resp = client
+            .get(format!("{}/api/patches/{}", api_url, patch_id))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            bail!("patch not found: {}", patch_id);
+        }
+
+        Ok(resp.json().await?)
+    }
+
+    /// Patches that target `wasm_hash`, i.e. ones a contract running that
+    /// build has yet to apply.
+    pub async fn pending_for(api_url: &str, wasm_hash: &str) -> Result<Vec<SecurityPatch>> {
+        Self::list(api_url, None, Some(wasm_hash)).await
+    }
+
+    /// Published patches, most severe and then newest first. The filters
+    /// are sent to the registry and applied again here for registries that
+    /// ignore them.
+    pub async fn list(
+        api_url: &str,
+        severity: Option<Severity>,
+        target_version: Option<&str>,
+    ) -> Result<Vec<SecurityPatch>> {
+        let mut query = Vec::new();
+        if let Some(severity) = severity {
+            query.push(("severity", severity.to_string().to_lowercase()));
+        }
+        if let Some(version) = target_version {
+            query.push(("target_version", version.to_string()));
+        }
+        let resp = reqwest::Client::new()
+            .get(format!("{}/api/patches", api_url))
+            .query(&query)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            bail!("failed to list patches: {}", resp.status());
+        }
+
+        let data: serde_json::Value = resp.json().await?;
+        let items = data.get("items").cloned().unwrap_or(data);
+        let mut patches: Vec<SecurityPatch> = serde_json::from_value(items)?;
+        patches.retain(|p| {
+            severity.is_none_or(|s| p.severity == s)
+                && target_version.is_none_or(|v| p.target_version.eq_ignore_ascii_case(v.trim()))
+        });
+        patches.sort_by(|a, b| {
+            a.severity
+                .cmp(&b.severity)
+                .then(b.created_at.cmp(&a.created_at))
+        });
+        Ok(patches)
+    }
+
+    /// Download the wasm referenced by a patch and check that its hash and
+    /// structure match what the patch claims. `wasm_url` overrides the
+    /// default `/api/wasm/{hash}` download location.
+    pub async fn verify(
+        api_url: &str,
+        patch_id: &str,
+        wasm_url: Option<&str>,
+    ) -> Result<PatchVerification> {
+        let patch = Self::get(api_url, patch_id).await?;
+
+        let url = wasm_url
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{}/api/wasm/{}", api_url, patch.new_wasm_hash));
+        let resp = reqwest::Client::new().get(&url).send().await?;
+        if !resp.status().is_success() {
+            bail!(
+                "wasm {} is not fetchable from {}: {}",
+                patch.new_wasm_hash,
+                url,
+                resp.status()
+            );
+        }
+        let bytes = resp.bytes().await?;
+
+        let computed_hash = crate::wasm::sha256_hex(&bytes);
+        let hash_matches = computed_hash.eq_ignore_ascii_case(patch.new_wasm_hash.trim());
+        let module_error = crate::wasm::validate_soroban_module(&bytes)
+            .err()
+            .map(|e| e.to_string());
+
+        Ok(PatchVerification {
+            patch,
+            wasm_size: bytes.len(),
+            computed_hash,
+            hash_matches,
+            module_error,
+        })
+    }
+
+    /// The build `contract_id` currently runs, per the registry.
+    async fn current_wasm_hash(
+        client: &reqwest::Client,
+        api_url: &str,
+        contract_id: &str,
+    ) -> Result<String> {
+        let resp = client
+            .get(format!("{}/api/contracts/{}", api_url, contract_id))
+            .send()
+            .await
+            .context("Failed to reach registry API")?;
+        if !resp.status().is_success() {
+            bail!(
+                "failed to fetch contract {}: {}",
+                contract_id,
+                resp.status()
+            );
+        }
+        let contract: serde_json::Value = resp.json().await?;
+        contract["wasm_hash"]
+            .as_str()
+            .map(str::to_string)
+            .with_context(|| format!("registry reports no wasm_hash for {}", contract_id))
+    }
+
+    /// Apply `patch_id` to `contract_id`, logging the build it replaces
+    /// for [`PatchManager::rollback`]. Also returns that build. Without it
+    /// the patch couldn't be rolled back, so the apply is refused unless
+    /// `force` is set, and then `None` is returned.
+    pub async fn apply(
+        api_url: &str,
+        contract_id: &str,
+        patch_id: &str,
+        force: bool,
+    ) -> Result<(PatchAudit, Option<String>)> {
         let client = reqwest::Client::new();
 
         let patch_resp = client
@@ -146,6 +544,32 @@ impl PatchManager {
 
         let patch: SecurityPatch = patch_resp.json().await?;
 
+        if !force && !Self::in_cohort(contract_id, patch.rollout_percentage) {
+            bail!(
+                "contract {} is not in the rollout cohort of patch {}: it is in bucket {} and \
+                 the patch is rolled out to buckets below {}%. Raise the rollout with \
+                 `patch promote --patch-id {} --rollout <n>`, or pass --force to apply it anyway",
+                contract_id,
+                patch_id,
+                Self::rollout_bucket(contract_id),
+                patch.rollout_percentage,
+                patch_id
+            );
+        }
+
+        // Read before applying: afterwards the registry reports the new build.
+        let previous = match Self::current_wasm_hash(&client, api_url, contract_id).await {
+            Ok(hash) => Some(hash),
+            Err(_) if force => None,
+            Err(err) => {
+                return Err(err.context(format!(
+                    "can't record the build {} runs before patching, so patch {} couldn't be \
+                     rolled back; pass --force to apply it anyway",
+                    contract_id, patch_id
+                )))
+            }
+        };
+
         let audits_resp = client
             .get(format!("{}/api/patches/{}/audits", api_url, patch_id))
             .send()
@@ -169,7 +593,7 @@ impl PatchManager {
             bail!(
                 "rollout quota exceeded: {}/{} ({}% of {} eligible)",
                 applied,
-                (total as f64 * patch.rollout_percentage as f64 / 100.0).ceil() as usize,
+                Self::rollout_quota(total, patch.rollout_percentage),
                 patch.rollout_percentage,
                 total
             );
@@ -189,8 +613,84 @@ impl PatchManager {
         if !resp.status().is_success() {
             bail!("failed to apply patch: {}", resp.text().await?);
         }
+        let audit: PatchAudit = resp.json().await?;
+
+        if let Some(previous_wasm_hash) = &previous {
+            append_patch_log(&PatchLogEntry {
+                action: PatchAction::Apply,
+                contract_id: contract_id.to_string(),
+                patch_id: patch_id.to_string(),
+                previous_wasm_hash: previous_wasm_hash.clone(),
+                wasm_hash: patch.new_wasm_hash.clone(),
+                at: audit.applied_at,
+            })?;
+        }
 
-        Ok(resp.json().await?)
+        Ok((audit, previous))
+    }
+
+    /// Widen a staged rollout to `rollout_pct`; rollouts only grow. Returns
+    /// the updated patch and the percentage it had before.
+    pub async fn promote(
+        api_url: &str,
+        patch_id: &str,
+        rollout_pct: u8,
+    ) -> Result<(SecurityPatch, u8)> {
+        if !(1..=100).contains(&rollout_pct) {
+            bail!("rollout must be between 1 and 100, got {}", rollout_pct);
+        }
+        let patch = Self::get(api_url, patch_id).await?;
+        if rollout_pct <= patch.rollout_percentage {
+            bail!(
+                "patch {} is already rolled out to {}%; promote it to a higher percentage",
+                patch_id,
+                patch.rollout_percentage
+            );
+        }
+
+        let resp = reqwest::Client::new()
+            .patch(format!("{}/api/patches/{}", api_url, patch_id))
+            .json(&serde_json::json!({ "rollout_percentage": rollout_pct }))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            bail!("failed to promote patch: {}", resp.text().await?);
+        }
+
+        Ok((resp.json().await?, patch.rollout_percentage))
+    }
+
+    /// Put a contract back on the build it ran before `applied`, and log
+    /// the rollback.
+    pub async fn rollback(api_url: &str, applied: &PatchLogEntry) -> Result<PatchLogEntry> {
+        let payload = serde_json::json!({
+            "contract_id": applied.contract_id,
+            "wasm_hash": applied.previous_wasm_hash,
+        });
+        let resp = reqwest::Client::new()
+            .post(format!(
+                "{}/api/patches/{}/rollback",
+                api_url, applied.patch_id
+            ))
+            .json(&payload)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            bail!("failed to roll back patch: {}", resp.text().await?);
+        }
+
+        let entry = PatchLogEntry {
+            action: PatchAction::Rollback,
+            contract_id: applied.contract_id.clone(),
+            patch_id: applied.patch_id.clone(),
+            previous_wasm_hash: applied.wasm_hash.clone(),
+            wasm_hash: applied.previous_wasm_hash.clone(),
+            at: Utc::now(),
+        };
+        append_patch_log(&entry)?;
+        Ok(entry)
     }
 }
 
@@ -245,6 +745,111 @@ mod tests {
         assert!(!PatchManager::check_rollout(2, 3, 50));
     }
 
+    #[test]
+    fn rollout_projection_follows_the_schedule() {
+        let schedule: RolloutSchedule = "10%:2h, 50%:1d, 100%".parse().unwrap();
+        let start = DateTime::parse_from_rfc3339("2024-06-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let projection = schedule.project(25, start);
+
+        let counts: Vec<_> = projection
+            .stages
+            .iter()
+            .map(|s| (s.patched, s.cumulative))
+            .collect();
+        assert_eq!(counts, [(3, 3), (10, 13), (12, 25)]);
+        assert_eq!(projection.stages[2].starts_at, start + Duration::hours(26));
+        assert_eq!(projection.completes_at, start + Duration::hours(26));
+        assert_eq!(projection.unpatched, 0);
+
+        let partial: RolloutSchedule = "20%:1h".parse().unwrap();
+        assert_eq!(partial.project(10, start).unpatched, 8);
+        assert!("50%,10%".parse::<RolloutSchedule>().is_err());
+        assert!("0%".parse::<RolloutSchedule>().is_err());
+        assert!("10%:soon".parse::<RolloutSchedule>().is_err());
+        assert!("".parse::<RolloutSchedule>().is_err());
+    }
+
+    #[test]
+    fn affected_contracts_run_the_target_build() {
+        let patch = SecurityPatch {
+            id: Uuid::nil(),
+            target_version: "ABC123".into(),
+            severity: Severity::High,
+            new_wasm_hash: "def456".into(),
+            rollout_percentage: 50,
+            description: None,
+            created_at: Utc::now(),
+        };
+        let contracts = [
+            serde_json::json!({ "contract_id": "CVAULT", "wasm_hash": "abc123" }),
+            serde_json::json!({ "contract_id": "CTOKEN", "wasm_hash": "def456" }),
+            serde_json::json!({ "contract_id": "CNEW" }),
+        ];
+        assert_eq!(affected_by(&patch, &contracts), ["CVAULT"]);
+        assert!(affected_by(&patch, &[]).is_empty());
+        assert!(Severity::Critical < Severity::Low);
+    }
+
+    #[test]
+    fn rollback_undoes_the_latest_unreverted_apply() {
+        let entry = |action, patch_id: &str, previous: &str, now: &str| PatchLogEntry {
+            action,
+            contract_id: "CVAULT".into(),
+            patch_id: patch_id.into(),
+            previous_wasm_hash: previous.into(),
+            wasm_hash: now.into(),
+            at: Utc::now(),
+        };
+        let mut log = vec![
+            entry(PatchAction::Apply, "p1", "aaa", "bbb"),
+            entry(PatchAction::Apply, "p2", "bbb", "ccc"),
+        ];
+        // p2 was applied on top of p1: undoing p1 would drop p2 as well.
+        let err = rollback_target(&log, "CVAULT", "p1").unwrap_err();
+        assert!(err.to_string().contains("patch p2 was applied after it"));
+        assert!(rollback_target(&log, "CTOKEN", "p1").unwrap().is_none());
+        assert!(rollback_target(&log, "CVAULT", "p3").unwrap().is_none());
+
+        log.push(entry(PatchAction::Rollback, "p2", "ccc", "bbb"));
+        assert!(rollback_target(&log, "CVAULT", "p2").unwrap().is_none());
+        let target = rollback_target(&log, "CVAULT", "p1").unwrap().unwrap();
+        assert_eq!(target.previous_wasm_hash, "aaa");
+        log.push(entry(PatchAction::Apply, "p2", "bbb", "ccc"));
+        assert_eq!(
+            rollback_target(&log, "CVAULT", "p2")
+                .unwrap()
+                .unwrap()
+                .previous_wasm_hash,
+            "bbb"
+        );
+    }
+
+    #[test]
+    fn rollout_cohorts_are_stable_and_only_grow() {
+        let ids: Vec<String> = (0..200).map(|i| format!("contract-{}", i)).collect();
+        for id in &ids {
+            let bucket = PatchManager::rollout_bucket(id);
+            assert!(bucket < 100);
+            assert_eq!(bucket, PatchManager::rollout_bucket(id));
+            assert!(PatchManager::in_cohort(id, 100));
+            assert!(!PatchManager::in_cohort(id, 0));
+            if PatchManager::in_cohort(id, 25) {
+                assert!(PatchManager::in_cohort(id, 50));
+            }
+        }
+        let canaries = ids
+            .iter()
+            .filter(|id| PatchManager::in_cohort(id, 25))
+            .count();
+        assert!(
+            (20..=80).contains(&canaries),
+            "{} of 200 in a 25% cohort",
+            canaries
+        );
+    }
+
     #[test]
     fn rollout_empty_total() {
         assert!(!PatchManager::check_rollout(0, 0, 100));
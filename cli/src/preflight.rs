@@ -0,0 +1,392 @@
+//! `publish --preflight`: every pre-publish check in one consolidated
+//! report, so a publish only goes out when all of them pass.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::publish_checks::PublishFields;
+
+/// Largest contract wasm the network accepts (`contract_max_size_bytes`).
+pub const MAX_WASM_SIZE_BYTES: usize = 128 * 1024;
+
+/// Build output directories searched for the contract's wasm.
+const WASM_TARGET_DIRS: &[&str] = &[
+    "target/wasm32v1-none/release",
+    "target/wasm32-unknown-unknown/release",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+    /// Not applicable to this contract, or its input isn't available.
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Check {
+            name,
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PreflightReport {
+    pub checks: Vec<Check>,
+}
+
+impl PreflightReport {
+    /// True when no check failed; warnings and skipped checks don't block.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|c| c.status != CheckStatus::Fail)
+    }
+
+    pub fn failures(&self) -> usize {
+        self.checks
+            .iter()
+            .filter(|c| c.status == CheckStatus::Fail)
+            .count()
+    }
+
+    pub fn print(&self) {
+        println!("\n{}", "Preflight Report".bold().cyan());
+        println!("{}", "=".repeat(80).cyan());
+        for check in &self.checks {
+            let glyph = match check.status {
+                CheckStatus::Pass => "✓".green(),
+                CheckStatus::Warn => "⚠".yellow(),
+                CheckStatus::Fail => "✗".red(),
+                CheckStatus::Skipped => "→".bright_black(),
+            };
+            println!(
+                "  {} {:<12} {}",
+                glyph,
+                check.name.bold(),
+                check.detail.bright_black()
+            );
+        }
+        println!("{}", "=".repeat(80).cyan());
+    }
+}
+
+/// What a publish is about to submit.
+pub struct PreflightInput<'a> {
+    pub contract_id: &'a str,
+    pub network: &'a str,
+    pub fields: PublishFields<'a>,
+    /// Fields `--strict` would require.
+    pub required: &'a [String],
+}
+
+/// The contract's wasm: `contract_path` itself when it is a `.wasm` file,
+/// otherwise the single wasm in the project's release build output.
+pub fn locate_wasm(contract_path: &Path) -> Option<PathBuf> {
+    if contract_path.extension().is_some_and(|e| e == "wasm") {
+        return contract_path.is_file().then(|| contract_path.to_path_buf());
+    }
+    WASM_TARGET_DIRS.iter().find_map(|dir| {
+        let (mut optimized, mut plain): (Vec<PathBuf>, Vec<PathBuf>) =
+            std::fs::read_dir(contract_path.join(dir))
+                .ok()?
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|e| e == "wasm"))
+                .partition(|p| p.to_string_lossy().ends_with(".optimized.wasm"));
+        // `publish --optimize` leaves x.optimized.wasm next to x.wasm.
+        if optimized.len() == 1 {
+            return Some(optimized.remove(0));
+        }
+        (plain.len() == 1).then(|| plain.remove(0))
+    })
+}
+
+fn check_manifest(contract_path: &Path) -> Check {
+    const NAME: &str = "manifest";
+    if contract_path.extension().is_some_and(|e| e == "wasm") {
+        return Check::new(NAME, CheckStatus::Skipped, "publishing a prebuilt wasm");
+    }
+    let raw = match std::fs::read_to_string(contract_path.join("Cargo.toml")) {
+        Ok(raw) => raw,
+        Err(_) => return Check::new(NAME, CheckStatus::Fail, "no Cargo.toml in contract path"),
+    };
+    let manifest = match raw.parse::<toml::Value>() {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            return Check::new(
+                NAME,
+                CheckStatus::Fail,
+                format!("invalid Cargo.toml: {}", e),
+            )
+        }
+    };
+    let Some(name) = manifest
+        .get("package")
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str())
+    else {
+        return Check::new(NAME, CheckStatus::Fail, "Cargo.toml has no [package] name");
+    };
+    let cdylib = manifest
+        .get("lib")
+        .and_then(|l| l.get("crate-type"))
+        .and_then(|t| t.as_array())
+        .is_some_and(|types| types.iter().any(|t| t.as_str() == Some("cdylib")));
+    if !cdylib {
+        return Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("{}: [lib] crate-type must include \"cdylib\"", name),
+        );
+    }
+    Check::new(NAME, CheckStatus::Pass, name)
+}
+
+fn check_metadata(input: &PreflightInput<'_>) -> Check {
+    const NAME: &str = "metadata";
+    match crate::publish_checks::checklist(input.required, &input.fields) {
+        Ok(items) => {
+            let missing: Vec<&str> = items
+                .iter()
+                .filter(|i| !i.passed)
+                .map(|i| i.field.as_str())
+                .collect();
+            if missing.is_empty() {
+                Check::new(NAME, CheckStatus::Pass, "all required fields present")
+            } else {
+                Check::new(
+                    NAME,
+                    CheckStatus::Fail,
+                    format!("missing: {}", missing.join(", ")),
+                )
+            }
+        }
+        Err(e) => Check::new(NAME, CheckStatus::Fail, e.to_string()),
+    }
+}
+
+fn check_contract_id(contract_id: &str) -> Check {
+    const NAME: &str = "contract id";
+    match stellar_strkey::Contract::from_string(contract_id) {
+        Ok(_) => Check::new(NAME, CheckStatus::Pass, contract_id),
+        Err(_) => Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("{} is not a valid C... contract address", contract_id),
+        ),
+    }
+}
+
+fn check_wasm(wasm: Option<&(PathBuf, Vec<u8>)>) -> Check {
+    const NAME: &str = "wasm";
+    let Some((path, bytes)) = wasm else {
+        return Check::new(
+            NAME,
+            CheckStatus::Skipped,
+            "no built wasm found; run `stellar contract build`",
+        );
+    };
+    match crate::wasm::validate_soroban_module(bytes) {
+        Ok(_) => Check::new(
+            NAME,
+            CheckStatus::Pass,
+            format!(
+                "{} (sha256 {})",
+                path.display(),
+                crate::wasm::sha256_hex(bytes)
+            ),
+        ),
+        Err(e) => Check::new(NAME, CheckStatus::Fail, e.to_string()),
+    }
+}
+
+fn check_size(wasm: Option<&(PathBuf, Vec<u8>)>) -> Check {
+    const NAME: &str = "size";
+    let Some((_, bytes)) = wasm else {
+        return Check::new(NAME, CheckStatus::Skipped, "no wasm to measure");
+    };
+    let detail = format!("{} of {} bytes", bytes.len(), MAX_WASM_SIZE_BYTES);
+    if bytes.len() > MAX_WASM_SIZE_BYTES {
+        Check::new(NAME, CheckStatus::Fail, detail)
+    } else if bytes.len() > MAX_WASM_SIZE_BYTES * 9 / 10 {
+        Check::new(NAME, CheckStatus::Warn, format!("{} (over 90%)", detail))
+    } else {
+        Check::new(NAME, CheckStatus::Pass, detail)
+    }
+}
+
+/// Whether the registry already has this contract on the target network.
+async fn check_duplicate(
+    client: &reqwest::Client,
+    api_url: &str,
+    input: &PreflightInput<'_>,
+) -> Check {
+    const NAME: &str = "duplicate";
+    let response = client
+        .get(format!("{}/api/contracts/{}", api_url, input.contract_id))
+        .query(&[("network", input.network)])
+        .send()
+        .await;
+    match response {
+        Ok(r) if r.status() == reqwest::StatusCode::NOT_FOUND => {
+            Check::new(NAME, CheckStatus::Pass, "not yet registered")
+        }
+        Ok(r) if r.status().is_success() => {
+            let body: serde_json::Value = r.json().await.unwrap_or_default();
+            let contract = body.get("contract").unwrap_or(&body);
+            Check::new(
+                NAME,
+                CheckStatus::Fail,
+                format!(
+                    "already registered on {} as {}",
+                    input.network,
+                    contract["name"].as_str().unwrap_or("?")
+                ),
+            )
+        }
+        Ok(r) => Check::new(
+            NAME,
+            CheckStatus::Warn,
+            format!("registry lookup failed ({})", r.status()),
+        ),
+        Err(e) => Check::new(
+            NAME,
+            CheckStatus::Warn,
+            format!("registry unreachable: {}", e),
+        ),
+    }
+}
+
+/// SEP-41 conformance, for contracts that export the token entry points.
+fn check_standards(wasm: Option<&(PathBuf, Vec<u8>)>) -> Check {
+    const NAME: &str = "standards";
+    let Some((path, bytes)) = wasm else {
+        return Check::new(NAME, CheckStatus::Skipped, "no wasm to inspect");
+    };
+    let exports = crate::wasm::parse_exports(bytes).unwrap_or_default();
+    let exported = |name: &str| exports.iter().any(|e| e.name == name);
+    if !(exported("transfer") && exported("balance")) {
+        return Check::new(NAME, CheckStatus::Skipped, "not a token contract");
+    }
+
+    let conformance = || -> Result<Vec<crate::standards::Gap>> {
+        let standard = crate::standards::bundled("sep-41")?;
+        let abi_json = crate::commands::load_abi_json(&path.to_string_lossy())?;
+        let abi = contract_abi::parse_json_spec(&abi_json, "contract")
+            .map_err(|e| anyhow::anyhow!("Failed to parse ABI: {}", e))?;
+        Ok(crate::standards::check(&standard, &abi))
+    };
+    match conformance() {
+        Ok(gaps) if gaps.is_empty() => Check::new(NAME, CheckStatus::Pass, "conforms to SEP-41"),
+        Ok(gaps) => Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!(
+                "SEP-41: {}",
+                gaps.iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ),
+        ),
+        Err(e) => Check::new(
+            NAME,
+            CheckStatus::Warn,
+            format!("couldn't read the ABI: {}", e),
+        ),
+    }
+}
+
+/// Run every applicable check. Checks never abort the run; each reports
+/// its own outcome.
+pub async fn run(api_url: &str, input: &PreflightInput<'_>) -> PreflightReport {
+    let client = reqwest::Client::new();
+    let wasm = locate_wasm(input.fields.contract_path)
+        .and_then(|path| std::fs::read(&path).ok().map(|bytes| (path, bytes)));
+
+    let checks = vec![
+        check_manifest(input.fields.contract_path),
+        check_metadata(input),
+        check_contract_id(input.contract_id),
+        check_wasm(wasm.as_ref()),
+        check_size(wasm.as_ref()),
+        check_duplicate(&client, api_url, input).await,
+        check_standards(wasm.as_ref()),
+    ];
+    PreflightReport { checks }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_and_contract_id_checks() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        assert_eq!(check_manifest(dir.path()).status, CheckStatus::Fail);
+
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"token\"\n",
+        )
+        .unwrap();
+        let check = check_manifest(dir.path());
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.detail.contains("cdylib"));
+
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"token\"\n\n[lib]\ncrate-type = [\"cdylib\"]\n",
+        )
+        .unwrap();
+        assert_eq!(check_manifest(dir.path()).status, CheckStatus::Pass);
+
+        let id = stellar_strkey::Contract([3u8; 32]).to_string();
+        assert_eq!(check_contract_id(&id).status, CheckStatus::Pass);
+        assert_eq!(check_contract_id("GABC").status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn size_limit_and_wasm_discovery() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        assert_eq!(locate_wasm(dir.path()), None);
+        assert_eq!(check_size(None).status, CheckStatus::Skipped);
+
+        let release = dir.path().join(WASM_TARGET_DIRS[1]);
+        std::fs::create_dir_all(&release).unwrap();
+        std::fs::write(release.join("token.wasm"), b"\0asm").unwrap();
+        assert_eq!(locate_wasm(dir.path()), Some(release.join("token.wasm")));
+
+        let wasm = |len: usize| (PathBuf::from("c.wasm"), vec![0u8; len]);
+        assert_eq!(check_size(Some(&wasm(1024))).status, CheckStatus::Pass);
+        assert_eq!(
+            check_size(Some(&wasm(MAX_WASM_SIZE_BYTES))).status,
+            CheckStatus::Warn
+        );
+        assert_eq!(
+            check_size(Some(&wasm(MAX_WASM_SIZE_BYTES + 1))).status,
+            CheckStatus::Fail
+        );
+
+        let report = PreflightReport {
+            checks: vec![
+                Check::new("a", CheckStatus::Warn, ""),
+                Check::new("b", CheckStatus::Skipped, ""),
+            ],
+        };
+        assert!(report.passed());
+    }
+}
@@ -0,0 +1,93 @@
+//! Progress reporting for long-running, multi-item operations.
+//!
+//! Progress always goes to stderr so it never mixes with a command's result
+//! on stdout. In JSON mode each update is one newline-delimited JSON event
+//! (`{"event":"progress","done":3,"total":10,...}`) that consumers can parse
+//! while waiting for the final result.
+
+use colored::Colorize;
+use serde::Serialize;
+
+/// How an individual item finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    /// `done` of `total` items have been processed; `item` is the latest.
+    Progress {
+        done: usize,
+        total: usize,
+        item: &'a str,
+        status: Status,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        message: Option<&'a str>,
+    },
+}
+
+/// Render one event as a JSON line.
+pub fn to_json_line(event: &Event) -> String {
+    serde_json::to_string(event).unwrap_or_default()
+}
+
+/// Report `event` on stderr: a JSON line when `json`, otherwise a
+/// human-readable status line.
+pub fn emit(json: bool, event: &Event) {
+    if json {
+        eprintln!("{}", to_json_line(event));
+        return;
+    }
+    match event {
+        Event::Progress {
+            done,
+            total,
+            item,
+            status,
+            message,
+        } => {
+            let status = match status {
+                Status::Ok => "✓".green(),
+                Status::Skipped => "⊙".bright_black(),
+                Status::Failed => "✗".red(),
+            };
+            let message = message.map(|m| format!(" — {}", m)).unwrap_or_default();
+            eprintln!("  [{}/{}] {} {}{}", done, total, status, item, message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_events_are_single_tagged_lines() {
+        let line = to_json_line(&Event::Progress {
+            done: 3,
+            total: 10,
+            item: "CABC",
+            status: Status::Ok,
+            message: None,
+        });
+        assert_eq!(
+            line,
+            r#"{"event":"progress","done":3,"total":10,"item":"CABC","status":"ok"}"#
+        );
+
+        let line = to_json_line(&Event::Progress {
+            done: 4,
+            total: 10,
+            item: "CDEF",
+            status: Status::Failed,
+            message: Some("timeout\nafter 30s"),
+        });
+        assert!(!line.contains('\n'));
+        assert!(line.contains(r#""status":"failed""#));
+    }
+}
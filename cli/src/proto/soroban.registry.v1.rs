@@ -0,0 +1,33 @@
+// This file is @generated by prost-build.
+/// One contract in a `list` or `search` result.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Contract {
+    /// On-chain contract address (C...).
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(bool, tag = "3")]
+    pub is_verified: bool,
+    #[prost(string, tag = "4")]
+    pub network: ::prost::alloc::string::String,
+    /// Empty when the contract is uncategorised.
+    #[prost(string, tag = "5")]
+    pub category: ::prost::alloc::string::String,
+    /// Relevance score, with `search --show-score`.
+    #[prost(double, optional, tag = "6")]
+    pub score: ::core::option::Option<f64>,
+    /// Where the score came from: "api" or "client_bm25".
+    #[prost(string, tag = "7")]
+    pub score_source: ::prost::alloc::string::String,
+    /// Registry web page for the contract.
+    #[prost(string, tag = "8")]
+    pub detail_url: ::prost::alloc::string::String,
+    /// Invocations (or RPC events) behind `search --min-invocations` /
+    /// `--active-since`.
+    #[prost(uint64, optional, tag = "9")]
+    pub invocations: ::core::option::Option<u64>,
+    /// RFC 3339 time of the last on-chain activity; empty when unknown.
+    #[prost(string, tag = "10")]
+    pub last_active: ::prost::alloc::string::String,
+}
@@ -0,0 +1,394 @@
+//! Source provenance for `verify --source`: attest that a contract was built
+//! from a specific public git commit.
+//!
+//! The commit is cloned and rebuilt locally, and the resulting wasm hash is
+//! compared with both the registry record and the code actually deployed
+//! on-chain. Attestations are kept per contract under
+//! `~/.soroban-registry/provenance/`.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::contract_verify::{ChainIntegrity, ChainIntegrityStatus};
+
+/// Target the rebuild compiles for; current Soroban SDKs build for it.
+const BUILD_TARGET: &str = "wasm32v1-none";
+
+/// A `<git-url>@<commit>` source reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceRef {
+    pub repo: String,
+    pub commit: String,
+}
+
+impl SourceRef {
+    /// Parse `https://host/org/repo@<commit>` or `git@host:org/repo@<commit>`.
+    /// The commit must be a full or abbreviated (7+ character) hex hash, so
+    /// the attestation can't silently follow a moving branch.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let Some((repo, commit)) = spec.rsplit_once('@') else {
+            bail!("Invalid --source '{}': expected <git-url>@<commit>", spec);
+        };
+        let commit = commit.trim().to_ascii_lowercase();
+        if !(7..=64).contains(&commit.len()) || !commit.chars().all(|c| c.is_ascii_hexdigit()) {
+            bail!(
+                "Invalid --source commit '{}': expected a git commit hash, not a branch or tag",
+                commit
+            );
+        }
+
+        let repo = repo.trim();
+        if repo.contains("://") {
+            let url = reqwest::Url::parse(repo)
+                .with_context(|| format!("Invalid --source repository URL: {}", repo))?;
+            if !matches!(url.scheme(), "https" | "http" | "git" | "ssh") {
+                bail!("Unsupported --source repository scheme: {}", url.scheme());
+            }
+        } else if !repo.contains(':') {
+            // Neither a URL nor scp-like `user@host:path`.
+            bail!("Invalid --source repository '{}'", repo);
+        }
+        Ok(Self {
+            repo: repo.to_string(),
+            commit,
+        })
+    }
+}
+
+impl std::fmt::Display for SourceRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.repo, self.commit)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttestationStatus {
+    /// Source, registry record and on-chain code all have the same hash.
+    Verified,
+    /// Some pair of hashes disagrees.
+    Mismatch,
+    /// Nothing disagrees, but a link couldn't be checked.
+    Unconfirmed,
+}
+
+/// One recorded claim that a contract was built from `repo@commit`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceAttestation {
+    pub contract_id: String,
+    pub network: String,
+    pub repo: String,
+    pub commit: String,
+    pub registry_hash: Option<String>,
+    pub onchain_hash: Option<String>,
+    pub built_hash: Option<String>,
+    pub status: AttestationStatus,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub problems: Vec<String>,
+    pub attested_at: String,
+}
+
+/// Judge the hash chain source → registry → on-chain.
+pub fn assess(
+    registry_hash: Option<&str>,
+    chain: &ChainIntegrity,
+    built_hash: Option<&str>,
+) -> (AttestationStatus, Vec<String>) {
+    let mut problems = Vec::new();
+    let mut mismatch = false;
+
+    match (built_hash, registry_hash) {
+        (Some(built), Some(registered)) if !built.eq_ignore_ascii_case(registered) => {
+            mismatch = true;
+            problems.push(format!(
+                "source builds {} but the registry records {}",
+                built, registered
+            ));
+        }
+        (Some(_), Some(_)) => {}
+        (None, _) => problems.push("source was not rebuilt".to_string()),
+        (Some(_), None) => problems.push("registry has no wasm hash for this contract".to_string()),
+    }
+
+    match chain.status {
+        ChainIntegrityStatus::Match => {}
+        ChainIntegrityStatus::Mismatch => {
+            mismatch = true;
+            problems.push(format!(
+                "on-chain wasm {} differs from registered {}",
+                chain.onchain_hash.as_deref().unwrap_or("?"),
+                chain.registered_hash.as_deref().unwrap_or("?")
+            ));
+        }
+        ChainIntegrityStatus::NotDeployed => {
+            mismatch = true;
+            problems.push("no contract instance on-chain".to_string());
+        }
+        ChainIntegrityStatus::Unknown => problems.push(format!(
+            "on-chain code unchecked: {}",
+            chain.detail.as_deref().unwrap_or("unknown")
+        )),
+    }
+
+    let status = if mismatch {
+        AttestationStatus::Mismatch
+    } else if problems.is_empty() {
+        AttestationStatus::Verified
+    } else {
+        AttestationStatus::Unconfirmed
+    };
+    (status, problems)
+}
+
+fn run(command: &mut Command, what: &str) -> Result<()> {
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run {}", what))?;
+    if !output.status.success() {
+        bail!(
+            "{} failed: {}",
+            what,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Clone `source`, check out its commit and build it in release mode;
+/// returns the SHA-256 of the wasm it produces.
+fn rebuild(source: &SourceRef) -> Result<String> {
+    let dir = tempfile::tempdir().context("Failed to create build directory")?;
+    let checkout = dir.path().join("src");
+    run(
+        Command::new("git")
+            .args(["clone", "--quiet", source.repo.as_str()])
+            .arg(&checkout),
+        "git clone",
+    )?;
+    run(
+        Command::new("git").arg("-C").arg(&checkout).args([
+            "checkout",
+            "--quiet",
+            "--detach",
+            source.commit.as_str(),
+        ]),
+        "git checkout",
+    )?;
+    run(
+        Command::new("cargo")
+            .args(["build", "--release", "--locked", "--target", BUILD_TARGET])
+            .current_dir(&checkout),
+        "cargo build",
+    )?;
+    let wasm = crate::preflight::locate_wasm(&checkout)
+        .context("Build did not produce exactly one wasm file")?;
+    let bytes =
+        std::fs::read(&wasm).with_context(|| format!("Failed to read {}", wasm.display()))?;
+    Ok(crate::wasm::sha256_hex(&bytes))
+}
+
+fn store_path(contract_id: &str) -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Cannot determine home directory")?;
+    Ok(home
+        .join(".soroban-registry")
+        .join("provenance")
+        .join(format!("{}.json", contract_id)))
+}
+
+fn load_from(path: &Path) -> Result<Vec<SourceAttestation>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&raw)
+        .with_context(|| format!("Corrupt attestation file {}", path.display()))
+}
+
+/// Record `attestation`, replacing an earlier one for the same commit.
+fn record_to(path: &Path, attestation: &SourceAttestation) -> Result<Vec<SourceAttestation>> {
+    let mut attestations = load_from(path)?;
+    attestations.retain(|a| !(a.repo == attestation.repo && a.commit == attestation.commit));
+    attestations.push(attestation.clone());
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&attestations)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(attestations)
+}
+
+/// `verify --source`: rebuild `source`, check the hash chain and record
+/// the attestation. Fails when any hash disagrees.
+pub async fn attest(api_url: &str, contract_id: &str, source: &str) -> Result<()> {
+    let source = SourceRef::parse(source)?;
+    let client = reqwest::Client::new();
+    let (contract, address, network, _) =
+        crate::onchain::resolve_contract(&client, api_url, contract_id).await?;
+    let registry_hash = contract["wasm_hash"].as_str();
+
+    let chain =
+        crate::contract_verify::check_chain_integrity(&client, &address, &network, registry_hash)
+            .await;
+
+    let emitter = crate::output::Emitter::current();
+    if !emitter.structured() {
+        println!("\n{}", "Source Attestation".bold().cyan());
+        println!("{}", "=".repeat(80).cyan());
+        println!("  {}: {}", "Contract".bold(), address.bright_black());
+        println!("  {}: {}", "Source".bold(), source);
+        println!(
+            "  {} Rebuilding for {}...",
+            "→".bright_black(),
+            BUILD_TARGET
+        );
+    }
+    let (built_hash, build_error) = match rebuild(&source) {
+        Ok(hash) => (Some(hash), None),
+        Err(e) => (None, Some(format!("{:#}", e))),
+    };
+
+    let (status, mut problems) = assess(registry_hash, &chain, built_hash.as_deref());
+    if let Some(error) = build_error {
+        problems.push(error);
+    }
+    let attestation = SourceAttestation {
+        contract_id: address.clone(),
+        network,
+        repo: source.repo.clone(),
+        commit: source.commit.clone(),
+        registry_hash: registry_hash.map(str::to_string),
+        onchain_hash: chain.onchain_hash.clone(),
+        built_hash,
+        status,
+        problems,
+        attested_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let recorded = record_to(&store_path(&address)?, &attestation)?;
+
+    if emitter.structured() {
+        emitter.emit(&attestation, crate::output::Format::Json)?;
+    } else {
+        let hash = |h: &Option<String>| h.clone().unwrap_or_else(|| "-".to_string());
+        println!("  {}: {}", "Built".bold(), hash(&attestation.built_hash));
+        println!(
+            "  {}: {}",
+            "Registry".bold(),
+            hash(&attestation.registry_hash)
+        );
+        println!(
+            "  {}: {}",
+            "On-chain".bold(),
+            hash(&attestation.onchain_hash)
+        );
+        for problem in &attestation.problems {
+            println!("  {} {}", "⚠".yellow(), problem);
+        }
+        match status {
+            AttestationStatus::Verified => println!(
+                "\n{} {} builds the code registered and deployed at {}",
+                "✓".green().bold(),
+                source,
+                address
+            ),
+            AttestationStatus::Mismatch => {
+                println!("\n{}", "✗ Source does not match the contract".red().bold())
+            }
+            AttestationStatus::Unconfirmed => {
+                println!(
+                    "\n{}",
+                    "⚠ Attestation recorded but not confirmed".yellow().bold()
+                )
+            }
+        }
+        let others = recorded.len() - 1;
+        if others > 0 {
+            println!(
+                "  {} {} other source attestation(s) recorded for this contract",
+                "→".bright_black(),
+                others
+            );
+        }
+        println!();
+    }
+
+    if status == AttestationStatus::Mismatch {
+        bail!("Source attestation failed for {}", address);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_source_references() {
+        let source = SourceRef::parse("https://github.com/org/token@ABCDEF1").unwrap();
+        assert_eq!(source.repo, "https://github.com/org/token");
+        assert_eq!(source.commit, "abcdef1");
+
+        let ssh = SourceRef::parse("git@github.com:org/token.git@0123456789abcdef").unwrap();
+        assert_eq!(ssh.repo, "git@github.com:org/token.git");
+        assert_eq!(
+            ssh.to_string(),
+            "git@github.com:org/token.git@0123456789abcdef"
+        );
+
+        assert!(SourceRef::parse("https://github.com/org/token").is_err());
+        assert!(SourceRef::parse("https://github.com/org/token@main").is_err());
+        assert!(SourceRef::parse("ftp://example.com/token@abcdef1").is_err());
+        assert!(SourceRef::parse("token@abcdef1").is_err());
+    }
+
+    #[test]
+    fn assesses_the_hash_chain_and_records_attestations() {
+        let hash = "a".repeat(64);
+        let matched = ChainIntegrity::classify(Some(&hash), Some(Some(hash.clone())));
+        assert_eq!(
+            assess(Some(&hash), &matched, Some(&hash)),
+            (AttestationStatus::Verified, vec![])
+        );
+
+        let (status, problems) = assess(Some(&hash), &matched, Some(&"b".repeat(64)));
+        assert_eq!(status, AttestationStatus::Mismatch);
+        assert!(problems[0].contains("registry records"));
+
+        let (status, _) = assess(Some(&hash), &matched, None);
+        assert_eq!(status, AttestationStatus::Unconfirmed);
+
+        let drifted = ChainIntegrity::classify(Some(&hash), Some(Some("c".repeat(64))));
+        let (status, problems) = assess(Some(&hash), &drifted, Some(&hash));
+        assert_eq!(status, AttestationStatus::Mismatch);
+        assert!(problems[0].starts_with("on-chain wasm"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("provenance").join("CABC.json");
+        let attestation = SourceAttestation {
+            contract_id: "CABC".into(),
+            network: "testnet".into(),
+            repo: "https://github.com/org/token".into(),
+            commit: "abcdef1".into(),
+            registry_hash: Some(hash.clone()),
+            onchain_hash: Some(hash.clone()),
+            built_hash: Some(hash.clone()),
+            status: AttestationStatus::Verified,
+            problems: Vec::new(),
+            attested_at: "2026-01-01T00:00:00Z".into(),
+        };
+        record_to(&path, &attestation).unwrap();
+        let again = record_to(&path, &attestation).unwrap();
+        assert_eq!(again, vec![attestation.clone()]);
+        let other = SourceAttestation {
+            commit: "1234567".into(),
+            ..attestation
+        };
+        assert_eq!(record_to(&path, &other).unwrap().len(), 2);
+        assert_eq!(load_from(&path).unwrap().len(), 2);
+    }
+}
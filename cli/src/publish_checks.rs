@@ -0,0 +1,179 @@
+//! Completeness checks enforced by `publish --strict`.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+
+/// Field names understood in `[publish] strict_required`.
+pub const KNOWN_FIELDS: &[&str] = &["description", "category", "tags", "license", "readme"];
+
+/// Metadata being published, as seen by the strict checks.
+pub struct PublishFields<'a> {
+    pub description: Option<&'a str>,
+    pub category: Option<&'a str>,
+    pub tags: &'a [String],
+    pub contract_path: &'a Path,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckItem {
+    pub field: String,
+    pub passed: bool,
+    pub hint: &'static str,
+}
+
+fn non_empty(value: Option<&str>) -> bool {
+    value.map(|v| !v.trim().is_empty()).unwrap_or(false)
+}
+
+fn has_file_with_prefix(dir: &Path, prefix: &str) -> bool {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries.filter_map(|e| e.ok()).any(|e| {
+                e.path().is_file()
+                    && e.file_name()
+                        .to_string_lossy()
+                        .to_uppercase()
+                        .starts_with(prefix)
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// A license is present when Cargo.toml declares `license`/`license-file`
+/// or the project ships a LICENSE/COPYING file.
+fn has_license(dir: &Path) -> bool {
+    let declared = fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|s| s.parse::<toml::Value>().ok())
+        .and_then(|v| v.get("package").cloned())
+        .map(|pkg| pkg.get("license").is_some() || pkg.get("license-file").is_some())
+        .unwrap_or(false);
+    declared || has_file_with_prefix(dir, "LICENSE") || has_file_with_prefix(dir, "COPYING")
+}
+
+/// Evaluate every required field, returning the full checklist.
+pub fn checklist(required: &[String], fields: &PublishFields<'_>) -> Result<Vec<CheckItem>> {
+    required
+        .iter()
+        .map(|field| {
+            let (passed, hint) = match field.as_str() {
+                "description" => (non_empty(fields.description), "pass --description"),
+                "category" => (non_empty(fields.category), "pass --category"),
+                "tags" => (
+                    fields.tags.iter().any(|t| !t.trim().is_empty()),
+                    "pass --tags with at least one tag",
+                ),
+                "license" => (
+                    has_license(fields.contract_path),
+                    "set `license` in Cargo.toml or add a LICENSE file",
+                ),
+                "readme" => (
+                    has_file_with_prefix(fields.contract_path, "README"),
+                    "add a README to the contract directory",
+                ),
+                other => bail!(
+                    "unknown strict publish field '{}' in config (known: {})",
+                    other,
+                    KNOWN_FIELDS.join(", ")
+                ),
+            };
+            Ok(CheckItem {
+                field: field.clone(),
+                passed,
+                hint,
+            })
+        })
+        .collect()
+}
+
+/// Print the checklist and fail when anything required is missing.
+pub fn enforce(required: &[String], fields: &PublishFields<'_>) -> Result<()> {
+    let items = checklist(required, fields)?;
+    let missing = items.iter().filter(|i| !i.passed).count();
+
+    println!("\n{}", "Strict publish checklist:".bold().cyan());
+    for item in &items {
+        if item.passed {
+            println!("  {} {}", "✓".green(), item.field);
+        } else {
+            println!(
+                "  {} {} — {}",
+                "✗".red(),
+                item.field.bold(),
+                item.hint.bright_black()
+            );
+        }
+    }
+
+    if missing > 0 {
+        bail!(
+            "strict publish rejected: {} required field(s) missing",
+            missing
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn required() -> Vec<String> {
+        KNOWN_FIELDS.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn reports_each_missing_field() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let fields = PublishFields {
+            description: Some("  "),
+            category: Some("dex"),
+            tags: &[],
+            contract_path: dir.path(),
+        };
+        let missing: Vec<String> = checklist(&required(), &fields)
+            .expect("checklist")
+            .into_iter()
+            .filter(|i| !i.passed)
+            .map(|i| i.field)
+            .collect();
+        assert_eq!(missing, vec!["description", "tags", "license", "readme"]);
+    }
+
+    #[test]
+    fn passes_complete_project() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"c\"\nlicense = \"MIT\"\n",
+        )
+        .expect("write Cargo.toml");
+        fs::write(dir.path().join("README.md"), "# c").expect("write README");
+        let tags = vec!["defi".to_string()];
+        let fields = PublishFields {
+            description: Some("A contract"),
+            category: Some("dex"),
+            tags: &tags,
+            contract_path: dir.path(),
+        };
+        assert!(checklist(&required(), &fields)
+            .expect("checklist")
+            .iter()
+            .all(|i| i.passed));
+    }
+
+    #[test]
+    fn unknown_field_is_an_error() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let fields = PublishFields {
+            description: None,
+            category: None,
+            tags: &[],
+            contract_path: dir.path(),
+        };
+        assert!(checklist(&["homepage".to_string()], &fields).is_err());
+    }
+}
@@ -0,0 +1,212 @@
+//! `publisher`: everything one publisher has registered, with portfolio
+//! stats for vetting them before trusting one of their contracts.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Page size for `/api/publishers/{id}/contracts` (the API's maximum).
+const PAGE_SIZE: usize = 100;
+
+/// Whether `contract` was registered by `account`, given either the
+/// publisher's registry UUID or their Stellar address.
+pub fn matches(contract: &Value, account: &str) -> bool {
+    [
+        "publisher_id",
+        "publisher_address",
+        "publisher_stellar_address",
+    ]
+    .iter()
+    .filter_map(|field| contract[*field].as_str())
+    .any(|value| value.eq_ignore_ascii_case(account))
+}
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct PortfolioSummary {
+    pub contracts: usize,
+    pub verified: usize,
+    /// `verified / contracts`, 0 for an empty portfolio.
+    pub verified_ratio: f64,
+    /// Category → number of contracts; uncategorised ones are left out.
+    pub categories: BTreeMap<String, usize>,
+    pub networks: BTreeMap<String, usize>,
+}
+
+pub fn summarize(contracts: &[Value]) -> PortfolioSummary {
+    let mut summary = PortfolioSummary {
+        contracts: contracts.len(),
+        ..Default::default()
+    };
+    for contract in contracts {
+        if contract["is_verified"].as_bool().unwrap_or(false) {
+            summary.verified += 1;
+        }
+        if let Some(category) = contract["category"].as_str().filter(|c| !c.is_empty()) {
+            *summary.categories.entry(category.to_string()).or_default() += 1;
+        }
+        if let Some(network) = contract["network"].as_str() {
+            *summary.networks.entry(network.to_string()).or_default() += 1;
+        }
+    }
+    if summary.contracts > 0 {
+        summary.verified_ratio = summary.verified as f64 / summary.contracts as f64;
+    }
+    summary
+}
+
+/// The publisher this CLI acts as: `[defaults] publisher` from the config
+/// file, else the subject of the API token.
+pub(crate) fn current() -> Result<Option<String>> {
+    if let Some(publisher) = crate::config::configured_publisher()? {
+        return Ok(Some(publisher));
+    }
+    let token = std::env::var(crate::http::API_TOKEN_ENV).ok();
+    Ok(crate::context::Identity::from_token(token.as_deref()).subject)
+}
+
+/// A publisher UUID has a dedicated endpoint; a Stellar address is matched
+/// against the full contract list.
+pub(crate) async fn fetch_contracts(
+    client: &reqwest::Client,
+    api_url: &str,
+    account: &str,
+) -> Result<Vec<Value>> {
+    if uuid::Uuid::parse_str(account).is_err() {
+        let mut contracts = crate::tags::fetch_all_contracts(client, api_url).await?;
+        contracts.retain(|c| matches(c, account));
+        return Ok(contracts);
+    }
+
+    let mut contracts = Vec::new();
+    loop {
+        let response = client
+            .get(format!("{}/api/publishers/{}/contracts", api_url, account))
+            .query(&[("limit", PAGE_SIZE), ("offset", contracts.len())])
+            .send()
+            .await
+            .context("Failed to fetch publisher contracts")?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            anyhow::bail!("Publisher not found: {}", account);
+        }
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to fetch publisher contracts: {}", response.status());
+        }
+        let body: Value = response.json().await?;
+        let items = body["items"].as_array().cloned().unwrap_or_default();
+        let total = body["total"].as_u64().unwrap_or(0) as usize;
+        let done = items.len() < PAGE_SIZE;
+        contracts.extend(items);
+        if done || contracts.len() >= total {
+            return Ok(contracts);
+        }
+    }
+}
+
+pub async fn portfolio(api_url: &str, account: &str, json: bool) -> Result<()> {
+    let client = reqwest::Client::new();
+    let contracts = fetch_contracts(&client, api_url, account).await?;
+    let summary = summarize(&contracts);
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(
+            &serde_json::json!({
+                "publisher": account,
+                "summary": summary,
+                "contracts": contracts,
+            }),
+            crate::output::Format::Json,
+        )?;
+        return Ok(());
+    }
+
+    println!("\n{} {}", "Publisher".bold().cyan(), account.bold());
+    println!("{}", "=".repeat(80).cyan());
+    if contracts.is_empty() {
+        println!("{}", "No contracts registered by this publisher.".yellow());
+        println!();
+        return Ok(());
+    }
+
+    println!("  {}: {}", "Contracts".bold(), summary.contracts);
+    println!(
+        "  {}: {}/{} ({:.0}%)",
+        "Verified".bold(),
+        summary.verified,
+        summary.contracts,
+        summary.verified_ratio * 100.0
+    );
+    let breakdown = |counts: &BTreeMap<String, usize>| {
+        counts
+            .iter()
+            .map(|(name, n)| format!("{} ({})", name, n))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    if !summary.categories.is_empty() {
+        println!(
+            "  {}: {}",
+            "Categories".bold(),
+            breakdown(&summary.categories)
+        );
+    }
+    println!(
+        "  {}: {}\n",
+        "Networks".bold(),
+        breakdown(&summary.networks)
+    );
+
+    for contract in &contracts {
+        let verified = if contract["is_verified"].as_bool().unwrap_or(false) {
+            "✓".green()
+        } else {
+            "✗".red()
+        };
+        println!(
+            "  {} {} {} {}",
+            verified,
+            contract["name"].as_str().unwrap_or("Unknown").bold(),
+            contract["contract_id"]
+                .as_str()
+                .unwrap_or("")
+                .bright_black(),
+            contract["category"]
+                .as_str()
+                .map(|c| format!("[{}]", c))
+                .unwrap_or_default()
+                .bright_black()
+        );
+    }
+    println!("\n{}", "=".repeat(80).cyan());
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn summarizes_a_publishers_portfolio() {
+        let contracts = vec![
+            json!({ "publisher_id": "P1", "is_verified": true, "category": "DeFi", "network": "mainnet" }),
+            json!({ "publisher_address": "GPUB", "is_verified": false, "category": "DeFi", "network": "testnet" }),
+            json!({ "publisher_id": "P1", "is_verified": true, "network": "mainnet" }),
+        ];
+        assert!(matches(&contracts[0], "p1"));
+        assert!(matches(&contracts[1], "GPUB"));
+        assert!(!matches(&contracts[1], "P1"));
+
+        let summary = summarize(&contracts);
+        assert_eq!(summary.contracts, 3);
+        assert_eq!(summary.verified, 2);
+        assert!((summary.verified_ratio - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(summary.categories["DeFi"], 2);
+        assert_eq!(summary.networks["mainnet"], 2);
+        assert_eq!(summarize(&[]).verified_ratio, 0.0);
+    }
+}
@@ -0,0 +1,372 @@
+//! Client-side filter expressions for `list` / `search` results.
+//!
+//! Grammar (case-insensitive keywords):
+//!
+//! ```text
+//! expr       := and_expr ( ("OR" | "||") and_expr )*
+//! and_expr   := unary ( ("AND" | "&&") unary )*
+//! unary      := ("NOT" | "!") unary | "(" expr ")" | comparison
+//! comparison := field [ op value ]
+//! op         := "==" | "=" | "!=" | ">" | ">=" | "<" | "<=" | "contains" | "~"
+//! ```
+//!
+//! Fields are dotted paths into the JSON result (`stats.downloads`). A bare
+//! field is true when it is present and truthy.
+//!
+//! Examples: `downloads>1000`, `tags contains defi AND is_verified`,
+//! `(network=mainnet OR network=testnet) AND NOT category=dex`.
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Contains,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Truthy(String),
+    Compare {
+        field: String,
+        op: Op,
+        value: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Op(Op),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '&' if next == Some('&') => {
+                tokens.push(Token::And);
+                i += 2;
+            }
+            '|' if next == Some('|') => {
+                tokens.push(Token::Or);
+                i += 2;
+            }
+            '!' if next == Some('=') => {
+                tokens.push(Token::Op(Op::Ne));
+                i += 2;
+            }
+            '!' => {
+                tokens.push(Token::Not);
+                i += 1;
+            }
+            '=' => {
+                tokens.push(Token::Op(Op::Eq));
+                i += if next == Some('=') { 2 } else { 1 };
+            }
+            '>' | '<' => {
+                let op = match (c, next == Some('=')) {
+                    ('>', true) => Op::Ge,
+                    ('>', false) => Op::Gt,
+                    ('<', true) => Op::Le,
+                    _ => Op::Lt,
+                };
+                tokens.push(Token::Op(op));
+                i += if next == Some('=') { 2 } else { 1 };
+            }
+            '~' => {
+                tokens.push(Token::Op(Op::Contains));
+                i += 1;
+            }
+            '"' | '\'' => {
+                let quote = c;
+                let start = i + 1;
+                let Some(len) = chars[start..].iter().position(|&ch| ch == quote) else {
+                    bail!("unterminated string starting at position {}", i);
+                };
+                tokens.push(Token::Quoted(chars[start..start + len].iter().collect()));
+                i = start + len + 1;
+            }
+            _ => {
+                let start = i;
+                while i < chars.len()
+                    && !chars[i].is_whitespace()
+                    && !"()=!<>~&|\"'".contains(chars[i])
+                {
+                    i += 1;
+                }
+                if start == i {
+                    bail!("unexpected character '{}' at position {}", c, i);
+                }
+                let word: String = chars[start..i].iter().collect();
+                tokens.push(match word.to_lowercase().as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    "contains" => Token::Op(Op::Contains),
+                    _ => Token::Word(word),
+                });
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn expr(&mut self) -> Result<Expr> {
+        let mut lhs = self.and_expr()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            lhs = Expr::Or(Box::new(lhs), Box::new(self.and_expr()?));
+        }
+        Ok(lhs)
+    }
+
+    fn and_expr(&mut self) -> Result<Expr> {
+        let mut lhs = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            lhs = Expr::And(Box::new(lhs), Box::new(self.unary()?));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::Not) => Ok(Expr::Not(Box::new(self.unary()?))),
+            Some(Token::LParen) => {
+                let inner = self.expr()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(inner),
+                    _ => bail!("expected ')'"),
+                }
+            }
+            Some(Token::Word(field)) => {
+                let Some(Token::Op(op)) = self.peek().cloned() else {
+                    return Ok(Expr::Truthy(field));
+                };
+                self.pos += 1;
+                match self.next() {
+                    Some(Token::Word(value)) | Some(Token::Quoted(value)) => {
+                        Ok(Expr::Compare { field, op, value })
+                    }
+                    _ => bail!("expected a value after the operator for '{}'", field),
+                }
+            }
+            Some(tok) => bail!("expected a field name, found {:?}", tok),
+            None => bail!("unexpected end of expression"),
+        }
+    }
+}
+
+/// Parse a filter expression.
+pub fn parse(input: &str) -> Result<Expr> {
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        bail!("empty filter expression");
+    }
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.expr()?;
+    if let Some(tok) = parser.peek() {
+        bail!("unexpected {:?} after complete expression", tok);
+    }
+    Ok(expr)
+}
+
+/// Parse `input`, prefixing any error with the offending expression.
+pub fn parse_filter(input: &str) -> Result<Expr> {
+    parse(input).map_err(|e| anyhow::anyhow!("invalid --filter expression '{}': {}", input, e))
+}
+
+fn lookup<'a>(item: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(item, |v, key| match v {
+        Value::Object(map) => map.get(key),
+        Value::Array(arr) => key.parse::<usize>().ok().and_then(|i| arr.get(i)),
+        _ => None,
+    })
+}
+
+fn truthy(v: &Value) -> bool {
+    match v {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().map(|f| f != 0.0).unwrap_or(false),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(a) => !a.is_empty(),
+        Value::Object(o) => !o.is_empty(),
+    }
+}
+
+fn as_text(v: &Value) -> String {
+    match v {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn as_number(v: &Value) -> Option<f64> {
+    match v {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn compare(actual: &Value, op: Op, expected: &str) -> bool {
+    if op == Op::Contains {
+        let needle = expected.to_lowercase();
+        return match actual {
+            Value::Array(items) => items.iter().any(|v| as_text(v).to_lowercase() == needle),
+            other => as_text(other).to_lowercase().contains(&needle),
+        };
+    }
+
+    if let (Some(a), Ok(b)) = (as_number(actual), expected.parse::<f64>()) {
+        return match op {
+            Op::Eq => a == b,
+            Op::Ne => a != b,
+            Op::Gt => a > b,
+            Op::Ge => a >= b,
+            Op::Lt => a < b,
+            Op::Le => a <= b,
+            Op::Contains => unreachable!(),
+        };
+    }
+
+    let a = as_text(actual).to_lowercase();
+    let b = expected.to_lowercase();
+    match op {
+        Op::Eq => a == b,
+        Op::Ne => a != b,
+        Op::Gt => a > b,
+        Op::Ge => a >= b,
+        Op::Lt => a < b,
+        Op::Le => a <= b,
+        Op::Contains => unreachable!(),
+    }
+}
+
+impl Expr {
+    /// Evaluate against one JSON result. Missing fields never match, except
+    /// under `!=`.
+    pub fn matches(&self, item: &Value) -> bool {
+        match self {
+            Expr::And(a, b) => a.matches(item) && b.matches(item),
+            Expr::Or(a, b) => a.matches(item) || b.matches(item),
+            Expr::Not(e) => !e.matches(item),
+            Expr::Truthy(field) => lookup(item, field).map(truthy).unwrap_or(false),
+            Expr::Compare { field, op, value } => match lookup(item, field) {
+                Some(actual) if !actual.is_null() => compare(actual, *op, value),
+                _ => *op == Op::Ne,
+            },
+        }
+    }
+}
+
+/// Keep only the items matching `filter`, if one was given.
+pub fn apply(items: Vec<Value>, filter: Option<&Expr>) -> Vec<Value> {
+    match filter {
+        Some(expr) => items.into_iter().filter(|i| expr.matches(i)).collect(),
+        None => items,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> Value {
+        json!({
+            "name": "Token Vault",
+            "downloads": 1500,
+            "is_verified": true,
+            "network": "mainnet",
+            "tags": ["DeFi", "vault"],
+            "stats": { "deployments": 3 }
+        })
+    }
+
+    #[test]
+    fn numeric_and_string_comparisons() {
+        let item = sample();
+        assert!(parse("downloads>1000").unwrap().matches(&item));
+        assert!(!parse("downloads <= 1000").unwrap().matches(&item));
+        assert!(parse("stats.deployments == 3").unwrap().matches(&item));
+        assert!(parse("network = MAINNET").unwrap().matches(&item));
+        assert!(parse("name ~ vault").unwrap().matches(&item));
+        assert!(parse("name = \"Token Vault\"").unwrap().matches(&item));
+    }
+
+    #[test]
+    fn boolean_logic_and_contains() {
+        let item = sample();
+        assert!(parse("tags contains defi AND is_verified")
+            .unwrap()
+            .matches(&item));
+        assert!(parse("network=testnet OR downloads>10")
+            .unwrap()
+            .matches(&item));
+        assert!(!parse("NOT is_verified").unwrap().matches(&item));
+        assert!(parse("(network=testnet || network=mainnet) && !missing")
+            .unwrap()
+            .matches(&item));
+        assert!(parse("missing != x").unwrap().matches(&item));
+    }
+
+    #[test]
+    fn invalid_expressions_error() {
+        assert!(parse("").is_err());
+        assert!(parse("downloads >").is_err());
+        assert!(parse("(a = 1").is_err());
+        assert!(parse("a = 1 b").is_err());
+        assert!(parse("name = \"open").is_err());
+        let err = parse_filter("AND").unwrap_err().to_string();
+        assert!(err.contains("invalid --filter expression"));
+    }
+}
@@ -0,0 +1,258 @@
+//! Client-side relevance scoring and ordering for search results.
+//!
+//! Scoring is used when the registry API does not return its own relevance
+//! scores, so `search --show-score` can still explain why results are
+//! ordered the way they are. `search --sort` re-sorts every page locally,
+//! since not every registry honours `sort_by`.
+
+use std::cmp::Ordering;
+
+use clap::ValueEnum;
+use serde_json::Value;
+
+/// BM25 term-frequency saturation.
+const K1: f64 = 1.2;
+/// BM25 document-length normalisation.
+const B: f64 = 0.75;
+/// Matches in the contract name count this many times more than description matches.
+const NAME_BOOST: usize = 3;
+
+/// Lowercase alphanumeric tokens of `text`.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+/// Score each document against `query` with Okapi BM25.
+pub fn bm25(query: &str, documents: &[Vec<String>]) -> Vec<f64> {
+    let terms = tokenize(query);
+    let n = documents.len() as f64;
+    if documents.is_empty() || terms.is_empty() {
+        return vec![0.0; documents.len()];
+    }
+
+    let avg_len = documents.iter().map(|d| d.len()).sum::<usize>() as f64 / n;
+    let avg_len = avg_len.max(1.0);
+
+    let idf: Vec<f64> = terms
+        .iter()
+        .map(|term| {
+            let df = documents.iter().filter(|d| d.contains(term)).count() as f64;
+            ((n - df + 0.5) / (df + 0.5) + 1.0).ln()
+        })
+        .collect();
+
+    documents
+        .iter()
+        .map(|doc| {
+            let len = doc.len() as f64;
+            terms
+                .iter()
+                .zip(&idf)
+                .map(|(term, idf)| {
+                    let tf = doc.iter().filter(|t| *t == term).count() as f64;
+                    if tf == 0.0 {
+                        return 0.0;
+                    }
+                    idf * (tf * (K1 + 1.0)) / (tf + K1 * (1.0 - B + B * len / avg_len))
+                })
+                .sum()
+        })
+        .collect()
+}
+
+/// Build the token bag for a search result from its name and description.
+pub fn contract_document(contract: &Value) -> Vec<String> {
+    let name = tokenize(contract["name"].as_str().unwrap_or(""));
+    let description = tokenize(contract["description"].as_str().unwrap_or(""));
+
+    let mut doc = Vec::with_capacity(name.len() * NAME_BOOST + description.len());
+    for _ in 0..NAME_BOOST {
+        doc.extend(name.iter().cloned());
+    }
+    doc.extend(description);
+    doc
+}
+
+/// Record keys for download counts, top level or under `stats`.
+const DOWNLOAD_KEYS: &[&str] = &["downloads", "download_count", "interaction_count"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SortKey {
+    Downloads,
+    Updated,
+    Name,
+    Relevance,
+}
+
+impl SortKey {
+    pub fn label(self) -> &'static str {
+        match self {
+            SortKey::Downloads => "downloads",
+            SortKey::Updated => "updated",
+            SortKey::Name => "name",
+            SortKey::Relevance => "relevance",
+        }
+    }
+
+    /// The registry's `sort_by` value, for keys it can sort by itself.
+    pub fn api_param(self) -> Option<&'static str> {
+        match self {
+            SortKey::Downloads => Some("downloads"),
+            SortKey::Updated => Some("updated_at"),
+            SortKey::Relevance => Some("relevance"),
+            SortKey::Name => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortOrder {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SortOrder::Asc => "asc",
+            SortOrder::Desc => "desc",
+        }
+    }
+}
+
+/// Download count the registry reports for `contract`, if any.
+pub fn downloads(contract: &Value) -> Option<u64> {
+    [contract, &contract["stats"]]
+        .into_iter()
+        .flat_map(|object| DOWNLOAD_KEYS.iter().map(move |k| &object[*k]))
+        .find_map(Value::as_u64)
+}
+
+/// Positions of `items` in sorted order. `relevance` holds each item's
+/// score for [`SortKey::Relevance`]. Ties are broken by `contract_id`,
+/// always ascending, so the order is the same from run to run.
+pub fn sorted_indices(
+    items: &[Value],
+    relevance: Option<&[f64]>,
+    key: SortKey,
+    order: SortOrder,
+) -> Vec<usize> {
+    let by_key = |a: usize, b: usize| -> Ordering {
+        let (x, y) = (&items[a], &items[b]);
+        match key {
+            SortKey::Downloads => downloads(x).unwrap_or(0).cmp(&downloads(y).unwrap_or(0)),
+            // RFC 3339 timestamps in UTC order correctly as strings.
+            SortKey::Updated => x["updated_at"]
+                .as_str()
+                .unwrap_or("")
+                .cmp(y["updated_at"].as_str().unwrap_or("")),
+            SortKey::Name => x["name"]
+                .as_str()
+                .unwrap_or("")
+                .to_lowercase()
+                .cmp(&y["name"].as_str().unwrap_or("").to_lowercase()),
+            SortKey::Relevance => {
+                let score = |i: usize| relevance.and_then(|r| r.get(i)).copied().unwrap_or(0.0);
+                score(a).total_cmp(&score(b))
+            }
+        }
+    };
+    let mut indices: Vec<usize> = (0..items.len()).collect();
+    indices.sort_by(|&a, &b| {
+        let primary = match order {
+            SortOrder::Asc => by_key(a, b),
+            SortOrder::Desc => by_key(b, a),
+        };
+        primary.then_with(|| {
+            items[a]["contract_id"]
+                .as_str()
+                .unwrap_or("")
+                .cmp(items[b]["contract_id"].as_str().unwrap_or(""))
+        })
+    });
+    indices
+}
+
+/// Relevance scores for `items`, preferring the API's own `relevance_score`
+/// (or `score`) and falling back to client-side BM25 when any are missing.
+/// The boolean is `true` when the scores came from the API.
+pub fn relevance_scores(query: &str, items: &[Value]) -> (Vec<f64>, bool) {
+    let from_api: Option<Vec<f64>> = items
+        .iter()
+        .map(|c| {
+            c["relevance_score"]
+                .as_f64()
+                .or_else(|| c["score"].as_f64())
+        })
+        .collect();
+
+    match from_api {
+        Some(scores) if !items.is_empty() => (scores, true),
+        _ => {
+            let docs: Vec<Vec<String>> = items.iter().map(contract_document).collect();
+            (bm25(query, &docs), false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn name_match_outranks_description_match() {
+        let items = vec![
+            json!({ "name": "Oracle Feed", "description": "price data for a token" }),
+            json!({ "name": "Token Vault", "description": "stores assets" }),
+            json!({ "name": "Lending Pool", "description": "borrow and lend" }),
+        ];
+        let (scores, from_api) = relevance_scores("token", &items);
+        assert!(!from_api);
+        assert!(scores[1] > scores[0]);
+        assert!(scores[0] > scores[2]);
+        assert_eq!(scores[2], 0.0);
+    }
+
+    #[test]
+    fn sorts_with_a_stable_contract_id_tiebreak() {
+        let items = vec![
+            json!({ "contract_id": "CC", "name": "beta", "downloads": 10, "updated_at": "2024-03-01T00:00:00Z" }),
+            json!({ "contract_id": "CB", "name": "Alpha", "stats": { "downloads": 50 } }),
+            json!({ "contract_id": "CA", "name": "Beta", "downloads": 10, "updated_at": "2024-05-01T00:00:00Z" }),
+        ];
+        let ids = |order: Vec<usize>| -> Vec<&str> {
+            order
+                .into_iter()
+                .map(|i| items[i]["contract_id"].as_str().unwrap())
+                .collect()
+        };
+
+        let by = |key, order| ids(sorted_indices(&items, None, key, order));
+        assert_eq!(by(SortKey::Downloads, SortOrder::Desc), ["CB", "CA", "CC"]);
+        assert_eq!(by(SortKey::Downloads, SortOrder::Asc), ["CA", "CC", "CB"]);
+        assert_eq!(by(SortKey::Name, SortOrder::Asc), ["CB", "CA", "CC"]);
+        assert_eq!(by(SortKey::Updated, SortOrder::Desc), ["CA", "CC", "CB"]);
+
+        let scores = [0.5, 0.9, 0.5];
+        let by_relevance =
+            sorted_indices(&items, Some(&scores), SortKey::Relevance, SortOrder::Desc);
+        assert_eq!(ids(by_relevance), ["CB", "CA", "CC"]);
+        assert_eq!(SortKey::Name.api_param(), None);
+    }
+
+    #[test]
+    fn api_scores_are_preferred_when_present() {
+        let items = vec![
+            json!({ "name": "a", "relevance_score": 0.2 }),
+            json!({ "name": "b", "relevance_score": 0.9 }),
+        ];
+        let (scores, from_api) = relevance_scores("a", &items);
+        assert!(from_api);
+        assert_eq!(scores, vec![0.2, 0.9]);
+    }
+}
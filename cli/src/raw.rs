@@ -0,0 +1,119 @@
+//! `raw`: arbitrary authenticated calls against the registry API, for
+//! endpoints the CLI has no dedicated command for yet.
+
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use reqwest::{Method, Url};
+
+/// Resolve `path` against `api_url`, refusing anything that would leave the
+/// configured registry (different scheme, host or port).
+pub fn resolve_url(api_url: &str, path: &str) -> Result<Url> {
+    let base = Url::parse(api_url).with_context(|| format!("Invalid API URL: {}", api_url))?;
+    if !path.starts_with('/') {
+        bail!("Path must start with '/', e.g. /api/contracts");
+    }
+    let url = Url::parse(&format!("{}{}", api_url.trim_end_matches('/'), path))
+        .with_context(|| format!("Invalid path: {}", path))?;
+    if url.origin() != base.origin() {
+        bail!(
+            "Refusing to send a request outside the configured registry ({})",
+            base.origin().ascii_serialization()
+        );
+    }
+    Ok(url)
+}
+
+/// Request body from `--body`: inline JSON, `@<file>`, or `-` for stdin.
+fn read_body(body: &str) -> Result<String> {
+    match body.strip_prefix('@') {
+        Some(path) => crate::io_utils::read_input_to_string(path),
+        None if crate::io_utils::is_stdin(body) => crate::io_utils::read_input_to_string(body),
+        None => Ok(body.to_string()),
+    }
+}
+
+pub async fn run(api_url: &str, method: &str, path: &str, body: Option<&str>) -> Result<()> {
+    let method = Method::from_str(&method.to_ascii_uppercase())
+        .with_context(|| format!("Invalid HTTP method: {}", method))?;
+    let url = resolve_url(api_url, path)?;
+    let body = body.map(read_body).transpose()?;
+    let json_body = body
+        .as_deref()
+        .map(|b| serde_json::from_str::<serde_json::Value>(b).context("--body is not valid JSON"))
+        .transpose()?;
+
+    // Writes carry an idempotency key so the shared retry loop can't apply
+    // them twice.
+    let idempotency_key = (!method.is_safe()).then(|| {
+        crate::http::idempotency_key(
+            "raw",
+            &serde_json::json!({
+                "method": method.as_str(),
+                "path": path,
+                "body": json_body,
+            }),
+        )
+    });
+
+    log::debug!("{} {}", method, url);
+    let client = crate::http::client()?;
+    let response = crate::http::send_with_retry(
+        || {
+            let request = client.request(method.clone(), url.clone());
+            match &json_body {
+                Some(body) => request.json(body),
+                None => request,
+            }
+        },
+        idempotency_key.as_deref(),
+        crate::http::RetryPolicy::default(),
+    )
+    .await
+    .with_context(|| format!("Failed to send {} {}", method, path))?;
+
+    let status = response.status();
+    let text = response
+        .text()
+        .await
+        .context("Failed to read response body")?;
+
+    let status_line = format!("{} {}", method, status);
+    if status.is_success() {
+        eprintln!("{}", status_line.green());
+    } else {
+        eprintln!("{}", status_line.red());
+    }
+    match serde_json::from_str::<serde_json::Value>(&text) {
+        Ok(value) => println!("{}", serde_json::to_string_pretty(&value)?),
+        Err(_) if !text.is_empty() => println!("{}", text),
+        Err(_) => {}
+    }
+
+    if !status.is_success() {
+        bail!("{} {} returned {}", method, path, status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls_stay_on_the_configured_registry() {
+        let url = resolve_url("https://registry.example/", "/api/contracts?page=2").unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://registry.example/api/contracts?page=2"
+        );
+
+        assert!(resolve_url("https://registry.example", "api/contracts").is_err());
+        assert!(resolve_url("https://registry.example", "@evil.example/x").is_err());
+        assert!(resolve_url("https://registry.example", ".evil.example/x").is_err());
+        assert!(resolve_url("http://localhost:3001", ":8080/admin").is_err());
+        let url = resolve_url("https://registry.example", "//evil.example/x").unwrap();
+        assert_eq!(url.host_str(), Some("registry.example"));
+    }
+}
@@ -0,0 +1,289 @@
+//! "Contracts like this one" (`search --near`): similarity from shared tags,
+//! category and ABI method overlap.
+
+use std::collections::BTreeSet;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::Value;
+
+const TAG_WEIGHT: f64 = 0.4;
+const CATEGORY_WEIGHT: f64 = 0.2;
+const INTERFACE_WEIGHT: f64 = 0.4;
+
+/// Candidates whose ABI is fetched for interface comparison, taken from the
+/// top of the metadata-only ranking.
+const MAX_ABI_LOOKUPS: usize = 50;
+
+/// The parts of a contract that similarity is computed from.
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    pub tags: BTreeSet<String>,
+    pub category: Option<String>,
+    /// Public method names; `None` when the ABI is unavailable.
+    pub methods: Option<BTreeSet<String>>,
+}
+
+impl Profile {
+    pub fn new(contract: &Value, abi: Option<&Value>) -> Self {
+        Profile {
+            tags: crate::tags::tag_names(contract)
+                .iter()
+                .map(|t| t.trim().to_lowercase())
+                .collect(),
+            category: contract["category"]
+                .as_str()
+                .map(|c| c.trim().to_lowercase())
+                .filter(|c| !c.is_empty()),
+            methods: abi.and_then(Value::as_array).map(|items| {
+                items
+                    .iter()
+                    .filter(|i| i["type"] == "function")
+                    .filter_map(|i| i["name"].as_str().map(str::to_string))
+                    .collect()
+            }),
+        }
+    }
+}
+
+fn jaccard(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Similarity in `[0, 1]`. Interface overlap only counts when both ABIs are
+/// known; otherwise the metadata weights are scaled up to fill the range.
+pub fn similarity(reference: &Profile, candidate: &Profile) -> f64 {
+    let tags = jaccard(&reference.tags, &candidate.tags);
+    let category = match (&reference.category, &candidate.category) {
+        (Some(a), Some(b)) if a == b => 1.0,
+        _ => 0.0,
+    };
+    let metadata = TAG_WEIGHT * tags + CATEGORY_WEIGHT * category;
+    match (&reference.methods, &candidate.methods) {
+        (Some(a), Some(b)) => metadata + INTERFACE_WEIGHT * jaccard(a, b),
+        _ => metadata / (TAG_WEIGHT + CATEGORY_WEIGHT),
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Recommendation {
+    pub id: String,
+    pub contract_id: String,
+    pub name: String,
+    pub category: Option<String>,
+    pub score: f64,
+    pub shared_tags: Vec<String>,
+    pub shared_methods: usize,
+}
+
+pub async fn fetch_contract(client: &reqwest::Client, api_url: &str, id: &str) -> Result<Value> {
+    let response = client
+        .get(format!("{}/api/contracts/{}", api_url, id))
+        .send()
+        .await
+        .context("Failed to fetch contract from registry")?;
+    if !response.status().is_success() {
+        anyhow::bail!("Contract not found: {} ({})", id, response.status());
+    }
+    let body: Value = response.json().await?;
+    Ok(body.get("contract").cloned().unwrap_or(body))
+}
+
+/// A contract's ABI items, or `None` if the registry has none for it.
+pub async fn fetch_abi(client: &reqwest::Client, api_url: &str, uuid: &str) -> Option<Value> {
+    let response = client
+        .get(format!("{}/api/contracts/{}/abi", api_url, uuid))
+        .send()
+        .await
+        .ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    response.json::<Value>().await.ok()?.get("abi").cloned()
+}
+
+pub async fn near(
+    api_url: &str,
+    reference_id: &str,
+    verified_only: bool,
+    category: Option<&str>,
+    limit: usize,
+    json: bool,
+) -> Result<()> {
+    let client = reqwest::Client::new();
+    let reference = fetch_contract(&client, api_url, reference_id).await?;
+    let reference_uuid = reference["id"]
+        .as_str()
+        .context("Contract response missing id")?
+        .to_string();
+    let reference_abi = fetch_abi(&client, api_url, &reference_uuid).await;
+    let reference_profile = Profile::new(&reference, reference_abi.as_ref());
+
+    let mut candidates: Vec<(Value, Profile, f64)> =
+        crate::tags::fetch_all_contracts(&client, api_url)
+            .await?
+            .into_iter()
+            .filter(|c| c["id"].as_str() != Some(reference_uuid.as_str()))
+            .filter(|c| !verified_only || c["is_verified"].as_bool().unwrap_or(false))
+            .filter(|c| {
+                category.is_none_or(|cat| {
+                    c["category"]
+                        .as_str()
+                        .is_some_and(|c| c.eq_ignore_ascii_case(cat))
+                })
+            })
+            .map(|c| {
+                let profile = Profile::new(&c, None);
+                let score = similarity(&reference_profile, &profile);
+                (c, profile, score)
+            })
+            .collect();
+    candidates.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+
+    if reference_profile.methods.is_some() {
+        for (contract, profile, score) in candidates.iter_mut().take(MAX_ABI_LOOKUPS) {
+            if let Some(uuid) = contract["id"].as_str() {
+                let abi = fetch_abi(&client, api_url, uuid).await;
+                *profile = Profile::new(contract, abi.as_ref());
+                *score = similarity(&reference_profile, profile);
+            }
+        }
+        // Anything past the lookup window has no interface score to compare.
+        candidates.truncate(MAX_ABI_LOOKUPS);
+        candidates.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+    }
+
+    let recommendations: Vec<Recommendation> = candidates
+        .into_iter()
+        .filter(|(_, _, score)| *score > 0.0)
+        .take(limit)
+        .map(|(contract, profile, score)| Recommendation {
+            id: contract["id"].as_str().unwrap_or_default().to_string(),
+            contract_id: contract["contract_id"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            name: contract["name"].as_str().unwrap_or("unknown").to_string(),
+            category: contract["category"].as_str().map(str::to_string),
+            score,
+            shared_tags: reference_profile
+                .tags
+                .intersection(&profile.tags)
+                .cloned()
+                .collect(),
+            shared_methods: match (&reference_profile.methods, &profile.methods) {
+                (Some(a), Some(b)) => a.intersection(b).count(),
+                _ => 0,
+            },
+        })
+        .collect();
+
+    let reference_name = reference["name"].as_str().unwrap_or(reference_id);
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(
+            &serde_json::json!({
+                "reference": { "id": reference_uuid, "name": reference_name },
+                "contracts": recommendations,
+            }),
+            crate::output::Format::Json,
+        )?;
+        return Ok(());
+    }
+
+    println!(
+        "\n{} {}",
+        "Contracts similar to".bold().cyan(),
+        reference_name.bold()
+    );
+    println!("{}", "=".repeat(80).cyan());
+    if reference_profile.methods.is_none() {
+        println!(
+            "  {} No ABI registered for {}; ranking by tags and category only\n",
+            "⚠".yellow(),
+            reference_name
+        );
+    }
+
+    if recommendations.is_empty() {
+        println!("{}", "No similar contracts found.".yellow());
+        println!("\n{}", "Suggestions:".bold());
+        println!("  • Add tags or a category to the reference contract");
+        if category.is_some() || verified_only {
+            println!("  • Remove --category / --verified-only to widen the pool");
+        }
+        println!();
+        return Ok(());
+    }
+
+    for (i, rec) in recommendations.iter().enumerate() {
+        println!(
+            "  {:>2}. {} {} {}",
+            i + 1,
+            rec.name.bold(),
+            format!("{:.0}%", rec.score * 100.0).green(),
+            rec.category
+                .as_deref()
+                .map(|c| format!("[{}]", c))
+                .unwrap_or_default()
+                .bright_black()
+        );
+        let mut why = Vec::new();
+        if !rec.shared_tags.is_empty() {
+            why.push(format!("tags: {}", rec.shared_tags.join(", ")));
+        }
+        if rec.shared_methods > 0 {
+            why.push(format!("{} shared method(s)", rec.shared_methods));
+        }
+        if !why.is_empty() {
+            println!("      {}", why.join(" | ").bright_black());
+        }
+    }
+    println!("\n{}", "=".repeat(80).cyan());
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn similarity_combines_metadata_and_interface() {
+        let token_abi = json!([
+            { "type": "function", "name": "transfer" },
+            { "type": "function", "name": "balance" }
+        ]);
+        let reference = Profile::new(
+            &json!({ "tags": ["defi", "token"], "category": "Token" }),
+            Some(&token_abi),
+        );
+        let twin = Profile::new(
+            &json!({ "tags": [{ "name": "Token" }, "defi"], "category": "token" }),
+            Some(&token_abi),
+        );
+        assert!((similarity(&reference, &twin) - 1.0).abs() < 1e-9);
+
+        let unrelated = Profile::new(
+            &json!({ "tags": ["nft"], "category": "art" }),
+            Some(&json!([{ "type": "function", "name": "mint" }])),
+        );
+        assert_eq!(similarity(&reference, &unrelated), 0.0);
+
+        // Same metadata, no ABI: metadata alone fills the range.
+        let no_abi = Profile::new(
+            &json!({ "tags": ["defi", "token"], "category": "token" }),
+            None,
+        );
+        assert!((similarity(&reference, &no_abi) - 1.0).abs() < 1e-9);
+
+        let same_interface = Profile::new(&json!({ "tags": [] }), Some(&token_abi));
+        assert!(similarity(&reference, &same_interface) > similarity(&reference, &unrelated));
+    }
+}
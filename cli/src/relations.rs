@@ -0,0 +1,326 @@
+//! Typed relationships between registered contracts: one contract
+//! supersedes, forks or is related to another.
+//!
+//! Edges are set at publish time (`--supersedes`, `--forks`, `--related`) or
+//! later with `link`, and kept by the registry under
+//! `/api/contracts/{id}/relations`. Targets are validated and stored by
+//! registry UUID so renames don't break them.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum Relation {
+    /// The source replaces the target (e.g. a v2 deployment).
+    Supersedes,
+    /// The source was forked from the target.
+    Forks,
+    /// Loosely related; reads the same in both directions.
+    Related,
+}
+
+impl Relation {
+    /// How the edge reads from its source.
+    pub fn label(self) -> &'static str {
+        match self {
+            Relation::Supersedes => "supersedes",
+            Relation::Forks => "forks",
+            Relation::Related => "related to",
+        }
+    }
+
+    /// How the edge reads from its target.
+    pub fn inverse_label(self) -> &'static str {
+        match self {
+            Relation::Supersedes => "superseded by",
+            Relation::Forks => "forked by",
+            Relation::Related => "related to",
+        }
+    }
+}
+
+/// One stored edge. Names are filled in by the registry when it knows them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Edge {
+    pub from: String,
+    pub relation: Relation,
+    pub to: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_name: Option<String>,
+}
+
+/// A relation to add to the contract being published or linked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target {
+    pub relation: Relation,
+    /// Registry UUID, contract address or name as given; a UUID once
+    /// [`resolve`]d.
+    pub contract: String,
+}
+
+/// Targets from publish's `--supersedes`, `--forks` and `--related`,
+/// without duplicates.
+pub fn from_publish_args(
+    supersedes: &[String],
+    forks: &[String],
+    related: &[String],
+) -> Vec<Target> {
+    let mut targets: Vec<Target> = Vec::new();
+    let requested = [
+        (Relation::Supersedes, supersedes),
+        (Relation::Forks, forks),
+        (Relation::Related, related),
+    ];
+    for (relation, contracts) in requested {
+        for contract in contracts {
+            let target = Target {
+                relation,
+                contract: contract.trim().to_string(),
+            };
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+    }
+    targets
+}
+
+/// Check every target exists and replace it with its registry UUID.
+pub async fn resolve(
+    client: &reqwest::Client,
+    api_url: &str,
+    targets: &[Target],
+) -> Result<Vec<Target>> {
+    let mut resolved = Vec::with_capacity(targets.len());
+    for target in targets {
+        let contract = crate::recommend::fetch_contract(client, api_url, &target.contract)
+            .await
+            .with_context(|| {
+                format!(
+                    "Cannot link ({} {}): referenced contract does not exist",
+                    target.relation.label(),
+                    target.contract
+                )
+            })?;
+        let uuid = contract["id"]
+            .as_str()
+            .with_context(|| format!("Registry returned no ID for {}", target.contract))?;
+        crate::context::echo_resolved(&target.contract, uuid, "registry ID");
+        resolved.push(Target {
+            relation: target.relation,
+            contract: uuid.to_string(),
+        });
+    }
+    Ok(resolved)
+}
+
+/// `relations` payload for a publish request.
+pub fn payload(targets: &[Target]) -> Value {
+    json!(targets
+        .iter()
+        .map(|t| json!({ "relation": t.relation, "target_id": t.contract }))
+        .collect::<Vec<_>>())
+}
+
+/// Edges touching one contract, split by direction.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RelationGraph {
+    #[serde(default)]
+    pub outgoing: Vec<Edge>,
+    #[serde(default)]
+    pub incoming: Vec<Edge>,
+}
+
+impl RelationGraph {
+    pub fn new(uuid: &str, edges: Vec<Edge>) -> Self {
+        let (outgoing, incoming) = edges.into_iter().partition(|e| e.from == uuid);
+        RelationGraph { outgoing, incoming }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.outgoing.is_empty() && self.incoming.is_empty()
+    }
+
+    /// `(label, other contract)` lines as seen from this contract.
+    pub fn lines(&self) -> Vec<(&'static str, String)> {
+        let name = |id: &str, name: &Option<String>| match name {
+            Some(name) => format!("{} ({})", name, id),
+            None => id.to_string(),
+        };
+        let outgoing = self
+            .outgoing
+            .iter()
+            .map(|e| (e.relation.label(), name(&e.to, &e.to_name)));
+        let incoming = self
+            .incoming
+            .iter()
+            .map(|e| (e.relation.inverse_label(), name(&e.from, &e.from_name)));
+        outgoing.chain(incoming).collect()
+    }
+}
+
+/// The relation graph around `uuid`; empty when the registry has none or
+/// can't be reached, so `info` still renders.
+pub async fn fetch_graph(client: &reqwest::Client, api_url: &str, uuid: &str) -> RelationGraph {
+    let edges = async {
+        let response = client
+            .get(format!("{}/api/contracts/{}/relations", api_url, uuid))
+            .send()
+            .await
+            .ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        let body: Value = response.json().await.ok()?;
+        let items = body.get("relations").cloned().unwrap_or(body);
+        serde_json::from_value::<Vec<Edge>>(items).ok()
+    };
+    RelationGraph::new(uuid, edges.await.unwrap_or_default())
+}
+
+/// `link`: add (or with `remove`, delete) the edge `from --relation--> to`.
+pub async fn link(
+    api_url: &str,
+    from: &str,
+    relation: Relation,
+    to: &str,
+    remove: bool,
+    json: bool,
+) -> Result<()> {
+    let client = reqwest::Client::new();
+    let source = crate::recommend::fetch_contract(&client, api_url, from)
+        .await
+        .with_context(|| format!("Cannot link from {}: contract does not exist", from))?;
+    let from_uuid = source["id"]
+        .as_str()
+        .context("Registry returned no contract ID")?;
+    let target = resolve(
+        &client,
+        api_url,
+        &[Target {
+            relation,
+            contract: to.to_string(),
+        }],
+    )
+    .await?
+    .remove(0);
+    if target.contract == from_uuid {
+        bail!("A contract can't be linked to itself");
+    }
+
+    if !remove && relation != Relation::Related {
+        // A supersedes B and B supersedes A would make lineage circular.
+        let graph = fetch_graph(&client, api_url, from_uuid).await;
+        if graph
+            .incoming
+            .iter()
+            .any(|e| e.relation == relation && e.from == target.contract)
+        {
+            bail!(
+                "{} already {} {}; the reverse link would be circular",
+                to,
+                relation.label(),
+                from
+            );
+        }
+    }
+
+    let url = format!("{}/api/contracts/{}/relations", api_url, from_uuid);
+    let body = json!({ "relation": relation, "target_id": target.contract });
+    let request = if remove {
+        client.delete(&url).json(&body)
+    } else {
+        client.post(&url).json(&body)
+    };
+    let response = request
+        .send()
+        .await
+        .context("Failed to update contract relations")?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        bail!("Failed to update relations ({}): {}", status, text);
+    }
+
+    let edge = Edge {
+        from: from_uuid.to_string(),
+        relation,
+        to: target.contract,
+        from_name: source["name"].as_str().map(str::to_string),
+        to_name: None,
+    };
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(
+            &json!({ "removed": remove, "edge": edge }),
+            crate::output::Format::Json,
+        )?;
+        return Ok(());
+    }
+    println!(
+        "{} {} {} {} {}",
+        if remove {
+            "✓ Removed:"
+        } else {
+            "✓ Linked:"
+        }
+        .green()
+        .bold(),
+        from.bold(),
+        relation.label(),
+        to.bold(),
+        edge.to.bright_black()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(from: &str, relation: Relation, to: &str) -> Edge {
+        Edge {
+            from: from.into(),
+            relation,
+            to: to.into(),
+            from_name: None,
+            to_name: None,
+        }
+    }
+
+    #[test]
+    fn builds_targets_and_reads_the_graph_from_both_ends() {
+        let targets =
+            from_publish_args(&["old".into()], &[], &["a".into(), "b".into(), "a".into()]);
+        assert_eq!(targets.len(), 3);
+        assert_eq!(targets[0].relation, Relation::Supersedes);
+        assert_eq!(
+            payload(&targets[..1]),
+            json!([{ "relation": "supersedes", "target_id": "old" }])
+        );
+
+        let graph = RelationGraph::new(
+            "me",
+            vec![
+                edge("me", Relation::Supersedes, "v1"),
+                Edge {
+                    from_name: Some("Fork".into()),
+                    ..edge("fork", Relation::Forks, "me")
+                },
+            ],
+        );
+        assert_eq!(
+            graph.lines(),
+            [
+                ("supersedes", "v1".to_string()),
+                ("forked by", "Fork (fork)".to_string())
+            ]
+        );
+        assert!(RelationGraph::new("me", Vec::new()).is_empty());
+    }
+}
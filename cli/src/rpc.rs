@@ -0,0 +1,568 @@
+//! Thin Soroban RPC client for reading on-chain contract state.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use stellar_strkey::Strkey;
+use stellar_xdr::curr::{
+    AccountId, ContractDataDurability, ContractExecutable, ContractId, Hash, HostFunction,
+    InvokeContractArgs, InvokeHostFunctionOp, LedgerEntry, LedgerEntryData, LedgerKey,
+    LedgerKeyContractCode, LedgerKeyContractData, Limits, Memo, MuxedAccount, Operation,
+    OperationBody, Preconditions, PublicKey, ReadXdr, ScAddress, ScSymbol, ScVal, SequenceNumber,
+    SorobanTransactionData, Transaction, TransactionEnvelope, TransactionExt,
+    TransactionV1Envelope, Uint256, WriteXdr,
+};
+
+const REQUEST_TIMEOUT_SECS: u64 = 15;
+
+#[derive(Deserialize)]
+struct RpcEnvelope<T> {
+    result: Option<T>,
+    error: Option<RpcErrorBody>,
+}
+
+#[derive(Deserialize)]
+struct RpcErrorBody {
+    code: Option<i64>,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerEntryResult {
+    pub key: String,
+    pub xdr: String,
+    /// Ledger through which the entry stays live before it is archived.
+    pub live_until_ledger_seq: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GetLedgerEntriesResult {
+    #[serde(default)]
+    entries: Vec<LedgerEntryResult>,
+    latest_ledger: Option<u64>,
+}
+
+/// Issue a JSON-RPC call and decode its `result`.
+pub async fn call<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    endpoint: &str,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<T> {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": params,
+    });
+
+    let response = client
+        .post(endpoint)
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .json(&body)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach Soroban RPC at {}", endpoint))?;
+
+    if !response.status().is_success() {
+        bail!("RPC {} returned HTTP {}", method, response.status());
+    }
+
+    let envelope: RpcEnvelope<T> = response
+        .json()
+        .await
+        .with_context(|| format!("Invalid {} response", method))?;
+
+    if let Some(err) = envelope.error {
+        bail!(
+            "RPC {} failed ({}): {}",
+            method,
+            err.code.unwrap_or_default(),
+            err.message
+        );
+    }
+    envelope
+        .result
+        .with_context(|| format!("RPC {} returned no result", method))
+}
+
+/// Fetch raw ledger entries for base64 XDR `LedgerKey`s.
+pub async fn get_ledger_entries(
+    client: &reqwest::Client,
+    endpoint: &str,
+    keys: Vec<String>,
+) -> Result<Vec<LedgerEntryResult>> {
+    let result: GetLedgerEntriesResult = call(
+        client,
+        endpoint,
+        "getLedgerEntries",
+        serde_json::json!({ "keys": keys, "xdrFormat": "base64" }),
+    )
+    .await?;
+    log::debug!(
+        "getLedgerEntries: {} entries at ledger {:?}",
+        result.entries.len(),
+        result.latest_ledger
+    );
+    Ok(result.entries)
+}
+
+#[derive(Deserialize)]
+struct NetworkResult {
+    passphrase: String,
+}
+
+/// Passphrase of the network an RPC endpoint serves.
+pub async fn network_passphrase(client: &reqwest::Client, endpoint: &str) -> Result<String> {
+    let result: NetworkResult = call(client, endpoint, "getNetwork", serde_json::json!({})).await?;
+    Ok(result.passphrase)
+}
+
+/// Fail when `endpoint` serves a different network than `network`.
+pub async fn ensure_network(
+    client: &reqwest::Client,
+    endpoint: &str,
+    network: crate::config::Network,
+) -> Result<()> {
+    let passphrase = network_passphrase(client, endpoint).await?;
+    crate::config::validate_passphrase(
+        network,
+        &passphrase,
+        &format!("the RPC endpoint {}", endpoint),
+    )
+}
+
+#[derive(Deserialize)]
+struct LatestLedger {
+    sequence: u64,
+}
+
+/// Sequence number of the most recently closed ledger.
+pub async fn latest_ledger(client: &reqwest::Client, endpoint: &str) -> Result<u64> {
+    let result: LatestLedger =
+        call(client, endpoint, "getLatestLedger", serde_json::json!({})).await?;
+    Ok(result.sequence)
+}
+
+pub fn contract_address(contract_id: &str) -> Result<ScAddress> {
+    match Strkey::from_string(contract_id)
+        .map_err(|e| anyhow::anyhow!("invalid contract address {}: {}", contract_id, e))?
+    {
+        Strkey::Contract(c) => Ok(ScAddress::Contract(ContractId(Hash(c.0)))),
+        _ => bail!("{} is not a contract address (expected C...)", contract_id),
+    }
+}
+
+/// Account (`G...`) or contract (`C...`) address as an `ScAddress`.
+pub fn sc_address(address: &str) -> Result<ScAddress> {
+    match Strkey::from_string(address)
+        .map_err(|e| anyhow::anyhow!("invalid address {}: {}", address, e))?
+    {
+        Strkey::Contract(c) => Ok(ScAddress::Contract(ContractId(Hash(c.0)))),
+        Strkey::PublicKeyEd25519(key) => Ok(ScAddress::Account(AccountId(
+            PublicKey::PublicKeyTypeEd25519(Uint256(key.0)),
+        ))),
+        _ => bail!("{} is not an account or contract address", address),
+    }
+}
+
+fn sc_symbol(name: &str) -> Result<ScSymbol> {
+    let sym = name
+        .as_bytes()
+        .to_vec()
+        .try_into()
+        .map_err(|e| anyhow::anyhow!("invalid symbol '{}': {:?}", name, e))?;
+    Ok(ScSymbol(sym))
+}
+
+pub fn symbol(name: &str) -> Result<ScVal> {
+    Ok(ScVal::Symbol(sc_symbol(name)?))
+}
+
+fn data_key(address: &ScAddress, key: ScVal, durability: ContractDataDurability) -> Result<String> {
+    LedgerKey::ContractData(LedgerKeyContractData {
+        contract: address.clone(),
+        key,
+        durability,
+    })
+    .to_xdr_base64(Limits::none())
+    .context("Failed to encode ledger key")
+}
+
+fn decode_data_val(entry: &LedgerEntryResult) -> Result<ScVal> {
+    let ledger_entry = LedgerEntry::from_xdr_base64(&entry.xdr, Limits::none())
+        .context("Failed to decode ledger entry")?;
+    match ledger_entry.data {
+        LedgerEntryData::ContractData(data) => Ok(data.val),
+        _ => bail!("unexpected ledger entry type for contract data"),
+    }
+}
+
+/// Base64 `LedgerKey` of a contract's instance entry.
+pub fn instance_key(address: &ScAddress) -> Result<String> {
+    data_key(
+        address,
+        ScVal::LedgerKeyContractInstance,
+        ContractDataDurability::Persistent,
+    )
+}
+
+/// A contract's instance entry and, for wasm contracts, its code entry.
+pub struct ContractEntries {
+    pub instance: LedgerEntryResult,
+    pub wasm_hash: Option<String>,
+    pub code: Option<LedgerEntryResult>,
+}
+
+/// Look up the ledger entries that keep a contract callable. Returns `None`
+/// when the contract instance does not exist (or was never deployed).
+pub async fn fetch_contract_entries(
+    client: &reqwest::Client,
+    endpoint: &str,
+    contract_id: &str,
+) -> Result<Option<ContractEntries>> {
+    let address = contract_address(contract_id)?;
+    let mut entries = get_ledger_entries(client, endpoint, vec![instance_key(&address)?]).await?;
+    if entries.is_empty() {
+        return Ok(None);
+    }
+    let instance = entries.remove(0);
+
+    let wasm_hash = match decode_data_val(&instance)? {
+        ScVal::ContractInstance(inst) => match inst.executable {
+            ContractExecutable::Wasm(hash) => Some(hash),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    let code = match &wasm_hash {
+        Some(hash) => {
+            let key = LedgerKey::ContractCode(LedgerKeyContractCode { hash: hash.clone() })
+                .to_xdr_base64(Limits::none())
+                .context("Failed to encode ledger key")?;
+            get_ledger_entries(client, endpoint, vec![key])
+                .await?
+                .into_iter()
+                .next()
+        }
+        None => None,
+    };
+
+    Ok(Some(ContractEntries {
+        instance,
+        wasm_hash: wasm_hash.map(|h| hex::encode(h.0)),
+        code,
+    }))
+}
+
+/// TTL of one ledger entry belonging to a contract.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EntryTtl {
+    /// `instance`, `code`, or the persistent storage key name
+    pub entry: String,
+    pub found: bool,
+    pub live_until_ledger: Option<u64>,
+}
+
+/// TTLs of a contract's instance, code and selected persistent storage.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ContractTtl {
+    pub latest_ledger: u64,
+    pub entries: Vec<EntryTtl>,
+}
+
+/// Query the TTLs of a contract's ledger entries. Persistent storage is
+/// looked up for each of `storage_keys` (symbol keys). Returns `None` when
+/// the contract instance does not exist.
+pub async fn contract_ttl(
+    client: &reqwest::Client,
+    endpoint: &str,
+    contract_id: &str,
+    storage_keys: &[String],
+) -> Result<Option<ContractTtl>> {
+    let latest_ledger = latest_ledger(client, endpoint).await?;
+    let Some(contract) = fetch_contract_entries(client, endpoint, contract_id).await? else {
+        return Ok(None);
+    };
+
+    let mut entries = vec![EntryTtl {
+        entry: "instance".to_string(),
+        found: true,
+        live_until_ledger: contract.instance.live_until_ledger_seq,
+    }];
+    if contract.wasm_hash.is_some() {
+        entries.push(EntryTtl {
+            entry: "code".to_string(),
+            found: contract.code.is_some(),
+            live_until_ledger: contract.code.as_ref().and_then(|c| c.live_until_ledger_seq),
+        });
+    }
+
+    if !storage_keys.is_empty() {
+        let address = contract_address(contract_id)?;
+        let mut encoded = BTreeMap::new();
+        for name in storage_keys {
+            let key = data_key(&address, symbol(name)?, ContractDataDurability::Persistent)?;
+            encoded.insert(key, name.clone());
+        }
+        let found: BTreeMap<String, LedgerEntryResult> =
+            get_ledger_entries(client, endpoint, encoded.keys().cloned().collect())
+                .await?
+                .into_iter()
+                .map(|e| (e.key.clone(), e))
+                .collect();
+        for name in storage_keys {
+            let entry = encoded
+                .iter()
+                .find(|(_, n)| *n == name)
+                .and_then(|(key, _)| found.get(key));
+            entries.push(EntryTtl {
+                entry: name.clone(),
+                found: entry.is_some(),
+                live_until_ledger: entry.and_then(|e| e.live_until_ledger_seq),
+            });
+        }
+    }
+
+    Ok(Some(ContractTtl {
+        latest_ledger,
+        entries,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+struct SimulateResult {
+    #[serde(default)]
+    results: Vec<SimulateHostFunctionResult>,
+    error: Option<String>,
+    #[serde(rename = "minResourceFee")]
+    min_resource_fee: Option<String>,
+    #[serde(rename = "transactionData")]
+    transaction_data: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SimulateHostFunctionResult {
+    xdr: String,
+}
+
+/// Outcome of simulating a contract call.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Simulation {
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub min_resource_fee: Option<String>,
+    /// Resources the call would consume, when the simulation succeeded.
+    pub resources: Option<SimulatedResources>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SimulatedResources {
+    pub instructions: u32,
+    pub disk_read_bytes: u32,
+    pub write_bytes: u32,
+    pub read_entries: usize,
+    pub write_entries: usize,
+}
+
+impl SimulatedResources {
+    fn from_transaction_data(xdr: &str) -> Result<Self> {
+        let data = SorobanTransactionData::from_xdr_base64(xdr, Limits::none())
+            .context("Failed to decode simulated transaction data")?;
+        let resources = data.resources;
+        Ok(SimulatedResources {
+            instructions: resources.instructions,
+            disk_read_bytes: resources.disk_read_bytes,
+            write_bytes: resources.write_bytes,
+            read_entries: resources.footprint.read_only.len(),
+            write_entries: resources.footprint.read_write.len(),
+        })
+    }
+}
+
+/// Simulate `function(args)` on a contract without submitting anything.
+///
+/// The transaction is built with a throwaway source account, which is
+/// enough for read-only calls that need no authorization.
+pub async fn simulate_invoke(
+    client: &reqwest::Client,
+    endpoint: &str,
+    contract_id: &str,
+    function: &str,
+    args: Vec<ScVal>,
+) -> Result<Simulation> {
+    let op = Operation {
+        source_account: None,
+        body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                contract_address: contract_address(contract_id)?,
+                function_name: sc_symbol(function)?,
+                args: args
+                    .try_into()
+                    .map_err(|_| anyhow::anyhow!("too many arguments for {}", function))?,
+            }),
+            auth: Default::default(),
+        }),
+    };
+    let tx = Transaction {
+        source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+        fee: 100,
+        seq_num: SequenceNumber(0),
+        cond: Preconditions::None,
+        memo: Memo::None,
+        operations: vec![op]
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("failed to build transaction"))?,
+        ext: TransactionExt::V0,
+    };
+    let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx,
+        signatures: Default::default(),
+    })
+    .to_xdr_base64(Limits::none())
+    .context("Failed to encode transaction")?;
+
+    let sim: SimulateResult = call(
+        client,
+        endpoint,
+        "simulateTransaction",
+        serde_json::json!({ "transaction": envelope }),
+    )
+    .await?;
+
+    let result = match sim.results.first() {
+        Some(r) => Some(scval_to_json(
+            &ScVal::from_xdr_base64(&r.xdr, Limits::none())
+                .context("Failed to decode simulation result")?,
+        )),
+        None => None,
+    };
+
+    let resources = sim
+        .transaction_data
+        .as_deref()
+        .map(SimulatedResources::from_transaction_data)
+        .transpose()?;
+
+    Ok(Simulation {
+        result,
+        error: sim.error,
+        min_resource_fee: sim.min_resource_fee,
+        resources,
+    })
+}
+
+/// Read the named storage entries of a contract.
+///
+/// Each key is looked up in the contract's instance storage first (where
+/// admin/config values usually live), then as a persistent entry keyed by
+/// the symbol. Keys that exist in neither map to `None`.
+pub async fn fetch_contract_storage(
+    client: &reqwest::Client,
+    endpoint: &str,
+    contract_id: &str,
+    keys: &[String],
+) -> Result<BTreeMap<String, Option<serde_json::Value>>> {
+    let address = contract_address(contract_id)?;
+    let mut values: BTreeMap<String, Option<serde_json::Value>> =
+        keys.iter().map(|k| (k.clone(), None)).collect();
+
+    let instance = get_ledger_entries(client, endpoint, vec![instance_key(&address)?]).await?;
+    let Some(instance) = instance.first() else {
+        bail!("contract {} not found on-chain", contract_id);
+    };
+    if let ScVal::ContractInstance(inst) = decode_data_val(instance)? {
+        for entry in inst.storage.iter().flat_map(|m| m.0.iter()) {
+            if let Some(name) = key_name(&entry.key) {
+                if let Some(slot) = values.get_mut(&name) {
+                    *slot = Some(scval_to_json(&entry.val));
+                }
+            }
+        }
+    }
+
+    let missing: Vec<String> = values
+        .iter()
+        .filter(|(_, v)| v.is_none())
+        .map(|(k, _)| k.clone())
+        .collect();
+    if !missing.is_empty() {
+        let mut encoded = BTreeMap::new();
+        for name in &missing {
+            let key = data_key(&address, symbol(name)?, ContractDataDurability::Persistent)?;
+            encoded.insert(key, name.clone());
+        }
+        let entries =
+            get_ledger_entries(client, endpoint, encoded.keys().cloned().collect()).await?;
+        for entry in &entries {
+            if let Some(name) = encoded.get(&entry.key) {
+                values.insert(name.clone(), Some(scval_to_json(&decode_data_val(entry)?)));
+            }
+        }
+    }
+
+    Ok(values)
+}
+
+fn key_name(key: &ScVal) -> Option<String> {
+    match key {
+        ScVal::Symbol(s) => Some(s.0.to_utf8_string_lossy()),
+        ScVal::String(s) => Some(s.0.to_utf8_string_lossy()),
+        // `enum DataKey { Admin }` style keys encode as a one-element vec.
+        ScVal::Vec(Some(v)) if v.0.len() == 1 => key_name(&v.0[0]),
+        _ => None,
+    }
+}
+
+fn address_to_string(address: &ScAddress) -> String {
+    match address {
+        ScAddress::Account(account) => match &account.0 {
+            PublicKey::PublicKeyTypeEd25519(key) => {
+                format!("{}", stellar_strkey::ed25519::PublicKey(key.0))
+            }
+        },
+        ScAddress::Contract(ContractId(Hash(bytes))) => {
+            format!("{}", stellar_strkey::Contract(*bytes))
+        }
+        other => format!("{:?}", other),
+    }
+}
+
+/// Render an `ScVal` as plain JSON for display and comparison.
+pub fn scval_to_json(val: &ScVal) -> serde_json::Value {
+    use serde_json::Value;
+    match val {
+        ScVal::Void => Value::Null,
+        ScVal::Bool(b) => Value::Bool(*b),
+        ScVal::U32(n) => Value::from(*n),
+        ScVal::I32(n) => Value::from(*n),
+        ScVal::U64(n) => Value::from(*n),
+        ScVal::I64(n) => Value::from(*n),
+        ScVal::Timepoint(t) => Value::from(t.0),
+        ScVal::Duration(d) => Value::from(d.0),
+        ScVal::U128(p) => Value::String((((p.hi as u128) << 64) | p.lo as u128).to_string()),
+        ScVal::I128(p) => Value::String((((p.hi as i128) << 64) | p.lo as i128).to_string()),
+        ScVal::Symbol(s) => Value::String(s.0.to_utf8_string_lossy()),
+        ScVal::String(s) => Value::String(s.0.to_utf8_string_lossy()),
+        ScVal::Bytes(b) => Value::String(hex::encode(&b.0)),
+        ScVal::Address(a) => Value::String(address_to_string(a)),
+        ScVal::Vec(Some(v)) => Value::Array(v.0.iter().map(scval_to_json).collect()),
+        ScVal::Vec(None) => Value::Array(vec![]),
+        ScVal::Map(Some(m)) => Value::Object(
+            m.0.iter()
+                .map(|e| {
+                    let key = key_name(&e.key).unwrap_or_else(|| scval_to_json(&e.key).to_string());
+                    (key, scval_to_json(&e.val))
+                })
+                .collect(),
+        ),
+        ScVal::Map(None) => Value::Object(Default::default()),
+        other => Value::String(format!("{:?}", other)),
+    }
+}
@@ -0,0 +1,106 @@
+//! Interrupt handling for commands that change registry state.
+//!
+//! Killing the process in the middle of a `publish` or `migrate` request
+//! leaves the outcome unknown. While a mutation is in flight the first
+//! Ctrl-C / SIGTERM is held back so the request can finish; a second one
+//! aborts immediately, warning that the registry state is indeterminate and
+//! how to check it.
+
+use std::future::Future;
+
+use anyhow::Result;
+use colored::Colorize;
+
+/// Exit status for a run aborted by a signal (128 + SIGINT).
+const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// A state-changing operation, as described to the user on interrupt.
+#[derive(Debug, Clone)]
+pub struct Mutation {
+    /// e.g. "publish", shown as "the in-flight publish".
+    pub operation: &'static str,
+    /// Command that shows whether the operation took effect.
+    pub check: Option<String>,
+}
+
+impl Mutation {
+    pub fn new(operation: &'static str, check: impl Into<Option<String>>) -> Self {
+        Mutation {
+            operation,
+            check: check.into(),
+        }
+    }
+}
+
+/// Resolves on the next Ctrl-C, or SIGTERM on Unix. Never resolves if no
+/// handler could be installed.
+async fn interrupted() {
+    let ctrl_c = async {
+        if tokio::signal::ctrl_c().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut term) => {
+                tokio::select! {
+                    _ = ctrl_c => {}
+                    _ = term.recv() => {}
+                }
+            }
+            Err(_) => ctrl_c.await,
+        }
+    }
+    #[cfg(not(unix))]
+    ctrl_c.await;
+}
+
+/// Run `operation` so that an interrupt can't cut it off mid-request.
+pub async fn guard<T>(
+    mutation: &Mutation,
+    operation: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    tokio::pin!(operation);
+
+    tokio::select! {
+        result = &mut operation => return result,
+        _ = interrupted() => {}
+    }
+    eprintln!(
+        "\n{} Interrupt received — letting the in-flight {} finish so registry state stays consistent. Press Ctrl-C again to abort.",
+        "⚠".yellow(),
+        mutation.operation
+    );
+
+    tokio::select! {
+        result = &mut operation => result,
+        _ = interrupted() => {
+            eprintln!(
+                "{} Aborted during {}: the registry may or may not have applied it.",
+                "✗".red(),
+                mutation.operation
+            );
+            if let Some(check) = &mutation.check {
+                eprintln!("  Check the current state with: {}", check.bold());
+            }
+            std::process::exit(INTERRUPTED_EXIT_CODE);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn uninterrupted_operations_pass_through() {
+        let mutation = Mutation::new("publish", None);
+        assert_eq!(guard(&mutation, async { Ok(7) }).await.unwrap(), 7);
+        assert!(guard::<()>(&mutation, async { anyhow::bail!("rejected") })
+            .await
+            .is_err());
+    }
+}
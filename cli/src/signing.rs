@@ -0,0 +1,180 @@
+//! Pluggable key access for commands that sign on the user's behalf.
+//!
+//! Commands take a `&dyn Signer` and never touch key material directly. The
+//! backend is chosen with the top-level `--signer <backend>` flag:
+//!
+//! * `file:<path>`   – base64 Ed25519 secret key stored in a file
+//! * `env[:<VAR>]`   – base64 secret key in an environment variable
+//!   (default [`DEFAULT_KEY_ENV`])
+//! * `keyring[:<name>]` – secret held by the OS keyring (macOS Keychain via
+//!   `security`, Secret Service via `secret-tool` elsewhere)
+//!
+//! New backends (hardware wallets, HSMs) only need a [`Signer`] impl and a
+//! case in [`from_spec`].
+
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
+
+pub const DEFAULT_KEY_ENV: &str = "SOROBAN_REGISTRY_SIGNING_KEY";
+const KEYRING_SERVICE: &str = "soroban-registry";
+
+pub trait Signer: Send + Sync {
+    /// Short description of where the key lives, for display.
+    fn describe(&self) -> String;
+
+    fn public_key(&self) -> VerifyingKey;
+
+    fn sign(&self, payload: &[u8]) -> Result<Signature>;
+}
+
+/// Signer backed by an Ed25519 secret key held in memory. The file, env and
+/// keyring backends all resolve to one of these once the secret is loaded.
+pub struct KeySigner {
+    key: SigningKey,
+    source: String,
+}
+
+impl KeySigner {
+    pub fn new(key: SigningKey, source: impl Into<String>) -> Self {
+        KeySigner {
+            key,
+            source: source.into(),
+        }
+    }
+
+    /// Parse a base64-encoded 32-byte Ed25519 secret.
+    pub fn from_base64(secret: &str, source: impl Into<String>) -> Result<Self> {
+        let source = source.into();
+        let bytes = BASE64
+            .decode(secret.trim())
+            .with_context(|| format!("Invalid private key in {} (expected base64)", source))?;
+        let bytes: [u8; 32] = bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Private key in {} must be 32 bytes", source))?;
+        Ok(KeySigner::new(SigningKey::from_bytes(&bytes), source))
+    }
+}
+
+impl Signer for KeySigner {
+    fn describe(&self) -> String {
+        self.source.clone()
+    }
+
+    fn public_key(&self) -> VerifyingKey {
+        self.key.verifying_key()
+    }
+
+    fn sign(&self, payload: &[u8]) -> Result<Signature> {
+        Ok(ed25519_dalek::Signer::sign(&self.key, payload))
+    }
+}
+
+fn from_file(path: &str) -> Result<KeySigner> {
+    let secret = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read signing key file {}", path))?;
+    KeySigner::from_base64(&secret, format!("file:{}", path))
+}
+
+fn from_env(var: &str) -> Result<KeySigner> {
+    let secret = std::env::var(var)
+        .with_context(|| format!("Signing key environment variable {} is not set", var))?;
+    KeySigner::from_base64(&secret, format!("env:{}", var))
+}
+
+fn from_keyring(name: &str) -> Result<KeySigner> {
+    let output = if cfg!(target_os = "macos") {
+        Command::new("security")
+            .args([
+                "find-generic-password",
+                "-s",
+                KEYRING_SERVICE,
+                "-a",
+                name,
+                "-w",
+            ])
+            .output()
+    } else {
+        Command::new("secret-tool")
+            .args(["lookup", "service", KEYRING_SERVICE, "account", name])
+            .output()
+    }
+    .context("Failed to query the OS keyring")?;
+
+    if !output.status.success() || output.stdout.is_empty() {
+        bail!(
+            "No key named '{}' in the OS keyring (service '{}')",
+            name,
+            KEYRING_SERVICE
+        );
+    }
+    let secret = String::from_utf8(output.stdout).context("Keyring entry is not valid UTF-8")?;
+    KeySigner::from_base64(&secret, format!("keyring:{}", name))
+}
+
+/// Build a signer from a `--signer` spec such as `file:key.txt`, `env`,
+/// `env:MY_KEY` or `keyring:deployer`.
+pub fn from_spec(spec: &str) -> Result<Box<dyn Signer>> {
+    let (backend, arg) = match spec.split_once(':') {
+        Some((backend, arg)) => (backend, Some(arg)),
+        None => (spec, None),
+    };
+    let signer = match (backend, arg) {
+        ("file", Some(path)) if !path.is_empty() => from_file(path)?,
+        ("file", _) => bail!("--signer file needs a path, e.g. file:./signing.key"),
+        ("env", var) => from_env(var.filter(|v| !v.is_empty()).unwrap_or(DEFAULT_KEY_ENV))?,
+        ("keyring", name) => from_keyring(name.filter(|n| !n.is_empty()).unwrap_or("default"))?,
+        (other, _) => bail!(
+            "Unknown signer backend '{}' (expected file, env or keyring)",
+            other
+        ),
+    };
+    Ok(Box::new(signer))
+}
+
+/// Base64 of a signature, as the registry API expects it.
+pub fn encode_signature(signature: &Signature) -> String {
+    BASE64.encode(signature.to_bytes())
+}
+
+pub fn encode_public_key(key: &VerifyingKey) -> String {
+    BASE64.encode(key.to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::Verifier;
+
+    fn test_signer() -> KeySigner {
+        KeySigner::new(SigningKey::from_bytes(&[7u8; 32]), "test")
+    }
+
+    #[test]
+    fn signatures_are_deterministic_and_verify() {
+        let signer = test_signer();
+        let a = signer.sign(b"proposal:GABC").unwrap();
+        let b = signer.sign(b"proposal:GABC").unwrap();
+        assert_eq!(a, b);
+        assert!(signer.public_key().verify(b"proposal:GABC", &a).is_ok());
+        assert!(signer.public_key().verify(b"proposal:GXYZ", &a).is_err());
+    }
+
+    #[test]
+    fn spec_selects_backend() {
+        let secret = BASE64.encode([7u8; 32]);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signing.key");
+        std::fs::write(&path, format!("{}\n", secret)).unwrap();
+
+        let signer = from_spec(&format!("file:{}", path.display())).unwrap();
+        assert_eq!(signer.public_key(), test_signer().public_key());
+
+        assert!(from_spec("file").is_err());
+        assert!(from_spec("ledger").is_err());
+        assert!(from_spec("env:SOROBAN_REGISTRY_TEST_UNSET_KEY").is_err());
+    }
+}
@@ -0,0 +1,334 @@
+//! Well-known contract interface standards and conformance checks against a
+//! contract's ABI (`conforms`).
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use contract_abi::{ContractABI, SorobanType};
+use serde::{Deserialize, Serialize};
+
+/// SEP-41 token interface.
+const SEP_41: &str = r#"
+name = "SEP-41"
+description = "Stellar token interface"
+
+[[functions]]
+name = "allowance"
+params = ["Address", "Address"]
+returns = "i128"
+
+[[functions]]
+name = "approve"
+params = ["Address", "Address", "i128", "u32"]
+
+[[functions]]
+name = "balance"
+params = ["Address"]
+returns = "i128"
+
+[[functions]]
+name = "transfer"
+params = ["Address", "Address", "i128"]
+
+[[functions]]
+name = "transfer_from"
+params = ["Address", "Address", "Address", "i128"]
+
+[[functions]]
+name = "burn"
+params = ["Address", "i128"]
+
+[[functions]]
+name = "burn_from"
+params = ["Address", "Address", "i128"]
+
+[[functions]]
+name = "decimals"
+returns = "u32"
+
+[[functions]]
+name = "name"
+returns = "String"
+
+[[functions]]
+name = "symbol"
+returns = "String"
+"#;
+
+/// Bundled standards, keyed by the name accepted by `--standard`.
+const BUNDLED: &[(&str, &str)] = &[("sep-41", SEP_41)];
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Standard {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub functions: Vec<RequiredFunction>,
+}
+
+/// A method the standard requires. Types use the ABI's type syntax
+/// (`Address`, `i128`, `Option<u32>`, ...); parameter names are not checked.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RequiredFunction {
+    pub name: String,
+    #[serde(default)]
+    pub params: Vec<String>,
+    #[serde(default = "void")]
+    pub returns: String,
+}
+
+fn void() -> String {
+    "void".to_string()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Gap {
+    Missing {
+        function: String,
+    },
+    Params {
+        function: String,
+        expected: Vec<String>,
+        found: Vec<String>,
+    },
+    Returns {
+        function: String,
+        expected: String,
+        found: String,
+    },
+}
+
+impl std::fmt::Display for Gap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Gap::Missing { function } => write!(f, "{}: missing", function),
+            Gap::Params {
+                function,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{}: expected ({}), found ({})",
+                function,
+                expected.join(", "),
+                found.join(", ")
+            ),
+            Gap::Returns {
+                function,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{}: expected return {}, found {}",
+                function, expected, found
+            ),
+        }
+    }
+}
+
+pub fn bundled(name: &str) -> Result<Standard> {
+    let key = name.to_ascii_lowercase();
+    let (_, source) = BUNDLED
+        .iter()
+        .find(|(k, _)| *k == key || k.replace('-', "") == key)
+        .with_context(|| {
+            let known: Vec<&str> = BUNDLED.iter().map(|(k, _)| *k).collect();
+            format!(
+                "Unknown standard '{}' (bundled: {}; use --standard-file for others)",
+                name,
+                known.join(", ")
+            )
+        })?;
+    Ok(toml::from_str(source)?)
+}
+
+/// Load a custom standard definition (TOML, or JSON for a `.json` path).
+pub fn from_file(path: &str) -> Result<Standard> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read standard file {}", path))?;
+    if Path::new(path).extension().and_then(|e| e.to_str()) == Some("json") {
+        serde_json::from_str(&content).with_context(|| format!("Invalid standard file {}", path))
+    } else {
+        toml::from_str(&content).with_context(|| format!("Invalid standard file {}", path))
+    }
+}
+
+fn type_name(raw: &str) -> String {
+    SorobanType::from_type_string(raw).display_name()
+}
+
+/// Every way `abi` falls short of `standard`, in the standard's order.
+pub fn check(standard: &Standard, abi: &ContractABI) -> Vec<Gap> {
+    let mut gaps = Vec::new();
+    for required in &standard.functions {
+        let Some(function) = abi.public_functions().find(|f| f.name == required.name) else {
+            gaps.push(Gap::Missing {
+                function: required.name.clone(),
+            });
+            continue;
+        };
+
+        let expected: Vec<String> = required.params.iter().map(|p| type_name(p)).collect();
+        let found: Vec<String> = function
+            .params
+            .iter()
+            .map(|p| p.param_type.display_name())
+            .collect();
+        if expected != found {
+            gaps.push(Gap::Params {
+                function: required.name.clone(),
+                expected,
+                found,
+            });
+        }
+
+        let expected = type_name(&required.returns);
+        let found = function.return_type.display_name();
+        if expected != found {
+            gaps.push(Gap::Returns {
+                function: required.name.clone(),
+                expected,
+                found,
+            });
+        }
+    }
+    gaps
+}
+
+pub fn conforms(
+    contract_path: &str,
+    standard: Option<&str>,
+    standard_file: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let standard = match (standard, standard_file) {
+        (_, Some(path)) => from_file(path)?,
+        (Some(name), None) => bundled(name)?,
+        (None, None) => anyhow::bail!("Pass --standard <name> or --standard-file <path>"),
+    };
+
+    let abi_json = crate::commands::load_abi_json(contract_path)?;
+    let contract_name = Path::new(contract_path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("contract");
+    let abi = contract_abi::parse_json_spec(&abi_json, contract_name)
+        .map_err(|e| anyhow::anyhow!("Failed to parse ABI: {}", e))?;
+    let gaps = check(&standard, &abi);
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(
+            &serde_json::json!({
+                "contract": contract_name,
+                "standard": standard.name,
+                "conforms": gaps.is_empty(),
+                "required_functions": standard.functions.len(),
+                "gaps": gaps,
+            }),
+            crate::output::Format::Json,
+        )?;
+    } else {
+        println!("\n{}", "Interface Conformance".bold().cyan());
+        println!("{}", "=".repeat(80).cyan());
+        println!("  {}: {}", "Contract".bold(), contract_name);
+        println!(
+            "  {}: {}{}\n",
+            "Standard".bold(),
+            standard.name,
+            standard
+                .description
+                .as_deref()
+                .map(|d| format!(" ({})", d))
+                .unwrap_or_default()
+                .bright_black()
+        );
+
+        for required in &standard.functions {
+            let failing: Vec<&Gap> = gaps
+                .iter()
+                .filter(|g| match g {
+                    Gap::Missing { function }
+                    | Gap::Params { function, .. }
+                    | Gap::Returns { function, .. } => *function == required.name,
+                })
+                .collect();
+            if failing.is_empty() {
+                println!("  {} {}", "✓".green(), required.name);
+            } else {
+                for gap in failing {
+                    println!("  {} {}", "✗".red(), gap);
+                }
+            }
+        }
+
+        println!("\n{}", "=".repeat(80).cyan());
+        if gaps.is_empty() {
+            println!(
+                "{}\n",
+                format!("✓ Conforms to {}", standard.name).green().bold()
+            );
+        }
+    }
+
+    if !gaps.is_empty() {
+        anyhow::bail!(
+            "{} does not conform to {} ({} gap(s))",
+            contract_name,
+            standard.name,
+            gaps.len()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contract_abi::{ContractFunction, FunctionParam, FunctionVisibility};
+
+    fn function(name: &str, params: &[&str], returns: &str) -> ContractFunction {
+        ContractFunction {
+            name: name.to_string(),
+            visibility: FunctionVisibility::Public,
+            params: params
+                .iter()
+                .enumerate()
+                .map(|(i, t)| FunctionParam {
+                    name: format!("arg{}", i),
+                    param_type: SorobanType::from_type_string(t),
+                    doc: None,
+                })
+                .collect(),
+            return_type: SorobanType::from_type_string(returns),
+            doc: None,
+            is_mutable: false,
+        }
+    }
+
+    #[test]
+    fn sep41_reports_missing_and_mismatched_methods() {
+        let standard = bundled("SEP41").unwrap();
+        let mut abi = ContractABI::new("token".into());
+        for f in &standard.functions {
+            let params: Vec<&str> = f.params.iter().map(String::as_str).collect();
+            abi.functions.push(function(&f.name, &params, &f.returns));
+        }
+        assert!(check(&standard, &abi).is_empty());
+
+        abi.functions.retain(|f| f.name != "burn_from");
+        abi.functions
+            .iter_mut()
+            .find(|f| f.name == "balance")
+            .unwrap()
+            .return_type = SorobanType::U64;
+        let gaps = check(&standard, &abi);
+        assert_eq!(gaps.len(), 2);
+        assert!(matches!(&gaps[0], Gap::Returns { function, .. } if function == "balance"));
+        assert!(matches!(&gaps[1], Gap::Missing { function } if function == "burn_from"));
+
+        assert!(bundled("erc-20").is_err());
+    }
+}
@@ -1,4 +1,77 @@
 use colored::Colorize;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Process-wide table style, set once from the global `--ascii` flag.
+static ASCII_TABLES: AtomicBool = AtomicBool::new(false);
+
+/// Widest a table cell may be before it is cut with an ellipsis, set once
+/// from `--max-field-width` / `--no-truncate`. 0 means unlimited.
+static MAX_FIELD_WIDTH: AtomicUsize = AtomicUsize::new(0);
+
+/// Border style used when rendering tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableStyle {
+    /// Unicode box-drawing characters (default).
+    Unicode,
+    /// Plain ASCII borders for terminals that garble Unicode.
+    Ascii,
+}
+
+/// Selects the table style used by [`render_table`] for the rest of the process.
+pub fn set_ascii(enabled: bool) {
+    ASCII_TABLES.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns the table style currently in effect.
+pub fn current_style() -> TableStyle {
+    if ASCII_TABLES.load(Ordering::Relaxed) {
+        TableStyle::Ascii
+    } else {
+        TableStyle::Unicode
+    }
+}
+
+/// Returns `true` when the terminal is unlikely to render Unicode or colors
+/// correctly. Only an explicit `TERM=dumb` counts: Windows consoles and many
+/// CI runners don't set `TERM` at all and render both fine.
+pub fn is_dumb_terminal() -> bool {
+    std::env::var("TERM").is_ok_and(|term| term == "dumb")
+}
+
+/// Sets the cell width limit used by [`render_table`] and [`fit`] for the
+/// rest of the process; `None` shows full values.
+pub fn set_max_field_width(width: Option<usize>) {
+    MAX_FIELD_WIDTH.store(width.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// Returns the cell width limit currently in effect.
+pub fn max_field_width() -> Option<usize> {
+    Some(MAX_FIELD_WIDTH.load(Ordering::Relaxed)).filter(|&w| w > 0)
+}
+
+/// Default cell width limit: a third of the terminal (`COLUMNS`, else 80
+/// columns), kept between 20 and 60. `None` when stdout is not a terminal,
+/// so piped output is never truncated.
+pub fn default_field_width() -> Option<usize> {
+    use std::io::IsTerminal;
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    let columns = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse::<usize>().ok())
+        .unwrap_or(80);
+    Some((columns / 3).clamp(20, 60))
+}
+
+/// Picks the Unicode or ASCII variant of a status glyph according to the
+/// current table style.
+pub fn glyph(unicode: &'static str, ascii: &'static str) -> &'static str {
+    match current_style() {
+        TableStyle::Unicode => unicode,
+        TableStyle::Ascii => ascii,
+    }
+}
 
 /// Returns the number of visible (non-ANSI-escape) characters in `s`.
 /// ANSI color escape sequences (\x1b[...m) are stripped before counting.
@@ -19,6 +92,55 @@ fn visible_len(s: &str) -> usize {
     count
 }
 
+/// Cuts `s` to at most `width` visible characters, ending with an ellipsis
+/// when anything was removed. ANSI escapes are kept, and colors are reset
+/// after the ellipsis so a cut inside styled text doesn't bleed.
+pub fn truncate(s: &str, width: usize) -> String {
+    if visible_len(s) <= width {
+        return s.to_string();
+    }
+    let ellipsis = glyph("…", "...");
+    let ellipsis = if width > ellipsis.chars().count() {
+        ellipsis
+    } else {
+        ""
+    };
+    let keep = width - ellipsis.chars().count();
+
+    let mut out = String::new();
+    let mut count = 0;
+    let mut in_escape = false;
+    let mut styled = false;
+    for c in s.chars() {
+        if c == '\x1b' {
+            in_escape = true;
+            styled = true;
+        } else if in_escape {
+            if c == 'm' {
+                in_escape = false;
+            }
+        } else if count == keep {
+            break;
+        } else {
+            count += 1;
+        }
+        out.push(c);
+    }
+    out.push_str(ellipsis);
+    if styled {
+        out.push_str("\x1b[0m");
+    }
+    out
+}
+
+/// [`truncate`]s `s` to the current max field width, if there is one.
+pub fn fit(s: &str) -> String {
+    match max_field_width() {
+        Some(width) => truncate(s, width),
+        None => s.to_string(),
+    }
+}
+
 /// Highlights all case-insensitive occurrences of `query` within `text`
 /// by wrapping each match in yellow+bold ANSI codes.
 /// Non-matching portions are returned verbatim.
@@ -61,8 +183,28 @@ pub fn pad_to(s: &str, width: usize) -> String {
 ///
 /// `col_widths` must be the *visible* column widths (not byte lengths).
 /// Cells in `rows` may contain ANSI escape sequences; alignment is handled correctly.
+/// Columns and cells are capped at the current max field width.
 pub fn render_table(headers: &[&str], col_widths: &[usize], rows: &[Vec<String>]) -> String {
+    render_table_with(current_style(), headers, col_widths, rows)
+}
+
+/// Same as [`render_table`], but with an explicit border style.
+pub fn render_table_with(
+    style: TableStyle,
+    headers: &[&str],
+    col_widths: &[usize],
+    rows: &[Vec<String>],
+) -> String {
     let sep = "  ";
+    let rule = match style {
+        TableStyle::Unicode => "─",
+        TableStyle::Ascii => "-",
+    };
+    let limit = max_field_width();
+    let col_widths: Vec<usize> = col_widths
+        .iter()
+        .map(|&w| limit.map_or(w, |l| w.min(l)))
+        .collect();
     let mut out = String::new();
 
     let header_parts: Vec<String> = headers
@@ -73,7 +215,7 @@ pub fn render_table(headers: &[&str], col_widths: &[usize], rows: &[Vec<String>]
     out.push_str(&header_parts.join(sep));
     out.push('\n');
 
-    let sep_parts: Vec<String> = col_widths.iter().map(|&w| "─".repeat(w)).collect();
+    let sep_parts: Vec<String> = col_widths.iter().map(|&w| rule.repeat(w)).collect();
     out.push_str(&sep_parts.join(sep).bright_black().to_string());
     out.push('\n');
 
@@ -81,7 +223,7 @@ pub fn render_table(headers: &[&str], col_widths: &[usize], rows: &[Vec<String>]
         let row_parts: Vec<String> = row
             .iter()
             .zip(col_widths.iter())
-            .map(|(cell, &w)| pad_to(cell, w))
+            .map(|(cell, &w)| pad_to(&fit(cell), w))
             .collect();
         out.push_str(&row_parts.join(sep));
         out.push('\n');
@@ -138,6 +280,20 @@ mod tests {
         assert_eq!(pad_to("abc", 3), "abc");
     }
 
+    #[test]
+    fn truncate_cuts_long_values_with_an_ellipsis() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("a long description", 7), "a long…");
+        assert_eq!(visible_len(&truncate("a long description", 7)), 7);
+        assert_eq!(truncate("abcdef", 1), "a");
+
+        let styled = format!("\x1b[1m{}\x1b[0m", "highlighted");
+        let cut = truncate(&styled, 5);
+        assert_eq!(visible_len(&cut), 5);
+        assert!(cut.starts_with("\x1b[1mhigh…"));
+        assert!(cut.ends_with("\x1b[0m"));
+    }
+
     #[test]
     fn render_table_contains_headers_and_row_data() {
         let rows = vec![vec!["alice".to_string(), "testnet".to_string()]];
@@ -155,6 +311,15 @@ mod tests {
         assert!(out.contains('─'));
     }
 
+    #[test]
+    fn render_table_ascii_uses_plain_separator() {
+        let rows = vec![vec!["alice".to_string()]];
+        let out = render_table_with(TableStyle::Ascii, &["Name"], &[5], &rows);
+        assert!(out.contains("-----"));
+        assert!(!out.contains('─'));
+        assert!(out.is_ascii());
+    }
+
     #[test]
     fn render_table_empty_rows_still_renders_header() {
         let out = render_table(&["Name", "Network"], &[4, 7], &[]);
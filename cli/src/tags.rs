@@ -0,0 +1,283 @@
+//! Registry-wide tag and category normalisation (`normalize-tags`).
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::Value;
+
+const PAGE_SIZE: u32 = 100;
+
+/// Canonical spelling of a single tag or category: trimmed, lower-cased and
+/// mapped through the alias table.
+pub fn canonical(raw: &str, aliases: &HashMap<String, String>) -> String {
+    let folded = raw.trim().to_lowercase();
+    aliases.get(&folded).cloned().unwrap_or(folded)
+}
+
+/// Canonicalise a tag list, dropping empties and duplicates while keeping the
+/// first occurrence's position.
+pub fn canonical_tags(tags: &[String], aliases: &HashMap<String, String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = canonical(tag, aliases);
+        if !tag.is_empty() && !out.contains(&tag) {
+            out.push(tag);
+        }
+    }
+    out
+}
+
+/// A contract whose tags or category would change.
+#[derive(Debug, Clone, Serialize)]
+pub struct Proposal {
+    pub id: String,
+    pub name: String,
+    pub tags_before: Vec<String>,
+    pub tags_after: Vec<String>,
+    pub category_before: Option<String>,
+    pub category_after: Option<String>,
+}
+
+impl Proposal {
+    fn tags_changed(&self) -> bool {
+        self.tags_before != self.tags_after
+    }
+
+    fn category_changed(&self) -> bool {
+        self.category_before != self.category_after
+    }
+}
+
+pub fn tag_names(contract: &Value) -> Vec<String> {
+    contract["tags"]
+        .as_array()
+        .map(|tags| {
+            tags.iter()
+                .filter_map(|t| t["name"].as_str().or_else(|| t.as_str()))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Work out the canonical tags/category for one contract, or `None` when it
+/// is already normalised.
+pub fn propose(contract: &Value, aliases: &HashMap<String, String>) -> Option<Proposal> {
+    let tags_before = tag_names(contract);
+    let category_before = contract["category"].as_str().map(str::to_string);
+    let proposal = Proposal {
+        id: contract["id"].as_str().unwrap_or_default().to_string(),
+        name: contract["name"].as_str().unwrap_or("unknown").to_string(),
+        tags_after: canonical_tags(&tags_before, aliases),
+        category_after: category_before
+            .as_deref()
+            .map(|c| canonical(c, aliases))
+            .filter(|c| !c.is_empty()),
+        tags_before,
+        category_before,
+    };
+    (proposal.tags_changed() || proposal.category_changed()).then_some(proposal)
+}
+
+/// Page through every contract in the registry.
+pub async fn fetch_all_contracts(client: &reqwest::Client, api_url: &str) -> Result<Vec<Value>> {
+    let mut contracts = Vec::new();
+    let mut page = 1u32;
+    loop {
+        let response = client
+            .get(format!("{}/api/contracts", api_url))
+            .query(&[("page", page), ("page_size", PAGE_SIZE)])
+            .send()
+            .await
+            .context("Failed to list contracts")?;
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to list contracts: {}", response.status());
+        }
+        let body: Value = response.json().await?;
+        let items = body["items"].as_array().cloned().unwrap_or_default();
+        let total_pages = body["total_pages"].as_u64().unwrap_or(1);
+        let done = items.is_empty() || u64::from(page) >= total_pages;
+        contracts.extend(items);
+        if done {
+            return Ok(contracts);
+        }
+        page += 1;
+    }
+}
+
+async fn apply(client: &reqwest::Client, api_url: &str, proposal: &Proposal) -> Result<()> {
+    let mut body = serde_json::Map::new();
+    if proposal.tags_changed() {
+        body.insert("tags".into(), serde_json::json!(proposal.tags_after));
+    }
+    if proposal.category_changed() {
+        body.insert(
+            "category".into(),
+            serde_json::json!(proposal.category_after),
+        );
+    }
+    let response = client
+        .patch(format!(
+            "{}/api/contracts/{}/metadata",
+            api_url, proposal.id
+        ))
+        .json(&body)
+        .send()
+        .await
+        .with_context(|| format!("Failed to update metadata for {}", proposal.name))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        anyhow::bail!("Failed to update {} ({}): {}", proposal.name, status, text);
+    }
+    Ok(())
+}
+
+fn print_report(proposals: &[Proposal], scanned: usize) {
+    println!("\n{}", "Tag Normalization".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+
+    for p in proposals {
+        println!("\n  {} {}", p.name.bold(), p.id.bright_black());
+        if p.tags_changed() {
+            println!(
+                "    {}  {}",
+                "tags before:".bold(),
+                p.tags_before.join(", ").red()
+            );
+            println!(
+                "    {}  {}",
+                "tags after: ".bold(),
+                p.tags_after.join(", ").green()
+            );
+        }
+        if p.category_changed() {
+            println!(
+                "    {}  {} → {}",
+                "category:   ".bold(),
+                p.category_before.as_deref().unwrap_or("-").red(),
+                p.category_after.as_deref().unwrap_or("-").green()
+            );
+        }
+    }
+
+    println!("\n{}", "=".repeat(80).cyan());
+    println!(
+        "  {} of {} contract(s) need normalizing\n",
+        proposals.len(),
+        scanned
+    );
+}
+
+pub async fn normalize(api_url: &str, dry_run: bool, yes: bool, json: bool) -> Result<()> {
+    let aliases = crate::config::tag_aliases()?;
+    let client = reqwest::Client::new();
+    let contracts = fetch_all_contracts(&client, api_url).await?;
+    let proposals: Vec<Proposal> = contracts
+        .iter()
+        .filter_map(|c| propose(c, &aliases))
+        .collect();
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(
+            &serde_json::json!({
+                "scanned": contracts.len(),
+                "dry_run": dry_run,
+                "changes": proposals,
+            }),
+            crate::output::Format::Json,
+        )?;
+    } else {
+        print_report(&proposals, contracts.len());
+    }
+
+    if proposals.is_empty() {
+        if !json {
+            println!("{}\n", "✓ All tags are already normalized".green().bold());
+        }
+        return Ok(());
+    }
+    if dry_run {
+        if !json {
+            println!("{}\n", "Dry run: no changes applied".yellow());
+        }
+        return Ok(());
+    }
+    if json && !yes {
+        anyhow::bail!("Pass --yes to apply changes together with --json");
+    }
+    if !yes
+        && !crate::wizard::confirm(
+            &format!("Apply changes to {} contract(s)?", proposals.len()),
+            false,
+        )?
+    {
+        println!("{}", "Aborted.".yellow());
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    for (i, proposal) in proposals.iter().enumerate() {
+        let outcome = apply(&client, api_url, proposal).await;
+        if outcome.is_err() {
+            failed += 1;
+        }
+        let error = outcome.err().map(|e| e.to_string());
+        crate::progress::emit(
+            json,
+            &crate::progress::Event::Progress {
+                done: i + 1,
+                total: proposals.len(),
+                item: &proposal.name,
+                status: if error.is_some() {
+                    crate::progress::Status::Failed
+                } else {
+                    crate::progress::Status::Ok
+                },
+                message: error.as_deref(),
+            },
+        );
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{} of {} update(s) failed", failed, proposals.len());
+    }
+    eprintln!(
+        "{} Normalized {} contract(s)",
+        "✓".green().bold(),
+        proposals.len()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn canonicalizes_case_aliases_and_duplicates() {
+        let aliases = HashMap::from([("de-fi".to_string(), "defi".to_string())]);
+        let tags: Vec<String> = ["DeFi", " de-fi", "Token", "token", ""]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(canonical_tags(&tags, &aliases), vec!["defi", "token"]);
+
+        let contract = json!({
+            "id": "c1",
+            "name": "vault",
+            "category": "DeFi",
+            "tags": [{ "name": "De-Fi" }, { "name": "yield" }]
+        });
+        let p = propose(&contract, &aliases).unwrap();
+        assert_eq!(p.tags_after, vec!["defi", "yield"]);
+        assert_eq!(p.category_after.as_deref(), Some("defi"));
+
+        let clean = json!({ "id": "c2", "category": "defi", "tags": [{ "name": "defi" }] });
+        assert!(propose(&clean, &aliases).is_none());
+    }
+}
@@ -0,0 +1,237 @@
+//! Contract project templates: fetching template file sets from the registry
+//! and tracking which template/version a scaffolded project came from.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::diff::{self, FileStatus};
+
+/// Written into a cloned project so later commands can find its template base.
+pub const MARKER_FILE: &str = ".soroban-registry/template.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateMarker {
+    pub template: String,
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cloned_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TemplateFile {
+    path: String,
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TemplateResponse {
+    name: String,
+    version: String,
+    #[serde(default)]
+    files: Vec<TemplateFile>,
+}
+
+/// A resolved template: its name, exact version and file contents keyed by
+/// project-relative path.
+#[derive(Debug, Clone)]
+pub struct Template {
+    pub name: String,
+    pub version: String,
+    pub files: BTreeMap<String, String>,
+}
+
+/// Fetch a template's files from the registry. `version` of `None` resolves
+/// the latest published version.
+pub async fn fetch(
+    client: &reqwest::Client,
+    api_url: &str,
+    name: &str,
+    version: Option<&str>,
+) -> Result<Template> {
+    let mut request = client.get(format!("{}/api/templates/{}", api_url, name));
+    if let Some(v) = version {
+        request = request.query(&[("version", v)]);
+    }
+    let response = request
+        .send()
+        .await
+        .context("Failed to fetch template from registry")?;
+
+    if !response.status().is_success() {
+        anyhow::bail!(
+            "Template {}{} not found ({})",
+            name,
+            version.map(|v| format!("@{}", v)).unwrap_or_default(),
+            response.status()
+        );
+    }
+
+    let body: TemplateResponse = response.json().await.context("Invalid template response")?;
+    Ok(Template {
+        name: body.name,
+        version: body.version,
+        files: body
+            .files
+            .into_iter()
+            .map(|f| (f.path, f.content))
+            .collect(),
+    })
+}
+
+pub fn read_marker(project_dir: &Path) -> Result<TemplateMarker> {
+    let path = project_dir.join(MARKER_FILE);
+    let data = fs::read_to_string(&path).with_context(|| {
+        format!(
+            "No template record found at {} (was this project created with `template clone`?)",
+            path.display()
+        )
+    })?;
+    serde_json::from_str(&data)
+        .with_context(|| format!("Invalid template record: {}", path.display()))
+}
+
+pub fn write_marker(project_dir: &Path, marker: &TemplateMarker) -> Result<()> {
+    let path = project_dir.join(MARKER_FILE);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(marker)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Read the text files of a project, keyed by `/`-separated relative path.
+/// Build output, VCS metadata and non-UTF-8 files are skipped.
+pub fn read_project_files(project_dir: &Path) -> Result<BTreeMap<String, String>> {
+    fn walk(root: &Path, dir: &Path, files: &mut BTreeMap<String, String>) -> Result<()> {
+        let entries =
+            fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+        for entry in entries {
+            let entry = entry?;
+            let path = entry.path();
+            let name = entry.file_name();
+            if entry.file_type()?.is_dir() {
+                if !crate::watch::IGNORED_DIRS.contains(&name.to_string_lossy().as_ref()) {
+                    walk(root, &path, files)?;
+                }
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let rel: PathBuf = path.strip_prefix(root)?.to_path_buf();
+            let key = rel
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.insert(key, content);
+        }
+        Ok(())
+    }
+
+    let mut files = BTreeMap::new();
+    walk(project_dir, project_dir, &mut files)?;
+    Ok(files)
+}
+
+/// Show how a cloned project has diverged from the template version it was
+/// created from.
+pub async fn diff(api_url: &str, project_dir: &str, json: bool) -> Result<()> {
+    let dir = Path::new(project_dir);
+    let marker = read_marker(dir)?;
+    let client = reqwest::Client::new();
+    let template = fetch(&client, api_url, &marker.template, Some(&marker.version)).await?;
+    let current = read_project_files(dir)?;
+
+    let changes: Vec<_> = diff::diff_trees(&template.files, &current)
+        .into_iter()
+        .filter(|c| c.status != FileStatus::Unchanged)
+        .collect();
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(
+            &serde_json::json!({
+                "template": template.name,
+                "version": template.version,
+                "project_dir": project_dir,
+                "changes": changes,
+            }),
+            crate::output::Format::Json,
+        )?;
+        return Ok(());
+    }
+
+    println!("\n{}", "Template Diff".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    println!(
+        "  {}: {}@{}",
+        "Template".bold(),
+        template.name,
+        template.version.bright_black()
+    );
+    println!("  {}: {}\n", "Project".bold(), project_dir);
+
+    if changes.is_empty() {
+        println!("{}\n", "✓ Project matches the template".green().bold());
+        return Ok(());
+    }
+
+    for change in &changes {
+        let label = match change.status {
+            FileStatus::Added => "added   ".green(),
+            FileStatus::Removed => "removed ".red(),
+            FileStatus::Modified => "modified".yellow(),
+            FileStatus::Unchanged => continue,
+        };
+        println!(
+            "  {} {} ({}, {})",
+            label,
+            change.path,
+            format!("+{}", change.lines_added).green(),
+            format!("-{}", change.lines_removed).red()
+        );
+    }
+
+    for change in changes.iter().filter(|c| c.status == FileStatus::Modified) {
+        if let Some(d) = &change.diff {
+            println!();
+            diff::print_unified(d);
+        }
+    }
+
+    println!("\n{}", "=".repeat(80).cyan());
+    println!("  {} file(s) differ from the template\n", changes.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_files_skip_ignored_dirs_and_record_marker() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::create_dir_all(dir.path().join("target/debug")).unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "// lib").unwrap();
+        fs::write(dir.path().join("target/debug/out"), "build").unwrap();
+
+        let marker = TemplateMarker {
+            template: "token".into(),
+            version: "1.2.0".into(),
+            cloned_at: None,
+        };
+        write_marker(dir.path(), &marker).unwrap();
+        assert_eq!(read_marker(dir.path()).unwrap().version, "1.2.0");
+
+        let files = read_project_files(dir.path()).unwrap();
+        assert_eq!(files.keys().collect::<Vec<_>>(), vec!["src/lib.rs"]);
+    }
+}
@@ -1,10 +1,13 @@
 #![allow(dead_code)]
 
 use anyhow::{Context, Result};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,10 +61,15 @@ pub struct Assertion {
 pub struct TestResult {
     pub scenario: String,
     pub passed: bool,
+    #[serde(default)]
+    pub seed: u64,
     pub duration: Duration,
     pub steps: Vec<StepResult>,
     pub error: Option<String>,
     pub coverage: CoverageMetrics,
+    /// Per-method coverage, keyed by `contract::method`.
+    #[serde(default)]
+    pub method_coverage: BTreeMap<String, MethodCoverage>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,6 +80,9 @@ pub struct StepResult {
     pub error: Option<String>,
     pub assertions_passed: usize,
     pub assertions_failed: usize,
+    /// Skipped because an earlier run passed with the same inputs.
+    #[serde(default)]
+    pub cached: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -84,16 +95,192 @@ pub struct CoverageMetrics {
     pub lines_total: usize,
 }
 
+/// Whether one contract method was exercised, plus a fingerprint of its
+/// source so a later run can tell whether the method changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MethodCoverage {
+    pub covered: bool,
+    pub fingerprint: String,
+}
+
+/// Coverage stored by `test --coverage-baseline` and compared against on
+/// later runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CoverageBaseline {
+    pub coverage_percent: f64,
+    pub methods: BTreeMap<String, MethodCoverage>,
+}
+
+/// A method that is new or whose source differs from the baseline.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChangedMethod {
+    pub method: String,
+    /// Coverage in the baseline; `None` for methods added since.
+    pub was_covered: Option<bool>,
+    pub covered: bool,
+}
+
+impl ChangedMethod {
+    /// Uncovered now, and either new or covered before.
+    pub fn is_regression(&self) -> bool {
+        !self.covered && self.was_covered != Some(false)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CoverageDelta {
+    pub baseline_percent: f64,
+    pub current_percent: f64,
+    pub changed: Vec<ChangedMethod>,
+}
+
+impl CoverageDelta {
+    pub fn delta(&self) -> f64 {
+        self.current_percent - self.baseline_percent
+    }
+
+    pub fn regressions(&self) -> impl Iterator<Item = &ChangedMethod> {
+        self.changed.iter().filter(|m| m.is_regression())
+    }
+}
+
+impl CoverageBaseline {
+    pub fn from_result(result: &TestResult) -> Self {
+        Self {
+            coverage_percent: result.coverage.coverage_percent,
+            methods: result.method_coverage.clone(),
+        }
+    }
+
+    /// The stored baseline, or `None` if `path` doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read coverage baseline: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .map(Some)
+            .with_context(|| format!("Failed to parse coverage baseline: {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write coverage baseline: {}", path.display()))
+    }
+
+    /// Compare `current` against this baseline. Only methods that were added
+    /// or whose source changed are reported.
+    pub fn compare(&self, current: &CoverageBaseline) -> CoverageDelta {
+        let changed = current
+            .methods
+            .iter()
+            .filter_map(|(method, now)| {
+                let before = self.methods.get(method);
+                if before.is_some_and(|b| b.fingerprint == now.fingerprint) {
+                    return None;
+                }
+                Some(ChangedMethod {
+                    method: method.clone(),
+                    was_covered: before.map(|b| b.covered),
+                    covered: now.covered,
+                })
+            })
+            .collect();
+        CoverageDelta {
+            baseline_percent: self.coverage_percent,
+            current_percent: current.coverage_percent,
+            changed,
+        }
+    }
+}
+
+/// Steps that passed on earlier runs of one scenario, so `test` can skip
+/// them while their inputs are unchanged.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResultCache {
+    /// Keyed by step name.
+    pub steps: BTreeMap<String, CachedStep>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedStep {
+    /// Hash of the contract sources, the fixtures and every step up to and
+    /// including this one; see [`TestRunner::case_inputs`].
+    pub inputs: String,
+    pub assertions_passed: usize,
+}
+
+impl ResultCache {
+    /// `~/.soroban-registry/test-cache`, where every cache file lives.
+    pub fn dir() -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Cannot determine home directory")?;
+        Ok(home.join(".soroban-registry").join("test-cache"))
+    }
+
+    /// Where results for `scenario` against `contract_path` are kept:
+    /// `<dir>/<key>.json`.
+    pub fn path(scenario: &str, contract_path: &str) -> Result<PathBuf> {
+        let contract =
+            fs::canonicalize(contract_path).unwrap_or_else(|_| PathBuf::from(contract_path));
+        let key = Sha256::digest(format!("{}\n{}", scenario, contract.display()).as_bytes());
+        let key: String = key[..8].iter().map(|b| format!("{:02x}", b)).collect();
+        Ok(Self::dir()?.join(format!("{}.json", key)))
+    }
+
+    /// The stored cache; empty when there is none yet or it can't be read,
+    /// which only costs a full run.
+    pub fn load(path: &Path) -> Self {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(_) => return Self::default(),
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::debug!("Ignoring unreadable test cache {}: {}", path.display(), e);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write test cache: {}", path.display()))
+    }
+
+    /// The cached result for `step`, if it was recorded with `inputs`.
+    pub fn lookup(&self, step: &str, inputs: &str) -> Option<&CachedStep> {
+        self.steps.get(step).filter(|c| c.inputs == inputs)
+    }
+}
+
+/// Environment variables that carry the run's seed to project test commands
+/// (our own, and the one proptest reads).
+pub const SEED_ENV_VARS: &[&str] = &["SOROBAN_REGISTRY_TEST_SEED", "PROPTEST_RNG_SEED"];
+
+/// The `--seed` value, or a fresh random one when none was given.
+pub fn resolve_seed(seed: Option<u64>) -> u64 {
+    seed.unwrap_or_else(rand::random)
+}
+
 pub struct TestRunner {
     contract_path: String,
     contracts: HashMap<String, ContractInfo>,
+    /// Hash of every discovered contract file.
+    contracts_hash: String,
     coverage: CoverageTracker,
+    seed: u64,
+    rng: StdRng,
+    cache: Option<ResultCache>,
 }
 
 #[derive(Debug, Clone)]
 struct ContractInfo {
     name: String,
     methods: Vec<String>,
+    fingerprints: HashMap<String, String>,
 }
 
 struct CoverageTracker {
@@ -137,21 +324,46 @@ impl CoverageTracker {
 }
 
 impl TestRunner {
-    pub fn new(contract_path: &str) -> Result<Self> {
+    pub fn new(contract_path: &str, seed: u64) -> Result<Self> {
         let contracts = Self::discover_contracts(contract_path)?;
         Ok(Self {
             contract_path: contract_path.to_string(),
             contracts,
+            contracts_hash: Self::hash_contracts(contract_path)?,
             coverage: CoverageTracker::new(),
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            cache: None,
         })
     }
 
+    /// Skip steps `cache` holds a passing result for. The cache is updated
+    /// as the run goes; read it back with [`cache`](TestRunner::cache).
+    pub fn with_cache(mut self, cache: ResultCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn cache(&self) -> Option<&ResultCache> {
+        self.cache.as_ref()
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The run's random source. Every randomized input must be drawn from
+    /// here so that a failing run can be replayed with `--seed`.
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
     fn discover_contracts(contract_path: &str) -> Result<HashMap<String, ContractInfo>> {
         let mut contracts = HashMap::new();
         let path = Path::new(contract_path);
 
         if path.is_file() {
-            let methods = Self::extract_methods(path)?;
+            let (methods, fingerprints) = Self::extract_methods(path)?;
             let name = path
                 .file_stem()
                 .and_then(|s| s.to_str())
@@ -162,6 +374,7 @@ impl TestRunner {
                 ContractInfo {
                     name: name.clone(),
                     methods,
+                    fingerprints,
                 },
             );
         } else if path.is_dir() {
@@ -169,7 +382,7 @@ impl TestRunner {
                 let entry = entry?;
                 let path = entry.path();
                 if path.extension().and_then(|s| s.to_str()) == Some("rs") {
-                    let methods = Self::extract_methods(&path)?;
+                    let (methods, fingerprints) = Self::extract_methods(&path)?;
                     let name = path
                         .file_stem()
                         .and_then(|s| s.to_str())
@@ -180,6 +393,7 @@ impl TestRunner {
                         ContractInfo {
                             name: name.clone(),
                             methods,
+                            fingerprints,
                         },
                     );
                 }
@@ -189,11 +403,66 @@ impl TestRunner {
         Ok(contracts)
     }
 
-    fn extract_methods(path: &Path) -> Result<Vec<String>> {
+    /// The same files [`discover_contracts`](TestRunner::discover_contracts)
+    /// reads, hashed with their names.
+    fn hash_contracts(contract_path: &str) -> Result<String> {
+        let path = Path::new(contract_path);
+        let mut files = Vec::new();
+        if path.is_file() {
+            files.push(path.to_path_buf());
+        } else if path.is_dir() {
+            for entry in fs::read_dir(path)? {
+                let path = entry?.path();
+                if path.extension().and_then(|s| s.to_str()) == Some("rs") {
+                    files.push(path);
+                }
+            }
+        }
+        files.sort();
+
+        let mut hasher = Sha256::new();
+        for file in files {
+            let bytes = fs::read(&file)
+                .with_context(|| format!("Failed to read contract: {}", file.display()))?;
+            let name = file.file_name().unwrap_or_default().to_string_lossy();
+            hasher.update(name.as_bytes());
+            hasher.update(&bytes);
+        }
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    /// Everything step `index` of `scenario` depends on: the contracts, the
+    /// setup fixtures and every step up to and including it, since earlier
+    /// steps set up the state later ones run against.
+    pub fn case_inputs(&self, scenario: &TestScenario, index: usize) -> String {
+        // Through `Value`, whose maps are ordered, so `TestValue::Object`
+        // hashes the same on every run.
+        let definition = serde_json::json!({
+            "setup": scenario.setup,
+            "steps": scenario.steps[..=index],
+        });
+        let mut hasher = Sha256::new();
+        hasher.update(self.contracts_hash.as_bytes());
+        hasher.update(definition.to_string().as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    fn extract_methods(path: &Path) -> Result<(Vec<String>, HashMap<String, String>)> {
+        if path.extension().and_then(|s| s.to_str()) == Some("wasm") {
+            let bytes = fs::read(path)
+                .with_context(|| format!("Failed to read contract: {}", path.display()))?;
+            return Self::wasm_methods(&bytes);
+        }
         let content = fs::read_to_string(path)
             .with_context(|| format!("Failed to read contract: {}", path.display()))?;
+        Ok(Self::parse_methods(&content))
+    }
 
+    /// Method names in declaration order, and a fingerprint of each method's
+    /// source (from its `fn` line up to the next one, whitespace-insensitive).
+    fn parse_methods(content: &str) -> (Vec<String>, HashMap<String, String>) {
         let mut methods = Vec::new();
+        let mut bodies: Vec<String> = Vec::new();
         for line in content.lines() {
             let trimmed = line.trim();
             if trimmed.starts_with("pub fn ") || trimmed.starts_with("fn ") {
@@ -203,13 +472,136 @@ impl TestRunner {
                         let method_name = after_fn[..end].trim();
                         if !method_name.is_empty() && !method_name.contains('<') {
                             methods.push(method_name.to_string());
+                            bodies.push(String::new());
                         }
                     }
                 }
             }
+            if let Some(body) = bodies.last_mut() {
+                for word in trimmed.split_whitespace() {
+                    body.push_str(word);
+                    body.push(' ');
+                }
+            }
+        }
+
+        let fingerprints = methods
+            .iter()
+            .zip(&bodies)
+            .map(|(name, body)| {
+                let digest = Sha256::digest(body.as_bytes());
+                let hex: String = digest[..8].iter().map(|b| format!("{:02x}", b)).collect();
+                (name.clone(), hex)
+            })
+            .collect();
+        (methods, fingerprints)
+    }
+
+    /// Exported functions of a built contract. Source isn't available, so
+    /// every method is fingerprinted with the module hash: any rebuild marks
+    /// them all as changed.
+    fn wasm_methods(bytes: &[u8]) -> Result<(Vec<String>, HashMap<String, String>)> {
+        let methods: Vec<String> = crate::wasm::parse_exports(bytes)?
+            .into_iter()
+            .filter(|e| e.kind == crate::wasm::EXPORT_KIND_FUNC && !e.name.starts_with('_'))
+            .map(|e| e.name)
+            .collect();
+        let module_hash = crate::wasm::sha256_hex(bytes)[..16].to_string();
+        let fingerprints = methods
+            .iter()
+            .map(|name| (name.clone(), module_hash.clone()))
+            .collect();
+        Ok((methods, fingerprints))
+    }
+
+    /// Coverage of every discovered method in this run.
+    fn method_coverage(&self) -> BTreeMap<String, MethodCoverage> {
+        let mut coverage = BTreeMap::new();
+        for (contract, info) in &self.contracts {
+            for method in &info.methods {
+                coverage.insert(
+                    format!("{}::{}", contract, method),
+                    MethodCoverage {
+                        covered: self
+                            .coverage
+                            .methods
+                            .contains(&(contract.clone(), method.clone())),
+                        fingerprint: info.fingerprints.get(method).cloned().unwrap_or_default(),
+                    },
+                );
+            }
+        }
+        coverage
+    }
+
+    /// Resolve `scenario` into the ordered cases [`run_scenario`] would
+    /// execute, without executing any of them.
+    ///
+    /// [`run_scenario`]: TestRunner::run_scenario
+    pub fn plan(&self, scenario: &TestScenario) -> TestPlan {
+        let fixture = |phase, action: &TestAction| {
+            let target = match (&action.contract, &action.method) {
+                (Some(contract), Some(method)) => Some(format!("{}::{}", contract, method)),
+                (Some(contract), None) => Some(contract.clone()),
+                _ => None,
+            };
+            let problem = (!matches!(action.action.as_str(), "deploy" | "invoke" | "set"))
+                .then(|| format!("Unknown action: {}", action.action));
+            PlannedCase {
+                phase,
+                name: action.action.clone(),
+                target,
+                assertions: 0,
+                expected_error: None,
+                problem,
+            }
+        };
+
+        let mut cases: Vec<PlannedCase> = scenario
+            .setup
+            .iter()
+            .flatten()
+            .map(|a| fixture(Phase::Setup, a))
+            .collect();
+        cases.extend(scenario.steps.iter().map(|step| PlannedCase {
+            phase: Phase::Step,
+            name: step.name.clone(),
+            target: Some(format!("{}::{}", step.contract, step.method)),
+            assertions: step.assertions.as_ref().map_or(0, Vec::len),
+            expected_error: step.expected_error.clone(),
+            problem: self.resolve_step(step).err(),
+        }));
+        cases.extend(
+            scenario
+                .teardown
+                .iter()
+                .flatten()
+                .map(|a| fixture(Phase::Teardown, a)),
+        );
+
+        let mut contracts: Vec<String> = self.contracts.keys().cloned().collect();
+        contracts.sort();
+        TestPlan {
+            scenario: scenario.name.clone(),
+            seed: self.seed,
+            contracts,
+            cases,
         }
+    }
 
-        Ok(methods)
+    /// The same lookup [`execute_step`](TestRunner::execute_step) fails on.
+    fn resolve_step(&self, step: &TestStep) -> std::result::Result<(), String> {
+        let contract_info = self
+            .contracts
+            .get(&step.contract)
+            .ok_or_else(|| format!("Contract not found: {}", step.contract))?;
+        if !contract_info.methods.contains(&step.method) {
+            return Err(format!(
+                "Method '{}' not found in contract '{}'",
+                step.method, step.contract
+            ));
+        }
+        Ok(())
     }
 
     pub async fn run_scenario(&mut self, scenario: TestScenario) -> Result<TestResult> {
@@ -223,7 +615,7 @@ impl TestRunner {
             }
         }
 
-        for step in &scenario.steps {
+        for (index, step) in scenario.steps.iter().enumerate() {
             let step_start = Instant::now();
             let mut assertions_passed = 0;
             let mut assertions_failed = 0;
@@ -232,6 +624,24 @@ impl TestRunner {
             self.coverage
                 .record_contract_call(&step.contract, &step.method);
 
+            let inputs = self.case_inputs(&scenario, index);
+            if let Some(cached) = self
+                .cache
+                .as_ref()
+                .and_then(|c| c.lookup(&step.name, &inputs))
+            {
+                step_results.push(StepResult {
+                    step_name: step.name.clone(),
+                    passed: true,
+                    duration: Duration::ZERO,
+                    error: None,
+                    assertions_passed: cached.assertions_passed,
+                    assertions_failed: 0,
+                    cached: true,
+                });
+                continue;
+            }
+
             let step_result = self.execute_step(step).await;
 
             match step_result {
@@ -278,13 +688,29 @@ impl TestRunner {
                 }
             }
 
+            let passed = assertions_failed == 0 && step_error.is_none();
+            if let Some(cache) = self.cache.as_mut() {
+                if passed {
+                    cache.steps.insert(
+                        step.name.clone(),
+                        CachedStep {
+                            inputs,
+                            assertions_passed,
+                        },
+                    );
+                } else {
+                    cache.steps.remove(&step.name);
+                }
+            }
+
             step_results.push(StepResult {
                 step_name: step.name.clone(),
-                passed: assertions_failed == 0 && step_error.is_none(),
+                passed,
                 duration: step_start.elapsed(),
                 error: step_error,
                 assertions_passed,
                 assertions_failed,
+                cached: false,
             });
 
             if step_results.last().unwrap().error.is_some() {
@@ -305,26 +731,17 @@ impl TestRunner {
         Ok(TestResult {
             scenario: scenario.name,
             passed: step_results.iter().all(|s| s.passed),
+            seed: self.seed,
             duration: start_time.elapsed(),
             steps: step_results,
             error,
             coverage,
+            method_coverage: self.method_coverage(),
         })
     }
 
     async fn execute_step(&self, step: &TestStep) -> Result<TestValue> {
-        let contract_info = self
-            .contracts
-            .get(&step.contract)
-            .ok_or_else(|| anyhow::anyhow!("Contract not found: {}", step.contract))?;
-
-        if !contract_info.methods.contains(&step.method) {
-            return Err(anyhow::anyhow!(
-                "Method '{}' not found in contract '{}'",
-                step.method,
-                step.contract
-            ));
-        }
+        self.resolve_step(step).map_err(anyhow::Error::msg)?;
 
         tokio::time::sleep(Duration::from_millis(10)).await;
 
@@ -418,18 +835,63 @@ impl TestRunner {
     }
 }
 
+/// Which part of a scenario a planned case belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Setup,
+    Step,
+    Teardown,
+}
+
+/// One case `test --dry-run` would execute, in run order.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedCase {
+    pub phase: Phase,
+    pub name: String,
+    /// `contract::method` the case calls, if any.
+    pub target: Option<String>,
+    pub assertions: usize,
+    pub expected_error: Option<String>,
+    /// Why the case would fail before reaching the contract: an unknown
+    /// action, contract or method.
+    pub problem: Option<String>,
+}
+
+/// A resolved scenario: every fixture and step in execution order, checked
+/// against the discovered contracts but not run.
+#[derive(Debug, Clone, Serialize)]
+pub struct TestPlan {
+    pub scenario: String,
+    pub seed: u64,
+    pub contracts: Vec<String>,
+    pub cases: Vec<PlannedCase>,
+}
+
+impl TestPlan {
+    pub fn problems(&self) -> impl Iterator<Item = &PlannedCase> {
+        self.cases.iter().filter(|c| c.problem.is_some())
+    }
+
+    pub fn count(&self, phase: Phase) -> usize {
+        self.cases.iter().filter(|c| c.phase == phase).count()
+    }
+}
+
 pub fn load_test_scenario(path: &Path) -> Result<TestScenario> {
-    let content = fs::read_to_string(path)
-        .with_context(|| format!("Failed to read test file: {}", path.display()))?;
+    let label = path.display().to_string();
+    let content = crate::io_utils::read_input_to_string(&label)
+        .with_context(|| format!("Failed to read test file: {}", label))?;
 
-    if path.extension().and_then(|s| s.to_str()) == Some("yaml")
-        || path.extension().and_then(|s| s.to_str()) == Some("yml")
-    {
+    // Scenarios piped through stdin carry no extension; parse them as YAML,
+    // which also accepts JSON.
+    let ext = path.extension().and_then(|s| s.to_str());
+    if crate::io_utils::is_stdin(&label) || ext == Some("yaml") || ext == Some("yml") {
         serde_yaml::from_str(&content)
-            .with_context(|| format!("Failed to parse YAML test file: {}", path.display()))
+            .with_context(|| format!("Failed to parse YAML test file: {}", label))
     } else {
         serde_json::from_str(&content)
-            .with_context(|| format!("Failed to parse JSON test file: {}", path.display()))
+            .with_context(|| format!("Failed to parse JSON test file: {}", label))
     }
 }
 
@@ -456,14 +918,15 @@ pub fn generate_junit_xml(results: &[TestResult], output_path: &Path) -> Result<
 
         if !result.passed {
             xml.push_str(&format!(
-                "      <failure message=\"{}\"/>\n",
+                "      <failure message=\"{} (seed {})\"/>\n",
                 result
                     .error
                     .as_deref()
                     .unwrap_or("Test failed")
                     .replace('"', "&quot;")
                     .replace('<', "&lt;")
-                    .replace('>', "&gt;")
+                    .replace('>', "&gt;"),
+                result.seed
             ));
         }
 
@@ -478,3 +941,178 @@ pub fn generate_junit_xml(results: &[TestResult], output_path: &Path) -> Result<
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn same_seed_replays_the_same_inputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_str().unwrap();
+        let draw = |seed| {
+            let mut runner = TestRunner::new(path, seed).unwrap();
+            (0..4).map(|_| runner.rng().gen()).collect::<Vec<u64>>()
+        };
+        assert_eq!(draw(42), draw(42));
+        assert_ne!(draw(42), draw(43));
+        assert_eq!(resolve_seed(Some(7)), 7);
+    }
+
+    #[test]
+    fn baseline_flags_uncovered_changed_methods() {
+        let (_, before) =
+            TestRunner::parse_methods("pub fn transfer() {\n    a();\n}\nfn mint() { b(); }\n");
+        let (_, after) = TestRunner::parse_methods(
+            "pub fn transfer() {\n  a();\n}\nfn mint() { c(); }\nfn burn() {}\n",
+        );
+        assert_eq!(before["transfer"], after["transfer"]);
+        assert_ne!(before["mint"], after["mint"]);
+
+        let entry = |fingerprints: &HashMap<String, String>, name: &str, covered| {
+            (
+                format!("token::{}", name),
+                MethodCoverage {
+                    covered,
+                    fingerprint: fingerprints[name].clone(),
+                },
+            )
+        };
+        let baseline = CoverageBaseline {
+            coverage_percent: 100.0,
+            methods: [
+                entry(&before, "transfer", true),
+                entry(&before, "mint", true),
+            ]
+            .into(),
+        };
+        let current = CoverageBaseline {
+            coverage_percent: 33.3,
+            methods: [
+                entry(&after, "transfer", false),
+                entry(&after, "mint", false),
+                entry(&after, "burn", true),
+            ]
+            .into(),
+        };
+
+        let delta = baseline.compare(&current);
+        assert!((delta.delta() + 66.7).abs() < 1e-9);
+        // `transfer` lost coverage but its source is unchanged, so it isn't
+        // reported; `burn` is new but covered.
+        assert_eq!(delta.changed.len(), 2);
+        let regressions: Vec<&str> = delta.regressions().map(|m| m.method.as_str()).collect();
+        assert_eq!(regressions, ["token::mint"]);
+    }
+
+    #[test]
+    fn built_wasm_contracts_expose_their_exported_functions() {
+        let mut module = b"\0asm\x01\0\0\0".to_vec();
+        module.extend([7, 16, 2, 8]);
+        module.extend(b"transfer");
+        module.extend([0, 0, 1, b'_', 0, 1]);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("token.wasm");
+        fs::write(&path, &module).unwrap();
+        let (methods, fingerprints) = TestRunner::extract_methods(&path).unwrap();
+        assert_eq!(methods, ["transfer"]);
+        assert_eq!(
+            fingerprints["transfer"],
+            crate::wasm::sha256_hex(&module)[..16]
+        );
+    }
+
+    #[test]
+    fn plan_lists_fixtures_and_steps_in_run_order() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("token.rs"), "pub fn transfer() {}\n").unwrap();
+        let runner = TestRunner::new(dir.path().to_str().unwrap(), 1).unwrap();
+        let scenario: TestScenario = serde_yaml::from_str(
+            "name: flow
+setup:
+  - action: deploy
+    contract: token
+steps:
+  - name: send
+    contract: token
+    method: transfer
+    assertions:
+      - type: eq
+        expected: ok
+  - name: burn
+    contract: token
+    method: burn
+teardown:
+  - action: reset
+",
+        )
+        .unwrap();
+
+        let plan = runner.plan(&scenario);
+        let order: Vec<(Phase, &str)> = plan
+            .cases
+            .iter()
+            .map(|c| (c.phase, c.name.as_str()))
+            .collect();
+        assert_eq!(
+            order,
+            [
+                (Phase::Setup, "deploy"),
+                (Phase::Step, "send"),
+                (Phase::Step, "burn"),
+                (Phase::Teardown, "reset"),
+            ]
+        );
+        assert_eq!(plan.cases[1].target.as_deref(), Some("token::transfer"));
+        assert_eq!(plan.cases[1].assertions, 1);
+        let problems: Vec<&str> = plan.problems().map(|c| c.name.as_str()).collect();
+        assert_eq!(problems, ["burn", "reset"]);
+        assert_eq!(plan.count(Phase::Step), 2);
+    }
+
+    #[tokio::test]
+    async fn cached_steps_are_skipped_until_their_inputs_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("token.rs");
+        fs::write(&source, "pub fn transfer() {}\npub fn mint() {}\n").unwrap();
+        let path = dir.path().to_str().unwrap();
+        let scenario = |mint_method: &str| -> TestScenario {
+            serde_yaml::from_str(&format!(
+                "name: flow
+steps:
+  - name: send
+    contract: token
+    method: transfer
+  - name: mint
+    contract: token
+    method: {}
+",
+                mint_method
+            ))
+            .unwrap()
+        };
+        let run = |cache: ResultCache, scenario: TestScenario| async move {
+            let mut runner = TestRunner::new(path, 1).unwrap().with_cache(cache);
+            let result = runner.run_scenario(scenario).await.unwrap();
+            let cached: Vec<bool> = result.steps.iter().map(|s| s.cached).collect();
+            (cached, runner.cache().unwrap().clone())
+        };
+
+        let (cached, cache) = run(ResultCache::default(), scenario("mint")).await;
+        assert_eq!(cached, [false, false]);
+        let (cached, cache) = run(cache, scenario("mint")).await;
+        assert_eq!(cached, [true, true]);
+
+        // A failing step is dropped from the cache; the steps before it
+        // still hit.
+        let (cached, cache) = run(cache, scenario("burn")).await;
+        assert_eq!(cached, [true, false]);
+        assert!(!cache.steps.contains_key("mint"));
+
+        fs::write(&source, "pub fn transfer() { a(); }\npub fn mint() {}\n").unwrap();
+        let (cached, _) = run(cache, scenario("mint")).await;
+        assert_eq!(cached, [false, false]);
+    }
+}
@@ -0,0 +1,1057 @@
+//! Minimal WebAssembly inspection helpers.
+//!
+//! Enough structure parsing to validate a binary and find Soroban's custom
+//! sections without pulling in a full wasm toolchain, plus the `optimize`
+//! pass, which leans on `wasmparser` to walk function bodies.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+pub const WASM_MAGIC: &[u8; 4] = b"\0asm";
+pub const WASM_VERSION: u32 = 1;
+
+/// Custom sections emitted by the Soroban SDK into every contract.
+pub const SOROBAN_SECTIONS: &[&str] = &["contractenvmetav0", "contractspecv0", "contractmetav0"];
+
+/// A top-level section of a wasm module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub id: u8,
+    /// Name for custom sections (id 0), `None` otherwise.
+    pub name: Option<String>,
+    pub offset: usize,
+    pub size: usize,
+}
+
+/// Hex-encoded SHA-256 of `bytes`, as used for registry wasm hashes.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// [`sha256_hex`] of a file, read in chunks so a large module is never
+/// held in memory whole.
+pub fn sha256_file(path: &std::path::Path) -> Result<String> {
+    let mut file =
+        std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Read an unsigned LEB128 u32 at `*pos`, advancing it.
+pub fn read_leb_u32(bytes: &[u8], pos: &mut usize) -> Result<u32> {
+    let mut result: u32 = 0;
+    let mut shift = 0;
+    loop {
+        let byte = *bytes
+            .get(*pos)
+            .context("unexpected end of wasm while reading LEB128")?;
+        *pos += 1;
+        result |= ((byte & 0x7f) as u32) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(result);
+        }
+        shift += 7;
+        if shift >= 35 {
+            bail!("LEB128 value too large at offset {}", *pos);
+        }
+    }
+}
+
+/// Parse the section headers of a wasm module, validating the preamble and
+/// that every section fits inside the binary.
+pub fn parse_sections(bytes: &[u8]) -> Result<Vec<Section>> {
+    if bytes.len() < 8 || &bytes[..4] != WASM_MAGIC {
+        bail!("not a wasm module (missing \\0asm magic)");
+    }
+    let version = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    if version != WASM_VERSION {
+        bail!("unsupported wasm version {}", version);
+    }
+
+    let mut sections = Vec::new();
+    let mut pos = 8;
+    while pos < bytes.len() {
+        let id = bytes[pos];
+        pos += 1;
+        if id > 12 {
+            bail!("invalid section id {} at offset {}", id, pos - 1);
+        }
+        let size = read_leb_u32(bytes, &mut pos)? as usize;
+        let offset = pos;
+        let end = offset
+            .checked_add(size)
+            .filter(|end| *end <= bytes.len())
+            .with_context(|| format!("section {} at offset {} overruns module", id, offset))?;
+
+        let name = if id == 0 {
+            let mut p = offset;
+            let len = read_leb_u32(bytes, &mut p)? as usize;
+            let raw = bytes
+                .get(p..p + len)
+                .filter(|_| p + len <= end)
+                .context("custom section name overruns section")?;
+            Some(String::from_utf8_lossy(raw).into_owned())
+        } else {
+            None
+        };
+
+        sections.push(Section {
+            id,
+            name,
+            offset,
+            size,
+        });
+        pos = end;
+    }
+
+    Ok(sections)
+}
+
+/// Validate that `bytes` is a well-formed wasm module carrying Soroban
+/// contract metadata.
+pub fn validate_soroban_module(bytes: &[u8]) -> Result<Vec<Section>> {
+    let sections = parse_sections(bytes)?;
+    let has_soroban_meta = sections
+        .iter()
+        .filter_map(|s| s.name.as_deref())
+        .any(|name| SOROBAN_SECTIONS.contains(&name));
+    if !has_soroban_meta {
+        bail!(
+            "wasm module has no Soroban contract metadata (expected one of: {})",
+            SOROBAN_SECTIONS.join(", ")
+        );
+    }
+    Ok(sections)
+}
+
+/// Export kind for functions in the export section.
+pub const EXPORT_KIND_FUNC: u8 = 0;
+
+/// An entry of the module's export section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Export {
+    pub name: String,
+    pub kind: u8,
+    /// Index into the kind's index space (imports first, then definitions).
+    pub index: u32,
+}
+
+fn read_name(bytes: &[u8], pos: &mut usize) -> Result<String> {
+    let len = read_leb_u32(bytes, pos)? as usize;
+    let raw = bytes
+        .get(*pos..*pos + len)
+        .context("name overruns section")?;
+    *pos += len;
+    Ok(String::from_utf8_lossy(raw).into_owned())
+}
+
+/// Parse the export section (id 7) of a module, if present.
+pub fn parse_exports(bytes: &[u8]) -> Result<Vec<Export>> {
+    let Some(section) = parse_sections(bytes)?.into_iter().find(|s| s.id == 7) else {
+        return Ok(Vec::new());
+    };
+    let body = &bytes[section.offset..section.offset + section.size];
+    let mut pos = 0;
+    let count = read_leb_u32(body, &mut pos)?;
+    let mut exports = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let name = read_name(body, &mut pos)?;
+        let kind = *body.get(pos).context("truncated export entry")?;
+        pos += 1;
+        let index = read_leb_u32(body, &mut pos)?;
+        exports.push(Export { name, kind, index });
+    }
+    Ok(exports)
+}
+
+/// Import kind for functions in the import section.
+pub const IMPORT_KIND_FUNC: u8 = 0;
+
+/// An entry of the module's import section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Import {
+    pub module: String,
+    pub name: String,
+    pub kind: u8,
+}
+
+fn skip_limits(bytes: &[u8], pos: &mut usize) -> Result<()> {
+    let flags = *bytes.get(*pos).context("truncated limits")?;
+    *pos += 1;
+    read_leb_u32(bytes, pos)?;
+    if flags & 1 != 0 {
+        read_leb_u32(bytes, pos)?;
+    }
+    Ok(())
+}
+
+/// Parse the import section (id 2) of a module, if present.
+pub fn parse_imports(bytes: &[u8]) -> Result<Vec<Import>> {
+    let Some(section) = parse_sections(bytes)?.into_iter().find(|s| s.id == 2) else {
+        return Ok(Vec::new());
+    };
+    let body = &bytes[section.offset..section.offset + section.size];
+    let mut pos = 0;
+    let count = read_leb_u32(body, &mut pos)?;
+    let mut imports = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let module = read_name(body, &mut pos)?;
+        let name = read_name(body, &mut pos)?;
+        let kind = *body.get(pos).context("truncated import entry")?;
+        pos += 1;
+        match kind {
+            // function: type index
+            0 => {
+                read_leb_u32(body, &mut pos)?;
+            }
+            // table: element type, limits
+            1 => {
+                pos += 1;
+                skip_limits(body, &mut pos)?;
+            }
+            // memory: limits
+            2 => skip_limits(body, &mut pos)?,
+            // global: value type, mutability
+            3 => pos += 2,
+            other => bail!("invalid import kind {} for {}.{}", other, module, name),
+        }
+        imports.push(Import { module, name, kind });
+    }
+    Ok(imports)
+}
+
+/// What a Soroban host function gives a contract access to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    Storage,
+    /// Deploying or replacing contract code.
+    Lifecycle,
+    Auth,
+    Crypto,
+    Events,
+    /// Calling other contracts.
+    Calls,
+    /// Ledger and invocation context (sequence, timestamp, own address).
+    Context,
+    Randomness,
+    /// Host object manipulation: integers, maps, vectors, bytes.
+    Data,
+    Unknown,
+}
+
+impl std::fmt::Display for Capability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            Capability::Storage => "Storage",
+            Capability::Lifecycle => "Deployment & upgrades",
+            Capability::Auth => "Authorization",
+            Capability::Crypto => "Cryptography",
+            Capability::Events => "Events & logging",
+            Capability::Calls => "Cross-contract calls",
+            Capability::Context => "Ledger context",
+            Capability::Randomness => "Randomness",
+            Capability::Data => "Host data types",
+            Capability::Unknown => "Unrecognised",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Soroban host functions worth naming, by (import module, import name).
+/// Modules not listed here are categorised by module alone.
+const HOST_FUNCTIONS: &[(&str, &str, &str, Capability)] = &[
+    ("x", "_", "log_from_linear_memory", Capability::Events),
+    ("x", "1", "contract_event", Capability::Events),
+    ("x", "5", "fail_with_error", Capability::Context),
+    (
+        "x",
+        "7",
+        "get_current_contract_address",
+        Capability::Context,
+    ),
+    ("l", "_", "put_contract_data", Capability::Storage),
+    ("l", "0", "has_contract_data", Capability::Storage),
+    ("l", "1", "get_contract_data", Capability::Storage),
+    ("l", "2", "del_contract_data", Capability::Storage),
+    ("l", "3", "create_contract", Capability::Lifecycle),
+    ("l", "4", "upload_wasm", Capability::Lifecycle),
+    (
+        "l",
+        "5",
+        "update_current_contract_wasm",
+        Capability::Lifecycle,
+    ),
+    ("l", "6", "extend_contract_data_ttl", Capability::Storage),
+    (
+        "l",
+        "7",
+        "extend_current_contract_instance_and_code_ttl",
+        Capability::Storage,
+    ),
+    ("l", "c", "create_asset_contract", Capability::Lifecycle),
+    ("d", "_", "call", Capability::Calls),
+    ("d", "0", "try_call", Capability::Calls),
+    ("c", "_", "compute_hash_sha256", Capability::Crypto),
+    ("c", "0", "verify_sig_ed25519", Capability::Crypto),
+    ("c", "1", "compute_hash_keccak256", Capability::Crypto),
+    ("c", "2", "recover_key_ecdsa_secp256k1", Capability::Crypto),
+    ("c", "3", "verify_sig_ecdsa_secp256r1", Capability::Crypto),
+    ("a", "_", "require_auth_for_args", Capability::Auth),
+    ("a", "0", "require_auth", Capability::Auth),
+    ("a", "3", "authorize_as_curr_contract", Capability::Auth),
+    ("p", "_", "prng_reseed", Capability::Randomness),
+    ("p", "0", "prng_bytes_new", Capability::Randomness),
+    (
+        "p",
+        "1",
+        "prng_u64_in_inclusive_range",
+        Capability::Randomness,
+    ),
+    ("p", "2", "prng_vec_shuffle", Capability::Randomness),
+];
+
+fn module_capability(module: &str) -> Capability {
+    match module {
+        "x" => Capability::Context,
+        "l" => Capability::Storage,
+        "d" => Capability::Calls,
+        "c" => Capability::Crypto,
+        "a" => Capability::Auth,
+        "p" => Capability::Randomness,
+        "i" | "m" | "v" | "b" => Capability::Data,
+        _ => Capability::Unknown,
+    }
+}
+
+/// Functions whose use deserves a reviewer's attention, and why.
+const NOTABLE: &[(&str, &str)] = &[
+    (
+        "update_current_contract_wasm",
+        "can replace its own code (upgradeable); check who is authorized to upgrade",
+    ),
+    ("create_contract", "deploys other contracts"),
+    ("upload_wasm", "uploads new contract code to the ledger"),
+    ("create_asset_contract", "deploys Stellar Asset Contracts"),
+    (
+        "authorize_as_curr_contract",
+        "authorizes sub-contract calls on its own behalf",
+    ),
+    (
+        "prng_reseed",
+        "reseeds the host PRNG; on-chain randomness is predictable to validators",
+    ),
+];
+
+/// One host function the contract imports.
+#[derive(Debug, Clone, Serialize)]
+pub struct HostFunctionUse {
+    /// `module.name` as imported, e.g. `l._`.
+    pub import: String,
+    /// Host function name when known.
+    pub function: Option<String>,
+    pub capability: Capability,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CapabilityReport {
+    pub host_functions: Vec<HostFunctionUse>,
+    pub capabilities: Vec<Capability>,
+    /// Privileged or unusual imports, as human-readable notes.
+    pub findings: Vec<String>,
+}
+
+/// Profile which host capabilities a contract uses from its imports.
+pub fn analyze_capabilities(bytes: &[u8]) -> Result<CapabilityReport> {
+    let mut host_functions = Vec::new();
+    let mut findings = Vec::new();
+
+    for import in parse_imports(bytes)? {
+        let label = format!("{}.{}", import.module, import.name);
+        if import.kind != IMPORT_KIND_FUNC {
+            findings.push(format!(
+                "imports a non-function item {} (Soroban contracts import only host functions)",
+                label
+            ));
+            continue;
+        }
+        let known = HOST_FUNCTIONS
+            .iter()
+            .find(|(m, n, _, _)| *m == import.module && *n == import.name);
+        let function = known.map(|(_, _, f, _)| f.to_string());
+        let capability = known.map_or_else(|| module_capability(&import.module), |k| k.3);
+
+        if capability == Capability::Unknown {
+            findings.push(format!(
+                "imports {} from unrecognised module '{}'",
+                label, import.module
+            ));
+        }
+        if let Some((_, why)) = function
+            .as_deref()
+            .and_then(|f| NOTABLE.iter().find(|(n, _)| *n == f))
+        {
+            findings.push(format!(
+                "{}: {}",
+                function.as_deref().unwrap_or(&label),
+                why
+            ));
+        }
+        if capability == Capability::Randomness
+            && !findings.iter().any(|f| f.starts_with("uses the host PRNG"))
+        {
+            findings.push(
+                "uses the host PRNG; its output is predictable to validators and must not \
+                 secure value"
+                    .to_string(),
+            );
+        }
+
+        host_functions.push(HostFunctionUse {
+            import: label,
+            function,
+            capability,
+        });
+    }
+
+    let mut capabilities: Vec<Capability> = host_functions.iter().map(|h| h.capability).collect();
+    capabilities.sort();
+    capabilities.dedup();
+    if !capabilities.contains(&Capability::Auth)
+        && capabilities
+            .iter()
+            .any(|c| matches!(c, Capability::Storage | Capability::Lifecycle))
+    {
+        findings.push(
+            "writes state but never calls require_auth; anyone may be able to invoke it"
+                .to_string(),
+        );
+    }
+
+    Ok(CapabilityReport {
+        host_functions,
+        capabilities,
+        findings,
+    })
+}
+
+/// `capabilities`: print the host-capability profile of a contract.
+pub fn capabilities(contract_path: &str, json: bool) -> Result<()> {
+    let bytes = std::fs::read(contract_path)
+        .with_context(|| format!("Failed to read wasm file: {}", contract_path))?;
+    let report = analyze_capabilities(&bytes)?;
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(&report, crate::output::Format::Json)?;
+        return Ok(());
+    }
+
+    println!("\n{}", "Contract Capabilities".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    println!("  {}: {}", "Contract".bold(), contract_path);
+    println!(
+        "  {}: {}",
+        "Host functions".bold(),
+        report.host_functions.len()
+    );
+
+    for capability in &report.capabilities {
+        let mut names: Vec<String> = report
+            .host_functions
+            .iter()
+            .filter(|h| h.capability == *capability)
+            .map(|h| h.function.clone().unwrap_or_else(|| h.import.clone()))
+            .collect();
+        names.sort();
+        names.dedup();
+        println!("\n  {}", capability.to_string().bold());
+        println!("    {}", names.join(", ").bright_black());
+    }
+
+    if report.findings.is_empty() {
+        println!("\n{}", "✓ Nothing unusual or privileged found".green());
+    } else {
+        println!("\n{}", "Findings:".bold().yellow());
+        for finding in &report.findings {
+            println!("  {} {}", "⚠".yellow(), finding);
+        }
+    }
+    println!("\n{}", "=".repeat(80).cyan());
+    println!();
+    Ok(())
+}
+
+/// Cut the `(func ...)` form for function `index` out of printed WAT.
+/// `wasmprinter` annotates every definition with its index as `(;N;)`.
+fn extract_func(wat: &str, index: u32) -> Option<&str> {
+    let marker = format!("(;{};)", index);
+    let mut offset = 0;
+    let start = wat.lines().find_map(|line| {
+        let line_start = offset;
+        offset += line.len() + 1;
+        let trimmed = line.trim_start();
+        let head = trimmed.split(" (type").next().unwrap_or(trimmed);
+        (trimmed.starts_with("(func") && head.ends_with(&marker))
+            .then(|| line_start + (line.len() - trimmed.len()))
+    })?;
+
+    let mut depth = 0usize;
+    for (i, c) in wat[start..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&wat[start..=start + i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Disassemble a module to WAT text, or just one exported function.
+pub fn disassemble(bytes: &[u8], function: Option<&str>) -> Result<String> {
+    parse_sections(bytes)?;
+    let wat = wasmprinter::print_bytes(bytes)
+        .map_err(|e| anyhow::anyhow!("failed to disassemble wasm: {}", e))?;
+
+    let Some(function) = function else {
+        return Ok(wat);
+    };
+
+    let exports: Vec<Export> = parse_exports(bytes)?
+        .into_iter()
+        .filter(|e| e.kind == EXPORT_KIND_FUNC)
+        .collect();
+    let Some(export) = exports.iter().find(|e| e.name == function) else {
+        let mut available: Vec<&str> = exports.iter().map(|e| e.name.as_str()).collect();
+        available.sort_unstable();
+        bail!(
+            "function '{}' is not exported by this contract. Available functions: {}",
+            function,
+            if available.is_empty() {
+                "(none)".to_string()
+            } else {
+                available.join(", ")
+            }
+        );
+    };
+
+    extract_func(&wat, export.index)
+        .map(|f| format!("{}\n", f))
+        .with_context(|| {
+            format!(
+                "function '{}' (index {}) is imported, not defined in this module",
+                function, export.index
+            )
+        })
+}
+
+/// `disasm`: print (or write to `output`) the WAT of a contract.
+pub fn disasm(contract_path: &str, function: Option<&str>, output: Option<&str>) -> Result<()> {
+    let bytes = std::fs::read(contract_path)
+        .with_context(|| format!("Failed to read wasm file: {}", contract_path))?;
+    let wat = disassemble(&bytes, function)?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, &wat)
+                .with_context(|| format!("Failed to write disassembly to {}", path))?;
+            eprintln!(
+                "{} Wrote {} lines of WAT to {}",
+                "✓".green(),
+                wat.lines().count(),
+                path
+            );
+        }
+        None => print!("{}", wat),
+    }
+    Ok(())
+}
+
+/// Size reduction achieved by [`optimize`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OptimizeReport {
+    pub original_size: usize,
+    pub optimized_size: usize,
+    pub original_hash: String,
+    pub optimized_hash: String,
+    /// Whether `wasm-opt` ran; otherwise only the built-in passes did.
+    pub wasm_opt: bool,
+    /// Functions unreachable from any export, start function, table or
+    /// global, whose bodies were replaced with `unreachable`.
+    pub stubbed_functions: usize,
+    /// Custom sections removed (debug info, names, producers, ...).
+    pub stripped_sections: Vec<String>,
+}
+
+impl OptimizeReport {
+    pub fn reduction_percent(&self) -> f64 {
+        if self.original_size == 0 {
+            return 0.0;
+        }
+        (self.original_size - self.optimized_size.min(self.original_size)) as f64 * 100.0
+            / self.original_size as f64
+    }
+}
+
+fn write_leb_u32(out: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Run `wasm-opt -Oz` over `bytes`. `None` when wasm-opt isn't installed.
+fn run_wasm_opt(bytes: &[u8]) -> Result<Option<Vec<u8>>> {
+    let dir = tempfile::tempdir().context("Failed to create temp dir")?;
+    let input = dir.path().join("input.wasm");
+    let output = dir.path().join("output.wasm");
+    std::fs::write(&input, bytes).context("Failed to write wasm for wasm-opt")?;
+    let result = std::process::Command::new("wasm-opt")
+        .arg("-Oz")
+        .arg(&input)
+        .arg("-o")
+        .arg(&output)
+        .output();
+    let result = match result {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        other => other.context("Failed to run wasm-opt")?,
+    };
+    if !result.status.success() {
+        bail!(
+            "wasm-opt failed: {}",
+            String::from_utf8_lossy(&result.stderr).trim()
+        );
+    }
+    Ok(Some(
+        std::fs::read(&output).context("Failed to read wasm-opt output")?,
+    ))
+}
+
+fn referenced_functions(
+    mut ops: wasmparser::OperatorsReader<'_>,
+    into: &mut Vec<u32>,
+) -> Result<()> {
+    use wasmparser::Operator;
+    while !ops.eof() {
+        match ops.read()? {
+            Operator::Call { function_index }
+            | Operator::ReturnCall { function_index }
+            | Operator::RefFunc { function_index } => into.push(function_index),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Dead-code elimination that keeps every index stable: functions nobody
+/// can reach get an `unreachable` body, and custom sections other than
+/// Soroban's metadata are dropped. Returns the new module, the number of
+/// stubbed functions and the names of the stripped sections.
+fn strip_and_stub(bytes: &[u8]) -> Result<(Vec<u8>, usize, Vec<String>)> {
+    use wasmparser::{ElementItems, ExternalKind, Payload, TypeRef};
+
+    let mut imported = 0u32;
+    let mut roots = Vec::new();
+    let mut bodies = Vec::new();
+    for payload in wasmparser::Parser::new(0).parse_all(bytes) {
+        match payload? {
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    if matches!(import?.ty, TypeRef::Func(_)) {
+                        imported += 1;
+                    }
+                }
+            }
+            Payload::ExportSection(reader) => {
+                for export in reader {
+                    let export = export?;
+                    if export.kind == ExternalKind::Func {
+                        roots.push(export.index);
+                    }
+                }
+            }
+            Payload::StartSection { func, .. } => roots.push(func),
+            Payload::ElementSection(reader) => {
+                for element in reader {
+                    match element?.items {
+                        ElementItems::Functions(functions) => {
+                            for function in functions {
+                                roots.push(function?);
+                            }
+                        }
+                        ElementItems::Expressions(_, exprs) => {
+                            for expr in exprs {
+                                referenced_functions(expr?.get_operators_reader(), &mut roots)?;
+                            }
+                        }
+                    }
+                }
+            }
+            Payload::GlobalSection(reader) => {
+                for global in reader {
+                    referenced_functions(global?.init_expr.get_operators_reader(), &mut roots)?;
+                }
+            }
+            Payload::CodeSectionEntry(body) => bodies.push(body),
+            _ => {}
+        }
+    }
+
+    let mut live = vec![false; bodies.len()];
+    while let Some(index) = roots.pop() {
+        let Some(defined) = index.checked_sub(imported).map(|i| i as usize) else {
+            continue;
+        };
+        if defined >= bodies.len() || live[defined] {
+            continue;
+        }
+        live[defined] = true;
+        referenced_functions(bodies[defined].get_operators_reader()?, &mut roots)?;
+    }
+
+    let mut code = Vec::new();
+    write_leb_u32(&mut code, bodies.len() as u32);
+    for (body, live) in bodies.iter().zip(&live) {
+        if *live {
+            let range = body.range();
+            write_leb_u32(&mut code, range.len() as u32);
+            code.extend_from_slice(&bytes[range]);
+        } else {
+            // No locals; `unreachable`; `end`.
+            code.extend_from_slice(&[3, 0, 0x00, 0x0b]);
+        }
+    }
+
+    let mut out = bytes[..8].to_vec();
+    let mut stripped = Vec::new();
+    for section in parse_sections(bytes)? {
+        if let Some(name) = &section.name {
+            if !SOROBAN_SECTIONS.contains(&name.as_str()) {
+                stripped.push(name.clone());
+                continue;
+            }
+        }
+        let payload = match section.id {
+            10 => &code[..],
+            _ => &bytes[section.offset..section.offset + section.size],
+        };
+        out.push(section.id);
+        write_leb_u32(&mut out, payload.len() as u32);
+        out.extend_from_slice(payload);
+    }
+    let stubbed = live.iter().filter(|l| !**l).count();
+    Ok((out, stubbed, stripped))
+}
+
+/// Shrink a contract: `wasm-opt -Oz` when it is installed (falling back if
+/// it fails), then the built-in dead-code and section stripping. The result
+/// must still be valid wasm carrying every Soroban metadata section the
+/// input had.
+pub fn optimize(bytes: &[u8]) -> Result<(Vec<u8>, OptimizeReport)> {
+    let input_sections = validate_soroban_module(bytes)?;
+
+    let (optimized, wasm_opt) = match run_wasm_opt(bytes) {
+        Ok(Some(optimized)) => (optimized, true),
+        Ok(None) => (bytes.to_vec(), false),
+        Err(e) => {
+            eprintln!("{} {}; using the built-in passes only", "⚠".yellow(), e);
+            (bytes.to_vec(), false)
+        }
+    };
+    let (optimized, stubbed_functions, stripped_sections) = strip_and_stub(&optimized)?;
+
+    wasmparser::validate(&optimized)
+        .map_err(|e| anyhow::anyhow!("optimized module is invalid: {}", e))?;
+    let output_sections = parse_sections(&optimized)?;
+    for name in input_sections.iter().filter_map(|s| s.name.as_deref()) {
+        if SOROBAN_SECTIONS.contains(&name)
+            && !output_sections
+                .iter()
+                .any(|s| s.name.as_deref() == Some(name))
+        {
+            bail!("optimization dropped the Soroban {} section", name);
+        }
+    }
+
+    let report = OptimizeReport {
+        original_size: bytes.len(),
+        optimized_size: optimized.len(),
+        original_hash: sha256_hex(bytes),
+        optimized_hash: sha256_hex(&optimized),
+        wasm_opt,
+        stubbed_functions,
+        stripped_sections,
+    };
+    Ok((optimized, report))
+}
+
+/// Where an optimized copy of `wasm` goes by default: `x.wasm` →
+/// `x.optimized.wasm`.
+pub fn optimized_path(wasm: &std::path::Path) -> std::path::PathBuf {
+    wasm.with_extension("optimized.wasm")
+}
+
+/// `optimize`: write an optimized copy of a contract and report the saving.
+pub fn optimize_file(wasm: &str, output: Option<&str>, json: bool) -> Result<OptimizeReport> {
+    let bytes =
+        std::fs::read(wasm).with_context(|| format!("Failed to read wasm file: {}", wasm))?;
+    let (optimized, report) = optimize(&bytes)?;
+    let output = output
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| optimized_path(std::path::Path::new(wasm)));
+    std::fs::write(&output, &optimized)
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(&report, crate::output::Format::Json)?;
+        return Ok(report);
+    }
+
+    println!("\n{}", "WASM Optimization".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    println!(
+        "  {}: {}",
+        "Passes".bold(),
+        if report.wasm_opt {
+            "wasm-opt -Oz, dead-code stubbing, section stripping"
+        } else {
+            "dead-code stubbing, section stripping (wasm-opt not found)"
+        }
+    );
+    println!(
+        "  {}: {} → {} bytes ({:.1}% smaller)",
+        "Size".bold(),
+        report.original_size,
+        report.optimized_size,
+        report.reduction_percent()
+    );
+    println!(
+        "  {}: {}",
+        "Unreachable functions stubbed".bold(),
+        report.stubbed_functions
+    );
+    if !report.stripped_sections.is_empty() {
+        println!(
+            "  {}: {}",
+            "Stripped sections".bold(),
+            report.stripped_sections.join(", ")
+        );
+    }
+    println!(
+        "  {}: {}",
+        "Hash".bold(),
+        report.optimized_hash.bright_black()
+    );
+    println!(
+        "{} Soroban metadata intact; wrote {}",
+        "✓".green(),
+        output.display()
+    );
+    println!();
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_section(name: &str, payload: &[u8]) -> Vec<u8> {
+        let mut body = vec![name.len() as u8];
+        body.extend_from_slice(name.as_bytes());
+        body.extend_from_slice(payload);
+        let mut section = vec![0u8, body.len() as u8];
+        section.extend(body);
+        section
+    }
+
+    fn module(sections: &[Vec<u8>]) -> Vec<u8> {
+        let mut bytes = WASM_MAGIC.to_vec();
+        bytes.extend_from_slice(&WASM_VERSION.to_le_bytes());
+        for s in sections {
+            bytes.extend_from_slice(s);
+        }
+        bytes
+    }
+
+    #[test]
+    fn accepts_module_with_soroban_metadata() {
+        let bytes = module(&[custom_section("contractenvmetav0", &[1, 2, 3])]);
+        let sections = validate_soroban_module(&bytes).expect("valid module");
+        assert_eq!(sections[0].name.as_deref(), Some("contractenvmetav0"));
+    }
+
+    #[test]
+    fn file_hash_matches_in_memory_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("contract.wasm");
+        // Bigger than the copy buffer, so it is hashed over several reads.
+        let bytes: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&path, &bytes).unwrap();
+        assert_eq!(sha256_file(&path).unwrap(), sha256_hex(&bytes));
+        assert!(sha256_file(&dir.path().join("missing.wasm")).is_err());
+    }
+
+    #[test]
+    fn rejects_plain_wasm_and_garbage() {
+        assert!(validate_soroban_module(&module(&[])).is_err());
+        assert!(parse_sections(b"not wasm at all").is_err());
+        // Section claims 100 bytes but the module ends immediately.
+        let mut truncated = module(&[]);
+        truncated.extend_from_slice(&[1, 100]);
+        assert!(parse_sections(&truncated).is_err());
+    }
+
+    #[test]
+    fn extracts_single_function_from_wat() {
+        let wat = "(module\n  (type (;0;) (func))\n  (func $a (;0;) (type 0)\n    nop\n  )\n  (func (;1;) (type 0)\n    (call 0)\n  )\n  (export \"run\" (func 1))\n)\n";
+        assert_eq!(
+            extract_func(wat, 1),
+            Some("(func (;1;) (type 0)\n    (call 0)\n  )")
+        );
+        assert!(extract_func(wat, 0).unwrap().contains("nop"));
+        assert_eq!(extract_func(wat, 7), None);
+    }
+
+    #[test]
+    fn parses_export_section() {
+        // export section: 1 entry, "run" -> func 2
+        let mut export = vec![7u8, 7, 1, 3];
+        export.extend_from_slice(b"run");
+        export.extend_from_slice(&[EXPORT_KIND_FUNC, 2]);
+        let exports = parse_exports(&module(&[export])).unwrap();
+        assert_eq!(
+            exports,
+            vec![Export {
+                name: "run".into(),
+                kind: EXPORT_KIND_FUNC,
+                index: 2
+            }]
+        );
+    }
+
+    fn import_section(imports: &[(&str, &str, u8)]) -> Vec<u8> {
+        let mut body = vec![imports.len() as u8];
+        for (module, name, kind) in imports {
+            body.push(module.len() as u8);
+            body.extend_from_slice(module.as_bytes());
+            body.push(name.len() as u8);
+            body.extend_from_slice(name.as_bytes());
+            body.push(*kind);
+            match kind {
+                2 => body.extend_from_slice(&[0, 1]),
+                _ => body.push(0),
+            }
+        }
+        let mut section = vec![2u8, body.len() as u8];
+        section.extend(body);
+        section
+    }
+
+    #[test]
+    fn classifies_host_function_imports() {
+        let bytes = module(&[import_section(&[
+            ("l", "_", 0),
+            ("l", "5", 0),
+            ("a", "0", 0),
+            ("i", "8", 0),
+            ("env", "memory", 2),
+            ("evil", "x", 0),
+        ])]);
+        let imports = parse_imports(&bytes).unwrap();
+        assert_eq!(imports.len(), 6);
+        assert_eq!(imports[4].kind, 2);
+
+        let report = analyze_capabilities(&bytes).unwrap();
+        assert_eq!(
+            report.capabilities,
+            vec![
+                Capability::Storage,
+                Capability::Lifecycle,
+                Capability::Auth,
+                Capability::Data,
+                Capability::Unknown
+            ]
+        );
+        assert_eq!(
+            report.host_functions[0].function.as_deref(),
+            Some("put_contract_data")
+        );
+        let findings = report.findings.join("\n");
+        assert!(findings.contains("update_current_contract_wasm: can replace its own code"));
+        assert!(findings.contains("non-function item env.memory"));
+        assert!(findings.contains("unrecognised module 'evil'"));
+        assert!(!findings.contains("never calls require_auth"));
+    }
+
+    #[test]
+    fn leb128_multi_byte() {
+        let mut pos = 0;
+        assert_eq!(
+            read_leb_u32(&[0xe5, 0x8e, 0x26], &mut pos).unwrap(),
+            624_485
+        );
+        assert_eq!(pos, 3);
+    }
+
+    #[test]
+    fn optimize_stubs_unreachable_functions_and_strips_sections() {
+        let types = vec![1u8, 4, 1, 0x60, 0, 0];
+        let functions = vec![3u8, 3, 2, 0, 0];
+        let mut export = vec![7u8, 7, 1, 3];
+        export.extend_from_slice(b"run");
+        export.extend_from_slice(&[EXPORT_KIND_FUNC, 0]);
+        // Two bodies: the exported `nop` and an uncalled function.
+        let code = vec![
+            10u8, 12, 2, 3, 0, 0x01, 0x0b, 6, 0, 0x01, 0x01, 0x01, 0x01, 0x0b,
+        ];
+        let bytes = module(&[
+            types,
+            functions,
+            export,
+            code,
+            custom_section("contractspecv0", &[1, 2, 3]),
+            custom_section("name", &[0; 8]),
+        ]);
+
+        let (optimized, stubbed, stripped) = strip_and_stub(&bytes).unwrap();
+        assert_eq!(stubbed, 1);
+        assert_eq!(stripped, ["name"]);
+        wasmparser::validate(&optimized).unwrap();
+        validate_soroban_module(&optimized).unwrap();
+        assert!(optimized.len() < bytes.len());
+        assert_eq!(
+            parse_exports(&optimized).unwrap(),
+            parse_exports(&bytes).unwrap()
+        );
+
+        let report = OptimizeReport {
+            original_size: 200,
+            optimized_size: 150,
+            original_hash: String::new(),
+            optimized_hash: String::new(),
+            wasm_opt: false,
+            stubbed_functions: 0,
+            stripped_sections: Vec::new(),
+        };
+        assert_eq!(report.reduction_percent(), 25.0);
+        assert_eq!(
+            optimized_path(std::path::Path::new("target/token.wasm")),
+            std::path::Path::new("target/token.optimized.wasm")
+        );
+    }
+}
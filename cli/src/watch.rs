@@ -0,0 +1,129 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Quiet period after the last file change before tests are re-run.
+const DEBOUNCE_MS: u64 = 300;
+
+/// Directories whose changes never trigger a re-run (build output, coverage reports).
+pub const IGNORED_DIRS: &[&str] = &["target", ".git", ".soroban-registry", "node_modules"];
+
+/// Watch `paths` for changes and invoke `run_once` after each debounced burst
+/// of modifications. Runs once immediately, then loops until interrupted.
+pub async fn run<F, Fut>(paths: Vec<PathBuf>, mut run_once: F) -> Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let (tx, mut rx) = mpsc::unbounded_channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    })
+    .context("Failed to initialise file watcher")?;
+
+    for path in &paths {
+        let mode = if path.is_dir() {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        watcher
+            .watch(path, mode)
+            .with_context(|| format!("Failed to watch {}", path.display()))?;
+    }
+
+    let mut cycle = 0usize;
+    loop {
+        cycle += 1;
+        clear_screen();
+        println!(
+            "{} {} {}",
+            "Watching".bold().cyan(),
+            paths
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+                .bright_black(),
+            format!("(run #{})", cycle).bright_black()
+        );
+
+        let start = Instant::now();
+        let outcome = run_once().await;
+        print_summary(&outcome, start.elapsed());
+
+        // Wait for the next relevant change, then debounce follow-up events.
+        loop {
+            match rx.recv().await {
+                Some(Ok(event)) if is_relevant(&event) => break,
+                Some(Ok(_)) => continue,
+                Some(Err(e)) => log::debug!("watch error: {}", e),
+                None => return Ok(()),
+            }
+        }
+        loop {
+            match tokio::time::timeout(Duration::from_millis(DEBOUNCE_MS), rx.recv()).await {
+                Ok(Some(_)) => continue,
+                Ok(None) => return Ok(()),
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+fn is_relevant(event: &Event) -> bool {
+    if matches!(event.kind, EventKind::Access(_)) {
+        return false;
+    }
+    event.paths.iter().any(|p| !is_ignored(p))
+}
+
+fn is_ignored(path: &Path) -> bool {
+    path.components().any(|c| {
+        c.as_os_str()
+            .to_str()
+            .map(|s| IGNORED_DIRS.contains(&s))
+            .unwrap_or(false)
+    })
+}
+
+fn clear_screen() {
+    print!("\x1B[2J\x1B[1;1H");
+}
+
+fn print_summary(outcome: &Result<()>, elapsed: Duration) {
+    let time = chrono::Local::now().format("%H:%M:%S");
+    match outcome {
+        Ok(()) => println!(
+            "{} {} in {:.2}s — waiting for changes...",
+            format!("[{}]", time).bright_black(),
+            "✓ PASS".green().bold(),
+            elapsed.as_secs_f64()
+        ),
+        Err(e) => println!(
+            "{} {} in {:.2}s: {} — waiting for changes...",
+            format!("[{}]", time).bright_black(),
+            "✗ FAIL".red().bold(),
+            elapsed.as_secs_f64(),
+            e
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignores_build_and_coverage_output() {
+        assert!(is_ignored(Path::new("contract/target/debug/foo.wasm")));
+        assert!(is_ignored(Path::new(
+            "contract/.soroban-registry/coverage/report.json"
+        )));
+        assert!(!is_ignored(Path::new("contract/src/lib.rs")));
+    }
+}
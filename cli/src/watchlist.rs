@@ -0,0 +1,327 @@
+//! `watchlist`: a personal list of contracts to keep an eye on, stored next
+//! to the CLI config, with a one-table status view.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::health::HealthStatus;
+
+const WATCHLIST_FILE_NAME: &str = "watchlist.json";
+
+/// Contracts whose status is fetched at the same time.
+const STATUS_CONCURRENCY: usize = 8;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Watchlist {
+    pub contracts: Vec<String>,
+}
+
+impl Watchlist {
+    /// Add `contract_id`; `false` if it was already watched.
+    pub fn add(&mut self, contract_id: &str) -> bool {
+        if self.contains(contract_id) {
+            return false;
+        }
+        self.contracts.push(contract_id.to_string());
+        true
+    }
+
+    /// Remove `contract_id`; `false` if it wasn't watched.
+    pub fn remove(&mut self, contract_id: &str) -> bool {
+        let before = self.contracts.len();
+        self.contracts
+            .retain(|c| !c.eq_ignore_ascii_case(contract_id));
+        self.contracts.len() != before
+    }
+
+    fn contains(&self, contract_id: &str) -> bool {
+        self.contracts
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(contract_id))
+    }
+
+    fn load() -> Result<Self> {
+        let path = watchlist_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(&path).context("Failed to read watchlist")?;
+        serde_json::from_str(&raw)
+            .with_context(|| format!("Watchlist file is corrupt: {}", path.display()))
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = watchlist_path()?;
+        let dir = path.parent().context("Invalid watchlist path")?;
+        std::fs::create_dir_all(dir).context("Failed to create config directory")?;
+        let mut file = tempfile::NamedTempFile::new_in(dir).context("Failed to save watchlist")?;
+        serde_json::to_writer_pretty(&mut file, self)?;
+        file.persist(&path).context("Failed to save watchlist")?;
+        Ok(())
+    }
+}
+
+fn watchlist_path() -> Result<PathBuf> {
+    Ok(crate::config::config_file_path()
+        .context("Could not determine home directory")?
+        .with_file_name(WATCHLIST_FILE_NAME))
+}
+
+pub fn add(contract_id: &str) -> Result<()> {
+    let mut watchlist = Watchlist::load()?;
+    if watchlist.add(contract_id) {
+        watchlist.save()?;
+        println!("{} Watching {}", "✓".green(), contract_id.bold());
+    } else {
+        println!("{} Already watching {}", "→".bright_black(), contract_id);
+    }
+    Ok(())
+}
+
+pub fn remove(contract_id: &str) -> Result<()> {
+    let mut watchlist = Watchlist::load()?;
+    if !watchlist.remove(contract_id) {
+        anyhow::bail!("{} is not on the watchlist", contract_id);
+    }
+    watchlist.save()?;
+    println!("{} Stopped watching {}", "✓".green(), contract_id.bold());
+    Ok(())
+}
+
+pub fn list(json: bool) -> Result<()> {
+    let watchlist = Watchlist::load()?;
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(&watchlist, crate::output::Format::Json)?;
+        return Ok(());
+    }
+
+    if watchlist.contracts.is_empty() {
+        println!("{}", "Watchlist is empty.".yellow());
+        println!("  Add a contract with: soroban-registry watchlist add <contract-id>");
+        return Ok(());
+    }
+    println!("\n{}", "Watchlist".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    for id in &watchlist.contracts {
+        println!("  • {}", id);
+    }
+    println!();
+    Ok(())
+}
+
+/// Current state of one watched contract. Each part is fetched
+/// independently; whatever couldn't be fetched is `None` with the reason in
+/// `errors`.
+#[derive(Debug, Default, Serialize)]
+pub struct WatchStatus {
+    pub contract_id: String,
+    pub name: Option<String>,
+    pub network: Option<String>,
+    pub version: Option<String>,
+    /// Ledgers until the first of the instance/code entries is archived.
+    pub ttl_ledgers: Option<u64>,
+    pub ttl_status: Option<HealthStatus>,
+    pub pending_patches: Option<usize>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+async fn fetch_registry_entry(client: &reqwest::Client, api_url: &str, id: &str) -> Result<Value> {
+    let response = client
+        .get(format!("{}/api/contracts/{}", api_url, id))
+        .send()
+        .await
+        .context("Failed to fetch contract from registry")?;
+    if !response.status().is_success() {
+        anyhow::bail!("Contract not found: {} ({})", id, response.status());
+    }
+    let body: Value = response.json().await?;
+    Ok(body.get("contract").cloned().unwrap_or(body))
+}
+
+async fn contract_status(client: reqwest::Client, api_url: String, id: String) -> WatchStatus {
+    let mut status = WatchStatus {
+        contract_id: id.clone(),
+        ..Default::default()
+    };
+
+    let contract = match fetch_registry_entry(&client, &api_url, &id).await {
+        Ok(contract) => contract,
+        Err(e) => {
+            status.errors.push(e.to_string());
+            return status;
+        }
+    };
+    status.name = contract["name"].as_str().map(str::to_string);
+    status.network = contract["network"].as_str().map(str::to_string);
+    let uuid = contract["id"].as_str().unwrap_or(&id);
+    let wasm_hash = contract["wasm_hash"].as_str().unwrap_or_default();
+
+    let (versions, ttl, patches) = tokio::join!(
+        crate::commands::fetch_version_history(&client, &api_url, uuid),
+        crate::health::ttl_report(
+            &client,
+            &api_url,
+            &id,
+            &[],
+            crate::health::DEFAULT_MIN_TTL_LEDGERS
+        ),
+        async {
+            if wasm_hash.is_empty() {
+                anyhow::bail!("no wasm hash registered");
+            }
+            crate::patch::PatchManager::pending_for(&api_url, wasm_hash).await
+        },
+    );
+
+    match versions {
+        Ok(versions) => {
+            status.version = versions
+                .last()
+                .and_then(|v| v["version"].as_str())
+                .map(str::to_string)
+        }
+        Err(e) => status.errors.push(format!("versions: {}", e)),
+    }
+    match ttl {
+        Ok(report) => {
+            let soonest = report
+                .entries
+                .iter()
+                .filter(|e| e.found)
+                .filter_map(|e| e.ledgers_remaining)
+                .min();
+            status.ttl_ledgers = soonest;
+            status.ttl_status = report.entries.iter().map(|e| e.status).max();
+        }
+        Err(e) => status.errors.push(format!("ttl: {}", e)),
+    }
+    match patches {
+        Ok(patches) => status.pending_patches = Some(patches.len()),
+        Err(e) => status.errors.push(format!("patches: {}", e)),
+    }
+    status
+}
+
+/// Fetch version, TTL and pending patches for every watched contract.
+pub async fn status(api_url: &str, json: bool) -> Result<()> {
+    let watchlist = Watchlist::load()?;
+    if watchlist.contracts.is_empty() {
+        anyhow::bail!("Watchlist is empty; add a contract with `watchlist add <contract-id>`");
+    }
+
+    let client = reqwest::Client::new();
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(STATUS_CONCURRENCY));
+    let mut tasks = tokio::task::JoinSet::new();
+    for (index, id) in watchlist.contracts.iter().enumerate() {
+        let client = client.clone();
+        let api_url = api_url.to_string();
+        let id = id.clone();
+        let semaphore = semaphore.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            (index, contract_status(client, api_url, id).await)
+        });
+    }
+
+    let mut statuses = Vec::with_capacity(watchlist.contracts.len());
+    while let Some(joined) = tasks.join_next().await {
+        statuses.push(joined.context("status task panicked")?);
+    }
+    statuses.sort_by_key(|(index, _)| *index);
+    let statuses: Vec<WatchStatus> = statuses.into_iter().map(|(_, s)| s).collect();
+
+    let emitter = crate::output::Emitter::current();
+    if json || emitter.structured() {
+        emitter.emit(&statuses, crate::output::Format::Json)?;
+        return Ok(());
+    }
+
+    println!("\n{}", "Watchlist Status".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    let rows: Vec<Vec<String>> = statuses.iter().map(status_row).collect();
+    print!(
+        "{}",
+        crate::table_format::render_table(
+            &["Contract", "Name", "Network", "Version", "TTL", "Patches"],
+            &[14, 20, 9, 9, 14, 8],
+            &rows
+        )
+    );
+
+    let problems: Vec<&WatchStatus> = statuses.iter().filter(|s| !s.errors.is_empty()).collect();
+    if !problems.is_empty() {
+        println!();
+        for s in problems {
+            println!(
+                "  {} {}: {}",
+                "⚠".yellow(),
+                s.contract_id,
+                s.errors.join("; ")
+            );
+        }
+    }
+    println!();
+    Ok(())
+}
+
+fn short_id(id: &str) -> String {
+    if id.len() > 12 {
+        format!("{}…{}", &id[..6], &id[id.len() - 4..])
+    } else {
+        id.to_string()
+    }
+}
+
+fn status_row(s: &WatchStatus) -> Vec<String> {
+    let missing = || "-".bright_black().to_string();
+    let ttl = match (s.ttl_ledgers, s.ttl_status) {
+        (Some(ledgers), Some(status)) => {
+            let text = crate::health::approx_duration(ledgers);
+            match status {
+                HealthStatus::Healthy => text.green().to_string(),
+                HealthStatus::Warning => text.yellow().to_string(),
+                HealthStatus::Unhealthy => "archived".red().to_string(),
+            }
+        }
+        (None, Some(HealthStatus::Unhealthy)) => "missing".red().to_string(),
+        _ => missing(),
+    };
+    let patches = match s.pending_patches {
+        Some(0) => "0".green().to_string(),
+        Some(n) => n.to_string().red().bold().to_string(),
+        None => missing(),
+    };
+    vec![
+        short_id(&s.contract_id),
+        s.name.clone().unwrap_or_else(missing),
+        s.network.clone().unwrap_or_else(missing),
+        s.version.clone().unwrap_or_else(missing),
+        ttl,
+        patches,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_remove_ignore_duplicates_and_case() {
+        let mut watchlist = Watchlist::default();
+        assert!(watchlist.add("CABC"));
+        assert!(watchlist.add("CDEF"));
+        assert!(!watchlist.add("cabc"));
+        assert_eq!(watchlist.contracts, ["CABC", "CDEF"]);
+
+        assert!(watchlist.remove("cabc"));
+        assert!(!watchlist.remove("CABC"));
+        assert_eq!(watchlist.contracts, ["CDEF"]);
+    }
+}
@@ -19,6 +19,7 @@ pub enum WebhookEvent {
     ContractVerified,
     ContractFailedVerification,
     VersionCreated,
+    ContractDeprecated,
 }
 
 impl std::fmt::Display for WebhookEvent {
@@ -28,6 +29,7 @@ impl std::fmt::Display for WebhookEvent {
             WebhookEvent::ContractVerified => "contract.verified",
             WebhookEvent::ContractFailedVerification => "contract.failed_verification",
             WebhookEvent::VersionCreated => "version.created",
+            WebhookEvent::ContractDeprecated => "contract.deprecated",
         };
         write!(f, "{}", s)
     }
@@ -42,8 +44,9 @@ impl std::str::FromStr for WebhookEvent {
             "contract.verified" => Ok(WebhookEvent::ContractVerified),
             "contract.failed_verification" => Ok(WebhookEvent::ContractFailedVerification),
             "version.created" => Ok(WebhookEvent::VersionCreated),
+            "contract.deprecated" => Ok(WebhookEvent::ContractDeprecated),
             _ => anyhow::bail!(
-                "Unknown event type: {}. Valid: contract.published, contract.verified, contract.failed_verification, version.created",
+                "Unknown event type: {}. Valid: contract.published, contract.verified, contract.failed_verification, version.created, contract.deprecated",
                 s
             ),
         }
@@ -1,69 +1,328 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::{create_dir_all, File, OpenOptions};
 use std::io::{self, BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
 const HISTORY_FILE_NAME: &str = "deployments.ndjson";
+const SESSION_FILE_NAME: &str = "wizard-session.json";
 
-pub async fn run(_api_url: &str) -> Result<()> {
+/// Answers given so far in an unfinished wizard run, saved after every step
+/// so `wizard --resume` can pick up where it stopped. Signer secrets are
+/// never written; a resumed session asks for them again.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct Session {
+    network: Option<String>,
+    signer: Option<String>,
+    wasm_path: Option<String>,
+    params: Option<serde_json::Value>,
+    max_fee: Option<String>,
+}
+
+impl Session {
+    /// The session as it may be written to disk.
+    fn persistable(&self) -> Session {
+        let mut session = self.clone();
+        if session.signer.as_deref().is_some_and(is_secret) {
+            session.signer = None;
+        }
+        session
+    }
+
+    fn load() -> Result<Option<Session>> {
+        let path = session_path()?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let raw = std::fs::read_to_string(&path).context("Failed to read wizard session")?;
+        let mut session: Session =
+            serde_json::from_str(&raw).context("Wizard session file is corrupt")?;
+        // The WASM may have moved since the session was saved.
+        session.wasm_path = session.wasm_path.filter(|p| Path::new(p).is_file());
+        Ok(Some(session.persistable()))
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = session_path()?;
+        let dir = path.parent().context("Invalid wizard session path")?;
+        let mut file =
+            tempfile::NamedTempFile::new_in(dir).context("Failed to save wizard session")?;
+        serde_json::to_writer_pretty(&mut file, &self.persistable())?;
+        file.persist(&path)
+            .context("Failed to save wizard session")?;
+        Ok(())
+    }
+
+    fn clear() {
+        if let Ok(path) = session_path() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Reuse a saved answer, or ask for it and record it in `slot`.
+fn answer<T: Clone + std::fmt::Display>(
+    slot: &mut Option<T>,
+    label: &str,
+    ask: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    if let Some(value) = slot {
+        println!(
+            "{} {}: {}",
+            "↺".cyan(),
+            label.bold(),
+            value.to_string().bright_black()
+        );
+        return Ok(value.clone());
+    }
+    let value = ask()?;
+    *slot = Some(value.clone());
+    Ok(value)
+}
+
+/// Answers for `wizard --answers`, keyed by prompt id: `network`, `signer`,
+/// `wasm_path`, `params` and `max_fee` (named as in [`Session`]), then
+/// `dry_run` and `execute` for the two confirmations.
+#[derive(Debug, Default)]
+struct Answers {
+    given: BTreeMap<String, serde_json::Value>,
+    /// Fail on a missing answer that has no default instead of prompting.
+    non_interactive: bool,
+    /// Where `--dump-answers` records this run's answers.
+    dump_path: Option<PathBuf>,
+    confirmed: BTreeMap<String, bool>,
+}
+
+impl Answers {
+    fn load(path: Option<&Path>, non_interactive: bool, dump_path: Option<&Path>) -> Result<Self> {
+        let given = match path {
+            Some(path) => {
+                let raw = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read answers file {}", path.display()))?;
+                // YAML is a superset of JSON, so this reads both.
+                serde_yaml::from_str(&raw).with_context(|| {
+                    format!("{} is not a YAML or JSON map of answers", path.display())
+                })?
+            }
+            None => BTreeMap::new(),
+        };
+        Ok(Answers {
+            given,
+            non_interactive,
+            dump_path: dump_path.map(Path::to_path_buf),
+            confirmed: BTreeMap::new(),
+        })
+    }
+
+    fn missing(&self, id: &str) -> anyhow::Error {
+        anyhow::anyhow!(
+            "no answer for '{}' and --non-interactive is set; add it to the answers file",
+            id
+        )
+    }
+
+    /// The answer to prompt `id` from the answers file, checked like a typed
+    /// one; without one, the default when non-interactive, else prompted.
+    fn text<F>(
+        &self,
+        id: &str,
+        label: &str,
+        default: Option<String>,
+        mut validate: F,
+        error_msg: &str,
+    ) -> Result<String>
+    where
+        F: FnMut(&str) -> bool,
+    {
+        let Some(given) = self.given.get(id) else {
+            if !self.non_interactive {
+                return prompt_with_validation(label, default, validate, error_msg);
+            }
+            return default.ok_or_else(|| self.missing(id));
+        };
+        let value = match given {
+            serde_json::Value::String(s) => s.trim().to_string(),
+            other => other.to_string(),
+        };
+        anyhow::ensure!(
+            validate(&value),
+            "invalid answer for '{}': {}",
+            id,
+            error_msg
+        );
+        let shown = if is_secret(&value) && value.len() >= 56 {
+            mask_secret(&value)
+        } else {
+            value.clone()
+        };
+        println!("{} {}: {}", "✓".green(), label.bold(), shown.bright_black());
+        Ok(value)
+    }
+
+    /// Constructor params: an object in the answers file, or JSON text.
+    fn params(&self, id: &str) -> Result<serde_json::Value> {
+        let raw = match self.given.get(id) {
+            Some(value @ serde_json::Value::Object(_)) => return Ok(value.clone()),
+            Some(serde_json::Value::String(s)) => s.clone(),
+            Some(_) => anyhow::bail!("invalid answer for '{}': expected a JSON object", id),
+            None if self.non_interactive => String::new(),
+            None => {
+                println!(
+                    "{}",
+                    "Enter constructor params as JSON object (e.g., {\"admin\":\"G...\"}). Leave blank for {}"
+                        .replace("{}", "none")
+                        .bright_black()
+                );
+                prompt("Params JSON", Some("".into()))?
+            }
+        };
+        if raw.trim().is_empty() {
+            Ok(serde_json::Value::Object(Default::default()))
+        } else {
+            serde_json::from_str::<serde_json::Value>(raw.trim())
+                .with_context(|| format!("Invalid JSON for '{}'", id))
+        }
+    }
+
+    fn confirm(&mut self, id: &str, label: &str, default_yes: bool) -> Result<bool> {
+        let yes = match self.given.get(id) {
+            Some(serde_json::Value::Bool(yes)) => *yes,
+            Some(serde_json::Value::String(s)) => match s.trim().to_lowercase().as_str() {
+                "y" | "yes" | "true" => true,
+                "n" | "no" | "false" => false,
+                _ => anyhow::bail!("invalid answer for '{}': expected yes or no", id),
+            },
+            Some(_) => anyhow::bail!("invalid answer for '{}': expected yes or no", id),
+            None if self.non_interactive => default_yes,
+            None => confirm(label, default_yes)?,
+        };
+        self.confirmed.insert(id.to_string(), yes);
+        Ok(yes)
+    }
+
+    /// With `--dump-answers`, write the answers given so far in a form
+    /// `--answers` reads back. Like the session file, it never holds a
+    /// signer secret.
+    fn dump(&self, session: &Session) -> Result<()> {
+        let Some(path) = &self.dump_path else {
+            return Ok(());
+        };
+        let mut answers: BTreeMap<String, serde_json::Value> =
+            serde_json::from_value(serde_json::to_value(session.persistable())?)?;
+        answers.retain(|_, v| !v.is_null());
+        for (id, yes) in &self.confirmed {
+            answers.insert(id.clone(), serde_json::Value::Bool(*yes));
+        }
+        let yaml = serde_yaml::to_string(&answers)?;
+        std::fs::write(path, yaml)
+            .with_context(|| format!("Failed to write answers to {}", path.display()))?;
+        if session.signer.as_deref().is_some_and(is_secret) {
+            println!(
+                "{} Signer secret left out of {}; supply 'signer' when replaying",
+                "⚠".yellow(),
+                path.display()
+            );
+        }
+        Ok(())
+    }
+}
+
+pub async fn run(
+    _api_url: &str,
+    resume: bool,
+    answers_path: Option<&Path>,
+    non_interactive: bool,
+    dump_answers: Option<&Path>,
+) -> Result<()> {
     println!("\n{}", "Contract Instantiation Wizard".bold().cyan());
     println!("{}", "=".repeat(80).cyan());
 
-    let network = prompt_with_validation(
-        "Select network [mainnet|testnet|futurenet] (default: testnet)",
-        Some("testnet".to_string()),
-        |s| {
-            matches!(
-                s.to_lowercase().as_str(),
-                "mainnet" | "testnet" | "futurenet"
-            )
-        },
-        "Invalid network. Choose mainnet, testnet, or futurenet.",
-    )?;
+    let saved = Session::load()?;
+    let mut session = match (resume, saved) {
+        (true, Some(session)) => {
+            println!("{}", "Resuming previous session.".bright_black());
+            session
+        }
+        (true, None) => {
+            println!(
+                "{}",
+                "No interrupted session found; starting a new one.".yellow()
+            );
+            Session::default()
+        }
+        (false, Some(_)) => {
+            println!(
+                "{}",
+                "Discarding the previous unfinished session (use --resume to continue it)."
+                    .bright_black()
+            );
+            Session::default()
+        }
+        (false, None) => Session::default(),
+    };
+    let mut answers = Answers::load(answers_path, non_interactive, dump_answers)?;
 
-    let signer = prompt_with_validation(
-        "Enter signer address or secret (starts with G… or S…)",
-        None::<String>,
-        |s: &str| {
-            let s = s.trim();
-            (s.starts_with('G') || s.starts_with('S')) && s.len() >= 56
-        },
-        "Invalid signer. Provide a Stellar address (G...) or secret (S...).",
-    )?;
+    let network = answer(&mut session.network, "Network", || {
+        answers.text(
+            "network",
+            "Select network [mainnet|testnet|futurenet] (default: testnet)",
+            Some("testnet".to_string()),
+            |s| {
+                matches!(
+                    s.to_lowercase().as_str(),
+                    "mainnet" | "testnet" | "futurenet"
+                )
+            },
+            "Invalid network. Choose mainnet, testnet, or futurenet.",
+        )
+    })?;
+    session.save()?;
 
-    let wasm_path = prompt_with_validation(
-        "Path to contract WASM (.wasm)",
-        None::<String>,
-        |s: &str| {
-            let p = Path::new(s.trim());
-            p.exists() && p.is_file() && p.extension().map(|e| e == "wasm").unwrap_or(false)
-        },
-        "File not found or not a .wasm file.",
-    )?;
+    let signer = answer(&mut session.signer, "Signer", || {
+        answers.text(
+            "signer",
+            "Enter signer address or secret (starts with G… or S…)",
+            None::<String>,
+            |s: &str| {
+                let s = s.trim();
+                (s.starts_with('G') || s.starts_with('S')) && s.len() >= 56
+            },
+            "Invalid signer. Provide a Stellar address (G...) or secret (S...).",
+        )
+    })?;
+    session.save()?;
 
-    println!(
-        "{}",
-        "Enter constructor params as JSON object (e.g., {\"admin\":\"G...\"}). Leave blank for {}"
-            .replace("{}", "none")
-            .bright_black()
-    );
-    let params_raw = prompt("Params JSON", Some("".into()))?;
-    let params_value = if params_raw.trim().is_empty() {
-        serde_json::Value::Object(Default::default())
-    } else {
-        serde_json::from_str::<serde_json::Value>(params_raw.trim())
-            .context("Invalid JSON for params")?
-    };
+    let wasm_path = answer(&mut session.wasm_path, "WASM", || {
+        answers.text(
+            "wasm_path",
+            "Path to contract WASM (.wasm)",
+            None::<String>,
+            |s: &str| {
+                let p = Path::new(s.trim());
+                p.exists() && p.is_file() && p.extension().map(|e| e == "wasm").unwrap_or(false)
+            },
+            "File not found or not a .wasm file.",
+        )
+    })?;
+    session.save()?;
 
-    let max_fee_str = prompt_with_validation(
-        "Max fee (stroops), integer",
-        Some("100000".to_string()),
-        |s| s.trim().parse::<u64>().is_ok(),
-        "Provide a positive integer.",
-    )?;
+    let params_value = answer(&mut session.params, "Params", || answers.params("params"))?;
+    session.save()?;
+
+    let max_fee_str = answer(&mut session.max_fee, "Max Fee", || {
+        answers.text(
+            "max_fee",
+            "Max fee (stroops), integer",
+            Some("100000".to_string()),
+            |s| s.trim().parse::<u64>().is_ok(),
+            "Provide a positive integer.",
+        )
+    })?;
+    session.save()?;
     let max_fee: u64 = max_fee_str.trim().parse().unwrap_or(100_000);
 
     println!("\n{}", "Deployment Plan Preview".bold().cyan());
@@ -87,7 +346,8 @@ pub async fn run(_api_url: &str) -> Result<()> {
     );
     println!("{}", "-".repeat(80).cyan());
 
-    let proceed = confirm("Proceed to dry-run? [y/N]", false)?;
+    let proceed = answers.confirm("dry_run", "Proceed to dry-run? [y/N]", false)?;
+    answers.dump(&session)?;
     if !proceed {
         println!("{}", "Aborted.".yellow());
         return Ok(());
@@ -110,7 +370,8 @@ pub async fn run(_api_url: &str) -> Result<()> {
         }
     }
 
-    let execute = confirm("Execute deployment? [y/N]", false)?;
+    let execute = answers.confirm("execute", "Execute deployment? [y/N]", false)?;
+    answers.dump(&session)?;
     if !execute {
         let _ = record_history(json!({
             "status": "planned",
@@ -121,6 +382,7 @@ pub async fn run(_api_url: &str) -> Result<()> {
             "max_fee": max_fee,
             "ts": now_ts(),
         }));
+        Session::clear();
         println!("{}", "Saved plan without executing.".yellow());
         return Ok(());
     }
@@ -165,6 +427,7 @@ pub async fn run(_api_url: &str) -> Result<()> {
             "ts": now_ts(),
         }));
     } else {
+        Session::clear();
         println!("{}", "✓ Deployment executed".green().bold());
         let _ = record_history(json!({
             "status": "success",
@@ -181,6 +444,156 @@ pub async fn run(_api_url: &str) -> Result<()> {
     Ok(())
 }
 
+/// `wizard multisig-policy`: name, signers, threshold and expiry, checked
+/// as they're entered, then created with [`crate::multisig::create_policy`].
+pub async fn multisig_policy(api_url: &str) -> Result<()> {
+    println!("\n{}", "Multi-Sig Policy Wizard".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+
+    let name = prompt_with_validation(
+        "Policy name",
+        None,
+        |s| !s.trim().is_empty(),
+        "The policy needs a name.",
+    )?;
+    let created_by = prompt_with_validation(
+        "Your address (recorded as the policy creator)",
+        None,
+        is_account_address,
+        "Not a Stellar account address (G...).",
+    )?;
+
+    println!(
+        "{}",
+        "Add signer addresses one at a time; leave blank when done.".bright_black()
+    );
+    let mut signers: Vec<String> = Vec::new();
+    loop {
+        let address = prompt(&format!("Signer {}", signers.len() + 1), None)?;
+        let address = address.trim();
+        if address.is_empty() {
+            if signers.is_empty() {
+                println!("{}", "Error: add at least one signer.".red());
+                continue;
+            }
+            break;
+        }
+        if !is_account_address(address) {
+            println!(
+                "{}",
+                "Error: not a Stellar account address (G...); signer not added.".red()
+            );
+        } else if signers.iter().any(|s| s == address) {
+            println!("{} {} is already a signer", "⚠".yellow(), address);
+        } else {
+            signers.push(address.to_string());
+        }
+    }
+
+    let count = signers.len();
+    let threshold = loop {
+        let threshold: usize = prompt_with_validation(
+            &format!("Approvals required (1-{})", count),
+            Some((count / 2 + 1).to_string()),
+            |s| {
+                s.trim()
+                    .parse::<usize>()
+                    .is_ok_and(|t| (1..=count).contains(&t))
+            },
+            &format!("Enter a number from 1 to {}.", count),
+        )?
+        .trim()
+        .parse()?;
+        let warnings = threshold_warnings(threshold, count);
+        for warning in &warnings {
+            println!("{} {}", "⚠".yellow(), warning.yellow());
+        }
+        if warnings.is_empty() || confirm("Keep this threshold? [y/N]", false)? {
+            break threshold;
+        }
+    };
+
+    let expiry = prompt_with_validation(
+        "Proposal expiry (e.g. 3600, 12h, 7d; blank for the registry default of 24h)",
+        Some(String::new()),
+        |s| s.trim().is_empty() || parse_expiry(s).is_some(),
+        "Use a positive number of seconds, or a number with an m, h or d suffix.",
+    )?;
+    let expiry_secs = parse_expiry(&expiry);
+
+    println!("\n{}", "Policy Summary".bold().cyan());
+    println!("{}", "-".repeat(80).cyan());
+    println!("{}: {}", "Name".bold(), name.trim());
+    println!("{}: {}", "Creator".bold(), created_by.trim().bright_black());
+    println!("{}:", "Signers".bold());
+    for signer in &signers {
+        println!("  • {}", signer);
+    }
+    println!("{}: {}-of-{}", "Threshold".bold(), threshold, count);
+    println!(
+        "{}: {}",
+        "Expiry".bold(),
+        expiry_secs
+            .map(|s| format!("{} seconds", s))
+            .unwrap_or_else(|| "registry default (24h)".to_string())
+    );
+    println!("{}", "-".repeat(80).cyan());
+    println!(
+        "{} {}",
+        "Security note:".bold().yellow(),
+        "keep signer keys with different people on separate devices. Anyone holding \
+         the threshold number of keys can approve deployments under this policy."
+            .bright_black()
+    );
+
+    if !confirm("Create this policy? [y/N]", false)? {
+        println!("{}", "Aborted.".yellow());
+        return Ok(());
+    }
+    crate::multisig::create_policy(
+        api_url,
+        name.trim(),
+        threshold as u32,
+        signers,
+        expiry_secs,
+        created_by.trim(),
+    )
+    .await
+}
+
+fn is_account_address(s: &str) -> bool {
+    stellar_strkey::ed25519::PublicKey::from_string(s.trim()).is_ok()
+}
+
+/// What's risky about requiring `threshold` of `signers` approvals.
+fn threshold_warnings(threshold: usize, signers: usize) -> Vec<&'static str> {
+    let mut warnings = Vec::new();
+    if signers == 1 {
+        warnings.push("A single signer gives no multi-sig protection.");
+    } else if threshold == 1 {
+        warnings.push("Any one signer can approve on their own.");
+    } else if threshold == signers {
+        warnings.push("Every signer must approve; one lost key blocks all proposals.");
+    } else if threshold <= signers / 2 {
+        warnings.push("Half or fewer of the signers can approve.");
+    }
+    warnings
+}
+
+/// Seconds from `3600`, `90m`, `12h` or `7d`; `None` if blank or invalid.
+fn parse_expiry(s: &str) -> Option<u32> {
+    let s = s.trim();
+    let (number, unit) = match s.char_indices().last()? {
+        (i, 'm') => (&s[..i], 60),
+        (i, 'h') => (&s[..i], 3600),
+        (i, 'd') => (&s[..i], 86_400),
+        (i, 's') => (&s[..i], 1),
+        _ => (s, 1),
+    };
+    let seconds = number.trim().parse::<u32>().ok()?.checked_mul(unit)?;
+    (seconds > 0).then_some(seconds)
+}
+
 pub fn show_history(search: Option<&str>, limit: usize) -> Result<()> {
     let path = ensure_history_path()?;
     if !path.exists() {
@@ -242,6 +655,266 @@ pub fn show_history(search: Option<&str>, limit: usize) -> Result<()> {
     Ok(())
 }
 
+/// How many times one command or contract appears in the history.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageCount {
+    pub name: String,
+    pub count: usize,
+}
+
+/// Aggregate view of the local history for `history show --stats`.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct HistoryStats {
+    pub entries: usize,
+    pub succeeded: usize,
+    /// Failed, rolled back, or failed at dry-run.
+    pub failed: usize,
+    /// `succeeded / (succeeded + failed)`; plans that were never executed
+    /// don't count either way.
+    pub success_rate: f64,
+    /// Most used first.
+    pub commands: Vec<UsageCount>,
+    pub statuses: BTreeMap<String, usize>,
+    pub networks: BTreeMap<String, usize>,
+    /// Most touched first; keyed by contract ID, or the WASM path for
+    /// deployments that never got one.
+    pub contracts: Vec<UsageCount>,
+    /// UTC day (YYYY-MM-DD) → entries recorded that day.
+    pub activity: BTreeMap<String, usize>,
+    pub first_ts: Option<u64>,
+    pub last_ts: Option<u64>,
+}
+
+fn ranked(counts: HashMap<String, usize>) -> Vec<UsageCount> {
+    let mut ranked: Vec<UsageCount> = counts
+        .into_iter()
+        .map(|(name, count)| UsageCount { name, count })
+        .collect();
+    ranked.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    ranked
+}
+
+pub fn history_stats(entries: &[serde_json::Value]) -> HistoryStats {
+    let mut stats = HistoryStats {
+        entries: entries.len(),
+        ..Default::default()
+    };
+    let mut commands = HashMap::new();
+    let mut contracts = HashMap::new();
+    for entry in entries {
+        let field = |name: &str| entry.get(name).and_then(|x| x.as_str());
+
+        // Entries written before commands were recorded all came from the wizard.
+        *commands
+            .entry(field("command").unwrap_or("wizard").to_string())
+            .or_default() += 1;
+        let status = field("status").unwrap_or("unknown");
+        match status {
+            "success" => stats.succeeded += 1,
+            "failed" | "rolled_back" | "dry_run_failed" => stats.failed += 1,
+            _ => {}
+        }
+        *stats.statuses.entry(status.to_string()).or_default() += 1;
+        if let Some(network) = field("network") {
+            *stats.networks.entry(network.to_string()).or_default() += 1;
+        }
+        if let Some(contract) = field("contract_id").or_else(|| field("wasm")) {
+            *contracts.entry(contract.to_string()).or_default() += 1;
+        }
+        if let Some(ts) = entry.get("ts").and_then(|x| x.as_u64()) {
+            stats.first_ts = Some(stats.first_ts.map_or(ts, |t| t.min(ts)));
+            stats.last_ts = Some(stats.last_ts.map_or(ts, |t| t.max(ts)));
+            if let Some(day) = chrono::DateTime::from_timestamp(ts as i64, 0) {
+                *stats
+                    .activity
+                    .entry(day.format("%Y-%m-%d").to_string())
+                    .or_default() += 1;
+            }
+        }
+    }
+    let finished = stats.succeeded + stats.failed;
+    if finished > 0 {
+        stats.success_rate = stats.succeeded as f64 / finished as f64;
+    }
+    stats.commands = ranked(commands);
+    stats.contracts = ranked(contracts);
+    stats
+}
+
+fn read_history() -> Result<Vec<serde_json::Value>> {
+    let path = ensure_history_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let file = File::open(&path).context("Failed to open history file")?;
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        if let Ok(v) = serde_json::from_str(&line?) {
+            entries.push(v);
+        }
+    }
+    Ok(entries)
+}
+
+/// `history show --stats`: what the local history says about how the CLI gets used.
+pub fn show_history_stats() -> Result<()> {
+    const TOP: usize = 10;
+    const RECENT_DAYS: usize = 14;
+
+    let stats = history_stats(&read_history()?);
+
+    let emitter = crate::output::Emitter::current();
+    if emitter.structured() {
+        emitter.emit(&stats, crate::output::Format::Json)?;
+        return Ok(());
+    }
+
+    println!("\n{}", "History Stats".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    if stats.entries == 0 {
+        println!("{}", "No history found.".yellow());
+        println!();
+        return Ok(());
+    }
+
+    println!("  {}: {}", "Entries".bold(), stats.entries);
+    println!(
+        "  {}: {} succeeded, {} failed ({:.0}% success)",
+        "Outcomes".bold(),
+        stats.succeeded.to_string().green(),
+        stats.failed.to_string().red(),
+        stats.success_rate * 100.0
+    );
+    let by_status: Vec<String> = stats
+        .statuses
+        .iter()
+        .map(|(status, n)| format!("{} ({})", status, n))
+        .collect();
+    println!("  {}: {}", "Statuses".bold(), by_status.join(", "));
+    let by_network: Vec<String> = stats
+        .networks
+        .iter()
+        .map(|(network, n)| format!("{} ({})", network, n))
+        .collect();
+    if !by_network.is_empty() {
+        println!("  {}: {}", "Networks".bold(), by_network.join(", "));
+    }
+
+    let print_ranked = |title: &str, counts: &[UsageCount]| {
+        if counts.is_empty() {
+            return;
+        }
+        println!("\n  {}", title.bold());
+        for usage in counts.iter().take(TOP) {
+            println!("    {:>5}  {}", usage.count, usage.name);
+        }
+        if counts.len() > TOP {
+            println!(
+                "    {}",
+                format!("... and {} more", counts.len() - TOP).bright_black()
+            );
+        }
+    };
+    print_ranked("Most used commands:", &stats.commands);
+    print_ranked("Most touched contracts:", &stats.contracts);
+
+    if !stats.activity.is_empty() {
+        println!("\n  {}", "Activity (most recent days):".bold());
+        let busiest = stats.activity.values().copied().max().unwrap_or(1);
+        let skip = stats.activity.len().saturating_sub(RECENT_DAYS);
+        for (day, count) in stats.activity.iter().skip(skip) {
+            let bar = "█".repeat((count * 40).div_ceil(busiest));
+            println!("    {}  {} {}", day, bar.cyan(), count);
+        }
+    }
+    println!();
+    Ok(())
+}
+
+/// One history entry as written by `history export`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportedEntry {
+    /// Seconds since the Unix epoch, as recorded.
+    pub ts: Option<u64>,
+    /// `ts` in RFC 3339.
+    pub timestamp: Option<String>,
+    pub command: String,
+    pub status: Option<String>,
+    /// Everything else recorded with the entry: network, WASM, params, fee...
+    pub arguments: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ExportedEntry {
+    pub fn from_record(record: &serde_json::Value) -> Self {
+        let mut arguments = record.as_object().cloned().unwrap_or_default();
+        let ts = arguments.remove("ts").and_then(|v| v.as_u64());
+        let mut take = |name: &str| match arguments.remove(name) {
+            Some(serde_json::Value::String(s)) => Some(s),
+            _ => None,
+        };
+        let command = take("command").unwrap_or_else(|| "wizard".to_string());
+        let status = take("status");
+        ExportedEntry {
+            ts,
+            timestamp: ts
+                .and_then(|ts| chrono::DateTime::from_timestamp(ts as i64, 0))
+                .map(|at| at.to_rfc3339()),
+            command,
+            status,
+            arguments,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct HistoryExport {
+    exported_at: String,
+    entries: Vec<ExportedEntry>,
+}
+
+/// `history export`: the whole history, oldest first, as JSON.
+pub fn export_history(output: &Path) -> Result<()> {
+    let entries: Vec<ExportedEntry> = read_history()?
+        .iter()
+        .map(ExportedEntry::from_record)
+        .collect();
+    let export = HistoryExport {
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        entries,
+    };
+    std::fs::write(output, serde_json::to_string_pretty(&export)?)
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+    println!(
+        "{} Exported {} record(s) to {}",
+        "✓".green(),
+        export.entries.len(),
+        output.display()
+    );
+    Ok(())
+}
+
+/// `history clear`: delete the history file, after confirming unless `yes`.
+pub fn clear_history(yes: bool) -> Result<()> {
+    let path = ensure_history_path()?;
+    if !path.exists() {
+        println!("{}", "No history found.".yellow());
+        return Ok(());
+    }
+    let count = read_history()?.len();
+    if !yes
+        && !confirm(
+            &format!("Delete {} record(s) in {}? [y/N]", count, path.display()),
+            false,
+        )?
+    {
+        println!("{}", "Aborted.".yellow());
+        return Ok(());
+    }
+    std::fs::remove_file(&path).with_context(|| format!("Failed to delete {}", path.display()))?;
+    println!("{} Cleared {} record(s)", "✓".green(), count);
+    Ok(())
+}
+
 fn print_item(v: &serde_json::Value) {
     let status = v.get("status").and_then(|x| x.as_str()).unwrap_or("");
     let status_str = match status {
@@ -286,6 +959,11 @@ fn dry_run(wasm_path: &str, params: &serde_json::Value) -> Result<()> {
     Ok(())
 }
 
+/// Stellar secret seeds start with `S`; addresses with `G`.
+fn is_secret(s: &str) -> bool {
+    s.trim().starts_with('S')
+}
+
 fn mask_secret(s: &str) -> String {
     let s = s.trim();
     if s.starts_with('S') && s.len() >= 8 {
@@ -340,7 +1018,7 @@ where
     }
 }
 
-fn confirm(label: &str, default_yes: bool) -> Result<bool> {
+pub fn confirm(label: &str, default_yes: bool) -> Result<bool> {
     let default = if default_yes { "Y" } else { "N" };
     let ans = prompt(label, Some(default.into()))?;
     let ans_l = ans.to_lowercase();
@@ -371,6 +1049,10 @@ fn ensure_history_path() -> Result<PathBuf> {
     Ok(dir.join(HISTORY_FILE_NAME))
 }
 
+fn session_path() -> Result<PathBuf> {
+    Ok(ensure_history_path()?.with_file_name(SESSION_FILE_NAME))
+}
+
 fn record_history(entry: serde_json::Value) -> Result<()> {
     let path = ensure_history_path()?;
     let mut file = OpenOptions::new()
@@ -391,3 +1073,168 @@ fn home_dir() -> Option<PathBuf> {
         std::env::var_os("HOME").map(PathBuf::from)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_never_persists_secret_signers() {
+        let session = Session {
+            network: Some("testnet".into()),
+            signer: Some(format!("S{}", "A".repeat(55))),
+            max_fee: Some("100000".into()),
+            ..Session::default()
+        };
+        let stored = session.persistable();
+        assert_eq!(stored.signer, None);
+        assert_eq!(stored.network, session.network);
+
+        let public = Session {
+            signer: Some(format!("G{}", "A".repeat(55))),
+            ..Session::default()
+        };
+        assert_eq!(public.persistable(), public);
+    }
+
+    #[test]
+    fn history_stats_aggregates_outcomes_contracts_and_activity() {
+        let entries = vec![
+            json!({ "status": "success", "network": "testnet", "wasm": "a.wasm", "ts": 1_700_000_000u64 }),
+            json!({ "status": "failed", "network": "testnet", "wasm": "a.wasm", "ts": 1_700_000_100u64 }),
+            json!({ "status": "planned", "network": "mainnet", "wasm": "b.wasm", "ts": 1_700_100_000u64 }),
+            json!({ "command": "publish", "status": "success", "contract_id": "CABC" }),
+        ];
+        let stats = history_stats(&entries);
+        assert_eq!(stats.entries, 4);
+        assert_eq!((stats.succeeded, stats.failed), (2, 1));
+        assert!((stats.success_rate - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(
+            stats.commands[0],
+            UsageCount {
+                name: "wizard".into(),
+                count: 3
+            }
+        );
+        assert_eq!(stats.contracts[0].name, "a.wasm");
+        assert_eq!(stats.contracts.len(), 3);
+        assert_eq!(stats.statuses["planned"], 1);
+        assert_eq!(stats.activity["2023-11-14"], 2);
+        assert_eq!(stats.activity["2023-11-16"], 1);
+        assert_eq!(stats.first_ts, Some(1_700_000_000));
+        assert_eq!(stats.last_ts, Some(1_700_100_000));
+        assert_eq!(history_stats(&[]).success_rate, 0.0);
+    }
+
+    #[test]
+    fn multisig_policy_inputs_are_checked() {
+        assert_eq!(
+            threshold_warnings(1, 3),
+            ["Any one signer can approve on their own."]
+        );
+        assert_eq!(threshold_warnings(3, 3).len(), 1);
+        assert_eq!(threshold_warnings(2, 4).len(), 1);
+        assert!(threshold_warnings(2, 3).is_empty());
+        assert_eq!(
+            threshold_warnings(1, 1),
+            ["A single signer gives no multi-sig protection."]
+        );
+
+        assert_eq!(parse_expiry("3600"), Some(3600));
+        assert_eq!(parse_expiry(" 12h "), Some(43_200));
+        assert_eq!(parse_expiry("7d"), Some(604_800));
+        assert_eq!(parse_expiry("0"), None);
+        assert_eq!(parse_expiry(""), None);
+        assert_eq!(parse_expiry("soon"), None);
+
+        let address = stellar_strkey::ed25519::PublicKey([7; 32]).to_string();
+        assert!(is_account_address(&address));
+        assert!(!is_account_address(&address[1..]));
+        assert!(!is_account_address(&format!("S{}", "A".repeat(55))));
+    }
+
+    #[test]
+    fn answers_file_replaces_prompts_and_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers.yaml");
+        std::fs::write(
+            &path,
+            "network: futurenet\nmax_fee: 5000\nparams: { admin: GABC }\ndry_run: yes\n",
+        )
+        .unwrap();
+        let dump = dir.path().join("dump.yaml");
+        let mut answers = Answers::load(Some(&path), true, Some(&dump)).unwrap();
+
+        let valid_network = |s: &str| matches!(s, "mainnet" | "testnet" | "futurenet");
+        assert_eq!(
+            answers
+                .text("network", "Network", None, valid_network, "bad network")
+                .unwrap(),
+            "futurenet"
+        );
+        let fee = answers.text("max_fee", "Max fee", None, |s| s.parse::<u64>().is_ok(), "");
+        assert_eq!(fee.unwrap(), "5000");
+        assert_eq!(
+            answers.params("params").unwrap(),
+            json!({ "admin": "GABC" })
+        );
+        assert!(answers.confirm("dry_run", "Proceed?", false).unwrap());
+        assert!(!answers.confirm("execute", "Execute?", false).unwrap());
+
+        let missing = answers
+            .text("signer", "Signer", None, |_| true, "")
+            .unwrap_err();
+        assert!(missing.to_string().contains("'signer'"));
+        assert_eq!(
+            answers
+                .text("wasm_path", "WASM", Some("a.wasm".into()), |_| true, "")
+                .unwrap(),
+            "a.wasm"
+        );
+
+        let session = Session {
+            network: Some("futurenet".into()),
+            signer: Some(format!("S{}", "A".repeat(55))),
+            ..Session::default()
+        };
+        answers.dump(&session).unwrap();
+        let replayed = Answers::load(Some(&dump), true, None).unwrap();
+        assert_eq!(replayed.given["network"], "futurenet");
+        assert_eq!(replayed.given["execute"], false);
+        assert!(!replayed.given.contains_key("signer"));
+
+        let invalid = Answers {
+            given: BTreeMap::from([("network".to_string(), json!("moon"))]),
+            ..Answers::default()
+        };
+        assert!(invalid
+            .text("network", "Network", None, valid_network, "bad network")
+            .is_err());
+    }
+
+    #[test]
+    fn exported_entries_carry_timestamps_and_arguments() {
+        let wizard = ExportedEntry::from_record(&json!({
+            "status": "success",
+            "network": "testnet",
+            "wasm": "token.wasm",
+            "max_fee": 100000,
+            "ts": 1_700_000_000u64,
+        }));
+        assert_eq!(wizard.command, "wizard");
+        assert_eq!(wizard.status.as_deref(), Some("success"));
+        assert_eq!(
+            wizard.timestamp.as_deref(),
+            Some("2023-11-14T22:13:20+00:00")
+        );
+        assert_eq!(
+            serde_json::Value::Object(wizard.arguments),
+            json!({ "network": "testnet", "wasm": "token.wasm", "max_fee": 100000 })
+        );
+
+        let bare = ExportedEntry::from_record(&json!({ "command": "publish" }));
+        assert_eq!(bare.command, "publish");
+        assert_eq!((bare.ts, bare.timestamp), (None, None));
+        assert!(bare.arguments.is_empty());
+    }
+}
@@ -0,0 +1,10 @@
+-- Let the proposer or a policy admin withdraw a deployment proposal before it
+-- is executed.
+--
+-- Status lifecycle: pending/approved -> cancelled
+
+ALTER TYPE proposal_status ADD VALUE IF NOT EXISTS 'cancelled';
+
+ALTER TABLE deploy_proposals
+    ADD COLUMN IF NOT EXISTS cancelled_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS cancelled_by VARCHAR(56);